@@ -0,0 +1,4 @@
+-- Add migration script here
+-- Idioma preferido do usuário para as mensagens da API (NULL = usa Accept-Language)
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS locale VARCHAR(10) NULL;
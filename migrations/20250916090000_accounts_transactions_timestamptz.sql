@@ -0,0 +1,18 @@
+-- Add migration script here
+-- Datas com fuso (como em users) e colunas que o código trata como obrigatórias
+ALTER TABLE accounts
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN updated_at TYPE TIMESTAMPTZ USING updated_at AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at SET NOT NULL,
+    ALTER COLUMN updated_at SET NOT NULL,
+    ALTER COLUMN balance SET NOT NULL,
+    ALTER COLUMN is_active SET NOT NULL;
+
+-- RN: saldo não pode ser negativo
+ALTER TABLE accounts
+    ADD CONSTRAINT accounts_balance_non_negative CHECK (balance >= 0);
+
+ALTER TABLE transactions
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC',
+    ALTER COLUMN created_at SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
@@ -0,0 +1,6 @@
+-- Add migration script here
+-- Papel do usuário: 'admin' libera as APIs administrativas (ex.: consulta de auditoria)
+-- Promoção manual: UPDATE users SET role = 'admin' WHERE email = '...';
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS role VARCHAR(20) NOT NULL DEFAULT 'customer'
+        CHECK (role IN ('customer', 'admin'));
@@ -0,0 +1,43 @@
+-- Add migration script here
+-- ========================
+-- Tabela: audit_events (somente inserção)
+-- ========================
+-- Cada linha guarda o hash da anterior (prev_hash) e o próprio hash, calculado
+-- pela aplicação sobre o conteúdo do evento; alterar ou remover uma linha quebra
+-- a cadeia a partir dela.
+CREATE TABLE IF NOT EXISTS audit_events (
+    seq BIGSERIAL PRIMARY KEY,
+    id UUID UNIQUE NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    actor_id UUID NULL,
+    action VARCHAR(64) NOT NULL,
+    target_type VARCHAR(32) NOT NULL,
+    target_id UUID NULL,
+    ip VARCHAR(64) NULL,
+    user_agent TEXT NULL,
+    request_id VARCHAR(128) NULL,
+    before JSONB NULL,
+    after JSONB NULL,
+    metadata JSONB NULL,
+    prev_hash CHAR(64) NOT NULL,
+    hash CHAR(64) UNIQUE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_events_actor ON audit_events(actor_id);
+CREATE INDEX IF NOT EXISTS idx_audit_events_target ON audit_events(target_type, target_id);
+CREATE INDEX IF NOT EXISTS idx_audit_events_action ON audit_events(action);
+CREATE INDEX IF NOT EXISTS idx_audit_events_occurred_at ON audit_events(occurred_at);
+
+CREATE OR REPLACE FUNCTION audit_events_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events é somente inserção (% não permitido)', TG_OP;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_events_no_update_delete
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW EXECUTE FUNCTION audit_events_append_only();
+
+CREATE TRIGGER audit_events_no_truncate
+    BEFORE TRUNCATE ON audit_events
+    FOR EACH STATEMENT EXECUTE FUNCTION audit_events_append_only();
@@ -0,0 +1,28 @@
+-- Add migration script here
+-- ========================
+-- Tabela: outbox_events
+-- ========================
+-- Eventos de domínio gravados na mesma transação da mudança de negócio e
+-- entregues depois pelo dispatcher (pelo menos uma vez). A ordem de entrega
+-- por agregado segue `seq`.
+CREATE TABLE IF NOT EXISTS outbox_events (
+    seq BIGSERIAL PRIMARY KEY,
+    id UUID UNIQUE NOT NULL,
+    aggregate_type VARCHAR(32) NOT NULL,
+    aggregate_id UUID NOT NULL,
+    event_type VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT NULL,
+    published_at TIMESTAMPTZ NULL
+);
+
+-- só os pendentes interessam ao dispatcher
+CREATE INDEX IF NOT EXISTS idx_outbox_events_pending
+    ON outbox_events(aggregate_type, aggregate_id, seq)
+    WHERE published_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_outbox_events_due
+    ON outbox_events(next_attempt_at)
+    WHERE published_at IS NULL;
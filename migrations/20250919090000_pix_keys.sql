@@ -0,0 +1,51 @@
+-- Add migration script here
+-- ========================
+-- Tabela: pix_keys
+-- ========================
+-- Chave removida ou portada fica com `deleted_at`; a unicidade vale só entre as ativas.
+CREATE TABLE IF NOT EXISTS pix_keys (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    user_id UUID NOT NULL REFERENCES users(id),
+    key_type VARCHAR(8) NOT NULL
+        CHECK (key_type IN ('cpf', 'email', 'phone', 'evp')),
+    key_value VARCHAR(77) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMPTZ NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_pix_keys_active_value
+    ON pix_keys(key_value)
+    WHERE deleted_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_pix_keys_account
+    ON pix_keys(account_id)
+    WHERE deleted_at IS NULL;
+
+-- ========================
+-- Tabela: pix_key_claims
+-- ========================
+-- Portabilidade (mesmo dono, outra conta) ou reivindicação de posse (outro dono).
+-- No máximo uma reivindicação aberta por chave.
+CREATE TABLE IF NOT EXISTS pix_key_claims (
+    id UUID PRIMARY KEY,
+    pix_key_id UUID NOT NULL REFERENCES pix_keys(id),
+    claim_type VARCHAR(16) NOT NULL
+        CHECK (claim_type IN ('portability', 'ownership')),
+    status VARCHAR(16) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'completed', 'cancelled')),
+    claimer_user_id UUID NOT NULL REFERENCES users(id),
+    claimer_account_id UUID NOT NULL REFERENCES accounts(id),
+    donor_user_id UUID NOT NULL REFERENCES users(id),
+    donor_account_id UUID NOT NULL REFERENCES accounts(id),
+    resolution_deadline TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_pix_key_claims_open
+    ON pix_key_claims(pix_key_id)
+    WHERE status = 'open';
+CREATE INDEX IF NOT EXISTS idx_pix_key_claims_claimer ON pix_key_claims(claimer_user_id);
+CREATE INDEX IF NOT EXISTS idx_pix_key_claims_donor ON pix_key_claims(donor_user_id);
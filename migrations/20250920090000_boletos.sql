@@ -0,0 +1,39 @@
+-- Add migration script here
+-- ========================
+-- Tipos de transação do boleto
+-- ========================
+-- `boleto_payment` debita o pagador; `boleto_credit` credita o beneficiário
+-- quando o boleto foi emitido aqui.
+ALTER TYPE transaction_type_enum ADD VALUE IF NOT EXISTS 'boleto_payment';
+ALTER TYPE transaction_type_enum ADD VALUE IF NOT EXISTS 'boleto_credit';
+
+-- ========================
+-- Tabela: boletos
+-- ========================
+-- Boletos emitidos pelo banco para receber em uma conta. O código de barras
+-- (44 dígitos) é único e é por ele que o pagamento encontra o boleto.
+CREATE SEQUENCE IF NOT EXISTS boleto_our_number_seq;
+
+CREATE TABLE IF NOT EXISTS boletos (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    user_id UUID NOT NULL REFERENCES users(id),
+    our_number VARCHAR(15) NOT NULL UNIQUE,
+    amount DECIMAL(15,2) NOT NULL CHECK (amount > 0),
+    due_date DATE NOT NULL,
+    payer_name VARCHAR(100) NOT NULL,
+    description TEXT NULL,
+    barcode CHAR(44) NOT NULL UNIQUE,
+    digitable_line CHAR(47) NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'paid', 'cancelled')),
+    payment_transaction_id UUID NULL REFERENCES transactions(id),
+    paid_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_boletos_user ON boletos(user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_boletos_account_open
+    ON boletos(account_id)
+    WHERE status = 'open';
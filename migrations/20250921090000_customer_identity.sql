@@ -0,0 +1,41 @@
+-- Add migration script here
+-- ========================
+-- Identidade do cliente
+-- ========================
+-- Pessoa física tem CPF, empresa tem CNPJ; o documento é gravado só com os dígitos.
+-- Usuários anteriores ficam sem documento; todo cadastro novo traz um.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS customer_type VARCHAR(20) NOT NULL DEFAULT 'individual'
+        CHECK (customer_type IN ('individual', 'business')),
+    ADD COLUMN IF NOT EXISTS document VARCHAR(14) NULL;
+
+ALTER TABLE users
+    ADD CONSTRAINT users_document_key UNIQUE (document),
+    ADD CONSTRAINT users_document_format CHECK (
+        document IS NULL
+        OR (customer_type = 'individual' AND document ~ '^[0-9]{11}$')
+        OR (customer_type = 'business' AND document ~ '^[0-9]{14}$')
+    );
+
+-- ========================
+-- Tabela: business_operators
+-- ========================
+-- Pessoas físicas, cada uma com o próprio login, autorizadas a operar as contas de uma empresa.
+-- Remover o operador apaga a linha; o histórico fica na auditoria.
+CREATE TABLE IF NOT EXISTS business_operators (
+    id UUID PRIMARY KEY,
+    business_id UUID NOT NULL REFERENCES users(id),
+    user_id UUID NOT NULL REFERENCES users(id),
+    permissions VARCHAR(20)[] NOT NULL
+        CHECK (
+            cardinality(permissions) > 0
+            AND permissions <@ ARRAY['view', 'transact', 'manage']::VARCHAR(20)[]
+        ),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (business_id, user_id),
+    CHECK (business_id <> user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_business_operators_user
+    ON business_operators(user_id);
@@ -0,0 +1,44 @@
+-- Add migration script here
+-- ========================
+-- Tabela: kyc_profiles
+-- ========================
+-- Situação do KYC de cada usuário:
+-- pending -> submitted -> under_review -> approved | rejected; rejeitado pode reenviar (submitted).
+-- Quem já tinha cadastro antes do KYC fica aprovado; cadastros novos começam em pending.
+CREATE TABLE IF NOT EXISTS kyc_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'submitted', 'under_review', 'approved', 'rejected')),
+    submitted_at TIMESTAMPTZ NULL,
+    reviewer_id UUID NULL REFERENCES users(id),
+    reviewed_at TIMESTAMPTZ NULL,
+    rejection_reason TEXT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO kyc_profiles (user_id, status, reviewed_at)
+SELECT id, 'approved', NOW() FROM users
+ON CONFLICT (user_id) DO NOTHING;
+
+-- fila de revisão: mais antigos primeiro
+CREATE INDEX IF NOT EXISTS idx_kyc_profiles_queue
+    ON kyc_profiles(status, submitted_at);
+
+-- ========================
+-- Tabela: kyc_documents
+-- ========================
+-- O arquivo fica no storage (`storage_key`); aqui só os metadados. Um documento por tipo:
+-- reenviar substitui o anterior.
+CREATE TABLE IF NOT EXISTS kyc_documents (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id),
+    document_type VARCHAR(30) NOT NULL
+        CHECK (document_type IN ('id_document', 'selfie', 'proof_of_address', 'company_registration')),
+    content_type VARCHAR(50) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes > 0),
+    sha256 CHAR(64) NOT NULL,
+    storage_key VARCHAR(200) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, document_type)
+);
@@ -0,0 +1,55 @@
+-- Add migration script here
+-- ========================
+-- Tabela: scheduled_transfers
+-- ========================
+-- Transferências agendadas (uma vez) e recorrentes (semanal ou mensal). O agendador
+-- pega as vencidas com FOR UPDATE SKIP LOCKED; `occurrence` é o índice da ocorrência
+-- atual, contado a partir de `start_at`. O destino é guardado como no pedido (número
+-- da conta ou chave PIX) e resolvido de novo em cada execução.
+CREATE TABLE IF NOT EXISTS scheduled_transfers (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    created_by UUID NOT NULL REFERENCES users(id),
+    to_account_number VARCHAR(20) NULL,
+    to_pix_key VARCHAR(77) NULL,
+    amount DECIMAL(15,2) NOT NULL CHECK (amount > 0),
+    description TEXT NOT NULL,
+    frequency VARCHAR(10) NOT NULL
+        CHECK (frequency IN ('once', 'weekly', 'monthly')),
+    start_at TIMESTAMPTZ NOT NULL,
+    end_date DATE NULL,
+    max_runs INTEGER NULL CHECK (max_runs > 0),
+    occurrence INTEGER NOT NULL DEFAULT 0,
+    next_run_at TIMESTAMPTZ NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error VARCHAR(64) NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'completed', 'cancelled', 'failed')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((to_account_number IS NULL) <> (to_pix_key IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_transfers_due
+    ON scheduled_transfers(next_run_at)
+    WHERE status = 'active';
+CREATE INDEX IF NOT EXISTS idx_scheduled_transfers_account
+    ON scheduled_transfers(account_id, created_at DESC);
+
+-- ========================
+-- Tabela: scheduled_transfer_runs
+-- ========================
+-- Uma linha por tentativa de execução, com a transferência gerada ou o código do erro
+CREATE TABLE IF NOT EXISTS scheduled_transfer_runs (
+    id UUID PRIMARY KEY,
+    scheduled_transfer_id UUID NOT NULL REFERENCES scheduled_transfers(id),
+    occurrence INTEGER NOT NULL,
+    attempt INTEGER NOT NULL,
+    status VARCHAR(16) NOT NULL CHECK (status IN ('completed', 'failed')),
+    transaction_id UUID NULL REFERENCES transactions(id),
+    error VARCHAR(64) NULL,
+    executed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_transfer_runs_schedule
+    ON scheduled_transfer_runs(scheduled_transfer_id, executed_at DESC);
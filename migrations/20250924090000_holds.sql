@@ -0,0 +1,42 @@
+-- Add migration script here
+-- ========================
+-- Tipo de transação da captura
+-- ========================
+-- A reserva grava um `hold_capture` pendente; a captura o conclui com o valor
+-- capturado e a liberação/expiração o marca como `failed`.
+ALTER TYPE transaction_type_enum ADD VALUE IF NOT EXISTS 'hold_capture';
+
+-- ========================
+-- Saldo reservado
+-- ========================
+-- `balance` continua sendo o saldo contábil; o disponível é `balance - held_balance`.
+ALTER TABLE accounts
+    ADD COLUMN IF NOT EXISTS held_balance DECIMAL(15,2) NOT NULL DEFAULT 0
+        CHECK (held_balance >= 0);
+
+-- ========================
+-- Tabela: holds
+-- ========================
+-- Reservas (autorizações) de valor em uma conta. Enquanto `active` o valor conta
+-- em `accounts.held_balance`; a captura debita o saldo contábil e o restante
+-- da reserva é liberado.
+CREATE TABLE IF NOT EXISTS holds (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    created_by UUID NOT NULL REFERENCES users(id),
+    amount DECIMAL(15,2) NOT NULL CHECK (amount > 0),
+    captured_amount DECIMAL(15,2) NULL CHECK (captured_amount > 0 AND captured_amount <= amount),
+    description TEXT NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'captured', 'released', 'expired')),
+    transaction_id UUID NOT NULL REFERENCES transactions(id),
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_holds_expiring
+    ON holds(expires_at)
+    WHERE status = 'active';
+CREATE INDEX IF NOT EXISTS idx_holds_account
+    ON holds(account_id, created_at DESC);
@@ -0,0 +1,39 @@
+-- Add migration script here
+-- ========================
+-- Tipo de transação do estorno
+-- ========================
+-- Cada perna do estorno é um lançamento `reversal` com `reference_id` apontando
+-- para a transação original; o histórico nunca é alterado.
+ALTER TYPE transaction_type_enum ADD VALUE IF NOT EXISTS 'reversal';
+
+-- ========================
+-- Tabela: reversals
+-- ========================
+-- Estornos feitos pela operação. A soma de `amount` por `transaction_id` nunca
+-- passa do valor original. Quando a conta debitada não tem saldo, o que faltou
+-- fica em `outstanding_amount` (`pending_recovery`) e é cobrado conforme entra
+-- dinheiro.
+CREATE TABLE IF NOT EXISTS reversals (
+    id UUID PRIMARY KEY,
+    transaction_id UUID NOT NULL REFERENCES transactions(id),
+    amount DECIMAL(15,2) NOT NULL CHECK (amount > 0),
+    reason VARCHAR(32) NOT NULL
+        CHECK (reason IN ('duplicate', 'fraud', 'operational_error', 'chargeback', 'other')),
+    note TEXT NULL,
+    created_by UUID NOT NULL REFERENCES users(id),
+    credit_account_id UUID NULL REFERENCES accounts(id),
+    debit_account_id UUID NULL REFERENCES accounts(id),
+    outstanding_amount DECIMAL(15,2) NOT NULL DEFAULT 0
+        CHECK (outstanding_amount >= 0 AND outstanding_amount <= amount),
+    status VARCHAR(16) NOT NULL
+        CHECK (status IN ('completed', 'pending_recovery')),
+    next_recovery_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_reversals_transaction
+    ON reversals(transaction_id);
+CREATE INDEX IF NOT EXISTS idx_reversals_recovery
+    ON reversals(next_recovery_at)
+    WHERE status = 'pending_recovery';
@@ -0,0 +1,24 @@
+-- Add migration script here
+-- ========================
+-- Tabela: account_limits
+-- ========================
+-- Limites personalizados pelo titular. Sem linha, vale o teto do tipo de conta
+-- (`[limits]` na configuração). Reduções gravam `amount` na hora; aumentos ficam
+-- em `pending_amount` até `pending_effective_at` (carência) e passam a valer a
+-- partir daí sem precisar de nenhum job.
+CREATE TABLE IF NOT EXISTS account_limits (
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    kind VARCHAR(16) NOT NULL
+        CHECK (kind IN ('per_transaction', 'daily', 'monthly', 'nightly')),
+    amount DECIMAL(15,2) NOT NULL CHECK (amount > 0),
+    pending_amount DECIMAL(15,2) NULL CHECK (pending_amount > amount),
+    pending_effective_at TIMESTAMPTZ NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (account_id, kind),
+    CHECK ((pending_amount IS NULL) = (pending_effective_at IS NULL))
+);
+
+-- Soma das saídas da conta por janela (dia, mês, noite)
+CREATE INDEX IF NOT EXISTS idx_transactions_outgoing
+    ON transactions(from_account_id, created_at)
+    WHERE transaction_type IN ('withdraw', 'transfer_debit');
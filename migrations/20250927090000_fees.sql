@@ -0,0 +1,36 @@
+-- Add migration script here
+-- ========================
+-- Tipo de transação da tarifa
+-- ========================
+-- A tarifa é um lançamento `fee` debitado da conta, com `reference_id` apontando
+-- para a operação que a gerou (a transação do saque ou da transferência, ou o
+-- boleto emitido).
+ALTER TYPE transaction_type_enum ADD VALUE IF NOT EXISTS 'fee';
+
+-- ========================
+-- Faixa do cliente
+-- ========================
+-- Define, junto com o tipo de conta, qual regra de `[fees]` vale.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS tier VARCHAR(16) NOT NULL DEFAULT 'standard'
+        CHECK (tier IN ('standard', 'premium'));
+
+-- ========================
+-- Tabela: fee_waivers
+-- ========================
+-- Isenções concedidas pela operação. Sem `operation`, isenta todas; sem
+-- `expires_at`, vale até ser revogada.
+CREATE TABLE IF NOT EXISTS fee_waivers (
+    id UUID PRIMARY KEY,
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    operation VARCHAR(16) NULL
+        CHECK (operation IN ('withdraw', 'transfer', 'boleto_issue')),
+    reason TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NULL,
+    created_by UUID NOT NULL REFERENCES users(id),
+    revoked_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_fee_waivers_account
+    ON fee_waivers(account_id, created_at DESC);
@@ -0,0 +1,29 @@
+-- Add migration script here
+-- ========================
+-- Tipo de transação do rendimento
+-- ========================
+-- Crédito mensal dos juros apurados; só tem `to_account_id`.
+ALTER TYPE transaction_type_enum ADD VALUE IF NOT EXISTS 'interest';
+
+-- ========================
+-- Tabela: interest_accruals
+-- ========================
+-- Uma linha por conta por dia apurado; a chave primária torna a apuração
+-- idempotente. `amount` guarda os juros do dia sem arredondar para centavos:
+-- o arredondamento acontece uma vez, na soma creditada no mês seguinte, que
+-- preenche `transaction_id` e `credited_at`.
+CREATE TABLE IF NOT EXISTS interest_accruals (
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    accrual_date DATE NOT NULL,
+    balance DECIMAL(15,2) NOT NULL,
+    annual_rate DECIMAL(9,4) NOT NULL,
+    amount DECIMAL(20,8) NOT NULL,
+    transaction_id UUID NULL REFERENCES transactions(id),
+    credited_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (account_id, accrual_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_interest_accruals_pending
+    ON interest_accruals(account_id, accrual_date)
+    WHERE credited_at IS NULL;
@@ -0,0 +1,21 @@
+-- Add migration script here
+-- ========================
+-- Cheque especial
+-- ========================
+-- Só a conta corrente pode ter limite, e só ela pode ficar com saldo negativo:
+-- o saldo negativo é o cheque especial em uso. Os juros cobrados no fim do mês
+-- podem levar o saldo além do limite.
+ALTER TABLE accounts
+    ADD COLUMN IF NOT EXISTS overdraft_limit DECIMAL(15,2) NOT NULL DEFAULT 0
+        CHECK (overdraft_limit >= 0 AND (overdraft_limit = 0 OR account_type = 'checking'));
+
+ALTER TABLE accounts DROP CONSTRAINT IF EXISTS accounts_balance_non_negative;
+ALTER TABLE accounts
+    ADD CONSTRAINT accounts_balance_non_negative CHECK (balance >= 0 OR account_type = 'checking');
+
+-- ========================
+-- Tipo de transação dos juros do cheque especial
+-- ========================
+-- Débito mensal dos juros apurados sobre o saldo negativo; só tem `from_account_id`.
+-- A apuração diária usa `interest_accruals`, com `amount` negativo.
+ALTER TYPE transaction_type_enum ADD VALUE IF NOT EXISTS 'overdraft_interest';
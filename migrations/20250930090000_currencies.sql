@@ -0,0 +1,45 @@
+-- Add migration script here
+-- ========================
+-- Moeda das contas e dos lançamentos
+-- ========================
+-- Código ISO 4217. As colunas de valor têm 2 casas, então só entram moedas com
+-- até 2 unidades menores; as contas existentes ficam em reais.
+ALTER TABLE accounts
+    ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'BRL'
+        CHECK (currency IN ('BRL', 'USD', 'EUR', 'GBP', 'CHF', 'CAD', 'MXN', 'ARS', 'JPY', 'CLP'));
+
+-- `fx_rate` e `fx_spread` só nas duas pernas de uma transferência entre moedas:
+-- a taxa aplicada (unidades da moeda de destino por unidade da de origem, já
+-- com o spread) e o spread em %.
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS currency VARCHAR(3) NOT NULL DEFAULT 'BRL'
+        CHECK (currency IN ('BRL', 'USD', 'EUR', 'GBP', 'CHF', 'CAD', 'MXN', 'ARS', 'JPY', 'CLP')),
+    ADD COLUMN IF NOT EXISTS fx_rate DECIMAL(20,10) NULL,
+    ADD COLUMN IF NOT EXISTS fx_spread DECIMAL(7,4) NULL;
+
+-- ========================
+-- Tabela: fx_quotes
+-- ========================
+-- Cotação travada para uma transferência entre moedas: vale até `expires_at` e
+-- uma vez só (`transaction_id` é a perna de débito que a usou).
+CREATE TABLE IF NOT EXISTS fx_quotes (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id),
+    from_account_id UUID NOT NULL REFERENCES accounts(id),
+    to_account_id UUID NOT NULL REFERENCES accounts(id),
+    from_currency VARCHAR(3) NOT NULL,
+    to_currency VARCHAR(3) NOT NULL,
+    amount DECIMAL(15,2) NOT NULL CHECK (amount > 0),
+    converted_amount DECIMAL(15,2) NOT NULL,
+    mid_rate DECIMAL(20,10) NOT NULL,
+    spread DECIMAL(7,4) NOT NULL,
+    rate DECIMAL(20,10) NOT NULL,
+    provider VARCHAR(50) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    transaction_id UUID NULL REFERENCES transactions(id),
+    used_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_fx_quotes_account
+    ON fx_quotes(from_account_id, created_at DESC);
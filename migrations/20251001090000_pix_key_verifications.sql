@@ -0,0 +1,23 @@
+-- ========================
+-- Tabela: pix_key_verifications
+-- ========================
+-- Código de uso único enviado ao email ou telefone antes de registrar ou reivindicar
+-- a chave. Só o hash do código é guardado.
+CREATE TABLE IF NOT EXISTS pix_key_verifications (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id),
+    key_type VARCHAR(8) NOT NULL
+        CHECK (key_type IN ('email', 'phone')),
+    key_value VARCHAR(77) NOT NULL,
+    code_hash VARCHAR(64) NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_pix_key_verifications_user
+    ON pix_key_verifications(user_id, created_at);
+
+-- Reivindicação de posse só conclui pelo prazo se o reivindicante provou a posse da chave
+ALTER TABLE pix_key_claims ADD COLUMN IF NOT EXISTS verified_at TIMESTAMPTZ NULL;
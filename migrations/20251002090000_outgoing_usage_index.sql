@@ -0,0 +1,5 @@
+-- Pagamentos de boleto e capturas de reserva também contam para os limites de saída
+DROP INDEX IF EXISTS idx_transactions_outgoing;
+CREATE INDEX IF NOT EXISTS idx_transactions_outgoing
+    ON transactions(from_account_id, created_at)
+    WHERE transaction_type IN ('withdraw', 'transfer_debit', 'boleto_payment', 'hold_capture');
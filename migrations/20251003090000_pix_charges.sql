@@ -0,0 +1,26 @@
+-- ========================
+-- Tabela: pix_charges
+-- ========================
+-- Cobrança de um BR Code dinâmico. O payload leva a URL dela (`/pix/cob/{id}`)
+-- no lugar da chave; a transferência que informa o `txid` paga a cobrança uma
+-- única vez. A situação `expired` não é gravada: vem de `expires_at`.
+CREATE TABLE IF NOT EXISTS pix_charges (
+    id UUID PRIMARY KEY,
+    txid VARCHAR(25) NOT NULL UNIQUE,
+    user_id UUID NOT NULL REFERENCES users(id),
+    account_id UUID NOT NULL REFERENCES accounts(id),
+    pix_key_id UUID NOT NULL REFERENCES pix_keys(id),
+    key_value VARCHAR(77) NOT NULL,
+    amount DECIMAL(15,2) NOT NULL CHECK (amount > 0),
+    description VARCHAR(140) NULL,
+    status VARCHAR(10) NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'completed')),
+    expires_at TIMESTAMPTZ NOT NULL,
+    transaction_id UUID NULL REFERENCES transactions(id),
+    paid_at TIMESTAMPTZ NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_pix_charges_user
+    ON pix_charges(user_id, created_at);
@@ -0,0 +1,6 @@
+-- Cadeia de auditoria dividida em partições: cada transação grava em uma
+-- (`txid_current() % 16`), com lock próprio, e o `prev_hash` aponta para o
+-- último evento da mesma partição. Os eventos anteriores ficam todos na 0.
+ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS chain SMALLINT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_audit_events_chain ON audit_events(chain, seq);
@@ -0,0 +1,229 @@
+//! Entrada standalone, fora do Shuttle: lê a configuração do ambiente
+//! (e de um `config.toml` opcional) e sobe o mesmo `app` com actix-web.
+use std::{
+    process::ExitCode,
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
+};
+
+use actix_files as fs;
+use actix_web::{App, HttpServer, rt, web};
+use api_mini_bank::{
+    DRAINING, METRICS_TOKEN, MIGRATOR, app,
+    config::AppConfig,
+    events::{NotificationHub, OutboxDispatcher, WebhookDispatcher, WebhookFanout},
+    fx::{FileRateProvider, FxRateProvider},
+    middleware::RequestTracing,
+    scheduler::{HoldExpirer, InterestAccruer, ReversalRecovery, TransferScheduler},
+    storage::{DocumentStorage, LocalStorage},
+    telemetry,
+    verification::{CodeSender, HttpCodeSender, LogCodeSender},
+};
+
+#[actix_web::main]
+async fn main() -> ExitCode {
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Configuração inválida: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // mantido até o fim do main para exportar os spans pendentes
+    let _telemetry = match telemetry::init(&config.telemetry) {
+        Ok(guard) => guard,
+        Err(err) => {
+            eprintln!("Falha ao configurar logs: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let pool = match config.connect().await {
+        Ok(pool) => pool,
+        Err(err) => {
+            tracing::error!("Falha ao conectar no banco de dados: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if config.database.run_migrations
+        && let Err(err) = MIGRATOR.run(&pool).await
+    {
+        tracing::error!("Falha ao executar migrations: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    config.auth.clone().install();
+    config.limits.clone().install();
+    config.fees.clone().install();
+    config.interest.clone().install();
+    config.fx.clone().install();
+    config.pix.clone().install();
+    config.webhooks.clone().install();
+    match config.server.metrics_token.clone() {
+        Some(token) => METRICS_TOKEN
+            .set(token)
+            .expect("METRICS_TOKEN já foi definido"),
+        None => tracing::warn!("METRICS_TOKEN não configurado: GET /metrics desligado"),
+    }
+
+    if config.outbox.enabled {
+        match OutboxDispatcher::from_config(pool.clone(), &config.outbox) {
+            Ok(mut dispatcher) => {
+                // os webhooks dependem do outbox: cada evento vira uma entrega por assinatura
+                if config.webhooks.enabled {
+                    dispatcher = dispatcher.with_sink(Box::new(WebhookFanout::new(pool.clone())));
+                }
+                rt::spawn(dispatcher.run());
+            }
+            Err(err) => {
+                tracing::error!("Falha ao configurar os sinks de eventos: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if config.webhooks.enabled {
+        match WebhookDispatcher::from_config(pool.clone(), &config.webhooks) {
+            Ok(dispatcher) => {
+                rt::spawn(dispatcher.run());
+            }
+            Err(err) => {
+                tracing::error!("Falha ao configurar o envio de webhooks: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let fx_rates: Arc<dyn FxRateProvider> =
+        match FileRateProvider::open(&config.fx.rates_file).await {
+            Ok(provider) => Arc::new(provider),
+            Err(err) => {
+                tracing::error!(
+                    "Falha ao carregar as taxas de câmbio de {}: {err}",
+                    config.fx.rates_file
+                );
+                return ExitCode::FAILURE;
+            }
+        };
+
+    if config.scheduler.enabled {
+        rt::spawn(TransferScheduler::new(pool.clone(), fx_rates.clone(), &config.scheduler).run());
+        rt::spawn(HoldExpirer::new(pool.clone(), &config.scheduler).run());
+        rt::spawn(ReversalRecovery::new(pool.clone(), &config.scheduler).run());
+        if config.interest.enabled {
+            rt::spawn(
+                InterestAccruer::new(pool.clone(), &config.scheduler, &config.interest).run(),
+            );
+        }
+    }
+
+    let storage: Arc<dyn DocumentStorage> =
+        match LocalStorage::open(&config.storage.local_dir).await {
+            Ok(storage) => Arc::new(storage),
+            Err(err) => {
+                tracing::error!(
+                    "Falha ao abrir o storage de documentos em {}: {err}",
+                    config.storage.local_dir
+                );
+                return ExitCode::FAILURE;
+            }
+        };
+    let storage = web::Data::from(storage);
+
+    let fx_rates = web::Data::from(fx_rates);
+
+    // a validação da configuração já exige o gateway fora do modo de desenvolvimento
+    let code_sender: Arc<dyn CodeSender> = match &config.verification.gateway_url {
+        Some(url) => match HttpCodeSender::new(
+            url,
+            config.verification.gateway_token.clone(),
+            Duration::from_secs(config.verification.timeout_secs),
+        ) {
+            Ok(sender) => Arc::new(sender),
+            Err(err) => {
+                tracing::error!("Falha ao configurar o envio dos códigos de verificação: {err}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => {
+            tracing::warn!(
+                "VERIFICATION_LOG_SENDER ativo: os códigos de verificação não são enviados"
+            );
+            Arc::new(LogCodeSender)
+        }
+    };
+    let code_sender = web::Data::from(code_sender);
+
+    // um LISTEN por réplica alimenta as conexões SSE/WebSocket abertas nela
+    let hub = web::Data::new(NotificationHub::new(&config.realtime));
+    let listener = rt::spawn(hub.listener(pool.clone()).run());
+
+    let static_dir = config.server.static_dir.clone();
+    let mut server = HttpServer::new(move || {
+        App::new()
+            .wrap(RequestTracing)
+            .app_data(web::Data::new(pool.clone()))
+            .app_data(hub.clone())
+            .app_data(storage.clone())
+            .app_data(fx_rates.clone())
+            .app_data(code_sender.clone())
+            .configure(app)
+            .service(fs::Files::new("/", &static_dir).index_file("index.html"))
+    })
+    .shutdown_timeout(config.server.shutdown_timeout_secs)
+    // os sinais são tratados abaixo, para drenar antes de parar
+    .disable_signals();
+    if let Some(workers) = config.server.workers {
+        server = server.workers(workers);
+    }
+
+    let server = match server.bind(&config.server.bind_address) {
+        Ok(server) => server.run(),
+        Err(err) => {
+            tracing::error!("Falha ao abrir {}: {err}", config.server.bind_address);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let handle = server.handle();
+    let drain = Duration::from_secs(config.server.drain_secs);
+    rt::spawn(async move {
+        shutdown_signal().await;
+        DRAINING.store(true, Ordering::SeqCst);
+        tracing::info!(drain_secs = drain.as_secs(), "Desligando: readiness em 503");
+        rt::time::sleep(drain).await;
+        handle.stop(true).await;
+    });
+
+    tracing::info!(bind_address = %config.server.bind_address, "Mini Bank ouvindo");
+    let result = server.await;
+    DRAINING.store(true, Ordering::SeqCst);
+    let _ = listener.await;
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            tracing::error!("Servidor encerrado com erro: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Aguarda SIGINT (Ctrl+C) ou, em Unix, SIGTERM do orquestrador
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut sigterm = signal(SignalKind::terminate()).expect("falha ao registrar SIGTERM");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = sigterm.recv() => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = tokio::signal::ctrl_c().await;
+}
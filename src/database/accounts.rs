@@ -0,0 +1,190 @@
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::account::{Account, error::AccountError, generate_account_number};
+
+/// Tentativas de gerar um número de conta livre antes de desistir
+const ACCOUNT_NUMBER_ATTEMPTS: usize = 5;
+
+pub struct AccountRepository;
+
+impl AccountRepository {
+    /// Insere a conta; em colisão do número, sorteia outro
+    #[tracing::instrument(name = "AccountRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(
+        conn: &mut PgConnection,
+        account: &mut Account,
+    ) -> Result<(), AccountError> {
+        let query = r#"
+              INSERT INTO accounts (id, user_id, account_number, account_type, currency, balance, is_active, created_at, updated_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+              ON CONFLICT (account_number) DO NOTHING
+              RETURNING id
+          "#;
+
+        for _ in 0..ACCOUNT_NUMBER_ATTEMPTS {
+            let inserted: Option<Uuid> = sqlx::query_scalar(query)
+                .bind(account.id)
+                .bind(account.user_id)
+                .bind(&account.account_number)
+                .bind(account.account_type)
+                .bind(account.currency)
+                .bind(account.balance)
+                .bind(account.is_active)
+                .bind(account.created_at)
+                .bind(account.updated_at)
+                .fetch_optional(&mut *conn)
+                .await?;
+
+            if inserted.is_some() {
+                return Ok(());
+            }
+            account.account_number = generate_account_number();
+        }
+
+        Err(AccountError::DuplicateAccountNumber)
+    }
+
+    /// Busca conta por ID
+    #[tracing::instrument(name = "AccountRepository::find_by_id", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_by_id(
+        pool: &PgPool,
+        account_id: Uuid,
+    ) -> Result<Option<Account>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, user_id, account_number, account_type, currency, balance, held_balance, overdraft_limit, is_active, created_at, updated_at
+                    FROM accounts
+                    WHERE id = $1
+                "#;
+        sqlx::query_as::<_, Account>(query)
+            .bind(account_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Contas do usuário e das empresas ativas em que ele é operador, mais antigas primeiro
+    #[tracing::instrument(name = "AccountRepository::list_accessible", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_accessible(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<Account>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, user_id, account_number, account_type, currency, balance, held_balance, overdraft_limit, is_active, created_at, updated_at
+                    FROM accounts
+                    WHERE user_id = $1
+                       OR user_id IN (
+                           SELECT o.business_id
+                           FROM business_operators o
+                           JOIN users b ON b.id = o.business_id AND b.is_active = true
+                           WHERE o.user_id = $1
+                       )
+                    ORDER BY created_at
+                "#;
+        sqlx::query_as::<_, Account>(query)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Busca conta por ID travando a linha até o fim da transação
+    #[tracing::instrument(name = "AccountRepository::lock_by_id", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_by_id(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<Option<Account>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, user_id, account_number, account_type, currency, balance, held_balance, overdraft_limit, is_active, created_at, updated_at
+                    FROM accounts
+                    WHERE id = $1
+                    FOR UPDATE
+                "#;
+        sqlx::query_as::<_, Account>(query)
+            .bind(account_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// ID da conta pelo número (sem trava; a trava vem em `lock_by_id`)
+    #[tracing::instrument(name = "AccountRepository::find_id_by_number", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_id_by_number(
+        conn: &mut PgConnection,
+        account_number: &str,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let query = r#"SELECT id FROM accounts WHERE account_number = $1"#;
+        sqlx::query_scalar(query)
+            .bind(account_number.trim())
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "AccountRepository::update_balance", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn update_balance(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        balance: Decimal,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE accounts
+                    SET balance = $1, updated_at = NOW()
+                    WHERE id = $2
+                "#;
+        sqlx::query(query)
+            .bind(balance)
+            .bind(account_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "AccountRepository::update_overdraft_limit", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn update_overdraft_limit(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        overdraft_limit: Decimal,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE accounts
+                    SET overdraft_limit = $1, updated_at = NOW()
+                    WHERE id = $2
+                "#;
+        sqlx::query(query)
+            .bind(overdraft_limit)
+            .bind(account_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Soma das reservas ativas; o saldo contábil não muda
+    #[tracing::instrument(name = "AccountRepository::update_held", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn update_held(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        held_balance: Decimal,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE accounts
+                    SET held_balance = $1, updated_at = NOW()
+                    WHERE id = $2
+                "#;
+        sqlx::query(query)
+            .bind(held_balance)
+            .bind(account_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Desativação (RF012); a conta e o histórico continuam no banco
+    #[tracing::instrument(name = "AccountRepository::deactivate", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn deactivate(conn: &mut PgConnection, account_id: Uuid) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE accounts
+                    SET is_active = false, updated_at = NOW()
+                    WHERE id = $1
+                "#;
+        sqlx::query(query).bind(account_id).execute(conn).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use futures_util::TryStreamExt;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
+
+use crate::models::audit::{
+    AUDIT_CHAINS, AuditEvent, AuditQuery, ChainVerification, GENESIS_HASH, NewAuditEvent,
+};
+
+/// Chave do advisory lock da partição 0; a partição `n` usa `AUDIT_CHAIN_LOCK + n`
+const AUDIT_CHAIN_LOCK: i64 = 0x4155_4449_5400;
+
+const AUDIT_COLUMNS: &str = "seq, chain, id, occurred_at, actor_id, action, target_type, target_id, ip, user_agent, request_id, before, after, metadata, prev_hash, hash";
+
+pub struct AuditRepository;
+
+impl AuditRepository {
+    /// Grava o evento na transação recebida, encadeado ao último hash da partição
+    ///
+    /// A partição vem do ID da transação do banco, então todos os eventos de uma
+    /// transação caem na mesma e ela nunca espera por dois locks. O lock é
+    /// liberado no commit/rollback: cada partição aceita uma transação por vez,
+    /// e as 16 juntas dividem a espera que um lock global impunha a toda escrita.
+    #[tracing::instrument(name = "AuditRepository::append", skip_all, fields(db.system = "postgresql", db.operation = "INSERT", audit.action = event.action))]
+    pub async fn append(conn: &mut PgConnection, event: &NewAuditEvent) -> Result<(), sqlx::Error> {
+        let chain: i16 = sqlx::query_scalar("SELECT (txid_current() % $1)::SMALLINT")
+            .bind(i64::from(AUDIT_CHAINS))
+            .fetch_one(&mut *conn)
+            .await?;
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(AUDIT_CHAIN_LOCK + i64::from(chain))
+            .execute(&mut *conn)
+            .await?;
+
+        let prev_hash: Option<String> = sqlx::query_scalar(
+            "SELECT hash FROM audit_events WHERE chain = $1 ORDER BY seq DESC LIMIT 1",
+        )
+        .bind(chain)
+        .fetch_optional(&mut *conn)
+        .await?;
+        let prev_hash = prev_hash.unwrap_or_else(|| GENESIS_HASH.to_string());
+        let hash = event.hash(&prev_hash);
+
+        let query = r#"
+              INSERT INTO audit_events (id, occurred_at, actor_id, action, target_type, target_id, ip, user_agent, request_id, before, after, metadata, prev_hash, hash, chain)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+          "#;
+        sqlx::query(query)
+            .bind(event.id)
+            .bind(event.occurred_at)
+            .bind(event.actor_id)
+            .bind(event.action)
+            .bind(event.target_type)
+            .bind(event.target_id)
+            .bind(&event.ip)
+            .bind(&event.user_agent)
+            .bind(&event.request_id)
+            .bind(&event.before)
+            .bind(&event.after)
+            .bind(&event.metadata)
+            .bind(prev_hash)
+            .bind(hash)
+            .bind(chain)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Consulta paginada para administradores, mais recentes primeiro
+    #[tracing::instrument(name = "AuditRepository::search", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn search(
+        pool: &PgPool,
+        filter: &AuditQuery,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<AuditEvent>, i64), sqlx::Error> {
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM audit_events WHERE TRUE");
+        push_filters(&mut count, filter);
+        let total: i64 = count.build_query_scalar().fetch_one(pool).await?;
+
+        let mut select = QueryBuilder::new(format!(
+            "SELECT {AUDIT_COLUMNS} FROM audit_events WHERE TRUE"
+        ));
+        push_filters(&mut select, filter);
+        select
+            .push(" ORDER BY seq DESC LIMIT ")
+            .push_bind(i64::from(limit))
+            .push(" OFFSET ")
+            .push_bind(offset);
+        let events = select
+            .build_query_as::<AuditEvent>()
+            .fetch_all(pool)
+            .await?;
+
+        Ok((events, total))
+    }
+
+    /// Percorre as partições da cadeia recalculando cada hash
+    #[tracing::instrument(name = "AuditRepository::verify_chain", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn verify_chain(pool: &PgPool) -> Result<ChainVerification, sqlx::Error> {
+        let query = format!("SELECT {AUDIT_COLUMNS} FROM audit_events ORDER BY seq");
+        let mut rows = sqlx::query_as::<_, AuditEvent>(&query).fetch(pool);
+
+        let mut expected_prev = vec![GENESIS_HASH.to_string(); AUDIT_CHAINS as usize];
+        let mut checked = 0;
+        while let Some(event) = rows.try_next().await? {
+            checked += 1;
+            let expected = usize::try_from(event.chain)
+                .ok()
+                .and_then(|chain| expected_prev.get_mut(chain));
+            match expected {
+                Some(expected)
+                    if event.prev_hash == *expected && event.recompute_hash() == event.hash =>
+                {
+                    *expected = event.hash;
+                }
+                _ => {
+                    return Ok(ChainVerification {
+                        valid: false,
+                        checked,
+                        broken_at: Some(event.seq),
+                    });
+                }
+            }
+        }
+
+        Ok(ChainVerification {
+            valid: true,
+            checked,
+            broken_at: None,
+        })
+    }
+}
+
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filter: &AuditQuery) {
+    if let Some(actor_id) = filter.actor_id {
+        builder.push(" AND actor_id = ").push_bind(actor_id);
+    }
+    if let Some(action) = &filter.action {
+        builder.push(" AND action = ").push_bind(action.clone());
+    }
+    if let Some(target_type) = &filter.target_type {
+        builder
+            .push(" AND target_type = ")
+            .push_bind(target_type.clone());
+    }
+    if let Some(target_id) = filter.target_id {
+        builder.push(" AND target_id = ").push_bind(target_id);
+    }
+    if let Some(from) = filter.from {
+        builder.push(" AND occurred_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        builder.push(" AND occurred_at < ").push_bind(to);
+    }
+}
@@ -0,0 +1,145 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::boleto::{Boleto, BoletoStatus};
+
+const COLUMNS: &str = "id, account_id, user_id, our_number, amount, due_date, payer_name, description, barcode, digitable_line, status, payment_transaction_id, paid_at, created_at, updated_at";
+
+pub struct BoletoRepository;
+
+impl BoletoRepository {
+    /// Próximo "nosso número" (sequência do banco, nunca reutilizado)
+    #[tracing::instrument(name = "BoletoRepository::next_our_number", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn next_our_number(conn: &mut PgConnection) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT nextval('boleto_our_number_seq')")
+            .fetch_one(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "BoletoRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(conn: &mut PgConnection, boleto: &Boleto) -> Result<(), sqlx::Error> {
+        let query = r#"
+              INSERT INTO boletos (id, account_id, user_id, our_number, amount, due_date, payer_name, description, barcode, digitable_line, status, created_at, updated_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+          "#;
+        sqlx::query(query)
+            .bind(boleto.id)
+            .bind(boleto.account_id)
+            .bind(boleto.user_id)
+            .bind(&boleto.our_number)
+            .bind(boleto.amount)
+            .bind(boleto.due_date)
+            .bind(&boleto.payer_name)
+            .bind(&boleto.description)
+            .bind(&boleto.barcode)
+            .bind(&boleto.digitable_line)
+            .bind(boleto.status)
+            .bind(boleto.created_at)
+            .bind(boleto.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Boletos emitidos pelo usuário, mais recentes primeiro
+    #[tracing::instrument(name = "BoletoRepository::list_by_user", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<Boleto>, sqlx::Error> {
+        let query =
+            format!("SELECT {COLUMNS} FROM boletos WHERE user_id = $1 ORDER BY created_at DESC");
+        sqlx::query_as::<_, Boleto>(&query)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    #[tracing::instrument(name = "BoletoRepository::find_owned", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_owned(
+        pool: &PgPool,
+        boleto_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Boleto>, sqlx::Error> {
+        let query = format!("SELECT {COLUMNS} FROM boletos WHERE id = $1 AND user_id = $2");
+        sqlx::query_as::<_, Boleto>(&query)
+            .bind(boleto_id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Boleto do usuário, travado até o fim da transação
+    #[tracing::instrument(name = "BoletoRepository::lock_owned", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_owned(
+        conn: &mut PgConnection,
+        boleto_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Boleto>, sqlx::Error> {
+        let query =
+            format!("SELECT {COLUMNS} FROM boletos WHERE id = $1 AND user_id = $2 FOR UPDATE");
+        sqlx::query_as::<_, Boleto>(&query)
+            .bind(boleto_id)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Boleto emitido aqui, pelo código de barras, travado até o fim da transação
+    #[tracing::instrument(name = "BoletoRepository::lock_by_barcode", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_by_barcode(
+        conn: &mut PgConnection,
+        barcode: &str,
+    ) -> Result<Option<Boleto>, sqlx::Error> {
+        let query = format!("SELECT {COLUMNS} FROM boletos WHERE barcode = $1 FOR UPDATE");
+        sqlx::query_as::<_, Boleto>(&query)
+            .bind(barcode)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "BoletoRepository::mark_paid", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn mark_paid(
+        conn: &mut PgConnection,
+        boleto_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<Boleto, sqlx::Error> {
+        let query = format!(
+            "UPDATE boletos SET status = $2, payment_transaction_id = $3, paid_at = NOW(), updated_at = NOW() WHERE id = $1 RETURNING {COLUMNS}"
+        );
+        sqlx::query_as::<_, Boleto>(&query)
+            .bind(boleto_id)
+            .bind(BoletoStatus::Paid)
+            .bind(transaction_id)
+            .fetch_one(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "BoletoRepository::cancel", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn cancel(conn: &mut PgConnection, boleto_id: Uuid) -> Result<Boleto, sqlx::Error> {
+        let query = format!(
+            "UPDATE boletos SET status = $2, updated_at = NOW() WHERE id = $1 RETURNING {COLUMNS}"
+        );
+        sqlx::query_as::<_, Boleto>(&query)
+            .bind(boleto_id)
+            .bind(BoletoStatus::Cancelled)
+            .fetch_one(conn)
+            .await
+    }
+
+    /// Cancela os boletos em aberto da conta (encerramento); devolve quantos
+    #[tracing::instrument(name = "BoletoRepository::cancel_by_account", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn cancel_by_account(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let query = r#"
+              UPDATE boletos SET status = $2, updated_at = NOW()
+              WHERE account_id = $1 AND status = $3
+          "#;
+        let result = sqlx::query(query)
+            .bind(account_id)
+            .bind(BoletoStatus::Cancelled)
+            .bind(BoletoStatus::Open)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,118 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::business::{BusinessOperator, OperatorPermission};
+
+/// Operador com nome, email e documento do login dele
+const OPERATOR_SELECT: &str = r#"
+    SELECT o.id, o.business_id, o.user_id, u.name, u.email, u.document, o.permissions, o.created_at, o.updated_at
+    FROM business_operators o
+    JOIN users u ON u.id = o.user_id
+"#;
+
+pub struct OperatorRepository;
+
+impl OperatorRepository {
+    /// Autoriza o usuário na empresa; `false` se ele já é operador dela
+    #[tracing::instrument(name = "OperatorRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(
+        conn: &mut PgConnection,
+        id: Uuid,
+        business_id: Uuid,
+        user_id: Uuid,
+        permissions: &[OperatorPermission],
+    ) -> Result<bool, sqlx::Error> {
+        let query = r#"
+              INSERT INTO business_operators (id, business_id, user_id, permissions)
+              VALUES ($1, $2, $3, $4)
+              ON CONFLICT (business_id, user_id) DO NOTHING
+          "#;
+        let result = sqlx::query(query)
+            .bind(id)
+            .bind(business_id)
+            .bind(user_id)
+            .bind(permissions)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Operadores da empresa, mais antigos primeiro
+    #[tracing::instrument(name = "OperatorRepository::list_by_business", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_by_business(
+        pool: &PgPool,
+        business_id: Uuid,
+    ) -> Result<Vec<BusinessOperator>, sqlx::Error> {
+        let query = format!("{OPERATOR_SELECT} WHERE o.business_id = $1 ORDER BY o.created_at");
+        sqlx::query_as::<_, BusinessOperator>(&query)
+            .bind(business_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Operador da empresa pelo ID, travado até o fim da transação
+    #[tracing::instrument(name = "OperatorRepository::lock", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock(
+        conn: &mut PgConnection,
+        operator_id: Uuid,
+        business_id: Uuid,
+    ) -> Result<Option<BusinessOperator>, sqlx::Error> {
+        let query =
+            format!("{OPERATOR_SELECT} WHERE o.id = $1 AND o.business_id = $2 FOR UPDATE OF o");
+        sqlx::query_as::<_, BusinessOperator>(&query)
+            .bind(operator_id)
+            .bind(business_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "OperatorRepository::update_permissions", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn update_permissions(
+        conn: &mut PgConnection,
+        operator_id: Uuid,
+        permissions: &[OperatorPermission],
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE business_operators
+                    SET permissions = $1, updated_at = NOW()
+                    WHERE id = $2
+                "#;
+        sqlx::query(query)
+            .bind(permissions)
+            .bind(operator_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "OperatorRepository::delete", skip_all, fields(db.system = "postgresql", db.operation = "DELETE"))]
+    pub async fn delete(conn: &mut PgConnection, operator_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM business_operators WHERE id = $1")
+            .bind(operator_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Permissões do usuário nas contas da empresa; `None` se ele não é operador
+    /// ativo dela (empresa ou operador desativados não contam)
+    #[tracing::instrument(name = "OperatorRepository::permissions", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn permissions(
+        conn: &mut PgConnection,
+        business_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<Vec<OperatorPermission>>, sqlx::Error> {
+        let query = r#"
+                    SELECT o.permissions
+                    FROM business_operators o
+                    JOIN users b ON b.id = o.business_id AND b.is_active = true
+                    JOIN users u ON u.id = o.user_id AND u.is_active = true
+                    WHERE o.business_id = $1 AND o.user_id = $2
+                "#;
+        sqlx::query_scalar(query)
+            .bind(business_id)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+}
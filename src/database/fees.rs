@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::fee::{FeeOperation, FeeWaiver};
+
+const COLUMNS: &str =
+    "id, account_id, operation, reason, expires_at, created_by, revoked_at, created_at";
+
+pub struct FeeRepository;
+
+impl FeeRepository {
+    /// Quantas operações tarifáveis a conta já fez desde `since`; transferências
+    /// só contam quando o destino é de outro cliente
+    #[tracing::instrument(name = "FeeRepository::count_since", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn count_since(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        operation: FeeOperation,
+        since: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        let query = match operation {
+            FeeOperation::Withdraw => {
+                r#"
+                    SELECT COUNT(*) FROM transactions
+                    WHERE from_account_id = $1 AND transaction_type = 'withdraw'
+                      AND status <> 'failed' AND created_at >= $2
+                "#
+            }
+            FeeOperation::Transfer => {
+                r#"
+                    SELECT COUNT(*) FROM transactions t
+                    JOIN accounts source ON source.id = t.from_account_id
+                    JOIN accounts destination ON destination.id = t.to_account_id
+                    WHERE t.from_account_id = $1 AND t.transaction_type = 'transfer_debit'
+                      AND destination.user_id <> source.user_id AND t.created_at >= $2
+                "#
+            }
+            FeeOperation::BoletoIssue => {
+                r#"
+                    SELECT COUNT(*) FROM boletos
+                    WHERE account_id = $1 AND created_at >= $2
+                "#
+            }
+        };
+        sqlx::query_scalar(query)
+            .bind(account_id)
+            .bind(since)
+            .fetch_one(conn)
+            .await
+    }
+
+    /// Isenção em vigor para a operação (a específica ou a de todas)
+    #[tracing::instrument(name = "FeeRepository::active_waiver", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn active_waiver(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        operation: FeeOperation,
+    ) -> Result<Option<FeeWaiver>, sqlx::Error> {
+        let query = format!(
+            r#"
+                    SELECT {COLUMNS} FROM fee_waivers
+                    WHERE account_id = $1
+                      AND (operation IS NULL OR operation = $2)
+                      AND revoked_at IS NULL
+                      AND (expires_at IS NULL OR expires_at > NOW())
+                    ORDER BY created_at
+                    LIMIT 1
+                "#
+        );
+        sqlx::query_as::<_, FeeWaiver>(&query)
+            .bind(account_id)
+            .bind(operation)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "FeeRepository::insert_waiver", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_waiver(
+        conn: &mut PgConnection,
+        waiver: &FeeWaiver,
+    ) -> Result<(), sqlx::Error> {
+        let query =
+            format!("INSERT INTO fee_waivers ({COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)");
+        sqlx::query(&query)
+            .bind(waiver.id)
+            .bind(waiver.account_id)
+            .bind(waiver.operation)
+            .bind(&waiver.reason)
+            .bind(waiver.expires_at)
+            .bind(waiver.created_by)
+            .bind(waiver.revoked_at)
+            .bind(waiver.created_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Isenções da conta, mais recentes primeiro (inclusive vencidas e revogadas)
+    #[tracing::instrument(name = "FeeRepository::list_waivers", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_waivers(
+        pool: &PgPool,
+        account_id: Uuid,
+    ) -> Result<Vec<FeeWaiver>, sqlx::Error> {
+        let query = format!(
+            "SELECT {COLUMNS} FROM fee_waivers WHERE account_id = $1 ORDER BY created_at DESC, id"
+        );
+        sqlx::query_as::<_, FeeWaiver>(&query)
+            .bind(account_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    #[tracing::instrument(name = "FeeRepository::lock_waiver", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_waiver(
+        conn: &mut PgConnection,
+        waiver_id: Uuid,
+    ) -> Result<Option<FeeWaiver>, sqlx::Error> {
+        let query = format!("SELECT {COLUMNS} FROM fee_waivers WHERE id = $1 FOR UPDATE");
+        sqlx::query_as::<_, FeeWaiver>(&query)
+            .bind(waiver_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "FeeRepository::revoke_waiver", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn revoke_waiver(
+        conn: &mut PgConnection,
+        waiver_id: Uuid,
+    ) -> Result<FeeWaiver, sqlx::Error> {
+        let query =
+            format!("UPDATE fee_waivers SET revoked_at = NOW() WHERE id = $1 RETURNING {COLUMNS}");
+        sqlx::query_as::<_, FeeWaiver>(&query)
+            .bind(waiver_id)
+            .fetch_one(conn)
+            .await
+    }
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::fx::FxQuote;
+
+const COLUMNS: &str = "id, user_id, from_account_id, to_account_id, from_currency, to_currency, amount, converted_amount, mid_rate, spread, rate, provider, expires_at, transaction_id, used_at, created_at";
+
+pub struct FxRepository;
+
+impl FxRepository {
+    #[tracing::instrument(name = "FxRepository::insert_quote", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_quote(conn: &mut PgConnection, quote: &FxQuote) -> Result<(), sqlx::Error> {
+        let query = format!(
+            "INSERT INTO fx_quotes ({COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)"
+        );
+        sqlx::query(&query)
+            .bind(quote.id)
+            .bind(quote.user_id)
+            .bind(quote.from_account_id)
+            .bind(quote.to_account_id)
+            .bind(quote.from_currency)
+            .bind(quote.to_currency)
+            .bind(quote.amount)
+            .bind(quote.converted_amount)
+            .bind(quote.mid_rate)
+            .bind(quote.spread)
+            .bind(quote.rate)
+            .bind(&quote.provider)
+            .bind(quote.expires_at)
+            .bind(quote.transaction_id)
+            .bind(quote.used_at)
+            .bind(quote.created_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Cotação pedida pelo usuário, travada até o fim da transação
+    #[tracing::instrument(name = "FxRepository::lock_quote", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_quote(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        quote_id: Uuid,
+    ) -> Result<Option<FxQuote>, sqlx::Error> {
+        let query =
+            format!("SELECT {COLUMNS} FROM fx_quotes WHERE id = $1 AND user_id = $2 FOR UPDATE");
+        sqlx::query_as::<_, FxQuote>(&query)
+            .bind(quote_id)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "FxRepository::mark_used", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn mark_used(
+        conn: &mut PgConnection,
+        quote_id: Uuid,
+        transaction_id: Uuid,
+        used_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE fx_quotes SET transaction_id = $2, used_at = $3 WHERE id = $1")
+            .bind(quote_id)
+            .bind(transaction_id)
+            .bind(used_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use sqlx::PgPool;
+
+pub struct HealthRepository;
+
+impl HealthRepository {
+    /// Consulta mínima para confirmar que o banco responde
+    pub async fn ping(pool: &PgPool) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1").execute(pool).await?;
+        Ok(())
+    }
+
+    /// Versões de migrations aplicadas com sucesso
+    pub async fn applied_migrations(pool: &PgPool) -> Result<Vec<i64>, sqlx::Error> {
+        let query = r#"
+                    SELECT version
+                    FROM _sqlx_migrations
+                    WHERE success = true
+                    ORDER BY version
+                "#;
+        sqlx::query_scalar(query).fetch_all(pool).await
+    }
+}
@@ -0,0 +1,134 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::hold::{Hold, HoldStatus};
+
+const COLUMNS: &str = "id, account_id, created_by, amount, captured_amount, description, status, transaction_id, expires_at, created_at, updated_at";
+
+pub struct HoldRepository;
+
+impl HoldRepository {
+    #[tracing::instrument(name = "HoldRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(conn: &mut PgConnection, hold: &Hold) -> Result<(), sqlx::Error> {
+        let query = format!(
+            "INSERT INTO holds ({COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
+        );
+        sqlx::query(&query)
+            .bind(hold.id)
+            .bind(hold.account_id)
+            .bind(hold.created_by)
+            .bind(hold.amount)
+            .bind(hold.captured_amount)
+            .bind(&hold.description)
+            .bind(hold.status)
+            .bind(hold.transaction_id)
+            .bind(hold.expires_at)
+            .bind(hold.created_at)
+            .bind(hold.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Reservas da conta, mais recentes primeiro
+    #[tracing::instrument(name = "HoldRepository::list_by_account", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_by_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        status: Option<HoldStatus>,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<Hold>, i64), sqlx::Error> {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM holds WHERE account_id = $1 AND ($2::varchar IS NULL OR status = $2)",
+        )
+        .bind(account_id)
+        .bind(status)
+        .fetch_one(pool)
+        .await?;
+
+        let query = format!(
+            r#"
+                    SELECT {COLUMNS} FROM holds
+                    WHERE account_id = $1 AND ($2::varchar IS NULL OR status = $2)
+                    ORDER BY created_at DESC, id
+                    LIMIT $3 OFFSET $4
+                "#
+        );
+        let holds = sqlx::query_as::<_, Hold>(&query)
+            .bind(account_id)
+            .bind(status)
+            .bind(i64::from(limit))
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok((holds, total))
+    }
+
+    #[tracing::instrument(name = "HoldRepository::find", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find(
+        pool: &PgPool,
+        account_id: Uuid,
+        hold_id: Uuid,
+    ) -> Result<Option<Hold>, sqlx::Error> {
+        let query = format!("SELECT {COLUMNS} FROM holds WHERE id = $1 AND account_id = $2");
+        sqlx::query_as::<_, Hold>(&query)
+            .bind(hold_id)
+            .bind(account_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Reserva da conta, travada até o fim da transação
+    ///
+    /// Trave a reserva antes da conta: é a mesma ordem da expiração.
+    #[tracing::instrument(name = "HoldRepository::lock", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        hold_id: Uuid,
+    ) -> Result<Option<Hold>, sqlx::Error> {
+        let query =
+            format!("SELECT {COLUMNS} FROM holds WHERE id = $1 AND account_id = $2 FOR UPDATE");
+        sqlx::query_as::<_, Hold>(&query)
+            .bind(hold_id)
+            .bind(account_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Trava a reserva ativa vencida mais antiga; `SKIP LOCKED` deixa cada
+    /// réplica expirar uma diferente
+    #[tracing::instrument(name = "HoldRepository::claim_expired", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn claim_expired(conn: &mut PgConnection) -> Result<Option<Hold>, sqlx::Error> {
+        let query = format!(
+            r#"
+                    SELECT {COLUMNS} FROM holds
+                    WHERE status = 'active' AND expires_at <= NOW()
+                    ORDER BY expires_at
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                "#
+        );
+        sqlx::query_as::<_, Hold>(&query).fetch_optional(conn).await
+    }
+
+    /// Grava o encerramento (captura, liberação ou expiração)
+    #[tracing::instrument(name = "HoldRepository::save", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn save(conn: &mut PgConnection, hold: &Hold) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE holds
+                    SET captured_amount = $2, status = $3, updated_at = $4
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(hold.id)
+            .bind(hold.captured_amount)
+            .bind(hold.status)
+            .bind(hold.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
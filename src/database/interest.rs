@@ -0,0 +1,166 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::{
+    account::{Account, AccountType},
+    interest::{InterestAccrual, PendingInterest},
+};
+
+pub struct InterestRepository;
+
+impl InterestRepository {
+    /// Trava uma conta ativa dos tipos que rendem (ou, com `overdraft`, corrente que
+    /// pode ter fechado o dia no cheque especial), aberta até `created_before` e ainda
+    /// sem apuração no dia; `SKIP LOCKED` deixa cada réplica apurar uma diferente
+    ///
+    /// A corrente entra se está negativa agora ou se teve lançamentos depois do fim
+    /// do dia: sem lançamentos, o saldo do fim do dia é o atual
+    #[tracing::instrument(name = "InterestRepository::claim_unaccrued", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn claim_unaccrued(
+        conn: &mut PgConnection,
+        accrual_date: NaiveDate,
+        created_before: DateTime<Utc>,
+        account_types: &[AccountType],
+        overdraft: bool,
+    ) -> Result<Option<Account>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, user_id, account_number, account_type, currency, balance, held_balance, overdraft_limit, is_active, created_at, updated_at
+                    FROM accounts a
+                    WHERE a.is_active AND a.created_at < $3
+                      AND (
+                          a.account_type = ANY($1)
+                          OR ($4 AND a.account_type = 'checking' AND (
+                              a.balance < 0
+                              OR EXISTS (
+                                  SELECT 1 FROM transactions t
+                                  WHERE (t.from_account_id = a.id OR t.to_account_id = a.id)
+                                    AND t.created_at >= $3
+                              )
+                          ))
+                      )
+                      AND NOT EXISTS (
+                          SELECT 1 FROM interest_accruals i
+                          WHERE i.account_id = a.id AND i.accrual_date = $2
+                      )
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                "#;
+        sqlx::query_as::<_, Account>(query)
+            .bind(account_types)
+            .bind(accrual_date)
+            .bind(created_before)
+            .bind(overdraft)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Último dia apurado em qualquer conta; a apuração retoma a partir dele
+    #[tracing::instrument(name = "InterestRepository::last_accrual_date", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn last_accrual_date(
+        conn: &mut PgConnection,
+    ) -> Result<Option<NaiveDate>, sqlx::Error> {
+        let query = "SELECT MAX(accrual_date) FROM interest_accruals";
+        sqlx::query_scalar(query).fetch_one(conn).await
+    }
+
+    /// Grava os juros do dia; `false` se o dia já tinha sido apurado
+    #[tracing::instrument(name = "InterestRepository::insert_accrual", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_accrual(
+        conn: &mut PgConnection,
+        accrual: &InterestAccrual,
+    ) -> Result<bool, sqlx::Error> {
+        let query = r#"
+                    INSERT INTO interest_accruals (account_id, accrual_date, balance, annual_rate, amount, created_at)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    ON CONFLICT (account_id, accrual_date) DO NOTHING
+                "#;
+        let result = sqlx::query(query)
+            .bind(accrual.account_id)
+            .bind(accrual.accrual_date)
+            .bind(accrual.balance)
+            .bind(accrual.annual_rate)
+            .bind(accrual.amount)
+            .bind(accrual.created_at)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Trava uma conta ativa com juros a creditar (ou debitar) de dias anteriores a `before`
+    #[tracing::instrument(name = "InterestRepository::claim_creditable", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn claim_creditable(
+        conn: &mut PgConnection,
+        before: NaiveDate,
+    ) -> Result<Option<Account>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, user_id, account_number, account_type, currency, balance, held_balance, overdraft_limit, is_active, created_at, updated_at
+                    FROM accounts a
+                    WHERE a.is_active AND EXISTS (
+                        SELECT 1 FROM interest_accruals i
+                        WHERE i.account_id = a.id AND i.credited_at IS NULL AND i.accrual_date < $1
+                    )
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                "#;
+        sqlx::query_as::<_, Account>(query)
+            .bind(before)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Soma dos juros ainda não creditados de dias anteriores a `before`
+    #[tracing::instrument(name = "InterestRepository::pending", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn pending(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        before: NaiveDate,
+    ) -> Result<PendingInterest, sqlx::Error> {
+        let query = r#"
+                    SELECT COALESCE(SUM(amount), 0), COUNT(*), MIN(accrual_date), MAX(accrual_date)
+                    FROM interest_accruals
+                    WHERE account_id = $1 AND credited_at IS NULL AND accrual_date < $2
+                "#;
+        let (amount, days, first_date, last_date): (
+            Decimal,
+            i64,
+            Option<NaiveDate>,
+            Option<NaiveDate>,
+        ) = sqlx::query_as(query)
+            .bind(account_id)
+            .bind(before)
+            .fetch_one(conn)
+            .await?;
+        Ok(PendingInterest {
+            amount,
+            days,
+            first_date,
+            last_date,
+        })
+    }
+
+    /// Marca como lançados os dias anteriores a `before`; sem lançamento quando a soma não chega a um centavo
+    #[tracing::instrument(name = "InterestRepository::mark_credited", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn mark_credited(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        before: NaiveDate,
+        transaction_id: Option<Uuid>,
+        credited_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE interest_accruals
+                    SET transaction_id = $3, credited_at = $4
+                    WHERE account_id = $1 AND credited_at IS NULL AND accrual_date < $2
+                "#;
+        sqlx::query(query)
+            .bind(account_id)
+            .bind(before)
+            .bind(transaction_id)
+            .bind(credited_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::kyc::{KycDocument, KycProfile, KycReviewItem, KycStatus};
+
+const PROFILE_COLUMNS: &str = "user_id, status, submitted_at, reviewer_id, reviewed_at, rejection_reason, created_at, updated_at";
+
+const DOCUMENT_COLUMNS: &str =
+    "id, user_id, document_type, content_type, size_bytes, sha256, storage_key, created_at";
+
+pub struct KycRepository;
+
+impl KycRepository {
+    /// Cadastro novo começa em `pending`
+    #[tracing::instrument(name = "KycRepository::create_profile", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn create_profile(conn: &mut PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO kyc_profiles (user_id) VALUES ($1)")
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "KycRepository::find_profile", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_profile(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Option<KycProfile>, sqlx::Error> {
+        let query = format!("SELECT {PROFILE_COLUMNS} FROM kyc_profiles WHERE user_id = $1");
+        sqlx::query_as::<_, KycProfile>(&query)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Trava o cadastro; serializa envio de documentos, envio e revisão
+    #[tracing::instrument(name = "KycRepository::lock_profile", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_profile(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<Option<KycProfile>, sqlx::Error> {
+        let query =
+            format!("SELECT {PROFILE_COLUMNS} FROM kyc_profiles WHERE user_id = $1 FOR UPDATE");
+        sqlx::query_as::<_, KycProfile>(&query)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Só a situação, para os bloqueios de conta e débito
+    #[tracing::instrument(name = "KycRepository::status", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn status(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<Option<KycStatus>, sqlx::Error> {
+        sqlx::query_scalar("SELECT status FROM kyc_profiles WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "KycRepository::save_profile", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn save_profile(
+        conn: &mut PgConnection,
+        profile: &KycProfile,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE kyc_profiles
+                    SET status = $1, submitted_at = $2, reviewer_id = $3, reviewed_at = $4,
+                        rejection_reason = $5, updated_at = $6
+                    WHERE user_id = $7
+                "#;
+        sqlx::query(query)
+            .bind(profile.status)
+            .bind(profile.submitted_at)
+            .bind(profile.reviewer_id)
+            .bind(profile.reviewed_at)
+            .bind(&profile.rejection_reason)
+            .bind(profile.updated_at)
+            .bind(profile.user_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "KycRepository::list_documents", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_documents(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<Vec<KycDocument>, sqlx::Error> {
+        let query = format!(
+            "SELECT {DOCUMENT_COLUMNS} FROM kyc_documents WHERE user_id = $1 ORDER BY created_at"
+        );
+        sqlx::query_as::<_, KycDocument>(&query)
+            .bind(user_id)
+            .fetch_all(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "KycRepository::find_document", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_document(
+        pool: &PgPool,
+        user_id: Uuid,
+        document_id: Uuid,
+    ) -> Result<Option<KycDocument>, sqlx::Error> {
+        let query =
+            format!("SELECT {DOCUMENT_COLUMNS} FROM kyc_documents WHERE id = $1 AND user_id = $2");
+        sqlx::query_as::<_, KycDocument>(&query)
+            .bind(document_id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Grava o documento, substituindo o do mesmo tipo; devolve o substituído
+    /// para o arquivo antigo ser apagado depois do commit
+    #[tracing::instrument(name = "KycRepository::replace_document", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn replace_document(
+        conn: &mut PgConnection,
+        document: &KycDocument,
+    ) -> Result<Option<KycDocument>, sqlx::Error> {
+        let query = format!(
+            "DELETE FROM kyc_documents WHERE user_id = $1 AND document_type = $2 RETURNING {DOCUMENT_COLUMNS}"
+        );
+        let previous = sqlx::query_as::<_, KycDocument>(&query)
+            .bind(document.user_id)
+            .bind(document.document_type)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        let query = r#"
+              INSERT INTO kyc_documents (id, user_id, document_type, content_type, size_bytes, sha256, storage_key, created_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+          "#;
+        sqlx::query(query)
+            .bind(document.id)
+            .bind(document.user_id)
+            .bind(document.document_type)
+            .bind(&document.content_type)
+            .bind(document.size_bytes)
+            .bind(&document.sha256)
+            .bind(&document.storage_key)
+            .bind(document.created_at)
+            .execute(conn)
+            .await?;
+        Ok(previous)
+    }
+
+    /// Fila de revisão, enviados há mais tempo primeiro
+    #[tracing::instrument(name = "KycRepository::review_queue", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn review_queue(
+        pool: &PgPool,
+        statuses: &[KycStatus],
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<KycReviewItem>, i64), sqlx::Error> {
+        let total: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM kyc_profiles WHERE status = ANY($1)")
+                .bind(statuses)
+                .fetch_one(pool)
+                .await?;
+
+        let query = r#"
+                    SELECT k.user_id, u.name, u.email, u.customer_type, u.document,
+                           k.status, k.submitted_at, k.reviewer_id
+                    FROM kyc_profiles k
+                    JOIN users u ON u.id = k.user_id
+                    WHERE k.status = ANY($1)
+                    ORDER BY k.submitted_at NULLS LAST, k.user_id
+                    LIMIT $2 OFFSET $3
+                "#;
+        let items = sqlx::query_as::<_, KycReviewItem>(query)
+            .bind(statuses)
+            .bind(i64::from(limit))
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok((items, total))
+    }
+}
@@ -0,0 +1,49 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::limit::AccountLimit;
+
+pub struct LimitRepository;
+
+impl LimitRepository {
+    /// Limites personalizados da conta; os ausentes seguem o tipo de conta
+    #[tracing::instrument(name = "LimitRepository::list", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<Vec<AccountLimit>, sqlx::Error> {
+        sqlx::query_as::<_, AccountLimit>(
+            r#"
+                    SELECT account_id, kind, amount, pending_amount, pending_effective_at, updated_at
+                    FROM account_limits
+                    WHERE account_id = $1
+                "#,
+        )
+        .bind(account_id)
+        .fetch_all(conn)
+        .await
+    }
+
+    #[tracing::instrument(name = "LimitRepository::upsert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn upsert(conn: &mut PgConnection, limit: &AccountLimit) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    INSERT INTO account_limits (account_id, kind, amount, pending_amount, pending_effective_at, updated_at)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    ON CONFLICT (account_id, kind) DO UPDATE
+                    SET amount = EXCLUDED.amount,
+                        pending_amount = EXCLUDED.pending_amount,
+                        pending_effective_at = EXCLUDED.pending_effective_at,
+                        updated_at = EXCLUDED.updated_at
+                "#;
+        sqlx::query(query)
+            .bind(limit.account_id)
+            .bind(limit.kind)
+            .bind(limit.amount)
+            .bind(limit.pending_amount)
+            .bind(limit.pending_effective_at)
+            .bind(limit.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,5 +1,41 @@
+mod accounts;
+mod audit;
+mod boletos;
+mod business;
+mod fees;
+mod fx;
+mod health;
+mod holds;
+mod interest;
+mod kyc;
+mod limits;
+mod notifications;
+mod outbox;
+mod pix;
 mod refresh_token;
+mod reversals;
+mod scheduled_transfers;
+mod transactions;
 mod users;
+mod webhooks;
 
+pub use accounts::AccountRepository;
+pub use audit::AuditRepository;
+pub use boletos::BoletoRepository;
+pub use business::OperatorRepository;
+pub use fees::FeeRepository;
+pub use fx::FxRepository;
+pub use health::HealthRepository;
+pub use holds::HoldRepository;
+pub use interest::InterestRepository;
+pub use kyc::KycRepository;
+pub use limits::LimitRepository;
+pub use notifications::NotificationRepository;
+pub use outbox::OutboxRepository;
+pub use pix::PixRepository;
 pub use refresh_token::RefreshTokenRepository;
+pub use reversals::ReversalRepository;
+pub use scheduled_transfers::ScheduledTransferRepository;
+pub use transactions::TransactionRepository;
 pub use users::UserRepository;
+pub use webhooks::WebhookRepository;
@@ -0,0 +1,24 @@
+use sqlx::PgConnection;
+
+use crate::models::notification::{NOTIFICATION_CHANNEL, Notification};
+
+pub struct NotificationRepository;
+
+impl NotificationRepository {
+    /// `pg_notify` na transação da mudança: o Postgres só entrega no commit
+    /// e descarta no rollback
+    #[tracing::instrument(name = "NotificationRepository::publish", skip_all, fields(db.system = "postgresql", db.operation = "NOTIFY", notification.type = notification.event_name()))]
+    pub async fn publish(
+        conn: &mut PgConnection,
+        notification: &Notification,
+    ) -> Result<(), sqlx::Error> {
+        let payload = serde_json::to_string(notification)
+            .map_err(|err| sqlx::Error::Encode(Box::new(err)))?;
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(NOTIFICATION_CHANNEL)
+            .bind(payload)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::event::{DomainEvent, OutboxEvent};
+
+pub struct OutboxRepository;
+
+impl OutboxRepository {
+    /// Grava o evento na transação da mudança de negócio
+    #[tracing::instrument(name = "OutboxRepository::enqueue", skip_all, fields(db.system = "postgresql", db.operation = "INSERT", event.type = event.event_type()))]
+    pub async fn enqueue(conn: &mut PgConnection, event: DomainEvent) -> Result<Uuid, sqlx::Error> {
+        let event = event.into_outbox();
+        let query = r#"
+              INSERT INTO outbox_events (id, aggregate_type, aggregate_id, event_type, payload, occurred_at)
+              VALUES ($1, $2, $3, $4, $5, $6)
+          "#;
+        sqlx::query(query)
+            .bind(event.id)
+            .bind(event.aggregate_type)
+            .bind(event.aggregate_id)
+            .bind(event.event_type)
+            .bind(&event.payload)
+            .bind(event.occurred_at)
+            .execute(conn)
+            .await?;
+        Ok(event.id)
+    }
+
+    /// Trava os próximos eventos a entregar
+    ///
+    /// Só o pendente mais antigo de cada agregado é elegível, então um evento
+    /// nunca sai antes dos anteriores do mesmo agregado, mesmo com vários
+    /// dispatchers (`SKIP LOCKED`) ou com o anterior aguardando nova tentativa.
+    #[tracing::instrument(name = "OutboxRepository::claim_batch", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn claim_batch(
+        conn: &mut PgConnection,
+        limit: u32,
+    ) -> Result<Vec<OutboxEvent>, sqlx::Error> {
+        let query = r#"
+                    SELECT e.id, e.seq, e.aggregate_type, e.aggregate_id, e.event_type, e.payload, e.occurred_at, e.attempts
+                    FROM outbox_events e
+                    WHERE e.published_at IS NULL
+                      AND e.next_attempt_at <= NOW()
+                      AND NOT EXISTS (
+                          SELECT 1 FROM outbox_events p
+                          WHERE p.aggregate_type = e.aggregate_type
+                            AND p.aggregate_id = e.aggregate_id
+                            AND p.published_at IS NULL
+                            AND p.seq < e.seq
+                      )
+                    ORDER BY e.seq
+                    LIMIT $1
+                    FOR UPDATE SKIP LOCKED
+                "#;
+        sqlx::query_as::<_, OutboxEvent>(query)
+            .bind(i64::from(limit))
+            .fetch_all(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "OutboxRepository::mark_published", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn mark_published(
+        conn: &mut PgConnection,
+        event_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE outbox_events
+                    SET published_at = NOW(), attempts = attempts + 1, last_error = NULL
+                    WHERE id = $1
+                "#;
+        sqlx::query(query).bind(event_id).execute(conn).await?;
+        Ok(())
+    }
+
+    /// Registra a falha e agenda a próxima tentativa
+    #[tracing::instrument(name = "OutboxRepository::mark_failed", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn mark_failed(
+        conn: &mut PgConnection,
+        event_id: Uuid,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE outbox_events
+                    SET attempts = attempts + 1, last_error = $2, next_attempt_at = $3
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(event_id)
+            .bind(error)
+            .bind(next_attempt_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,463 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::pix::{
+    ClaimStatus, PixCharge, PixClaim, PixKey, PixKeyLookup, PixKeyVerification,
+};
+
+const KEY_COLUMNS: &str = "id, account_id, user_id, key_type, key_value, created_at, updated_at";
+
+const CLAIM_COLUMNS: &str = "id, pix_key_id, claim_type, status, claimer_user_id, claimer_account_id, donor_user_id, donor_account_id, resolution_deadline, created_at, updated_at, resolved_at, verified_at";
+
+const CHARGE_COLUMNS: &str = "id, txid, user_id, account_id, pix_key_id, key_value, amount, description, status, expires_at, paid_at, created_at, updated_at";
+
+const VERIFICATION_COLUMNS: &str =
+    "id, user_id, key_type, key_value, code_hash, attempts, expires_at, consumed_at, created_at";
+
+pub struct PixRepository;
+
+impl PixRepository {
+    /// Registra a chave; `false` se ela já está ativa em alguma conta
+    #[tracing::instrument(name = "PixRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(conn: &mut PgConnection, key: &PixKey) -> Result<bool, sqlx::Error> {
+        let query = r#"
+              INSERT INTO pix_keys (id, account_id, user_id, key_type, key_value, created_at, updated_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7)
+              ON CONFLICT (key_value) WHERE deleted_at IS NULL DO NOTHING
+          "#;
+        let result = sqlx::query(query)
+            .bind(key.id)
+            .bind(key.account_id)
+            .bind(key.user_id)
+            .bind(key.key_type)
+            .bind(&key.key)
+            .bind(key.created_at)
+            .bind(key.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Chaves ativas da conta; chame com a conta travada para o limite valer
+    #[tracing::instrument(name = "PixRepository::count_by_account", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn count_by_account(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<i64, sqlx::Error> {
+        let query = r#"SELECT COUNT(*) FROM pix_keys WHERE account_id = $1 AND deleted_at IS NULL"#;
+        sqlx::query_scalar(query)
+            .bind(account_id)
+            .fetch_one(conn)
+            .await
+    }
+
+    /// Chaves ativas do usuário, em todas as contas
+    #[tracing::instrument(name = "PixRepository::list_by_user", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<PixKey>, sqlx::Error> {
+        let query = format!(
+            "SELECT {KEY_COLUMNS} FROM pix_keys WHERE user_id = $1 AND deleted_at IS NULL ORDER BY created_at"
+        );
+        sqlx::query_as::<_, PixKey>(&query)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Chave ativa pelo ID, desde que pertença ao usuário
+    #[tracing::instrument(name = "PixRepository::find_owned", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_owned(
+        pool: &PgPool,
+        key_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<PixKey>, sqlx::Error> {
+        let query = format!(
+            "SELECT {KEY_COLUMNS} FROM pix_keys WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"
+        );
+        sqlx::query_as::<_, PixKey>(&query)
+            .bind(key_id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Chave ativa pelo valor normalizado
+    #[tracing::instrument(name = "PixRepository::find_active", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_active(
+        conn: &mut PgConnection,
+        key: &str,
+    ) -> Result<Option<PixKey>, sqlx::Error> {
+        let query = format!(
+            "SELECT {KEY_COLUMNS} FROM pix_keys WHERE key_value = $1 AND deleted_at IS NULL"
+        );
+        sqlx::query_as::<_, PixKey>(&query)
+            .bind(key)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Chave ativa pelo valor, travada até o fim da transação
+    #[tracing::instrument(name = "PixRepository::lock_active", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_active(
+        conn: &mut PgConnection,
+        key: &str,
+    ) -> Result<Option<PixKey>, sqlx::Error> {
+        let query = format!(
+            "SELECT {KEY_COLUMNS} FROM pix_keys WHERE key_value = $1 AND deleted_at IS NULL FOR UPDATE"
+        );
+        sqlx::query_as::<_, PixKey>(&query)
+            .bind(key)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Chave ativa pelo ID, travada até o fim da transação
+    #[tracing::instrument(name = "PixRepository::lock_by_id", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_by_id(
+        conn: &mut PgConnection,
+        key_id: Uuid,
+    ) -> Result<Option<PixKey>, sqlx::Error> {
+        let query = format!(
+            "SELECT {KEY_COLUMNS} FROM pix_keys WHERE id = $1 AND deleted_at IS NULL FOR UPDATE"
+        );
+        sqlx::query_as::<_, PixKey>(&query)
+            .bind(key_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "PixRepository::soft_delete", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn soft_delete(conn: &mut PgConnection, key_id: Uuid) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE pix_keys
+                    SET deleted_at = NOW(), updated_at = NOW()
+                    WHERE id = $1
+                "#;
+        sqlx::query(query).bind(key_id).execute(conn).await?;
+        Ok(())
+    }
+
+    /// Leva a chave para outra conta (e, na reivindicação de posse, outro titular)
+    #[tracing::instrument(name = "PixRepository::move_to", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn move_to(
+        conn: &mut PgConnection,
+        key_id: Uuid,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE pix_keys
+                    SET user_id = $2, account_id = $3, updated_at = NOW()
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(key_id)
+            .bind(user_id)
+            .bind(account_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Dados públicos da chave; só chaves de contas e titulares ativos
+    #[tracing::instrument(name = "PixRepository::lookup", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lookup(pool: &PgPool, key: &str) -> Result<Option<PixKeyLookup>, sqlx::Error> {
+        let query = r#"
+                    SELECT k.key_type, k.key_value, u.name AS owner_name, a.account_type
+                    FROM pix_keys k
+                    JOIN accounts a ON a.id = k.account_id
+                    JOIN users u ON u.id = k.user_id
+                    WHERE k.key_value = $1
+                      AND k.deleted_at IS NULL
+                      AND a.is_active = true
+                      AND u.is_active = true
+                "#;
+        sqlx::query_as::<_, PixKeyLookup>(query)
+            .bind(key)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Conta encerrada: cancela as reivindicações abertas que a envolvem e
+    /// libera as chaves dela; devolve quantas chaves foram liberadas
+    #[tracing::instrument(name = "PixRepository::release_account", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn release_account(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let query = r#"
+                    UPDATE pix_key_claims
+                    SET status = 'cancelled', resolved_at = NOW(), updated_at = NOW()
+                    WHERE status = 'open'
+                      AND (claimer_account_id = $1 OR donor_account_id = $1)
+                "#;
+        sqlx::query(query)
+            .bind(account_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let query = r#"
+                    UPDATE pix_keys
+                    SET deleted_at = NOW(), updated_at = NOW()
+                    WHERE account_id = $1 AND deleted_at IS NULL
+                "#;
+        let result = sqlx::query(query).bind(account_id).execute(conn).await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Grava a reivindicação; `false` se a chave já tem uma em aberto
+    #[tracing::instrument(name = "PixRepository::insert_claim", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_claim(
+        conn: &mut PgConnection,
+        claim: &PixClaim,
+    ) -> Result<bool, sqlx::Error> {
+        let query = format!(
+            r#"
+              INSERT INTO pix_key_claims ({CLAIM_COLUMNS})
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+              ON CONFLICT (pix_key_id) WHERE status = 'open' DO NOTHING
+          "#
+        );
+        let result = sqlx::query(&query)
+            .bind(claim.id)
+            .bind(claim.pix_key_id)
+            .bind(claim.claim_type)
+            .bind(claim.status)
+            .bind(claim.claimer_user_id)
+            .bind(claim.claimer_account_id)
+            .bind(claim.donor_user_id)
+            .bind(claim.donor_account_id)
+            .bind(claim.resolution_deadline)
+            .bind(claim.created_at)
+            .bind(claim.updated_at)
+            .bind(claim.resolved_at)
+            .bind(claim.verified_at)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Há reivindicação aberta para a chave?
+    #[tracing::instrument(name = "PixRepository::has_open_claim", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn has_open_claim(
+        conn: &mut PgConnection,
+        key_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let query = r#"SELECT EXISTS (SELECT 1 FROM pix_key_claims WHERE pix_key_id = $1 AND status = 'open')"#;
+        sqlx::query_scalar(query).bind(key_id).fetch_one(conn).await
+    }
+
+    /// Reivindicação em que o usuário é reivindicante ou dono atual, travada
+    #[tracing::instrument(name = "PixRepository::lock_claim", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_claim(
+        conn: &mut PgConnection,
+        claim_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<PixClaim>, sqlx::Error> {
+        let query = format!(
+            "SELECT {CLAIM_COLUMNS} FROM pix_key_claims WHERE id = $1 AND (claimer_user_id = $2 OR donor_user_id = $2) FOR UPDATE"
+        );
+        sqlx::query_as::<_, PixClaim>(&query)
+            .bind(claim_id)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Reivindicações feitas ou recebidas pelo usuário, mais recentes primeiro
+    #[tracing::instrument(name = "PixRepository::list_claims", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_claims(pool: &PgPool, user_id: Uuid) -> Result<Vec<PixClaim>, sqlx::Error> {
+        let query = format!(
+            "SELECT {CLAIM_COLUMNS} FROM pix_key_claims WHERE claimer_user_id = $1 OR donor_user_id = $1 ORDER BY created_at DESC"
+        );
+        sqlx::query_as::<_, PixClaim>(&query)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    #[tracing::instrument(name = "PixRepository::resolve_claim", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn resolve_claim(
+        conn: &mut PgConnection,
+        claim_id: Uuid,
+        status: ClaimStatus,
+    ) -> Result<PixClaim, sqlx::Error> {
+        let query = format!(
+            r#"
+                    UPDATE pix_key_claims
+                    SET status = $2, resolved_at = NOW(), updated_at = NOW()
+                    WHERE id = $1
+                    RETURNING {CLAIM_COLUMNS}
+                "#
+        );
+        sqlx::query_as::<_, PixClaim>(&query)
+            .bind(claim_id)
+            .bind(status)
+            .fetch_one(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "PixRepository::insert_verification", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_verification(
+        pool: &PgPool,
+        verification: &PixKeyVerification,
+    ) -> Result<(), sqlx::Error> {
+        let query = format!(
+            "INSERT INTO pix_key_verifications ({VERIFICATION_COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+        );
+        sqlx::query(&query)
+            .bind(verification.id)
+            .bind(verification.user_id)
+            .bind(verification.key_type)
+            .bind(&verification.key)
+            .bind(&verification.code_hash)
+            .bind(verification.attempts)
+            .bind(verification.expires_at)
+            .bind(verification.consumed_at)
+            .bind(verification.created_at)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Códigos pedidos pelo usuário desde `since`
+    #[tracing::instrument(name = "PixRepository::count_recent_verifications", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn count_recent_verifications(
+        pool: &PgPool,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<i64, sqlx::Error> {
+        let query =
+            r#"SELECT COUNT(*) FROM pix_key_verifications WHERE user_id = $1 AND created_at >= $2"#;
+        sqlx::query_scalar(query)
+            .bind(user_id)
+            .bind(since)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Pedido de código do usuário, travado até o fim da transação
+    #[tracing::instrument(name = "PixRepository::lock_verification", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_verification(
+        conn: &mut PgConnection,
+        verification_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<PixKeyVerification>, sqlx::Error> {
+        let query = format!(
+            "SELECT {VERIFICATION_COLUMNS} FROM pix_key_verifications WHERE id = $1 AND user_id = $2 FOR UPDATE"
+        );
+        sqlx::query_as::<_, PixKeyVerification>(&query)
+            .bind(verification_id)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "PixRepository::record_failed_attempt", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn record_failed_attempt(
+        conn: &mut PgConnection,
+        verification_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"UPDATE pix_key_verifications SET attempts = attempts + 1 WHERE id = $1"#;
+        sqlx::query(query)
+            .bind(verification_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Marca o código como usado; `false` se outra requisição já o consumiu
+    #[tracing::instrument(name = "PixRepository::consume_verification", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn consume_verification(
+        conn: &mut PgConnection,
+        verification_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let query = r#"
+                    UPDATE pix_key_verifications
+                    SET consumed_at = NOW()
+                    WHERE id = $1 AND consumed_at IS NULL
+                "#;
+        let result = sqlx::query(query)
+            .bind(verification_id)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Registra a cobrança; `false` se o `txid` já existe
+    #[tracing::instrument(name = "PixRepository::insert_charge", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_charge(
+        conn: &mut PgConnection,
+        charge: &PixCharge,
+    ) -> Result<bool, sqlx::Error> {
+        let query = r#"
+              INSERT INTO pix_charges (id, txid, user_id, account_id, pix_key_id, key_value, amount, description, status, expires_at, created_at, updated_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+              ON CONFLICT (txid) DO NOTHING
+          "#;
+        let result = sqlx::query(query)
+            .bind(charge.id)
+            .bind(&charge.txid)
+            .bind(charge.user_id)
+            .bind(charge.account_id)
+            .bind(charge.pix_key_id)
+            .bind(&charge.key)
+            .bind(charge.amount)
+            .bind(&charge.description)
+            .bind(charge.status)
+            .bind(charge.expires_at)
+            .bind(charge.created_at)
+            .bind(charge.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[tracing::instrument(name = "PixRepository::find_charge", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_charge(
+        pool: &PgPool,
+        charge_id: Uuid,
+    ) -> Result<Option<PixCharge>, sqlx::Error> {
+        let query = format!("SELECT {CHARGE_COLUMNS} FROM pix_charges WHERE id = $1");
+        sqlx::query_as::<_, PixCharge>(&query)
+            .bind(charge_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Cobrança pelo `txid`, travada até o fim da transação que a paga
+    #[tracing::instrument(name = "PixRepository::lock_charge", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_charge(
+        conn: &mut PgConnection,
+        txid: &str,
+    ) -> Result<Option<PixCharge>, sqlx::Error> {
+        let query = format!("SELECT {CHARGE_COLUMNS} FROM pix_charges WHERE txid = $1 FOR UPDATE");
+        sqlx::query_as::<_, PixCharge>(&query)
+            .bind(txid)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Marca a cobrança como paga pela transferência `transaction_id`
+    #[tracing::instrument(name = "PixRepository::complete_charge", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn complete_charge(
+        conn: &mut PgConnection,
+        charge_id: Uuid,
+        transaction_id: Uuid,
+        paid_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE pix_charges
+                    SET status = 'completed', transaction_id = $2, paid_at = $3, updated_at = $3
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(charge_id)
+            .bind(transaction_id)
+            .bind(paid_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
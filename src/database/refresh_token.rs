@@ -1,25 +1,42 @@
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
 use uuid::Uuid;
 
 pub struct RefreshTokenRepository;
 
 impl RefreshTokenRepository {
     pub async fn insert(
-        pool: &PgPool,
+        conn: &mut PgConnection,
         user_id: Uuid,
         refresh_token: &str,
-        expires_at: i64,
+        expires_at: DateTime<Utc>,
     ) -> Result<(), sqlx::Error> {
         let query = r#"
             insert into refresh_tokens (user_id,token,expires_at)
-            values ($1,$2,$3,$4)
+            values ($1,$2,$3)
             "#;
         sqlx::query(query)
             .bind(user_id)
             .bind(refresh_token)
             .bind(expires_at)
-            .execute(pool)
+            .execute(conn)
             .await?;
         Ok(())
     }
+
+    /// Remove o token e devolve dono e validade: cada refresh token é de uso único
+    pub async fn take(
+        conn: &mut PgConnection,
+        refresh_token: &str,
+    ) -> Result<Option<(Uuid, DateTime<Utc>)>, sqlx::Error> {
+        let query = r#"
+            delete from refresh_tokens
+            where token = $1
+            returning user_id, expires_at
+            "#;
+        sqlx::query_as(query)
+            .bind(refresh_token)
+            .fetch_optional(conn)
+            .await
+    }
 }
@@ -0,0 +1,151 @@
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::reversal::{Reversal, ReversalStatus};
+
+const COLUMNS: &str = "id, transaction_id, amount, reason, note, created_by, credit_account_id, debit_account_id, outstanding_amount, status, next_recovery_at, created_at, updated_at";
+
+pub struct ReversalRepository;
+
+impl ReversalRepository {
+    #[tracing::instrument(name = "ReversalRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(conn: &mut PgConnection, reversal: &Reversal) -> Result<(), sqlx::Error> {
+        let query = format!(
+            "INSERT INTO reversals ({COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)"
+        );
+        sqlx::query(&query)
+            .bind(reversal.id)
+            .bind(reversal.transaction_id)
+            .bind(reversal.amount)
+            .bind(reversal.reason)
+            .bind(&reversal.note)
+            .bind(reversal.created_by)
+            .bind(reversal.credit_account_id)
+            .bind(reversal.debit_account_id)
+            .bind(reversal.outstanding_amount)
+            .bind(reversal.status)
+            .bind(reversal.next_recovery_at)
+            .bind(reversal.created_at)
+            .bind(reversal.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Quanto da transação já foi estornado; chame com a transação original travada
+    #[tracing::instrument(name = "ReversalRepository::reversed_total", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn reversed_total(
+        conn: &mut PgConnection,
+        transaction_id: Uuid,
+    ) -> Result<Decimal, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COALESCE(SUM(amount), 0) FROM reversals WHERE transaction_id = $1",
+        )
+        .bind(transaction_id)
+        .fetch_one(conn)
+        .await
+    }
+
+    /// Há estorno com valor a recuperar debitando a conta?
+    #[tracing::instrument(name = "ReversalRepository::has_pending_recovery", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn has_pending_recovery(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let query = r#"SELECT EXISTS (SELECT 1 FROM reversals WHERE debit_account_id = $1 AND status = 'pending_recovery')"#;
+        sqlx::query_scalar(query)
+            .bind(account_id)
+            .fetch_one(conn)
+            .await
+    }
+
+    /// Estornos de uma transação, na ordem em que foram feitos
+    #[tracing::instrument(name = "ReversalRepository::list_by_transaction", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_by_transaction(
+        pool: &PgPool,
+        transaction_id: Uuid,
+    ) -> Result<Vec<Reversal>, sqlx::Error> {
+        let query = format!(
+            "SELECT {COLUMNS} FROM reversals WHERE transaction_id = $1 ORDER BY created_at, id"
+        );
+        sqlx::query_as::<_, Reversal>(&query)
+            .bind(transaction_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Todos os estornos, mais recentes primeiro
+    #[tracing::instrument(name = "ReversalRepository::list", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list(
+        pool: &PgPool,
+        status: Option<ReversalStatus>,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<Reversal>, i64), sqlx::Error> {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM reversals WHERE ($1::varchar IS NULL OR status = $1)",
+        )
+        .bind(status)
+        .fetch_one(pool)
+        .await?;
+
+        let query = format!(
+            r#"
+                    SELECT {COLUMNS} FROM reversals
+                    WHERE ($1::varchar IS NULL OR status = $1)
+                    ORDER BY created_at DESC, id
+                    LIMIT $2 OFFSET $3
+                "#
+        );
+        let reversals = sqlx::query_as::<_, Reversal>(&query)
+            .bind(status)
+            .bind(i64::from(limit))
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok((reversals, total))
+    }
+
+    /// Trava o estorno pendente mais antigo com cobrança vencida (a dívida mais
+    /// antiga é cobrada primeiro); `SKIP LOCKED` deixa cada réplica cobrar um diferente.
+    /// Conta inativa fica de fora: não entra dinheiro nela
+    #[tracing::instrument(name = "ReversalRepository::claim_recoverable", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn claim_recoverable(
+        conn: &mut PgConnection,
+    ) -> Result<Option<Reversal>, sqlx::Error> {
+        let query = format!(
+            r#"
+                    SELECT {COLUMNS} FROM reversals r
+                    WHERE r.status = 'pending_recovery' AND r.next_recovery_at <= NOW()
+                      AND EXISTS (SELECT 1 FROM accounts a WHERE a.id = r.debit_account_id AND a.is_active)
+                    ORDER BY r.created_at
+                    LIMIT 1
+                    FOR UPDATE OF r SKIP LOCKED
+                "#
+        );
+        sqlx::query_as::<_, Reversal>(&query)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Grava o andamento da cobrança
+    #[tracing::instrument(name = "ReversalRepository::save", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn save(conn: &mut PgConnection, reversal: &Reversal) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE reversals
+                    SET outstanding_amount = $2, status = $3, next_recovery_at = $4, updated_at = $5
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(reversal.id)
+            .bind(reversal.outstanding_amount)
+            .bind(reversal.status)
+            .bind(reversal.next_recovery_at)
+            .bind(reversal.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::models::scheduled_transfer::{ScheduleStatus, ScheduledTransfer, ScheduledTransferRun};
+
+const COLUMNS: &str = "id, account_id, created_by, to_account_number, to_pix_key, amount, description, frequency, start_at, end_date, max_runs, occurrence, next_run_at, attempts, last_error, status, created_at, updated_at";
+
+const RUN_COLUMNS: &str =
+    "id, scheduled_transfer_id, occurrence, attempt, status, transaction_id, error, executed_at";
+
+pub struct ScheduledTransferRepository;
+
+impl ScheduledTransferRepository {
+    #[tracing::instrument(name = "ScheduledTransferRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(
+        conn: &mut PgConnection,
+        schedule: &ScheduledTransfer,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+              INSERT INTO scheduled_transfers (id, account_id, created_by, to_account_number, to_pix_key, amount, description, frequency, start_at, end_date, max_runs, next_run_at, status, created_at, updated_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+          "#;
+        sqlx::query(query)
+            .bind(schedule.id)
+            .bind(schedule.account_id)
+            .bind(schedule.created_by)
+            .bind(&schedule.to_account_number)
+            .bind(&schedule.to_pix_key)
+            .bind(schedule.amount)
+            .bind(&schedule.description)
+            .bind(schedule.frequency)
+            .bind(schedule.start_at)
+            .bind(schedule.end_date)
+            .bind(schedule.max_runs)
+            .bind(schedule.next_run_at)
+            .bind(schedule.status)
+            .bind(schedule.created_at)
+            .bind(schedule.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Agendamentos da conta, mais recentes primeiro
+    #[tracing::instrument(name = "ScheduledTransferRepository::list_by_account", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_by_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        status: Option<ScheduleStatus>,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<ScheduledTransfer>, i64), sqlx::Error> {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM scheduled_transfers WHERE account_id = $1 AND ($2::varchar IS NULL OR status = $2)",
+        )
+        .bind(account_id)
+        .bind(status)
+        .fetch_one(pool)
+        .await?;
+
+        let query = format!(
+            r#"
+                    SELECT {COLUMNS} FROM scheduled_transfers
+                    WHERE account_id = $1 AND ($2::varchar IS NULL OR status = $2)
+                    ORDER BY created_at DESC, id
+                    LIMIT $3 OFFSET $4
+                "#
+        );
+        let schedules = sqlx::query_as::<_, ScheduledTransfer>(&query)
+            .bind(account_id)
+            .bind(status)
+            .bind(i64::from(limit))
+            .bind(offset)
+            .fetch_all(pool)
+            .await?;
+
+        Ok((schedules, total))
+    }
+
+    #[tracing::instrument(name = "ScheduledTransferRepository::find", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find(
+        pool: &PgPool,
+        account_id: Uuid,
+        schedule_id: Uuid,
+    ) -> Result<Option<ScheduledTransfer>, sqlx::Error> {
+        let query =
+            format!("SELECT {COLUMNS} FROM scheduled_transfers WHERE id = $1 AND account_id = $2");
+        sqlx::query_as::<_, ScheduledTransfer>(&query)
+            .bind(schedule_id)
+            .bind(account_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Agendamento da conta, travado até o fim da transação
+    #[tracing::instrument(name = "ScheduledTransferRepository::lock", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        schedule_id: Uuid,
+    ) -> Result<Option<ScheduledTransfer>, sqlx::Error> {
+        let query = format!(
+            "SELECT {COLUMNS} FROM scheduled_transfers WHERE id = $1 AND account_id = $2 FOR UPDATE"
+        );
+        sqlx::query_as::<_, ScheduledTransfer>(&query)
+            .bind(schedule_id)
+            .bind(account_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Trava o agendamento vencido mais antigo
+    ///
+    /// `SKIP LOCKED` deixa cada réplica pegar um agendamento diferente; a linha
+    /// fica travada até o fim da execução, então ninguém executa a mesma
+    /// ocorrência duas vezes.
+    #[tracing::instrument(name = "ScheduledTransferRepository::claim_due", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn claim_due(
+        conn: &mut PgConnection,
+    ) -> Result<Option<ScheduledTransfer>, sqlx::Error> {
+        let query = format!(
+            r#"
+                    SELECT {COLUMNS} FROM scheduled_transfers
+                    WHERE status = 'active' AND next_run_at <= NOW()
+                    ORDER BY next_run_at
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                "#
+        );
+        sqlx::query_as::<_, ScheduledTransfer>(&query)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Grava o andamento (ocorrência, tentativas, próxima execução e situação)
+    #[tracing::instrument(name = "ScheduledTransferRepository::save", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn save(
+        conn: &mut PgConnection,
+        schedule: &ScheduledTransfer,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE scheduled_transfers
+                    SET occurrence = $2, next_run_at = $3, attempts = $4, last_error = $5,
+                        status = $6, updated_at = $7
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(schedule.id)
+            .bind(schedule.occurrence)
+            .bind(schedule.next_run_at)
+            .bind(schedule.attempts)
+            .bind(&schedule.last_error)
+            .bind(schedule.status)
+            .bind(schedule.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Adia a execução depois de uma falha de infraestrutura, sem contar tentativa
+    #[tracing::instrument(name = "ScheduledTransferRepository::postpone", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn postpone(
+        pool: &PgPool,
+        schedule_id: Uuid,
+        next_run_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE scheduled_transfers SET next_run_at = $2, updated_at = NOW()
+                    WHERE id = $1 AND status = 'active'
+                "#;
+        sqlx::query(query)
+            .bind(schedule_id)
+            .bind(next_run_at)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Cancela os agendamentos ativos da conta (encerramento); devolve quantos
+    #[tracing::instrument(name = "ScheduledTransferRepository::cancel_by_account", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn cancel_by_account(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let query = r#"
+              UPDATE scheduled_transfers SET status = $2, next_run_at = NULL, updated_at = NOW()
+              WHERE account_id = $1 AND status = $3
+          "#;
+        let result = sqlx::query(query)
+            .bind(account_id)
+            .bind(ScheduleStatus::Cancelled)
+            .bind(ScheduleStatus::Active)
+            .execute(conn)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    #[tracing::instrument(name = "ScheduledTransferRepository::insert_run", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_run(
+        conn: &mut PgConnection,
+        run: &ScheduledTransferRun,
+    ) -> Result<(), sqlx::Error> {
+        let query = format!(
+            "INSERT INTO scheduled_transfer_runs ({RUN_COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+        );
+        sqlx::query(&query)
+            .bind(run.id)
+            .bind(run.scheduled_transfer_id)
+            .bind(run.occurrence)
+            .bind(run.attempt)
+            .bind(run.status)
+            .bind(run.transaction_id)
+            .bind(&run.error)
+            .bind(run.executed_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Últimas tentativas do agendamento, mais recentes primeiro
+    #[tracing::instrument(name = "ScheduledTransferRepository::list_runs", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_runs(
+        pool: &PgPool,
+        schedule_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<ScheduledTransferRun>, sqlx::Error> {
+        let query = format!(
+            "SELECT {RUN_COLUMNS} FROM scheduled_transfer_runs WHERE scheduled_transfer_id = $1 ORDER BY executed_at DESC LIMIT $2"
+        );
+        sqlx::query_as::<_, ScheduledTransferRun>(&query)
+            .bind(schedule_id)
+            .bind(i64::from(limit))
+            .fetch_all(pool)
+            .await
+    }
+}
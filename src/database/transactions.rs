@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use rust_decimal::Decimal;
+
+use crate::models::{
+    currency::Currency,
+    limit::{LimitUsage, LimitWindows},
+    transaction::{Transaction, TransactionFilter, TransactionStatus},
+};
+
+pub struct TransactionRepository;
+
+impl TransactionRepository {
+    #[tracing::instrument(name = "TransactionRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(
+        conn: &mut PgConnection,
+        transaction: &Transaction,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+              INSERT INTO transactions (id, from_account_id, to_account_id, amount, currency, transaction_type, description, reference_id, status, fx_rate, fx_spread, created_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+          "#;
+        sqlx::query(query)
+            .bind(transaction.id)
+            .bind(transaction.from_account_id)
+            .bind(transaction.to_account_id)
+            .bind(transaction.amount)
+            .bind(transaction.currency)
+            .bind(transaction.transaction_type)
+            .bind(&transaction.description)
+            .bind(transaction.reference_id)
+            .bind(transaction.status)
+            .bind(transaction.fx_rate)
+            .bind(transaction.fx_spread)
+            .bind(transaction.created_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "TransactionRepository::find", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find(
+        conn: &mut PgConnection,
+        transaction_id: Uuid,
+    ) -> Result<Option<Transaction>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, from_account_id, to_account_id, amount, currency, transaction_type, description, reference_id, status, fx_rate, fx_spread, created_at
+                    FROM transactions
+                    WHERE id = $1
+                "#;
+        sqlx::query_as::<_, Transaction>(query)
+            .bind(transaction_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Lançamento travado até o fim da transação; serializa os estornos da mesma transação
+    #[tracing::instrument(name = "TransactionRepository::lock", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock(
+        conn: &mut PgConnection,
+        transaction_id: Uuid,
+    ) -> Result<Option<Transaction>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, from_account_id, to_account_id, amount, currency, transaction_type, description, reference_id, status, fx_rate, fx_spread, created_at
+                    FROM transactions
+                    WHERE id = $1
+                    FOR UPDATE
+                "#;
+        sqlx::query_as::<_, Transaction>(query)
+            .bind(transaction_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    /// Conclui ou cancela um lançamento pendente; na conclusão, `amount` é o valor efetivo
+    #[tracing::instrument(name = "TransactionRepository::settle", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn settle(
+        conn: &mut PgConnection,
+        transaction_id: Uuid,
+        status: TransactionStatus,
+        amount: Decimal,
+    ) -> Result<Transaction, sqlx::Error> {
+        let query = r#"
+                    UPDATE transactions
+                    SET status = $2, amount = $3
+                    WHERE id = $1 AND status = 'pending'
+                    RETURNING id, from_account_id, to_account_id, amount, currency, transaction_type, description, reference_id, status, fx_rate, fx_spread, created_at
+                "#;
+        sqlx::query_as::<_, Transaction>(query)
+            .bind(transaction_id)
+            .bind(status)
+            .bind(amount)
+            .fetch_one(conn)
+            .await
+    }
+
+    /// Quanto a conta já tirou em saques, transferências, boletos pagos e reservas em
+    /// cada janela dos limites;
+    /// chame com a conta travada para a soma não mudar até o commit
+    #[tracing::instrument(name = "TransactionRepository::outgoing_usage", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn outgoing_usage(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        windows: &LimitWindows,
+    ) -> Result<LimitUsage, sqlx::Error> {
+        let query = r#"
+                    SELECT
+                        COALESCE(SUM(amount) FILTER (WHERE created_at >= $2), 0),
+                        COALESCE(SUM(amount) FILTER (WHERE created_at >= $3), 0),
+                        COALESCE(SUM(amount) FILTER (WHERE created_at >= $4), 0)
+                    FROM transactions
+                    WHERE from_account_id = $1
+                      AND transaction_type IN ('withdraw', 'transfer_debit', 'boleto_payment', 'hold_capture')
+                      AND status <> 'failed'
+                      AND created_at >= LEAST($2, $3, COALESCE($4, $3))
+                "#;
+        let (daily, monthly, nightly): (Decimal, Decimal, Decimal) = sqlx::query_as(query)
+            .bind(account_id)
+            .bind(windows.day_start)
+            .bind(windows.month_start)
+            .bind(windows.night_start)
+            .fetch_one(conn)
+            .await?;
+        Ok(LimitUsage {
+            daily,
+            monthly,
+            nightly: windows.night_start.map(|_| nightly),
+        })
+    }
+
+    /// O mesmo uso de `outgoing_usage`, somando todas as contas do titular, uma
+    /// linha por moeda; chame com a linha do titular travada
+    #[tracing::instrument(name = "TransactionRepository::outgoing_usage_by_user", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn outgoing_usage_by_user(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        windows: &LimitWindows,
+    ) -> Result<Vec<(Currency, LimitUsage)>, sqlx::Error> {
+        let query = r#"
+                    SELECT
+                        a.currency,
+                        COALESCE(SUM(t.amount) FILTER (WHERE t.created_at >= $2), 0),
+                        COALESCE(SUM(t.amount) FILTER (WHERE t.created_at >= $3), 0),
+                        COALESCE(SUM(t.amount) FILTER (WHERE t.created_at >= $4), 0)
+                    FROM transactions t
+                    JOIN accounts a ON a.id = t.from_account_id
+                    WHERE a.user_id = $1
+                      AND t.transaction_type IN ('withdraw', 'transfer_debit', 'boleto_payment', 'hold_capture')
+                      AND t.status <> 'failed'
+                      AND t.created_at >= LEAST($2, $3, COALESCE($4, $3))
+                    GROUP BY a.currency
+                "#;
+        let rows: Vec<(Currency, Decimal, Decimal, Decimal)> = sqlx::query_as(query)
+            .bind(user_id)
+            .bind(windows.day_start)
+            .bind(windows.month_start)
+            .bind(windows.night_start)
+            .fetch_all(conn)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(currency, daily, monthly, nightly)| {
+                let usage = LimitUsage {
+                    daily,
+                    monthly,
+                    nightly: windows.night_start.map(|_| nightly),
+                };
+                (currency, usage)
+            })
+            .collect())
+    }
+
+    /// Saldo contábil da conta no instante `at`: o saldo atual (`balance`) menos o
+    /// que entrou e mais o que saiu desde então
+    ///
+    /// Transferências e boletos pagos aqui têm duas pernas com as duas contas; cada
+    /// uma vale só para o lado dela. A captura de reserva mexe no saldo quando é
+    /// capturada (`holds.updated_at`), não quando foi reservada.
+    #[tracing::instrument(name = "TransactionRepository::balance_at", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn balance_at(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        balance: Decimal,
+        at: DateTime<Utc>,
+    ) -> Result<Decimal, sqlx::Error> {
+        let query = r#"
+                    SELECT
+                        COALESCE(SUM(t.amount) FILTER (
+                            WHERE t.to_account_id = $1 AND t.transaction_type NOT IN ('transfer_debit', 'boleto_payment')
+                        ), 0),
+                        COALESCE(SUM(t.amount) FILTER (
+                            WHERE t.from_account_id = $1 AND t.transaction_type NOT IN ('transfer_credit', 'boleto_credit')
+                        ), 0)
+                    FROM transactions t
+                    LEFT JOIN holds h ON h.transaction_id = t.id
+                    WHERE (t.from_account_id = $1 OR t.to_account_id = $1)
+                      AND t.status = 'completed'
+                      AND COALESCE(h.updated_at, t.created_at) >= $2
+                "#;
+        let (credited, debited): (Decimal, Decimal) = sqlx::query_as(query)
+            .bind(account_id)
+            .bind(at)
+            .fetch_one(conn)
+            .await?;
+        Ok(balance - credited + debited)
+    }
+
+    /// Histórico da conta (RF016): mais recentes primeiro, com filtros e paginação
+    #[tracing::instrument(name = "TransactionRepository::list_by_account", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_by_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        filter: &TransactionFilter,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<Transaction>, i64), sqlx::Error> {
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM transactions");
+        push_filters(&mut count, account_id, filter);
+        let total: i64 = count.build_query_scalar().fetch_one(pool).await?;
+
+        let mut select = QueryBuilder::new(
+            "SELECT id, from_account_id, to_account_id, amount, currency, transaction_type, description, reference_id, status, fx_rate, fx_spread, created_at FROM transactions",
+        );
+        push_filters(&mut select, account_id, filter);
+        select
+            .push(" ORDER BY created_at DESC, id LIMIT ")
+            .push_bind(i64::from(limit))
+            .push(" OFFSET ")
+            .push_bind(offset);
+        let transactions = select
+            .build_query_as::<Transaction>()
+            .fetch_all(pool)
+            .await?;
+
+        Ok((transactions, total))
+    }
+}
+
+/// Lançamentos que movimentam a conta: saque/débito saem dela, depósito/crédito entram;
+/// cada perna de estorno tem só a conta que ela movimenta; tarifas saem da conta e
+/// rendimentos entram.
+/// Capturas pendentes aparecem (reservas em andamento); as canceladas, não
+fn push_filters(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    account_id: Uuid,
+    filter: &TransactionFilter,
+) {
+    builder
+        .push(" WHERE ((from_account_id = ")
+        .push_bind(account_id)
+        .push(" AND transaction_type IN ('withdraw', 'transfer_debit', 'hold_capture', 'reversal', 'fee', 'overdraft_interest')) OR (to_account_id = ")
+        .push_bind(account_id)
+        .push(" AND transaction_type IN ('deposit', 'transfer_credit', 'reversal', 'interest'))) AND status <> 'failed'");
+
+    if let Some(transaction_type) = filter.transaction_type {
+        builder
+            .push(" AND transaction_type = ")
+            .push_bind(transaction_type);
+    }
+    if let Some(from) = filter.from {
+        builder.push(" AND created_at >= ").push_bind(from);
+    }
+    if let Some(to) = filter.to {
+        builder.push(" AND created_at < ").push_bind(to);
+    }
+    if let Some(min) = filter.min_amount {
+        builder.push(" AND amount >= ").push_bind(min);
+    }
+    if let Some(max) = filter.max_amount {
+        builder.push(" AND amount <= ").push_bind(max);
+    }
+}
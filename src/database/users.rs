@@ -1,17 +1,18 @@
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
-use crate::models::{User, error::UserError};
+use crate::models::{CustomerTier, User, error::UserError};
 
 pub struct UserRepository;
 
 impl UserRepository {
     /// Insere um novo usuário no banco de dados
-    /// Retorna o ID do usuário criado ou erro se email já existir
-    pub async fn insert(pool: &PgPool, user: &User) -> Result<Uuid, UserError> {
+    /// Retorna o ID do usuário criado ou erro se email ou documento já existirem
+    #[tracing::instrument(name = "UserRepository::insert", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert(conn: &mut PgConnection, user: &User) -> Result<Uuid, UserError> {
         let query = r#"
-              INSERT INTO users (id, email, name, password_hash, is_active, created_at, updated_at)
-              VALUES ($1, $2, $3, $4, $5, $6, $7)
+              INSERT INTO users (id, email, name, password_hash, locale, role, customer_type, tier, document, is_active, created_at, updated_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
               ON CONFLICT (email) DO NOTHING
               RETURNING id
           "#;
@@ -20,11 +21,24 @@ impl UserRepository {
             .bind(&user.email)
             .bind(&user.name)
             .bind(&user.password_hash)
+            .bind(&user.locale)
+            .bind(user.role)
+            .bind(user.customer_type)
+            .bind(user.tier)
+            .bind(&user.document)
             .bind(user.is_active)
             .bind(&user.created_at)
             .bind(&user.updated_at)
-            .fetch_optional(pool)
-            .await?;
+            .fetch_optional(conn)
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err)
+                    if db_err.constraint() == Some("users_document_key") =>
+                {
+                    UserError::DocumentAlreadyExists
+                }
+                _ => err.into(),
+            })?;
 
         match result {
             Some(id) => Ok(id),
@@ -33,9 +47,10 @@ impl UserRepository {
     }
 
     /// Busca usuário por ID
+    #[tracing::instrument(name = "UserRepository::find_by_id", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
     pub async fn find_by_id(pool: &PgPool, user_id: Uuid) -> Result<Option<User>, sqlx::Error> {
         let query = r#"
-                    SELECT id, email, name, password_hash, is_active, created_at, updated_at
+                    SELECT id, email, name, password_hash, locale, role, customer_type, tier, document, is_active, created_at, updated_at
                     FROM users
                     WHERE id = $1 AND is_active = true
                 "#;
@@ -45,10 +60,29 @@ impl UserRepository {
             .await
     }
 
+    /// Busca usuário ativo travando a linha até o fim da transação
+    #[tracing::instrument(name = "UserRepository::lock_by_id", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_by_id(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<Option<User>, sqlx::Error> {
+        let query = r#"
+                    SELECT id, email, name, password_hash, locale, role, customer_type, tier, document, is_active, created_at, updated_at
+                    FROM users
+                    WHERE id = $1 AND is_active = true
+                    FOR UPDATE
+                "#;
+        sqlx::query_as::<_, User>(query)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
     /// Busca usuário por email (útil para login)
+    #[tracing::instrument(name = "UserRepository::find_by_email", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
     pub async fn find_by_email(pool: &PgPool, email: &str) -> Result<Option<User>, sqlx::Error> {
         let query = r#"
-                    SELECT id, email, name, password_hash, is_active, created_at, updated_at
+                    SELECT id, email, name, password_hash, locale, role, customer_type, tier, document, is_active, created_at, updated_at
                     FROM users
                     WHERE LOWER(email) = LOWER($1)
                 "#;
@@ -60,13 +94,14 @@ impl UserRepository {
     }
 
     /// Lista usuários com paginação
+    #[tracing::instrument(name = "UserRepository::find_all", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
     pub async fn find_all(
         pool: &PgPool,
         limit: i32,
         offset: i64,
     ) -> Result<Vec<User>, sqlx::Error> {
         let query = r#"
-                   SELECT id, email, name, password_hash, is_active, created_at, updated_at
+                   SELECT id, email, name, password_hash, locale, role, customer_type, tier, document, is_active, created_at, updated_at
                    FROM users
                    WHERE is_active = true
                    ORDER BY name
@@ -80,6 +115,7 @@ impl UserRepository {
     }
 
     /// Atualiza dados do usuário
+    #[tracing::instrument(name = "UserRepository::update", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
     pub async fn update(pool: &PgPool, user: &User) -> Result<(), sqlx::Error> {
         let query = r#"
                     UPDATE users
@@ -98,26 +134,97 @@ impl UserRepository {
         Ok(())
     }
 
+    /// Atualiza o idioma preferido do usuário
+    #[tracing::instrument(name = "UserRepository::update_locale", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn update_locale(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        locale: &str,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE users
+                    SET locale = $1, updated_at = NOW()
+                    WHERE id = $2
+                "#;
+
+        sqlx::query(query)
+            .bind(locale)
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Faixa do cliente, para escolher a regra de tarifa
+    #[tracing::instrument(name = "UserRepository::find_tier", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_tier(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<CustomerTier, sqlx::Error> {
+        sqlx::query_scalar("SELECT tier FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_one(conn)
+            .await
+    }
+
+    /// Trava a linha do titular para conferir os limites que somam todas as
+    /// contas dele; `NO KEY UPDATE` não bloqueia quem só referencia o usuário
+    #[tracing::instrument(name = "UserRepository::lock_for_limits", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_for_limits(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1 FROM users WHERE id = $1 FOR NO KEY UPDATE")
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Troca a faixa do cliente (tarifas)
+    #[tracing::instrument(name = "UserRepository::update_tier", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn update_tier(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        tier: CustomerTier,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE users
+                    SET tier = $1, updated_at = NOW()
+                    WHERE id = $2
+                "#;
+
+        sqlx::query(query)
+            .bind(tier)
+            .bind(user_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
     /// Soft delete (marca como inativo)
-    pub async fn delete(pool: &PgPool, user_id: Uuid) -> Result<(), sqlx::Error> {
+    #[tracing::instrument(name = "UserRepository::delete", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn delete(conn: &mut PgConnection, user_id: Uuid) -> Result<(), sqlx::Error> {
         let query = r#"
                     UPDATE users
                     SET is_active = false, updated_at = NOW()
                     WHERE id = $1
                 "#;
 
-        sqlx::query(query).bind(&user_id).execute(pool).await?;
+        sqlx::query(query).bind(user_id).execute(conn).await?;
 
         Ok(())
     }
 
     /// Conta o total de usuários (para paginação)
+    #[tracing::instrument(name = "UserRepository::count_active", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
     pub async fn count_active(pool: &PgPool) -> Result<i64, sqlx::Error> {
         let query = r#"SELECT COUNT(*) FROM users WHERE is_active = true"#;
         sqlx::query_scalar(query).fetch_one(pool).await
     }
 
     /// Verifica se um usuário existe e está ativo
+    #[tracing::instrument(name = "UserRepository::exists_and_active", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
     pub async fn exists_and_active(pool: &PgPool, user_id: Uuid) -> Result<bool, sqlx::Error> {
         let query = r#"
                    SELECT EXISTS(
@@ -135,7 +242,7 @@ impl UserRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::CreateUser;
+    use crate::models::{CreateUser, CustomerType};
 
     #[tokio::test]
     async fn test_create_user() {
@@ -143,6 +250,9 @@ mod tests {
             email: "test@gmail.com".to_string(), // Email válido
             name: "Fulano Ciclano".to_string(),
             password: "Senha123".to_string(),
+            locale: None,
+            customer_type: CustomerType::Individual,
+            document: "529.982.247-25".to_string(),
         };
 
         let user_result = User::try_from(create_user);
@@ -151,6 +261,10 @@ mod tests {
         let user = user_result.unwrap();
         assert_eq!(user.email, "test@gmail.com");
         assert_eq!(user.name, "Fulano Ciclano");
+        assert_eq!(
+            user.document.as_ref().map(|document| document.as_str()),
+            Some("52998224725")
+        );
         assert!(user.is_active);
     }
 
@@ -160,14 +274,20 @@ mod tests {
             email: "fodase@gmail".to_string(), // Email inválido
             name: "Fulano Ciclano".to_string(),
             password: "Senha123".to_string(),
+            locale: None,
+            customer_type: CustomerType::Individual,
+            document: "529.982.247-25".to_string(),
         };
 
         let user_result = User::try_from(create_user);
         assert!(user_result.is_err());
 
         match user_result.unwrap_err() {
-            UserError::InvalidEmail(_) => {} // Esperado
-            _ => panic!("Deveria ser InvalidEmail"),
+            UserError::Validation(errors) => {
+                assert!(errors.has_field("email"));
+                assert!(!errors.has_field("name"));
+            } // Esperado
+            _ => panic!("Deveria ser Validation no campo email"),
         }
     }
 
@@ -177,14 +297,25 @@ mod tests {
             email: "test@gmail.com".to_string(),
             name: "Fulano Ciclano".to_string(),
             password: "123".to_string(), // Senha fraca
+            locale: None,
+            customer_type: CustomerType::Individual,
+            document: "529.982.247-25".to_string(),
         };
 
         let user_result = User::try_from(create_user);
         assert!(user_result.is_err());
 
         match user_result.unwrap_err() {
-            UserError::WeakPassword => {} // Esperado
-            _ => panic!("Deveria ser WeakPassword"),
+            UserError::Validation(errors) => {
+                assert!(errors.has_field("password"));
+                assert!(
+                    errors
+                        .errors()
+                        .iter()
+                        .all(|err| err.code == "WEAK_PASSWORD")
+                );
+            } // Esperado
+            _ => panic!("Deveria ser Validation no campo password"),
         }
     }
 }
@@ -0,0 +1,301 @@
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::models::{
+    event::OutboxEvent,
+    webhook::{DeliveryAttempt, DeliveryFilter, DueDelivery, WebhookDelivery, WebhookSubscription},
+};
+
+const SUBSCRIPTION_COLUMNS: &str =
+    "id, user_id, url, event_types, secret, is_active, created_at, updated_at";
+
+const DELIVERY_COLUMNS: &str = "id, subscription_id, event_id, event_type, payload, status, attempts, next_attempt_at, last_status_code, last_error, created_at, updated_at, delivered_at";
+
+pub struct WebhookRepository;
+
+impl WebhookRepository {
+    #[tracing::instrument(name = "WebhookRepository::insert_subscription", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn insert_subscription(
+        conn: &mut PgConnection,
+        subscription: &WebhookSubscription,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+              INSERT INTO webhook_subscriptions (id, user_id, url, event_types, secret, is_active, created_at, updated_at)
+              VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+          "#;
+        sqlx::query(query)
+            .bind(subscription.id)
+            .bind(subscription.user_id)
+            .bind(&subscription.url)
+            .bind(&subscription.event_types)
+            .bind(&subscription.secret)
+            .bind(subscription.is_active)
+            .bind(subscription.created_at)
+            .bind(subscription.updated_at)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Assinaturas ativas do usuário
+    #[tracing::instrument(name = "WebhookRepository::list_subscriptions", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_subscriptions(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<WebhookSubscription>, sqlx::Error> {
+        let query = format!(
+            "SELECT {SUBSCRIPTION_COLUMNS} FROM webhook_subscriptions WHERE user_id = $1 AND is_active = true ORDER BY created_at"
+        );
+        sqlx::query_as::<_, WebhookSubscription>(&query)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Assinatura ativa, desde que pertença ao usuário
+    #[tracing::instrument(name = "WebhookRepository::find_subscription", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn find_subscription(
+        conn: &mut PgConnection,
+        subscription_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<WebhookSubscription>, sqlx::Error> {
+        let query = format!(
+            "SELECT {SUBSCRIPTION_COLUMNS} FROM webhook_subscriptions WHERE id = $1 AND user_id = $2 AND is_active = true"
+        );
+        sqlx::query_as::<_, WebhookSubscription>(&query)
+            .bind(subscription_id)
+            .bind(user_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "WebhookRepository::deactivate_subscription", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn deactivate_subscription(
+        conn: &mut PgConnection,
+        subscription_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+                    UPDATE webhook_subscriptions
+                    SET is_active = false, updated_at = NOW()
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(subscription_id)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Cria uma entrega para cada assinatura interessada no evento
+    ///
+    /// Interessa quem assinou o tipo e é dono de um dos usuários ou contas
+    /// citados. Repetir o mesmo evento não duplica entregas.
+    #[tracing::instrument(name = "WebhookRepository::fan_out", skip_all, fields(db.system = "postgresql", db.operation = "INSERT"))]
+    pub async fn fan_out(
+        pool: &PgPool,
+        event: &OutboxEvent,
+        user_ids: &[Uuid],
+        account_ids: &[Uuid],
+    ) -> Result<u64, sqlx::Error> {
+        let payload = serde_json::to_value(event).unwrap_or_default();
+        let query = r#"
+              INSERT INTO webhook_deliveries (id, subscription_id, event_id, event_type, payload)
+              SELECT gen_random_uuid(), s.id, $1, $2, $3
+              FROM webhook_subscriptions s
+              WHERE s.is_active = true
+                AND $2 = ANY(s.event_types)
+                AND (s.user_id = ANY($4)
+                     OR s.user_id IN (SELECT user_id FROM accounts WHERE id = ANY($5)))
+              ON CONFLICT (subscription_id, event_id) DO NOTHING
+          "#;
+        let result = sqlx::query(query)
+            .bind(event.id)
+            .bind(&event.event_type)
+            .bind(payload)
+            .bind(user_ids)
+            .bind(account_ids)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Reserva entregas vencidas por `lease_secs`
+    ///
+    /// A reserva empurra `next_attempt_at` para frente, então o envio acontece fora
+    /// de transação e, se o processo cair no meio, a entrega volta sozinha depois.
+    #[tracing::instrument(name = "WebhookRepository::claim_due", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn claim_due(
+        pool: &PgPool,
+        limit: u32,
+        lease_secs: u64,
+    ) -> Result<Vec<DueDelivery>, sqlx::Error> {
+        let query = r#"
+                    UPDATE webhook_deliveries d
+                    SET next_attempt_at = NOW() + make_interval(secs => $2), updated_at = NOW()
+                    FROM webhook_subscriptions s
+                    WHERE s.id = d.subscription_id
+                      AND d.id IN (
+                          SELECT pending.id
+                          FROM webhook_deliveries pending
+                          JOIN webhook_subscriptions active ON active.id = pending.subscription_id
+                          WHERE pending.status = 'pending'
+                            AND pending.next_attempt_at <= NOW()
+                            AND active.is_active = true
+                          ORDER BY pending.next_attempt_at
+                          LIMIT $1
+                          FOR UPDATE OF pending SKIP LOCKED
+                      )
+                    RETURNING d.id, d.event_id, d.event_type, d.payload, d.attempts, s.url, s.secret
+                "#;
+        sqlx::query_as::<_, DueDelivery>(query)
+            .bind(i64::from(limit))
+            .bind(lease_secs as f64)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Registra a tentativa no log e atualiza a situação da entrega
+    #[tracing::instrument(name = "WebhookRepository::record_attempt", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn record_attempt(
+        pool: &PgPool,
+        delivery_id: Uuid,
+        attempt: &DeliveryAttempt,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        let query = r#"
+              INSERT INTO webhook_delivery_attempts (delivery_id, attempt, status_code, error, duration_ms, attempted_at)
+              VALUES ($1, $2, $3, $4, $5, $6)
+          "#;
+        sqlx::query(query)
+            .bind(delivery_id)
+            .bind(attempt.attempt)
+            .bind(attempt.status_code)
+            .bind(&attempt.error)
+            .bind(attempt.duration_ms)
+            .bind(attempt.attempted_at)
+            .execute(&mut *tx)
+            .await?;
+
+        // sem erro: entregue; com erro e próxima data: nova tentativa; senão, fila morta
+        let query = r#"
+                    UPDATE webhook_deliveries
+                    SET attempts = $2,
+                        last_status_code = $3,
+                        last_error = $4,
+                        status = CASE
+                            WHEN $4 IS NULL THEN 'delivered'
+                            WHEN $5::timestamptz IS NULL THEN 'dead'
+                            ELSE 'pending'
+                        END,
+                        next_attempt_at = COALESCE($5, next_attempt_at),
+                        delivered_at = CASE WHEN $4 IS NULL THEN NOW() ELSE delivered_at END,
+                        updated_at = NOW()
+                    WHERE id = $1
+                "#;
+        sqlx::query(query)
+            .bind(delivery_id)
+            .bind(attempt.attempt)
+            .bind(attempt.status_code)
+            .bind(&attempt.error)
+            .bind(next_attempt_at)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await
+    }
+
+    /// Log de entregas de uma assinatura, mais recentes primeiro
+    #[tracing::instrument(name = "WebhookRepository::list_deliveries", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_deliveries(
+        pool: &PgPool,
+        subscription_id: Uuid,
+        filter: &DeliveryFilter,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<WebhookDelivery>, i64), sqlx::Error> {
+        let mut count =
+            QueryBuilder::new("SELECT COUNT(*) FROM webhook_deliveries WHERE subscription_id = ");
+        count.push_bind(subscription_id);
+        push_status(&mut count, filter);
+        let total: i64 = count.build_query_scalar().fetch_one(pool).await?;
+
+        let mut select = QueryBuilder::new(format!(
+            "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries WHERE subscription_id = "
+        ));
+        select.push_bind(subscription_id);
+        push_status(&mut select, filter);
+        select
+            .push(" ORDER BY created_at DESC LIMIT ")
+            .push_bind(i64::from(limit))
+            .push(" OFFSET ")
+            .push_bind(offset);
+        let deliveries = select
+            .build_query_as::<WebhookDelivery>()
+            .fetch_all(pool)
+            .await?;
+
+        Ok((deliveries, total))
+    }
+
+    #[tracing::instrument(name = "WebhookRepository::lock_delivery", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn lock_delivery(
+        conn: &mut PgConnection,
+        subscription_id: Uuid,
+        delivery_id: Uuid,
+    ) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        let query = format!(
+            "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries WHERE id = $1 AND subscription_id = $2 FOR UPDATE"
+        );
+        sqlx::query_as::<_, WebhookDelivery>(&query)
+            .bind(delivery_id)
+            .bind(subscription_id)
+            .fetch_optional(conn)
+            .await
+    }
+
+    #[tracing::instrument(name = "WebhookRepository::list_attempts", skip_all, fields(db.system = "postgresql", db.operation = "SELECT"))]
+    pub async fn list_attempts(
+        pool: &PgPool,
+        delivery_id: Uuid,
+    ) -> Result<Vec<DeliveryAttempt>, sqlx::Error> {
+        let query = r#"
+                    SELECT attempt, status_code, error, duration_ms, attempted_at
+                    FROM webhook_delivery_attempts
+                    WHERE delivery_id = $1
+                    ORDER BY id
+                "#;
+        sqlx::query_as::<_, DeliveryAttempt>(query)
+            .bind(delivery_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Devolve a entrega à fila com o orçamento de tentativas zerado
+    #[tracing::instrument(name = "WebhookRepository::replay", skip_all, fields(db.system = "postgresql", db.operation = "UPDATE"))]
+    pub async fn replay(
+        conn: &mut PgConnection,
+        delivery_id: Uuid,
+    ) -> Result<WebhookDelivery, sqlx::Error> {
+        let query = format!(
+            r#"
+                    UPDATE webhook_deliveries
+                    SET status = 'pending', attempts = 0, next_attempt_at = NOW(), updated_at = NOW()
+                    WHERE id = $1
+                    RETURNING {DELIVERY_COLUMNS}
+                "#
+        );
+        sqlx::query_as::<_, WebhookDelivery>(&query)
+            .bind(delivery_id)
+            .fetch_one(conn)
+            .await
+    }
+}
+
+fn push_status(builder: &mut QueryBuilder<'_, Postgres>, filter: &DeliveryFilter) {
+    if let Some(status) = filter.status {
+        builder.push(" AND status = ").push_bind(status);
+    }
+}
@@ -0,0 +1,127 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use chrono::Utc;
+use sqlx::PgPool;
+
+use super::{EventSink, FileSink, LogSink, OutboxEvent, SinkError, WebhookSink, backoff};
+use crate::{DRAINING, config::OutboxConfig, database::OutboxRepository, metrics};
+
+/// Lê o outbox em lotes e entrega cada evento a todos os sinks
+pub struct OutboxDispatcher {
+    pool: PgPool,
+    sinks: Vec<Box<dyn EventSink>>,
+    batch_size: u32,
+    poll_interval: Duration,
+    max_backoff: Duration,
+}
+
+impl OutboxDispatcher {
+    pub fn new(pool: PgPool, sinks: Vec<Box<dyn EventSink>>, config: &OutboxConfig) -> Self {
+        Self {
+            pool,
+            sinks,
+            batch_size: config.batch_size,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            max_backoff: Duration::from_secs(config.max_backoff_secs),
+        }
+    }
+
+    /// Monta os sinks habilitados em `[outbox]`
+    pub fn from_config(pool: PgPool, config: &OutboxConfig) -> Result<Self, SinkError> {
+        let mut sinks: Vec<Box<dyn EventSink>> = Vec::new();
+        if config.log_sink {
+            sinks.push(Box::new(LogSink));
+        }
+        if let Some(path) = &config.file_path {
+            sinks.push(Box::new(FileSink::new(path)));
+        }
+        if let Some(url) = &config.webhook_url {
+            sinks.push(Box::new(WebhookSink::new(
+                url,
+                Duration::from_secs(config.webhook_timeout_secs),
+            )?));
+        }
+        Ok(Self::new(pool, sinks, config))
+    }
+
+    /// Acrescenta um sink além dos configurados (ex.: o fan-out de webhooks)
+    pub fn with_sink(mut self, sink: Box<dyn EventSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Entrega até o desligamento; sem sinks, os eventos ficam pendentes no banco
+    pub async fn run(self) {
+        if self.sinks.is_empty() {
+            tracing::warn!("Nenhum sink de eventos configurado; dispatcher do outbox não iniciado");
+            return;
+        }
+
+        let sinks: Vec<&str> = self.sinks.iter().map(|sink| sink.name()).collect();
+        tracing::info!(sinks = ?sinks, "Dispatcher do outbox iniciado");
+        while !DRAINING.load(Ordering::SeqCst) {
+            match self.dispatch_batch().await {
+                // lote cheio: provavelmente há mais esperando
+                Ok(claimed) if claimed == self.batch_size as usize => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!("Falha ao ler o outbox: {err}"),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        tracing::info!("Dispatcher do outbox encerrado");
+    }
+
+    /// Processa um lote e devolve quantos eventos foram lidos
+    ///
+    /// As linhas ficam travadas até o fim do lote, então outro dispatcher não
+    /// entrega o mesmo evento em paralelo.
+    #[tracing::instrument(name = "OutboxDispatcher::dispatch_batch", skip_all, fields(claimed = tracing::field::Empty))]
+    pub async fn dispatch_batch(&self) -> Result<usize, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let events = OutboxRepository::claim_batch(&mut tx, self.batch_size).await?;
+        tracing::Span::current().record("claimed", events.len());
+
+        for event in &events {
+            match self.deliver(event).await {
+                Ok(()) => {
+                    OutboxRepository::mark_published(&mut tx, event.id).await?;
+                    metrics::record_outbox_delivery(&event.event_type, true);
+                }
+                Err((sink, err)) => {
+                    let delay = backoff(event.attempts + 1, self.max_backoff);
+                    tracing::warn!(
+                        event_id = %event.id,
+                        event_type = %event.event_type,
+                        sink,
+                        attempts = event.attempts + 1,
+                        retry_in_secs = delay.as_secs(),
+                        "Falha ao entregar evento: {err}"
+                    );
+                    let next_attempt_at = Utc::now()
+                        + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+                    OutboxRepository::mark_failed(
+                        &mut tx,
+                        event.id,
+                        &format!("{sink}: {err}"),
+                        next_attempt_at,
+                    )
+                    .await?;
+                    metrics::record_outbox_delivery(&event.event_type, false);
+                }
+            }
+        }
+
+        tx.commit().await?;
+        Ok(events.len())
+    }
+
+    /// Entrega em todos os sinks, parando no primeiro que falhar
+    async fn deliver(&self, event: &OutboxEvent) -> Result<(), (&'static str, SinkError)> {
+        for sink in &self.sinks {
+            sink.deliver(event)
+                .await
+                .map_err(|err| (sink.name(), err))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Publicação dos eventos de domínio gravados no outbox (entrega pelo menos uma vez)
+mod dispatcher;
+mod realtime;
+mod sinks;
+mod webhooks;
+
+pub use dispatcher::OutboxDispatcher;
+pub use realtime::{NotificationHub, NotificationListener, NotificationSubscription, StreamItem};
+pub use sinks::{FileSink, LogSink, WebhookSink};
+pub use webhooks::{
+    SIGNATURE_HEADER, TIMESTAMP_HEADER, WebhookDispatcher, WebhookFanout, WebhookSender, signature,
+};
+
+use std::{future::Future, pin::Pin, time::Duration};
+
+pub use crate::models::event::OutboxEvent;
+
+pub type SinkFuture<'a> = Pin<Box<dyn Future<Output = Result<(), SinkError>> + Send + 'a>>;
+
+/// Destino dos eventos; o evento só é marcado como publicado quando todos os sinks aceitam
+///
+/// Um evento pode chegar mais de uma vez (ex.: outro sink falhou e a entrega foi
+/// repetida), então quem consome deve deduplicar pelo `id`.
+pub trait EventSink: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn deliver<'a>(&'a self, event: &'a OutboxEvent) -> SinkFuture<'a>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SinkError {
+    #[error("Falha na requisição HTTP: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Destino respondeu com status {0}")]
+    Status(u16),
+
+    #[error("Falha de E/S: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Falha no banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Falha ao serializar o evento: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// Espera exponencial (2, 4, 8... segundos) limitada a `max`
+pub(crate) fn backoff(attempts: i32, max: Duration) -> Duration {
+    let exponent = attempts.clamp(1, 30) as u32;
+    Duration::from_secs(2u64.pow(exponent)).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_the_cap() {
+        let max = Duration::from_secs(300);
+        assert_eq!(backoff(1, max), Duration::from_secs(2));
+        assert_eq!(backoff(3, max), Duration::from_secs(8));
+        assert_eq!(backoff(9, max), max);
+        assert_eq!(backoff(i32::MAX, max), max);
+    }
+}
@@ -0,0 +1,253 @@
+//! Notificações em tempo real: um `LISTEN` por réplica repassa para as conexões
+//! SSE/WebSocket abertas nela
+use std::{
+    pin::Pin,
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, postgres::PgListener};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    time::{Instant, Interval, MissedTickBehavior, Sleep},
+};
+use uuid::Uuid;
+
+use crate::{
+    DRAINING,
+    config::RealtimeConfig,
+    models::notification::{NOTIFICATION_CHANNEL, Notification},
+};
+
+/// Espera antes de reabrir o `LISTEN` depois de uma falha
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// De quanto em quanto tempo a escuta confere se o servidor está desligando
+const DRAIN_CHECK: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+enum HubMessage {
+    Notification(Arc<Notification>),
+    /// O `LISTEN` caiu e notificações podem ter se perdido
+    Resync,
+}
+
+/// Distribui as notificações recebidas do Postgres entre as conexões da réplica
+///
+/// Fica no `app_data` como `web::Data<NotificationHub>`.
+#[derive(Clone)]
+pub struct NotificationHub {
+    sender: broadcast::Sender<HubMessage>,
+    heartbeat: Duration,
+}
+
+impl NotificationHub {
+    pub fn new(config: &RealtimeConfig) -> Self {
+        let (sender, _) = broadcast::channel(config.buffer_size);
+        Self {
+            sender,
+            heartbeat: Duration::from_secs(config.heartbeat_secs),
+        }
+    }
+
+    /// Conexão de um usuário; termina quando o token vence (`expires_at`)
+    pub fn subscribe(&self, user_id: Uuid, expires_at: DateTime<Utc>) -> NotificationSubscription {
+        let remaining = (expires_at - Utc::now()).to_std().unwrap_or_default();
+        let mut heartbeat =
+            tokio::time::interval_at(Instant::now() + self.heartbeat, self.heartbeat);
+        heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        NotificationSubscription {
+            user_id,
+            receiver: self.sender.subscribe(),
+            heartbeat,
+            expiry: Box::pin(tokio::time::sleep(remaining)),
+            finished: false,
+        }
+    }
+
+    /// Tarefa que escuta o canal no Postgres; roda uma por processo
+    pub fn listener(&self, pool: PgPool) -> NotificationListener {
+        NotificationListener {
+            pool,
+            hub: self.clone(),
+        }
+    }
+
+    fn broadcast(&self, message: HubMessage) {
+        // sem conexões abertas o envio falha, e tudo bem
+        let _ = self.sender.send(message);
+    }
+}
+
+pub struct NotificationListener {
+    pool: PgPool,
+    hub: NotificationHub,
+}
+
+impl NotificationListener {
+    /// Termina pouco depois de `DRAINING`; o binário espera o fim para a conexão
+    /// do `LISTEN` ser fechada ainda dentro do runtime
+    pub async fn run(self) {
+        tracing::info!("Escuta de notificações iniciada");
+        while !DRAINING.load(Ordering::SeqCst) {
+            if let Err(err) = self.listen().await {
+                tracing::error!("Falha na escuta de notificações: {err}");
+                self.hub.broadcast(HubMessage::Resync);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+        tracing::info!("Escuta de notificações encerrada");
+    }
+
+    async fn listen(&self) -> Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(NOTIFICATION_CHANNEL).await?;
+
+        while !DRAINING.load(Ordering::SeqCst) {
+            // o `try_recv` pode ser cancelado sem perder mensagens
+            let Ok(received) = tokio::time::timeout(DRAIN_CHECK, listener.try_recv()).await else {
+                continue;
+            };
+            // `None`: a conexão caiu; a próxima chamada reconecta sozinha
+            let Some(message) = received? else {
+                tracing::warn!("Conexão do LISTEN caiu; os clientes vão receber resync");
+                self.hub.broadcast(HubMessage::Resync);
+                continue;
+            };
+            match serde_json::from_str::<Notification>(message.payload()) {
+                Ok(notification) => self
+                    .hub
+                    .broadcast(HubMessage::Notification(Arc::new(notification))),
+                Err(err) => tracing::warn!("Notificação ignorada, payload inválido: {err}"),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// O que enviar ao cliente a seguir
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamItem {
+    Notification(Arc<Notification>),
+    Heartbeat,
+    /// Algo pode ter se perdido: o cliente deve recarregar saldos
+    Resync,
+    /// O token venceu; o cliente reconecta com um token novo
+    Expired,
+    /// A réplica está desligando; o cliente reconecta em outra
+    ShuttingDown,
+}
+
+impl StreamItem {
+    /// Quadro no formato `text/event-stream`
+    pub fn sse_frame(&self) -> String {
+        match self {
+            StreamItem::Notification(notification) => format!(
+                "id: {}\nevent: {}\ndata: {}\n\n",
+                notification.id,
+                notification.event_name(),
+                serde_json::to_string(notification.as_ref()).unwrap_or_default()
+            ),
+            StreamItem::Heartbeat => ": heartbeat\n\n".to_string(),
+            StreamItem::Resync => "event: resync\ndata: {}\n\n".to_string(),
+            StreamItem::Expired => "event: token_expired\ndata: {}\n\n".to_string(),
+            StreamItem::ShuttingDown => "event: shutdown\ndata: {}\n\n".to_string(),
+        }
+    }
+}
+
+/// Notificações de um usuário, com heartbeat e fim no vencimento do token
+pub struct NotificationSubscription {
+    user_id: Uuid,
+    receiver: broadcast::Receiver<HubMessage>,
+    heartbeat: Interval,
+    expiry: Pin<Box<Sleep>>,
+    finished: bool,
+}
+
+impl NotificationSubscription {
+    /// Próximo item; `None` depois de um item que encerra a conexão
+    ///
+    /// Pode ser cancelado (ex.: dentro de `select!`) sem perder notificações.
+    pub async fn next(&mut self) -> Option<StreamItem> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            tokio::select! {
+                _ = &mut self.expiry => {
+                    self.finished = true;
+                    return Some(StreamItem::Expired);
+                }
+                _ = self.heartbeat.tick() => {
+                    if DRAINING.load(Ordering::SeqCst) {
+                        self.finished = true;
+                        return Some(StreamItem::ShuttingDown);
+                    }
+                    return Some(StreamItem::Heartbeat);
+                }
+                message = self.receiver.recv() => match message {
+                    Ok(HubMessage::Notification(notification)) if notification.user_id == self.user_id => {
+                        self.finished = notification.ends_session();
+                        return Some(StreamItem::Notification(notification));
+                    }
+                    Ok(HubMessage::Notification(_)) => continue,
+                    Ok(HubMessage::Resync) | Err(RecvError::Lagged(_)) => {
+                        return Some(StreamItem::Resync);
+                    }
+                    Err(RecvError::Closed) => {
+                        self.finished = true;
+                        return None;
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::audit::{AuditAction, AuditContext};
+
+    #[tokio::test]
+    async fn test_subscription_filters_by_user_and_expires() {
+        let hub = NotificationHub::new(&RealtimeConfig::default());
+        let user_id = Uuid::new_v4();
+        let mut subscription =
+            hub.subscribe(user_id, Utc::now() + chrono::Duration::milliseconds(200));
+
+        let other = Notification::security(
+            Uuid::new_v4(),
+            AuditAction::LoginFailed,
+            &AuditContext::default(),
+        );
+        let mine =
+            Notification::security(user_id, AuditAction::LoginFailed, &AuditContext::default());
+        hub.broadcast(HubMessage::Notification(Arc::new(other)));
+        hub.broadcast(HubMessage::Notification(Arc::new(mine.clone())));
+
+        assert_eq!(
+            subscription.next().await,
+            Some(StreamItem::Notification(Arc::new(mine)))
+        );
+        assert_eq!(subscription.next().await, Some(StreamItem::Expired));
+        assert_eq!(subscription.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_ends_immediately() {
+        let hub = NotificationHub::new(&RealtimeConfig::default());
+        let mut subscription =
+            hub.subscribe(Uuid::new_v4(), Utc::now() - chrono::Duration::seconds(1));
+
+        assert_eq!(subscription.next().await, Some(StreamItem::Expired));
+        assert!(
+            StreamItem::Expired
+                .sse_frame()
+                .starts_with("event: token_expired\n")
+        );
+    }
+}
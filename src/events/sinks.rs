@@ -0,0 +1,145 @@
+use std::{path::PathBuf, time::Duration};
+
+use tokio::{fs::OpenOptions, io::AsyncWriteExt, sync::Mutex};
+
+use super::{EventSink, OutboxEvent, SinkError, SinkFuture};
+
+/// Escreve o evento no log estruturado
+pub struct LogSink;
+
+impl EventSink for LogSink {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    fn deliver<'a>(&'a self, event: &'a OutboxEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            tracing::info!(
+                target: "events",
+                event_id = %event.id,
+                event_type = %event.event_type,
+                aggregate_type = %event.aggregate_type,
+                aggregate_id = %event.aggregate_id,
+                payload = %event.payload,
+                "evento de domínio"
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Acrescenta cada evento como uma linha JSON num arquivo local
+pub struct FileSink {
+    path: PathBuf,
+    // serializa as escritas para as linhas não se misturarem
+    lock: Mutex<()>,
+}
+
+impl FileSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl EventSink for FileSink {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn deliver<'a>(&'a self, event: &'a OutboxEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            let mut line = serde_json::to_vec(event)?;
+            line.push(b'\n');
+
+            let _guard = self.lock.lock().await;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await?;
+            file.write_all(&line).await?;
+            file.flush().await?;
+            Ok(())
+        })
+    }
+}
+
+/// Envia o evento por POST; qualquer status fora de 2xx conta como falha
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Result<Self, SinkError> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+        Ok(Self {
+            client,
+            url: url.into(),
+        })
+    }
+}
+
+impl EventSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn deliver<'a>(&'a self, event: &'a OutboxEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(&self.url)
+                .header("X-Event-Id", event.id.to_string())
+                .header("X-Event-Type", &event.event_type)
+                .json(event)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(SinkError::Status(response.status().as_u16()));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_file_sink_appends_one_json_line_per_event() {
+        let path = std::env::temp_dir().join(format!("outbox-{}.jsonl", Uuid::new_v4()));
+        let sink = FileSink::new(&path);
+        let event = OutboxEvent {
+            id: Uuid::new_v4(),
+            seq: 1,
+            aggregate_type: "user".into(),
+            aggregate_id: Uuid::new_v4(),
+            event_type: "UserRegistered".into(),
+            payload: json!({ "email": "joao@email.com" }),
+            occurred_at: Utc::now(),
+            attempts: 0,
+        };
+
+        sink.deliver(&event).await.unwrap();
+        sink.deliver(&event).await.unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event_type"], "UserRegistered");
+        assert_eq!(lines[0]["payload"]["email"], "joao@email.com");
+        assert!(lines[0].get("seq").is_none());
+    }
+}
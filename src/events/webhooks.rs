@@ -0,0 +1,410 @@
+use std::{
+    fmt::Write as _,
+    net::SocketAddr,
+    sync::{Arc, atomic::Ordering},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures_util::future::join_all;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect,
+};
+use sha2::Sha256;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{EventSink, OutboxEvent, SinkError, SinkFuture, backoff};
+use crate::{
+    DRAINING,
+    config::WebhookConfig,
+    database::WebhookRepository,
+    models::webhook::{DeliveryAttempt, DueDelivery},
+    validators::WebhookValidator,
+};
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const DELIVERY_ID_HEADER: &str = "X-Webhook-Id";
+
+/// `sha256=` + HMAC-SHA256 hex de `"{timestamp}.{corpo}"`
+///
+/// O timestamp (segundos Unix, também enviado em `X-Webhook-Timestamp`) entra na
+/// assinatura para o destino recusar reenvios antigos de uma requisição capturada.
+pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC aceita chave de qualquer tamanho");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    let mut hex = String::from("sha256=");
+    for byte in mac.finalize().into_bytes() {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// Sink do outbox que transforma cada evento em entregas para as assinaturas interessadas
+pub struct WebhookFanout {
+    pool: PgPool,
+}
+
+impl WebhookFanout {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl EventSink for WebhookFanout {
+    fn name(&self) -> &'static str {
+        "webhook_fanout"
+    }
+
+    fn deliver<'a>(&'a self, event: &'a OutboxEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            let (user_ids, account_ids) = related_ids(event);
+            WebhookRepository::fan_out(&self.pool, event, &user_ids, &account_ids).await?;
+            Ok(())
+        })
+    }
+}
+
+/// Usuários e contas citados no payload; seus donos recebem o evento
+fn related_ids(event: &OutboxEvent) -> (Vec<Uuid>, Vec<Uuid>) {
+    let field = |name: &str| {
+        event
+            .payload
+            .get(name)
+            .and_then(|value| value.as_str())
+            .and_then(|raw| Uuid::parse_str(raw).ok())
+    };
+
+    let user_ids = field("user_id").into_iter().collect();
+    let account_ids = [
+        "account_id",
+        "from_account_id",
+        "to_account_id",
+        "beneficiary_account_id",
+        "credit_account_id",
+        "debit_account_id",
+    ]
+    .into_iter()
+    .filter_map(field)
+    .collect();
+    (user_ids, account_ids)
+}
+
+/// Resolver do cliente dos webhooks: só devolve endereços públicos
+///
+/// A checagem acontece na hora de conectar, então um DNS alterado depois do cadastro não
+/// leva o envio para a rede interna.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| WebhookValidator::is_public_ip(address.ip()))
+                .collect();
+            if addresses.is_empty() {
+                return Err(format!("{} não resolve para endereço público", name.as_str()).into());
+            }
+            let addresses: Addrs = Box::new(addresses.into_iter());
+            Ok(addresses)
+        })
+    }
+}
+
+/// Envia uma entrega assinada e mede o resultado
+pub struct WebhookSender {
+    client: reqwest::Client,
+    allow_local: bool,
+}
+
+impl WebhookSender {
+    /// Sem `allow_local`, só https para endereços públicos; redirects nunca são seguidos,
+    /// já que um 3xx poderia apontar para a rede interna
+    pub fn new(timeout: Duration, allow_local: bool) -> Result<Self, SinkError> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(redirect::Policy::none());
+        if !allow_local {
+            // um proxy resolveria o host por conta própria, fora do PublicResolver
+            builder = builder.no_proxy().dns_resolver(Arc::new(PublicResolver));
+        }
+        Ok(Self {
+            client: builder.build()?,
+            allow_local,
+        })
+    }
+
+    /// IPs literais não passam pelo resolver; assinaturas antigas podem ser http
+    fn check_target(&self, url: &str) -> Result<(), String> {
+        if self.allow_local {
+            return Ok(());
+        }
+        let url = Url::parse(url).map_err(|_| "URL inválida".to_string())?;
+        if url.scheme() != "https" {
+            return Err("destino recusado: só https".to_string());
+        }
+        if let Some(ip) = WebhookValidator::literal_ip(&url)
+            && !WebhookValidator::is_public_ip(ip)
+        {
+            return Err("destino recusado: endereço não público".to_string());
+        }
+        Ok(())
+    }
+
+    /// Qualquer status fora de 2xx conta como falha; do destino só o status é guardado
+    pub async fn send(&self, delivery: &DueDelivery) -> DeliveryAttempt {
+        let attempt = delivery.attempts + 1;
+        let attempted_at = Utc::now();
+        let started = Instant::now();
+        let body = serde_json::to_vec(&delivery.payload).unwrap_or_default();
+        let timestamp = attempted_at.timestamp();
+
+        if let Err(error) = self.check_target(&delivery.url) {
+            return DeliveryAttempt {
+                attempt,
+                status_code: None,
+                error: Some(error),
+                duration_ms: 0,
+                attempted_at,
+            };
+        }
+
+        let result = self
+            .client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(DELIVERY_ID_HEADER, delivery.id.to_string())
+            .header("X-Event-Id", delivery.event_id.to_string())
+            .header("X-Event-Type", &delivery.event_type)
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                signature(&delivery.secret, timestamp, &body),
+            )
+            .body(body)
+            .send()
+            .await;
+
+        let (status_code, error) = match result {
+            Ok(response) if response.status().is_success() => {
+                (Some(i32::from(response.status().as_u16())), None)
+            }
+            Ok(response) => {
+                let status = response.status();
+                (
+                    Some(i32::from(status.as_u16())),
+                    Some(format!("HTTP {status}")),
+                )
+            }
+            Err(err) => (None, Some(err.to_string())),
+        };
+
+        DeliveryAttempt {
+            attempt,
+            status_code,
+            error,
+            duration_ms: started.elapsed().as_millis() as i64,
+            attempted_at,
+        }
+    }
+}
+
+/// Entrega as notificações pendentes, com nova tentativa e fila morta
+pub struct WebhookDispatcher {
+    pool: PgPool,
+    sender: WebhookSender,
+    batch_size: u32,
+    poll_interval: Duration,
+    max_attempts: i32,
+    max_backoff: Duration,
+    lease_secs: u64,
+}
+
+impl WebhookDispatcher {
+    pub fn from_config(pool: PgPool, config: &WebhookConfig) -> Result<Self, SinkError> {
+        Ok(Self {
+            pool,
+            sender: WebhookSender::new(
+                Duration::from_secs(config.timeout_secs),
+                config.allow_local_targets,
+            )?,
+            batch_size: config.batch_size,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            max_attempts: config.max_attempts as i32,
+            max_backoff: Duration::from_secs(config.max_backoff_secs),
+            // folga sobre o timeout para a reserva não vencer durante o envio
+            lease_secs: config.timeout_secs * 2 + 5,
+        })
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Dispatcher de webhooks iniciado");
+        while !DRAINING.load(Ordering::SeqCst) {
+            match self.dispatch_batch().await {
+                Ok(claimed) if claimed == self.batch_size as usize => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!("Falha ao ler as entregas de webhook: {err}"),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        tracing::info!("Dispatcher de webhooks encerrado");
+    }
+
+    /// Envia um lote em paralelo e devolve quantas entregas foram reservadas
+    #[tracing::instrument(name = "WebhookDispatcher::dispatch_batch", skip_all, fields(claimed = tracing::field::Empty))]
+    pub async fn dispatch_batch(&self) -> Result<usize, sqlx::Error> {
+        let due =
+            WebhookRepository::claim_due(&self.pool, self.batch_size, self.lease_secs).await?;
+        tracing::Span::current().record("claimed", due.len());
+
+        let attempts = join_all(due.iter().map(|delivery| self.sender.send(delivery))).await;
+        for (delivery, attempt) in due.iter().zip(attempts) {
+            let next_attempt_at = match &attempt.error {
+                None => None,
+                Some(_) if attempt.attempt >= self.max_attempts => {
+                    tracing::warn!(
+                        delivery_id = %delivery.id,
+                        event_type = %delivery.event_type,
+                        attempts = attempt.attempt,
+                        "Webhook movido para a fila morta"
+                    );
+                    None
+                }
+                Some(_) => {
+                    let delay = backoff(attempt.attempt, self.max_backoff);
+                    Some(
+                        Utc::now()
+                            + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX),
+                    )
+                }
+            };
+            WebhookRepository::record_attempt(&self.pool, delivery.id, &attempt, next_attempt_at)
+                .await?;
+        }
+
+        Ok(due.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+    use serde_json::json;
+
+    #[test]
+    fn test_signature_covers_timestamp_and_body() {
+        let base = signature("whsec_teste", 1_700_000_000, b"{\"a\":1}");
+        assert!(base.starts_with("sha256="));
+        assert_eq!(base.len(), "sha256=".len() + 64);
+        assert_eq!(base, signature("whsec_teste", 1_700_000_000, b"{\"a\":1}"));
+        assert_ne!(base, signature("whsec_teste", 1_700_000_001, b"{\"a\":1}"));
+        assert_ne!(base, signature("whsec_outro", 1_700_000_000, b"{\"a\":1}"));
+    }
+
+    #[test]
+    fn test_related_ids_include_counterparty_accounts() {
+        let (payer, beneficiary) = (Uuid::new_v4(), Uuid::new_v4());
+        let event = OutboxEvent {
+            id: Uuid::new_v4(),
+            seq: 1,
+            aggregate_type: "account".into(),
+            aggregate_id: payer,
+            event_type: "BoletoPaid".into(),
+            payload: json!({
+                "account_id": payer,
+                "beneficiary_account_id": beneficiary,
+                "boleto_id": null,
+            }),
+            occurred_at: Utc::now(),
+            attempts: 0,
+        };
+
+        let (user_ids, account_ids) = related_ids(&event);
+        assert!(user_ids.is_empty());
+        assert_eq!(account_ids, vec![payer, beneficiary]);
+
+        let reversed = OutboxEvent {
+            event_type: "TransactionReversed".into(),
+            payload: json!({
+                "credit_account_id": payer,
+                "debit_account_id": beneficiary,
+            }),
+            ..event
+        };
+        assert_eq!(related_ids(&reversed).1, vec![payer, beneficiary]);
+    }
+
+    /// Stub que só aceita a requisição se a assinatura conferir
+    async fn stub(req: HttpRequest, body: web::Bytes) -> HttpResponse {
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap_or_default();
+        if header(SIGNATURE_HEADER) == signature("whsec_stub_secret", timestamp, &body) {
+            HttpResponse::NoContent().finish()
+        } else {
+            HttpResponse::Unauthorized().body("assinatura inválida")
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_sender_against_local_stub() {
+        let server = HttpServer::new(|| App::new().default_service(web::to(stub)))
+            .workers(1)
+            .bind("127.0.0.1:0")
+            .unwrap();
+        let address = server.addrs()[0];
+        let handle = server.run();
+        let stop = handle.handle();
+        actix_web::rt::spawn(handle);
+
+        let sender = WebhookSender::new(Duration::from_secs(5), true).unwrap();
+        let mut delivery = DueDelivery {
+            id: Uuid::new_v4(),
+            event_id: Uuid::new_v4(),
+            event_type: "DepositCompleted".into(),
+            payload: json!({ "event_type": "DepositCompleted", "payload": { "amount": "10.00" } }),
+            attempts: 0,
+            url: format!("http://{address}/hook"),
+            secret: "whsec_stub_secret".into(),
+        };
+
+        let ok = sender.send(&delivery).await;
+        assert_eq!(ok.attempt, 1);
+        assert_eq!(ok.status_code, Some(204));
+        assert!(ok.error.is_none());
+
+        delivery.secret = "whsec_segredo_errado".into();
+        delivery.attempts = 1;
+        let rejected = sender.send(&delivery).await;
+        assert_eq!(rejected.attempt, 2);
+        assert_eq!(rejected.status_code, Some(401));
+        assert_eq!(rejected.error.as_deref(), Some("HTTP 401 Unauthorized"));
+
+        // sem a exceção de ambiente local, o stub em loopback nem é contatado
+        let strict = WebhookSender::new(Duration::from_secs(5), false).unwrap();
+        let refused = strict.send(&delivery).await;
+        assert_eq!(refused.status_code, None);
+        assert!(refused.error.is_some());
+        delivery.url = "https://localhost:1/hook".into();
+        let refused = strict.send(&delivery).await;
+        assert_eq!(refused.status_code, None);
+        assert!(refused.error.is_some());
+
+        stop.stop(false).await;
+    }
+}
@@ -0,0 +1,103 @@
+use std::{collections::HashMap, path::Path};
+
+use rust_decimal::Decimal;
+
+use super::{FxRateError, FxRateProvider, RateFuture};
+use crate::models::currency::Currency;
+
+/// Taxas fixas lidas de um CSV `moeda,valor`, com o valor de uma unidade da
+/// moeda em reais (ex.: `USD,5.42`)
+///
+/// Os pares sem real saem cruzando pelas duas cotações em reais. Linhas vazias
+/// e começando com `#` são ignoradas.
+pub struct FileRateProvider {
+    /// Valor em reais de uma unidade de cada moeda; o real vale 1
+    in_brl: HashMap<Currency, Decimal>,
+}
+
+impl FileRateProvider {
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, FxRateError> {
+        let raw = tokio::fs::read_to_string(path).await?;
+        Self::parse(&raw)
+    }
+
+    pub fn parse(raw: &str) -> Result<Self, FxRateError> {
+        let mut in_brl = HashMap::from([(Currency::Brl, Decimal::ONE)]);
+        for (index, line) in raw.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |reason: String| FxRateError::InvalidFile {
+                line: index + 1,
+                reason,
+            };
+            let (currency, value) = line
+                .split_once(',')
+                .ok_or_else(|| invalid("esperado moeda,valor".into()))?;
+            let currency: Currency = currency.parse().map_err(invalid)?;
+            let value: Decimal = value
+                .trim()
+                .parse()
+                .map_err(|err: rust_decimal::Error| invalid(err.to_string()))?;
+            if value <= Decimal::ZERO {
+                return Err(invalid("o valor deve ser maior que zero".into()));
+            }
+            if currency == Currency::Brl && value != Decimal::ONE {
+                return Err(invalid("o real é a base e vale 1".into()));
+            }
+            in_brl.insert(currency, value);
+        }
+        Ok(Self { in_brl })
+    }
+
+    fn cross(&self, from: Currency, to: Currency) -> Result<Decimal, FxRateError> {
+        match (self.in_brl.get(&from), self.in_brl.get(&to)) {
+            (Some(from_brl), Some(to_brl)) => Ok(from_brl / to_brl),
+            _ => Err(FxRateError::Unavailable(from, to)),
+        }
+    }
+}
+
+impl FxRateProvider for FileRateProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn rate<'a>(&'a self, from: Currency, to: Currency) -> RateFuture<'a> {
+        Box::pin(async move { self.cross(from, to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_cross_rates() {
+        let provider = FileRateProvider::parse("# em reais\nUSD,5.00\n\neur, 6.00\n").unwrap();
+        assert_eq!(
+            provider.cross(Currency::Usd, Currency::Brl).unwrap(),
+            Decimal::from(5)
+        );
+        assert_eq!(
+            provider.cross(Currency::Brl, Currency::Usd).unwrap(),
+            Decimal::new(2, 1)
+        );
+        assert_eq!(
+            provider.cross(Currency::Eur, Currency::Usd).unwrap(),
+            Decimal::new(12, 1)
+        );
+        assert!(matches!(
+            provider.cross(Currency::Usd, Currency::Jpy),
+            Err(FxRateError::Unavailable(Currency::Usd, Currency::Jpy))
+        ));
+
+        assert!(matches!(
+            FileRateProvider::parse("USD;5.00"),
+            Err(FxRateError::InvalidFile { line: 1, .. })
+        ));
+        assert!(FileRateProvider::parse("KWD,17.6").is_err());
+        assert!(FileRateProvider::parse("USD,0").is_err());
+    }
+}
@@ -0,0 +1,57 @@
+//! Taxas de câmbio usadas nas cotações entre moedas
+//!
+//! Por enquanto há o provedor lido de um arquivo local, mas as cotações dependem
+//! só do trait.
+mod file;
+
+pub use file::FileRateProvider;
+
+use std::{future::Future, pin::Pin};
+
+use rust_decimal::Decimal;
+
+use crate::models::{app_error::AppError, currency::Currency, fx::error::FxError};
+
+pub type RateFuture<'a> = Pin<Box<dyn Future<Output = Result<Decimal, FxRateError>> + Send + 'a>>;
+
+/// Fonte da taxa média de um par de moedas, sem spread
+pub trait FxRateProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Unidades de `to` por unidade de `from`
+    fn rate<'a>(&'a self, from: Currency, to: Currency) -> RateFuture<'a>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FxRateError {
+    #[error("Sem taxa para {0}/{1}")]
+    Unavailable(Currency, Currency),
+
+    #[error("Arquivo de taxas inválido, linha {line}: {reason}")]
+    InvalidFile { line: usize, reason: String },
+
+    #[error("Falha de E/S: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Unidades de `currency` por real, para levar à moeda da conta os valores de
+/// `[limits]` e `[fees]`, que são configurados em reais
+pub async fn brl_rate(
+    provider: &dyn FxRateProvider,
+    currency: Currency,
+) -> Result<Decimal, AppError> {
+    if currency == Currency::Brl {
+        return Ok(Decimal::ONE);
+    }
+    Ok(provider.rate(Currency::Brl, currency).await?)
+}
+
+/// Par sem taxa vira erro de negócio; falhas do provedor, erro interno
+impl From<FxRateError> for AppError {
+    fn from(err: FxRateError) -> Self {
+        match err {
+            FxRateError::Unavailable(..) => FxError::RateUnavailable.into(),
+            err => AppError::Internal(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,472 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{
+    fx::fx_routes, holds::hold_routes, interest::interest_routes, limits::limit_routes,
+    scheduled_transfers::scheduled_transfer_routes,
+};
+use crate::{
+    database::{
+        AccountRepository, AuditRepository, BoletoRepository, InterestRepository, OutboxRepository,
+        PixRepository, ReversalRepository, ScheduledTransferRepository, TransactionRepository,
+    },
+    fx::FxRateProvider,
+    i18n::Locale,
+    middleware,
+    models::{
+        account::{Account, AccountBalance, CreateAccount, error::AccountError},
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        business::OperatorPermission,
+        claims::Claims,
+        event::DomainEvent,
+        interest::local_date,
+        pagination::{Pagination, PaginationResponse},
+        transaction::{CreateTransaction, CreateTransfer, Transaction, TransactionFilter},
+    },
+    services::{InterestService, KycService, TransactionService, authorize_account},
+    utils::limit_settings,
+};
+
+/// Busca a conta garantindo que o usuário do token é o dono ou um operador dela
+async fn owned_account(
+    pool: &PgPool,
+    claims: &Claims,
+    account_id: Uuid,
+) -> Result<Account, AppError> {
+    let account = AccountRepository::find_by_id(pool, account_id)
+        .await?
+        .ok_or(AccountError::NotFound)?;
+    let mut conn = pool.acquire().await?;
+    authorize_account(
+        &mut conn,
+        &account,
+        claims.user_id()?,
+        OperatorPermission::View,
+    )
+    .await?;
+    Ok(account)
+}
+
+/// abre uma nova conta para o usuário do token (RF009)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "accounts",
+    request_body = CreateAccount,
+    responses(
+        (status = 201, description = "Conta criada", body = ApiResponse<Account>),
+        (status = 400, description = "Dados inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Cadastro (KYC) ainda não aprovado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("")]
+#[tracing::instrument(name = "handler.create_account", skip_all, fields(user_id = %claims.sub))]
+async fn create_account(
+    pool: web::Data<PgPool>,
+    web::Json(request): web::Json<CreateAccount>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let mut account = Account::open(claims.user_id()?, request.account_type, request.currency);
+
+    let mut tx = pool.begin().await?;
+    KycService::ensure_approved(&mut tx, account.user_id).await?;
+    AccountRepository::insert(&mut tx, &mut account).await?;
+    let event = audit
+        .event(AuditAction::AccountOpened, "account", Some(account.id))
+        .after(account.audit_snapshot());
+    AuditRepository::append(&mut tx, &event).await?;
+    let event = DomainEvent::AccountOpened {
+        account_id: account.id,
+        user_id: account.user_id,
+        account_number: account.account_number.clone(),
+        account_type: account.account_type,
+        currency: account.currency,
+    };
+    OutboxRepository::enqueue(&mut tx, event).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        account,
+        locale.message("ACCOUNT_CREATED"),
+    )))
+}
+
+/// lista as contas do usuário do token e as das empresas em que ele é operador (RF010)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "accounts",
+    responses(
+        (status = 200, description = "Contas do usuário e das empresas que ele opera", body = ApiResponse<Vec<Account>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("")]
+#[tracing::instrument(name = "handler.list_accounts", skip_all, fields(user_id = %claims.sub))]
+async fn list_accounts(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let accounts = AccountRepository::list_accessible(&pool, claims.user_id()?).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        accounts,
+        locale.message("ACCOUNTS_LISTED"),
+    )))
+}
+
+/// detalhes de uma conta do usuário (RF011)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "accounts",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    responses(
+        (status = 200, description = "Detalhes da conta", body = ApiResponse<Account>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}")]
+#[tracing::instrument(name = "handler.get_account", skip_all, fields(user_id = %claims.sub))]
+async fn get_account(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let account = owned_account(&pool, &claims, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        account,
+        locale.message("ACCOUNT_DETAILS"),
+    )))
+}
+
+/// saldo contábil, reservado e disponível da conta (RF012)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "accounts",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    responses(
+        (status = 200, description = "Saldo da conta", body = ApiResponse<AccountBalance>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/balance")]
+#[tracing::instrument(name = "handler.get_balance", skip_all, fields(user_id = %claims.sub))]
+async fn get_balance(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let account = owned_account(&pool, &claims, path.into_inner()).await?;
+    let balance = AccountBalance {
+        available_balance: account.available_balance(),
+        account_id: account.id,
+        account_number: account.account_number,
+        currency: account.currency,
+        balance: account.balance,
+        overdraft_limit: account.overdraft_limit,
+        held_balance: account.held_balance,
+    };
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        balance,
+        locale.message("BALANCE_RETRIEVED"),
+    )))
+}
+
+/// encerra a conta; só é permitido com saldo zerado, sem bloqueios e sem estorno a recuperar
+///
+/// Juros apurados e não lançados que somem ao menos um centavo recusam o
+/// encerramento: o cliente lança antes (`POST /accounts/{id}/interest/settle`) e
+/// retira ou cobre o saldo. Menos que isso só marca os dias como lançados.
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "accounts",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    responses(
+        (status = 200, description = "Conta encerrada", body = ApiResponse<Uuid>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Conta inativa, com saldo, com valor bloqueado, com estorno a recuperar ou com juros a lançar", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/{id}")]
+#[tracing::instrument(name = "handler.close_account", skip_all, fields(user_id = %claims.sub))]
+async fn close_account(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let account_id = path.into_inner();
+
+    let mut tx = pool.begin().await?;
+    let mut account = AccountRepository::lock_by_id(&mut tx, account_id)
+        .await?
+        .ok_or(AccountError::NotFound)?;
+    authorize_account(
+        &mut tx,
+        &account,
+        claims.user_id()?,
+        OperatorPermission::Manage,
+    )
+    .await?;
+    if !account.is_active {
+        return Err(AccountError::Inactive.into());
+    }
+    if !account.balance.is_zero() {
+        return Err(AccountError::NonZeroBalance.into());
+    }
+    if !account.held_balance.is_zero() {
+        return Err(AccountError::HeldBalance.into());
+    }
+    // o estorno pendente é cobrado dos créditos que chegarem; com a conta fechada, ficaria sem cobrança
+    if ReversalRepository::has_pending_recovery(&mut tx, account.id).await? {
+        return Err(AccountError::PendingRecovery.into());
+    }
+    // conta inativa não entra no lançamento mensal: os juros apurados precisam sair antes
+    let today = local_date(Utc::now(), limit_settings().utc_offset_hours);
+    let until = today.succ_opt().unwrap_or(today);
+    let pending = InterestRepository::pending(&mut tx, account.id, until).await?;
+    if !pending.credit_amount(account.currency).is_zero() {
+        return Err(AccountError::PendingInterest.into());
+    }
+    InterestService::settle(&mut tx, &account, until).await?;
+
+    AccountRepository::deactivate(&mut tx, account.id).await?;
+    let released_keys = PixRepository::release_account(&mut tx, account.id).await?;
+    let cancelled_boletos = BoletoRepository::cancel_by_account(&mut tx, account.id).await?;
+    let cancelled_schedules =
+        ScheduledTransferRepository::cancel_by_account(&mut tx, account.id).await?;
+    let before = account.audit_snapshot();
+    account.is_active = false;
+    let event = audit
+        .event(AuditAction::AccountDeactivated, "account", Some(account.id))
+        .before(before)
+        .after(account.audit_snapshot())
+        .metadata(serde_json::json!({
+            "released_pix_keys": released_keys,
+            "cancelled_boletos": cancelled_boletos,
+            "cancelled_scheduled_transfers": cancelled_schedules,
+        }));
+    AuditRepository::append(&mut tx, &event).await?;
+    let event = DomainEvent::AccountClosed {
+        account_id: account.id,
+        user_id: account.user_id,
+    };
+    OutboxRepository::enqueue(&mut tx, event).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        account.id,
+        locale.message("ACCOUNT_CLOSED"),
+    )))
+}
+
+/// depósito na conta (RF013)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "transactions",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    request_body = CreateTransaction,
+    responses(
+        (status = 201, description = "Depósito realizado", body = ApiResponse<Transaction>),
+        (status = 400, description = "Valor ou descrição inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Conta inativa", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/deposit")]
+#[tracing::instrument(name = "handler.deposit", skip_all, fields(user_id = %claims.sub))]
+async fn deposit(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateTransaction>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let transaction = TransactionService::deposit(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        transaction,
+        locale.message("DEPOSIT_COMPLETED"),
+    )))
+}
+
+/// saque da conta (RF014)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "transactions",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    request_body = CreateTransaction,
+    responses(
+        (status = 201, description = "Saque realizado", body = ApiResponse<Transaction>),
+        (status = 400, description = "Valor ou descrição inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão; titular sem KYC aprovado", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Saldo insuficiente (inclusive para a tarifa), limite excedido, conta inativa ou sem taxa de câmbio para a moeda da conta", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/withdraw")]
+#[tracing::instrument(name = "handler.withdraw", skip_all, fields(user_id = %claims.sub))]
+async fn withdraw(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateTransaction>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let transaction = TransactionService::withdraw(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        transaction,
+        locale.message("WITHDRAW_COMPLETED"),
+    )))
+}
+
+/// transferência para outra conta pelo número (RF015)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "transactions",
+    params(("id" = Uuid, Path, description = "ID da conta de origem")),
+    request_body = CreateTransfer,
+    responses(
+        (status = 201, description = "Transferência realizada (perna de débito)", body = ApiResponse<Transaction>),
+        (status = 400, description = "Valor ou descrição inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de origem de outro usuário ou operador sem permissão; titular sem KYC aprovado", body = ApiErrorResponse),
+        (status = 404, description = "Conta de origem ou destino não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Saldo insuficiente (inclusive para a tarifa), limite excedido, mesma conta, conta inativa ou sem taxa de câmbio para a moeda da conta", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/transfer")]
+#[tracing::instrument(name = "handler.transfer", skip_all, fields(user_id = %claims.sub))]
+async fn transfer(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateTransfer>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let transaction = TransactionService::transfer(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        transaction,
+        locale.message("TRANSFER_COMPLETED"),
+    )))
+}
+
+/// extrato paginado da conta, mais recentes primeiro (RF016)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "transactions",
+    params(("id" = Uuid, Path, description = "ID da conta"), TransactionFilter),
+    responses(
+        (status = 200, description = "Extrato da conta", body = PaginationResponse<Transaction>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/transactions")]
+#[tracing::instrument(name = "handler.list_transactions", skip_all, fields(user_id = %claims.sub))]
+async fn list_transactions(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Query(filter): web::Query<TransactionFilter>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let account = owned_account(&pool, &claims, path.into_inner()).await?;
+
+    let (page, limit) = Pagination::bounds(filter.page, filter.limit);
+    let (transactions, total) = TransactionRepository::list_by_account(
+        &pool,
+        account.id,
+        &filter,
+        limit,
+        Pagination::offset(page, limit),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(PaginationResponse::new(
+        transactions,
+        Pagination::new(page, limit, total as u64),
+        locale.message("TRANSACTIONS_LISTED"),
+    )))
+}
+
+pub fn account_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/accounts")
+            .wrap(middleware::Authentication)
+            .service(create_account)
+            .service(list_accounts)
+            .service(get_account)
+            .service(get_balance)
+            .service(close_account)
+            .service(deposit)
+            .service(withdraw)
+            .service(transfer)
+            .service(list_transactions)
+            .configure(scheduled_transfer_routes)
+            .configure(hold_routes)
+            .configure(limit_routes)
+            .configure(interest_routes)
+            .configure(fx_routes),
+    );
+}
@@ -0,0 +1,639 @@
+use actix_web::{HttpResponse, delete, get, http::header, post, put, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::{AuditRepository, KycRepository, ReversalRepository, UserRepository},
+    fx::FxRateProvider,
+    i18n::Locale,
+    middleware,
+    models::{
+        UpdateTier, UserProfile,
+        account::{Account, UpdateOverdraft},
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext, AuditEvent, AuditQuery, ChainVerification},
+        claims::Claims,
+        error::UserError,
+        fee::{CreateFeeWaiver, FeeWaiver},
+        kyc::{
+            KycOverview, KycProfile, KycQuery, KycReviewItem, KycStatus, RejectKyc, error::KycError,
+        },
+        pagination::{Pagination, PaginationResponse},
+        reversal::{CreateReversal, Reversal, ReversalQuery},
+    },
+    services::{FeeService, KycService, OverdraftService, ReversalService},
+    storage::DocumentStorage,
+};
+
+/// consulta o log de auditoria, mais recentes primeiro
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "Eventos de auditoria", body = PaginationResponse<AuditEvent>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/audit")]
+#[tracing::instrument(name = "handler.list_audit_events", skip_all, fields(user_id = %claims.sub))]
+async fn list_audit_events(
+    pool: web::Data<PgPool>,
+    web::Query(query): web::Query<AuditQuery>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let (page, limit) = Pagination::bounds(query.page, query.limit);
+    let (events, total) =
+        AuditRepository::search(&pool, &query, limit, Pagination::offset(page, limit)).await?;
+
+    Ok(HttpResponse::Ok().json(PaginationResponse::new(
+        events,
+        Pagination::new(page, limit, total as u64),
+        locale.message("AUDIT_EVENTS_LISTED"),
+    )))
+}
+
+/// recalcula a cadeia de hashes e aponta o primeiro evento adulterado
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Resultado da verificação", body = ApiResponse<ChainVerification>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/audit/verify")]
+#[tracing::instrument(name = "handler.verify_audit_chain", skip_all, fields(user_id = %claims.sub))]
+async fn verify_audit_chain(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let verification = AuditRepository::verify_chain(&pool).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        verification,
+        locale.message("AUDIT_CHAIN_VERIFIED"),
+    )))
+}
+
+/// fila de revisão do KYC, enviados há mais tempo primeiro
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(KycQuery),
+    responses(
+        (status = 200, description = "Cadastros na fila", body = PaginationResponse<KycReviewItem>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/kyc")]
+#[tracing::instrument(name = "handler.list_kyc_queue", skip_all, fields(user_id = %claims.sub))]
+async fn list_kyc_queue(
+    pool: web::Data<PgPool>,
+    web::Query(query): web::Query<KycQuery>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    // sem filtro, tudo que ainda espera decisão
+    let statuses = match query.status {
+        Some(status) => vec![status],
+        None => vec![KycStatus::Submitted, KycStatus::UnderReview],
+    };
+    let (page, limit) = Pagination::bounds(query.page, query.limit);
+    let (items, total) =
+        KycRepository::review_queue(&pool, &statuses, limit, Pagination::offset(page, limit))
+            .await?;
+
+    Ok(HttpResponse::Ok().json(PaginationResponse::new(
+        items,
+        Pagination::new(page, limit, total as u64),
+        locale.message("KYC_QUEUE_LISTED"),
+    )))
+}
+
+/// cadastro de um usuário com os documentos enviados
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "ID do usuário")),
+    responses(
+        (status = 200, description = "Situação do cadastro", body = ApiResponse<KycOverview>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Usuário ou cadastro não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/kyc/{user_id}")]
+#[tracing::instrument(name = "handler.get_kyc_profile", skip_all, fields(user_id = %claims.sub))]
+async fn get_kyc_profile(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let overview = KycService::overview(&pool, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        overview,
+        locale.message("KYC_RETRIEVED"),
+    )))
+}
+
+/// baixa o arquivo de um documento enviado
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(
+        ("user_id" = Uuid, Path, description = "ID do usuário"),
+        ("document_id" = Uuid, Path, description = "ID do documento"),
+    ),
+    responses(
+        (status = 200, description = "Arquivo do documento", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Documento não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/kyc/{user_id}/documents/{document_id}")]
+#[tracing::instrument(name = "handler.download_kyc_document", skip_all, fields(user_id = %claims.sub))]
+async fn download_kyc_document(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn DocumentStorage>,
+    path: web::Path<(Uuid, Uuid)>,
+    claims: Claims,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let (user_id, document_id) = path.into_inner();
+    let document = KycRepository::find_document(&pool, user_id, document_id)
+        .await?
+        .ok_or(KycError::DocumentNotFound)?;
+    let content = storage.get(&document.storage_key).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(document.content_type.as_str())
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}-{}\"",
+                document.document_type.as_str(),
+                document.id
+            ),
+        ))
+        .body(content))
+}
+
+/// assume a revisão de um cadastro enviado
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "ID do usuário")),
+    responses(
+        (status = 200, description = "Cadastro em revisão", body = ApiResponse<KycProfile>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador ou é o próprio cadastro", body = ApiErrorResponse),
+        (status = 404, description = "Cadastro não encontrado", body = ApiErrorResponse),
+        (status = 409, description = "Cadastro não está aguardando revisão", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/kyc/{user_id}/review")]
+#[tracing::instrument(name = "handler.start_kyc_review", skip_all, fields(user_id = %claims.sub))]
+async fn start_kyc_review(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let profile =
+        KycService::start_review(&pool, claims.user_id()?, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        profile,
+        locale.message("KYC_REVIEW_STARTED"),
+    )))
+}
+
+/// aprova um cadastro em revisão; o usuário passa a abrir contas e movimentar
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "ID do usuário")),
+    responses(
+        (status = 200, description = "Cadastro aprovado", body = ApiResponse<KycProfile>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador ou é o próprio cadastro", body = ApiErrorResponse),
+        (status = 404, description = "Cadastro não encontrado", body = ApiErrorResponse),
+        (status = 409, description = "Cadastro não está em revisão", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/kyc/{user_id}/approve")]
+#[tracing::instrument(name = "handler.approve_kyc", skip_all, fields(user_id = %claims.sub))]
+async fn approve_kyc(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let profile = KycService::approve(&pool, claims.user_id()?, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(profile, locale.message("KYC_APPROVED"))))
+}
+
+/// rejeita um cadastro em revisão; o motivo é mostrado ao usuário, que pode reenviar
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("user_id" = Uuid, Path, description = "ID do usuário")),
+    request_body = RejectKyc,
+    responses(
+        (status = 200, description = "Cadastro rejeitado", body = ApiResponse<KycProfile>),
+        (status = 400, description = "Motivo ausente ou longo demais", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador ou é o próprio cadastro", body = ApiErrorResponse),
+        (status = 404, description = "Cadastro não encontrado", body = ApiErrorResponse),
+        (status = 409, description = "Cadastro não está em revisão", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/kyc/{user_id}/reject")]
+#[tracing::instrument(name = "handler.reject_kyc", skip_all, fields(user_id = %claims.sub))]
+async fn reject_kyc(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<RejectKyc>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let profile = KycService::reject(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(profile, locale.message("KYC_REJECTED"))))
+}
+
+/// estorna uma transação concluída, total ou parcialmente, com lançamentos de compensação
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "ID da transação (qualquer perna, se for transferência)")),
+    request_body = CreateReversal,
+    responses(
+        (status = 201, description = "Estorno feito; `pending_recovery` se a conta debitada não tinha saldo", body = ApiResponse<Reversal>),
+        (status = 400, description = "Valor, motivo ou observação inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Transação não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Transação já estornada por completo", body = ApiErrorResponse),
+        (status = 422, description = "Transação não estornável, valor acima do restante ou conta a creditar inativa", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/{id}/reversals")]
+#[tracing::instrument(name = "handler.reverse_transaction", skip_all, fields(user_id = %claims.sub))]
+async fn reverse_transaction(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateReversal>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let reversal = ReversalService::reverse(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        reversal,
+        locale.message("REVERSAL_CREATED"),
+    )))
+}
+
+/// estornos de uma transação, na ordem em que foram feitos
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "ID da transação")),
+    responses(
+        (status = 200, description = "Estornos da transação", body = ApiResponse<Vec<Reversal>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Transação não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/{id}/reversals")]
+#[tracing::instrument(name = "handler.list_transaction_reversals", skip_all, fields(user_id = %claims.sub))]
+async fn list_transaction_reversals(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let reversals = ReversalService::list_for_transaction(&pool, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        reversals,
+        locale.message("REVERSALS_LISTED"),
+    )))
+}
+
+/// todos os estornos, mais recentes primeiro; `?status=pending_recovery` mostra as cobranças em aberto
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(ReversalQuery),
+    responses(
+        (status = 200, description = "Estornos", body = PaginationResponse<Reversal>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/reversals")]
+#[tracing::instrument(name = "handler.list_reversals", skip_all, fields(user_id = %claims.sub))]
+async fn list_reversals(
+    pool: web::Data<PgPool>,
+    web::Query(query): web::Query<ReversalQuery>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let (page, limit) = Pagination::bounds(query.page, query.limit);
+    let (reversals, total) =
+        ReversalRepository::list(&pool, query.status, limit, Pagination::offset(page, limit))
+            .await?;
+
+    Ok(HttpResponse::Ok().json(PaginationResponse::new(
+        reversals,
+        Pagination::new(page, limit, total as u64),
+        locale.message("REVERSALS_LISTED"),
+    )))
+}
+
+/// concede isenção de tarifas a uma conta, de uma operação ou de todas
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    request_body = CreateFeeWaiver,
+    responses(
+        (status = 201, description = "Isenção concedida", body = ApiResponse<FeeWaiver>),
+        (status = 400, description = "Motivo ausente ou validade no passado", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/accounts/{id}/fee-waivers")]
+#[tracing::instrument(name = "handler.grant_fee_waiver", skip_all, fields(user_id = %claims.sub))]
+async fn grant_fee_waiver(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateFeeWaiver>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let waiver = FeeService::grant_waiver(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        waiver,
+        locale.message("FEE_WAIVER_GRANTED"),
+    )))
+}
+
+/// isenções da conta, mais recentes primeiro (inclusive vencidas e revogadas)
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    responses(
+        (status = 200, description = "Isenções da conta", body = ApiResponse<Vec<FeeWaiver>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/accounts/{id}/fee-waivers")]
+#[tracing::instrument(name = "handler.list_fee_waivers", skip_all, fields(user_id = %claims.sub))]
+async fn list_fee_waivers(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let waivers = FeeService::list_waivers(&pool, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        waivers,
+        locale.message("FEE_WAIVERS_LISTED"),
+    )))
+}
+
+/// revoga uma isenção; as próximas operações voltam a ser tarifadas
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "ID da isenção")),
+    responses(
+        (status = 200, description = "Isenção revogada", body = ApiResponse<FeeWaiver>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Isenção não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Isenção já revogada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/fee-waivers/{id}")]
+#[tracing::instrument(name = "handler.revoke_fee_waiver", skip_all, fields(user_id = %claims.sub))]
+async fn revoke_fee_waiver(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let waiver = FeeService::revoke_waiver(&pool, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        waiver,
+        locale.message("FEE_WAIVER_REVOKED"),
+    )))
+}
+
+/// troca a faixa do cliente, que define as tarifas cobradas dali em diante
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "ID do usuário")),
+    request_body = UpdateTier,
+    responses(
+        (status = 200, description = "Faixa alterada", body = ApiResponse<UserProfile>),
+        (status = 400, description = "Faixa inválida", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Usuário não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/users/{id}/tier")]
+#[tracing::instrument(name = "handler.update_user_tier", skip_all, fields(user_id = %claims.sub))]
+async fn update_user_tier(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<UpdateTier>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let mut tx = pool.begin().await?;
+    let mut user = UserRepository::lock_by_id(&mut tx, path.into_inner())
+        .await?
+        .ok_or(UserError::NotFound)?;
+    UserRepository::update_tier(&mut tx, user.id, request.tier).await?;
+
+    let before = user.audit_snapshot();
+    user.tier = request.tier;
+    let event = audit
+        .event(AuditAction::UserUpdated, "user", Some(user.id))
+        .before(before)
+        .after(user.audit_snapshot());
+    AuditRepository::append(&mut tx, &event).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        UserProfile::from(user),
+        locale.message("TIER_UPDATED"),
+    )))
+}
+
+/// define o limite do cheque especial de uma conta corrente; zero desliga
+#[utoipa::path(
+    context_path = "/admin",
+    tag = "admin",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    request_body = UpdateOverdraft,
+    responses(
+        (status = 200, description = "Limite alterado", body = ApiResponse<Account>),
+        (status = 400, description = "Limite inválido", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é administrador", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Limite menor que o cheque especial em uso", body = ApiErrorResponse),
+        (status = 422, description = "Conta inativa, que não é corrente ou sem taxa de câmbio para converter o teto", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/accounts/{id}/overdraft")]
+#[tracing::instrument(name = "handler.update_overdraft", skip_all, fields(user_id = %claims.sub))]
+async fn update_overdraft(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<UpdateOverdraft>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    claims.require_admin()?;
+
+    let account = OverdraftService::update_limit(
+        &pool,
+        provider.get_ref(),
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        account,
+        locale.message("OVERDRAFT_UPDATED"),
+    )))
+}
+
+pub fn admin_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/admin")
+            .wrap(middleware::Authentication)
+            .service(list_audit_events)
+            .service(verify_audit_chain)
+            .service(list_kyc_queue)
+            .service(get_kyc_profile)
+            .service(download_kyc_document)
+            .service(start_kyc_review)
+            .service(approve_kyc)
+            .service(reject_kyc)
+            .service(reverse_transaction)
+            .service(list_transaction_reversals)
+            .service(list_reversals)
+            .service(grant_fee_waiver)
+            .service(list_fee_waivers)
+            .service(revoke_fee_waiver)
+            .service(update_user_tier)
+            .service(update_overdraft),
+    );
+}
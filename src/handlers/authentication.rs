@@ -1,108 +1,206 @@
 use actix_web::{
-    HttpResponse, Responder, post,
+    HttpResponse, post,
     web::{self, Data, Json, ServiceConfig},
 };
+use chrono::Utc;
+use serde_json::json;
 use sqlx::PgPool;
 
 use crate::{
-    database::{RefreshTokenRepository, UserRepository},
+    database::{
+        AuditRepository, KycRepository, NotificationRepository, OutboxRepository,
+        RefreshTokenRepository, UserRepository,
+    },
+    i18n::Locale,
+    metrics,
     models::{
-        CreateUser, LoginUserRequest, LoginUserResponse, User, api_response::ApiResponse,
+        CreateUser, LoginUserRequest, LoginUserResponse, RefreshTokenRequest, User,
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
         error::UserError,
+        event::DomainEvent,
+        notification::Notification,
     },
     utils::{create_token, create_token_refresh, verify_password},
     validators::LoginValidator,
 };
 
+#[utoipa::path(
+    context_path = "/auth",
+    tag = "auth",
+    request_body = CreateUser,
+    responses(
+        (status = 201, description = "Usuário criado", body = ApiResponse<Uuid>),
+        (status = 400, description = "Dados inválidos", body = ApiErrorResponse),
+        (status = 409, description = "Email ou documento já cadastrado", body = ApiErrorResponse),
+    )
+)]
 #[post("/register")]
-async fn register(pool: Data<PgPool>, Json(create_user): Json<CreateUser>) -> impl Responder {
-    let user = match User::try_from(create_user) {
-        Ok(u) => u,
-        Err(err) => {
-            return HttpResponse::Conflict().json(ApiResponse::<()>::error(
-                "Falha ao criar usuario",
-                &err.to_string(),
-            ));
-        }
+#[tracing::instrument(name = "handler.register", skip_all)]
+async fn register(
+    pool: Data<PgPool>,
+    locale: Locale,
+    Json(create_user): Json<CreateUser>,
+) -> Result<HttpResponse, AppError> {
+    let user = User::try_from(create_user)?;
+
+    let mut tx = pool.begin().await?;
+    let uuid = UserRepository::insert(&mut tx, &user).await?;
+    KycRepository::create_profile(&mut tx, uuid).await?;
+    let event = DomainEvent::UserRegistered {
+        user_id: uuid,
+        email: user.email,
+        name: user.name,
     };
+    OutboxRepository::enqueue(&mut tx, event).await?;
+    tx.commit().await?;
 
-    match UserRepository::insert(&pool, &user).await {
-        Ok(uuid) => {
-            HttpResponse::Created().json(ApiResponse::sucess(uuid, "Usuario criado com sucesso"))
-        }
-        Err(err) => HttpResponse::Conflict().json(ApiResponse::<()>::error(
-            "Erro ao criar usuario, verifique informaçoes e tente novamente",
-            &err.to_string(),
-        )),
-    }
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(uuid, locale.message("USER_CREATED"))))
 }
 
+#[utoipa::path(
+    context_path = "/auth",
+    tag = "auth",
+    request_body = LoginUserRequest,
+    responses(
+        (status = 200, description = "Login efetuado", body = ApiResponse<LoginUserResponse>),
+        (status = 400, description = "Dados inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Credenciais inválidas", body = ApiErrorResponse),
+    )
+)]
 #[post("/login")]
-pub async fn login(pool: Data<PgPool>, login: Json<LoginUserRequest>) -> impl Responder {
-    let validated_login = match LoginValidator::validate_login_data(&login.email, &login.password) {
-        Ok(data) => data,
-        Err(_) => {
-            return HttpResponse::BadRequest().json(ApiResponse::<()>::error(
-                "INVALID INPUT",
-                "Dados de login inválidos",
-            ));
-        }
-    };
+#[tracing::instrument(name = "handler.login", skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn login(
+    pool: Data<PgPool>,
+    locale: Locale,
+    audit: AuditContext,
+    login: Json<LoginUserRequest>,
+) -> Result<HttpResponse, AppError> {
+    let validated_login = LoginValidator::validate_login_data(&login.email, &login.password)?;
 
-    let user = match UserRepository::find_by_email(&pool, &validated_login.email).await {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            return HttpResponse::Unauthorized().json(ApiResponse::<()>::error(
-                &UserError::InvalidCredentials.to_string(),
-                "Email ou senha incorretos",
-            ));
-        }
-        Err(_) => {
-            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
-                &UserError::InvalidCredentials.to_string(),
-                "Erro interno do servidor",
-            ));
+    let user = UserRepository::find_by_email(&pool, &validated_login.email).await?;
+    let user = match user {
+        Some(user) if verify_password(&validated_login.password, &user.password_hash) => user,
+        user => {
+            metrics::record_login(false);
+            // o email tentado fica só no metadata; sem usuário, o alvo fica vazio
+            let user_id = user.map(|user| user.id);
+            let event = audit
+                .event(AuditAction::LoginFailed, "user", user_id)
+                .metadata(json!({ "email": validated_login.email }));
+            let mut tx = pool.begin().await?;
+            AuditRepository::append(&mut tx, &event).await?;
+            if let Some(user_id) = user_id {
+                let notification =
+                    Notification::security(user_id, AuditAction::LoginFailed, &audit);
+                NotificationRepository::publish(&mut tx, &notification).await?;
+            }
+            tx.commit().await?;
+            return Err(UserError::InvalidCredentials.into());
         }
     };
 
-    if !verify_password(&validated_login.password, &user.password_hash) {
-        return HttpResponse::Unauthorized().json(ApiResponse::<()>::error(
-            &UserError::InvalidCredentials.to_string(),
-            "Email ou senha incorretos",
-        ));
+    let token = create_token(&user).map_err(|err| AppError::Internal(err.to_string()))?;
+
+    let (refresh_token, expires_at) = create_token_refresh();
+    let mut tx = pool.begin().await?;
+    RefreshTokenRepository::insert(&mut tx, user.id, &refresh_token, expires_at).await?;
+    let audit = audit.with_actor(user.id);
+    let event = audit.event(AuditAction::LoginSucceeded, "user", Some(user.id));
+    AuditRepository::append(&mut tx, &event).await?;
+    let notification = Notification::security(user.id, AuditAction::LoginSucceeded, &audit);
+    NotificationRepository::publish(&mut tx, &notification).await?;
+    tx.commit().await?;
+
+    metrics::record_login(true);
+    tracing::Span::current().record("user_id", tracing::field::display(user.id));
+
+    // sem preferência salva, a resposta segue o idioma negociado na requisição
+    let locale = user
+        .locale
+        .as_deref()
+        .and_then(Locale::from_tag)
+        .unwrap_or(locale);
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        LoginUserResponse {
+            refresh_token,
+            token,
+            user_id: user.id,
+            email: user.email,
+        },
+        locale.message("LOGIN_SUCCESS"),
+    )))
+}
+
+/// troca um refresh token válido por um novo par de tokens; o antigo deixa de valer
+#[utoipa::path(
+    context_path = "/auth",
+    tag = "auth",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Tokens renovados", body = ApiResponse<LoginUserResponse>),
+        (status = 401, description = "Refresh token inválido ou expirado", body = ApiErrorResponse),
+    )
+)]
+#[post("/refresh")]
+#[tracing::instrument(name = "handler.refresh", skip_all, fields(user_id = tracing::field::Empty))]
+async fn refresh(
+    pool: Data<PgPool>,
+    locale: Locale,
+    audit: AuditContext,
+    Json(request): Json<RefreshTokenRequest>,
+) -> Result<HttpResponse, AppError> {
+    let mut tx = pool.begin().await?;
+    let (user_id, expires_at) = RefreshTokenRepository::take(&mut tx, &request.refresh_token)
+        .await?
+        .ok_or(UserError::InvalidRefreshToken)?;
+    if expires_at <= Utc::now() {
+        // o token vencido já foi removido; a remoção vale mesmo com o erro
+        tx.commit().await?;
+        return Err(UserError::InvalidRefreshToken.into());
     }
 
-    let token = match create_token(&user) {
-        Ok(token) => token,
-        Err(_) => {
-            return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
-                "FAILED CREATE TOKEN",
-                "Erro interno do servidore",
-            ));
-        }
-    };
+    let user = UserRepository::lock_by_id(&mut tx, user_id)
+        .await?
+        .ok_or(UserError::InvalidRefreshToken)?;
+    let token = create_token(&user).map_err(|err| AppError::Internal(err.to_string()))?;
 
     let (refresh_token, expires_at) = create_token_refresh();
+    RefreshTokenRepository::insert(&mut tx, user.id, &refresh_token, expires_at).await?;
+    let audit = audit.with_actor(user.id);
+    let event = audit.event(AuditAction::TokenRefreshed, "user", Some(user.id));
+    AuditRepository::append(&mut tx, &event).await?;
+    let notification = Notification::security(user.id, AuditAction::TokenRefreshed, &audit);
+    NotificationRepository::publish(&mut tx, &notification).await?;
+    tx.commit().await?;
 
-    if let Err(_) = RefreshTokenRepository::insert(&pool, user.id, &refresh_token, expires_at).await
-    {
-        return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
-            "Erro a criar token",
-            "Error ao criar token refresh",
-        ));
-    }
+    tracing::Span::current().record("user_id", tracing::field::display(user.id));
+
+    let locale = user
+        .locale
+        .as_deref()
+        .and_then(Locale::from_tag)
+        .unwrap_or(locale);
 
-    HttpResponse::Ok().json(ApiResponse::sucess(
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
         LoginUserResponse {
             refresh_token,
             token,
             user_id: user.id,
             email: user.email,
         },
-        "login efetuado com sucesso",
-    ))
+        locale.message("TOKEN_REFRESHED"),
+    )))
 }
 
 pub fn auth_routes(cfg: &mut ServiceConfig) {
-    cfg.service(web::scope("/auth").service(register).service(login));
+    cfg.service(
+        web::scope("/auth")
+            .service(register)
+            .service(login)
+            .service(refresh)
+            .configure(super::users::legacy_user_routes),
+    );
 }
@@ -0,0 +1,282 @@
+use actix_web::{
+    HttpResponse, get,
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    post, web,
+};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::{AccountRepository, BoletoRepository, UserRepository},
+    fx::FxRateProvider,
+    i18n::Locale,
+    middleware,
+    models::{
+        account::error::AccountError,
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::AuditContext,
+        boleto::{
+            Boleto, BoletoBarcode, CreateBoleto, ParseBoleto, ParsedBoleto, PayBoleto,
+            error::BoletoError,
+        },
+        claims::Claims,
+        error::UserError,
+        interest::local_date,
+        transaction::Transaction,
+    },
+    pdf::{self, boleto::Beneficiary},
+    services::BoletoService,
+    utils::limit_settings,
+};
+
+/// emite um boleto para receber em uma conta do usuário
+#[utoipa::path(
+    context_path = "/boletos",
+    tag = "boletos",
+    request_body = CreateBoleto,
+    responses(
+        (status = 201, description = "Boleto emitido", body = ApiResponse<Boleto>),
+        (status = 400, description = "Valor, vencimento ou pagador inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Conta inativa ou saldo insuficiente para a tarifa", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("")]
+#[tracing::instrument(name = "handler.issue_boleto", skip_all, fields(user_id = %claims.sub))]
+async fn issue_boleto(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    web::Json(request): web::Json<CreateBoleto>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let boleto = BoletoService::issue(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(boleto, locale.message("BOLETO_ISSUED"))))
+}
+
+/// lista os boletos emitidos pelo usuário, mais recentes primeiro
+#[utoipa::path(
+    context_path = "/boletos",
+    tag = "boletos",
+    responses(
+        (status = 200, description = "Boletos do usuário", body = ApiResponse<Vec<Boleto>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("")]
+#[tracing::instrument(name = "handler.list_boletos", skip_all, fields(user_id = %claims.sub))]
+async fn list_boletos(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let boletos = BoletoRepository::list_by_user(&pool, claims.user_id()?).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        boletos,
+        locale.message("BOLETOS_LISTED"),
+    )))
+}
+
+#[utoipa::path(
+    context_path = "/boletos",
+    tag = "boletos",
+    params(("id" = Uuid, Path, description = "ID do boleto")),
+    responses(
+        (status = 200, description = "Boleto", body = ApiResponse<Boleto>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Boleto não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}")]
+#[tracing::instrument(name = "handler.get_boleto", skip_all, fields(user_id = %claims.sub))]
+async fn get_boleto(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let boleto = BoletoRepository::find_owned(&pool, path.into_inner(), claims.user_id()?)
+        .await?
+        .ok_or(BoletoError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(boleto, locale.message("BOLETO_FOUND"))))
+}
+
+/// boleto em PDF para impressão, com recibo do pagador e ficha de compensação
+#[utoipa::path(
+    context_path = "/boletos",
+    tag = "boletos",
+    params(("id" = Uuid, Path, description = "ID do boleto")),
+    responses(
+        (status = 200, description = "PDF do boleto", content_type = "application/pdf"),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Boleto não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/pdf")]
+#[tracing::instrument(name = "handler.boleto_pdf", skip_all, fields(user_id = %claims.sub))]
+async fn boleto_pdf(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+) -> Result<HttpResponse, AppError> {
+    let user_id = claims.user_id()?;
+    let boleto = BoletoRepository::find_owned(&pool, path.into_inner(), user_id)
+        .await?
+        .ok_or(BoletoError::NotFound)?;
+    let account = AccountRepository::find_by_id(&pool, boleto.account_id)
+        .await?
+        .ok_or(AccountError::NotFound)?;
+    let owner = UserRepository::find_by_id(&pool, user_id)
+        .await?
+        .ok_or(UserError::NotFound)?;
+
+    let document = pdf::boleto::render(
+        &boleto,
+        &Beneficiary {
+            name: &owner.name,
+            account_number: &account.account_number,
+        },
+    );
+    Ok(HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Inline,
+            parameters: vec![DispositionParam::Filename(format!(
+                "boleto-{}.pdf",
+                boleto.our_number
+            ))],
+        })
+        .body(document))
+}
+
+/// cancela um boleto em aberto; ele deixa de poder ser pago
+#[utoipa::path(
+    context_path = "/boletos",
+    tag = "boletos",
+    params(("id" = Uuid, Path, description = "ID do boleto")),
+    responses(
+        (status = 200, description = "Boleto cancelado", body = ApiResponse<Boleto>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Boleto não encontrado", body = ApiErrorResponse),
+        (status = 409, description = "Boleto já pago ou cancelado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/cancel")]
+#[tracing::instrument(name = "handler.cancel_boleto", skip_all, fields(user_id = %claims.sub))]
+async fn cancel_boleto(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let boleto = BoletoService::cancel(&pool, claims.user_id()?, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        boleto,
+        locale.message("BOLETO_CANCELLED"),
+    )))
+}
+
+/// confere a linha digitável (ou o código de barras) e mostra banco, vencimento e valor
+#[utoipa::path(
+    context_path = "/boletos",
+    tag = "boletos",
+    request_body = ParseBoleto,
+    responses(
+        (status = 200, description = "Dados do boleto", body = ApiResponse<ParsedBoleto>),
+        (status = 400, description = "Linha digitável malformada ou com dígito verificador errado", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/parse")]
+#[tracing::instrument(name = "handler.parse_boleto", skip_all, fields(user_id = %claims.sub))]
+async fn parse_boleto(
+    web::Json(request): web::Json<ParseBoleto>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let barcode =
+        BoletoBarcode::parse(&request.line).map_err(|err| AppError::Validation(err.into()))?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        barcode.into_parsed(local_date(Utc::now(), limit_settings().utc_offset_hours)),
+        locale.message("BOLETO_PARSED"),
+    )))
+}
+
+/// paga um boleto com débito na conta; boleto emitido aqui credita o beneficiário na hora
+#[utoipa::path(
+    context_path = "/boletos",
+    tag = "boletos",
+    request_body = PayBoleto,
+    responses(
+        (status = 201, description = "Boleto pago; devolve o lançamento de débito", body = ApiResponse<Transaction>),
+        (status = 400, description = "Linha digitável, valor ou descrição inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão; titular sem KYC aprovado", body = ApiErrorResponse),
+        (status = 404, description = "Conta ou boleto não encontrado", body = ApiErrorResponse),
+        (status = 409, description = "Boleto já pago ou cancelado", body = ApiErrorResponse),
+        (status = 422, description = "Saldo insuficiente, boleto vencido ou conta beneficiária", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/payments")]
+#[tracing::instrument(name = "handler.pay_boleto", skip_all, fields(user_id = %claims.sub))]
+async fn pay_boleto(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    web::Json(request): web::Json<PayBoleto>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let transaction = BoletoService::pay(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        transaction,
+        locale.message("BOLETO_PAID"),
+    )))
+}
+
+pub fn boleto_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/boletos")
+            .wrap(middleware::Authentication)
+            .service(issue_boleto)
+            .service(list_boletos)
+            .service(parse_boleto)
+            .service(pay_boleto)
+            .service(get_boleto)
+            .service(boleto_pdf)
+            .service(cancel_boleto),
+    );
+}
@@ -0,0 +1,168 @@
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::{OperatorRepository, UserRepository},
+    i18n::Locale,
+    middleware,
+    models::{
+        CustomerType,
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::AuditContext,
+        business::{AddOperator, BusinessOperator, UpdateOperator, error::BusinessError},
+        claims::Claims,
+        error::UserError,
+    },
+    services::BusinessService,
+};
+
+/// lista os operadores da empresa do token
+#[utoipa::path(
+    context_path = "/business",
+    tag = "business",
+    responses(
+        (status = 200, description = "Operadores da empresa", body = ApiResponse<Vec<BusinessOperator>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é cliente empresa", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/operators")]
+#[tracing::instrument(name = "handler.list_operators", skip_all, fields(user_id = %claims.sub))]
+async fn list_operators(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let business = UserRepository::find_by_id(&pool, claims.user_id()?)
+        .await?
+        .ok_or(UserError::NotFound)?;
+    if business.customer_type != CustomerType::Business {
+        return Err(BusinessError::NotBusiness.into());
+    }
+    let operators = OperatorRepository::list_by_business(&pool, business.id).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        operators,
+        locale.message("OPERATORS_LISTED"),
+    )))
+}
+
+/// autoriza uma pessoa física cadastrada a operar as contas da empresa
+#[utoipa::path(
+    context_path = "/business",
+    tag = "business",
+    request_body = AddOperator,
+    responses(
+        (status = 201, description = "Operador autorizado", body = ApiResponse<BusinessOperator>),
+        (status = 400, description = "Permissões inválidas", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é cliente empresa", body = ApiErrorResponse),
+        (status = 404, description = "Nenhum usuário com esse email", body = ApiErrorResponse),
+        (status = 409, description = "Usuário já é operador da empresa", body = ApiErrorResponse),
+        (status = 422, description = "Usuário inativo ou não é pessoa física", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/operators")]
+#[tracing::instrument(name = "handler.add_operator", skip_all, fields(user_id = %claims.sub))]
+async fn add_operator(
+    pool: web::Data<PgPool>,
+    web::Json(request): web::Json<AddOperator>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let operator =
+        BusinessService::add_operator(&pool, claims.user_id()?, &request, &audit).await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        operator,
+        locale.message("OPERATOR_ADDED"),
+    )))
+}
+
+/// troca as permissões de um operador; vale na próxima requisição dele
+#[utoipa::path(
+    context_path = "/business",
+    tag = "business",
+    params(("id" = Uuid, Path, description = "ID do operador")),
+    request_body = UpdateOperator,
+    responses(
+        (status = 200, description = "Permissões atualizadas", body = ApiResponse<BusinessOperator>),
+        (status = 400, description = "Permissões inválidas", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é cliente empresa", body = ApiErrorResponse),
+        (status = 404, description = "Operador não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/operators/{id}")]
+#[tracing::instrument(name = "handler.update_operator", skip_all, fields(user_id = %claims.sub))]
+async fn update_operator(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<UpdateOperator>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let operator = BusinessService::update_operator(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        operator,
+        locale.message("OPERATOR_UPDATED"),
+    )))
+}
+
+/// revoga o acesso de um operador às contas da empresa
+#[utoipa::path(
+    context_path = "/business",
+    tag = "business",
+    params(("id" = Uuid, Path, description = "ID do operador")),
+    responses(
+        (status = 200, description = "Operador removido", body = ApiResponse<BusinessOperator>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Usuário não é cliente empresa", body = ApiErrorResponse),
+        (status = 404, description = "Operador não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/operators/{id}")]
+#[tracing::instrument(name = "handler.remove_operator", skip_all, fields(user_id = %claims.sub))]
+async fn remove_operator(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let operator =
+        BusinessService::remove_operator(&pool, claims.user_id()?, path.into_inner(), &audit)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        operator,
+        locale.message("OPERATOR_REMOVED"),
+    )))
+}
+
+pub fn business_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/business")
+            .wrap(middleware::Authentication)
+            .service(list_operators)
+            .service(add_operator)
+            .service(update_operator)
+            .service(remove_operator),
+    );
+}
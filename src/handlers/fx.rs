@@ -0,0 +1,61 @@
+use actix_web::{HttpResponse, post, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    fx::FxRateProvider,
+    i18n::Locale,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        claims::Claims,
+        fx::{CreateFxQuote, FxQuote},
+    },
+    services::FxService,
+};
+
+/// trava a taxa de uma transferência para conta de outra moeda; informe o `id` em `fx_quote_id` antes de `expires_at`
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "fx",
+    params(("id" = Uuid, Path, description = "ID da conta de origem")),
+    request_body = CreateFxQuote,
+    responses(
+        (status = 201, description = "Cotação travada", body = ApiResponse<FxQuote>),
+        (status = 400, description = "Valor inválido ou com mais casas que a moeda da conta", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta de origem ou de destino não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Contas na mesma moeda, par sem taxa, valor convertido zerado ou conta inativa", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/fx-quotes")]
+#[tracing::instrument(name = "handler.create_fx_quote", skip_all, fields(user_id = %claims.sub))]
+async fn create_fx_quote(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateFxQuote>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let quote = FxService::quote(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        quote,
+        locale.message("FX_QUOTE_CREATED"),
+    )))
+}
+
+/// Rotas dentro do escopo `/accounts` (a autenticação vem dele)
+pub(super) fn fx_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(create_fx_quote);
+}
@@ -0,0 +1,222 @@
+use std::{
+    future::Future,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use actix_web::{
+    HttpResponse, get,
+    web::{self, Data},
+};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+
+use crate::{
+    DRAINING, MIGRATOR,
+    database::HealthRepository,
+    models::health::{HealthCheck, HealthReport, HealthStatus, NamedCheck},
+};
+
+/// Tempo máximo de cada verificação antes de ser considerada `down`
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A partir desta fração de conexões em uso o pool é reportado como `degraded`
+const POOL_DEGRADED_RATIO: f64 = 0.8;
+
+/// Liveness: o processo está de pé e respondendo
+#[get("/live")]
+#[tracing::instrument(name = "handler.live", skip_all)]
+async fn live() -> HttpResponse {
+    HttpResponse::Ok().json(HealthReport::with_status(HealthStatus::Up))
+}
+
+/// Readiness: banco acessível, migrations aplicadas e pool com folga
+#[get("/ready")]
+#[tracing::instrument(name = "handler.ready", skip_all)]
+async fn ready(pool: Data<PgPool>) -> HttpResponse {
+    if DRAINING.load(Ordering::SeqCst) {
+        return HttpResponse::ServiceUnavailable()
+            .json(HealthReport::with_status(HealthStatus::Draining));
+    }
+
+    // lido antes das verificações, que também pegam conexões do pool
+    let pool_check = check_pool(&pool);
+    let (database, migrations) = tokio::join!(
+        timed("database", async {
+            HealthRepository::ping(&pool).await?;
+            Ok((HealthStatus::Up, None))
+        }),
+        timed("migrations", check_migrations(&pool)),
+    );
+
+    let report = HealthReport::new(vec![
+        NamedCheck {
+            name: "database",
+            check: database,
+        },
+        NamedCheck {
+            name: "migrations",
+            check: migrations,
+        },
+        NamedCheck {
+            name: "pool",
+            check: pool_check,
+        },
+    ]);
+
+    if report.is_ready() {
+        HttpResponse::Ok().json(report)
+    } else {
+        HttpResponse::ServiceUnavailable().json(report)
+    }
+}
+
+async fn check_migrations(pool: &PgPool) -> Result<(HealthStatus, Option<Value>), sqlx::Error> {
+    let applied = HealthRepository::applied_migrations(pool).await?;
+    let pending: Vec<i64> = MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| migration.version)
+        .filter(|version| !applied.contains(version))
+        .collect();
+
+    let status = if pending.is_empty() {
+        HealthStatus::Up
+    } else {
+        HealthStatus::Down
+    };
+    Ok((
+        status,
+        Some(json!({ "applied": applied.len(), "pending": pending })),
+    ))
+}
+
+fn check_pool(pool: &PgPool) -> HealthCheck {
+    let started = Instant::now();
+    let max = pool.options().get_max_connections();
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    let in_use = size.saturating_sub(idle);
+
+    HealthCheck {
+        status: pool_status(in_use, max),
+        latency_ms: elapsed_ms(started),
+        details: Some(json!({
+            "size": size,
+            "idle": idle,
+            "in_use": in_use,
+            "max": max,
+            "saturation": saturation(in_use, max),
+        })),
+        error: None,
+    }
+}
+
+fn saturation(in_use: u32, max: u32) -> f64 {
+    if max == 0 {
+        1.0
+    } else {
+        f64::from(in_use) / f64::from(max)
+    }
+}
+
+/// Sem conexão livre o pool está saturado e novas requisições ficariam na fila
+fn pool_status(in_use: u32, max: u32) -> HealthStatus {
+    let ratio = saturation(in_use, max);
+    if ratio >= 1.0 {
+        HealthStatus::Down
+    } else if ratio >= POOL_DEGRADED_RATIO {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Up
+    }
+}
+
+/// Executa a verificação com timeout, medindo a latência
+///
+/// O endpoint é público: a causa da falha vai só para o log, a resposta leva um texto fixo
+async fn timed<F>(name: &'static str, check: F) -> HealthCheck
+where
+    F: Future<Output = Result<(HealthStatus, Option<Value>), sqlx::Error>>,
+{
+    let started = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, check).await;
+    let latency_ms = elapsed_ms(started);
+
+    match result {
+        Ok(Ok((status, details))) => HealthCheck {
+            status,
+            latency_ms,
+            details,
+            error: None,
+        },
+        Ok(Err(err)) => {
+            tracing::error!(check = name, "Falha na verificação de saúde: {err}");
+            HealthCheck {
+                status: HealthStatus::Down,
+                latency_ms,
+                details: None,
+                error: Some("unavailable".to_string()),
+            }
+        }
+        Err(_) => {
+            tracing::warn!(check = name, "Verificação de saúde excedeu o timeout");
+            HealthCheck {
+                status: HealthStatus::Down,
+                latency_ms,
+                details: None,
+                error: Some(format!("timeout após {}s", CHECK_TIMEOUT.as_secs())),
+            }
+        }
+    }
+}
+
+fn elapsed_ms(started: Instant) -> f64 {
+    started.elapsed().as_secs_f64() * 1000.0
+}
+
+pub fn health_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/health").service(live).service(ready));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_status_thresholds() {
+        assert_eq!(pool_status(2, 10), HealthStatus::Up);
+        assert_eq!(pool_status(8, 10), HealthStatus::Degraded);
+        assert_eq!(pool_status(10, 10), HealthStatus::Down);
+        assert_eq!(pool_status(0, 0), HealthStatus::Down);
+    }
+
+    #[test]
+    fn test_report_aggregates_checks() {
+        let check = |status| HealthCheck {
+            status,
+            latency_ms: 0.0,
+            details: None,
+            error: None,
+        };
+
+        let report = HealthReport::new(vec![
+            NamedCheck {
+                name: "database",
+                check: check(HealthStatus::Up),
+            },
+            NamedCheck {
+                name: "pool",
+                check: check(HealthStatus::Degraded),
+            },
+        ]);
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.is_ready());
+
+        let report = HealthReport::new(vec![NamedCheck {
+            name: "migrations",
+            check: check(HealthStatus::Down),
+        }]);
+        assert!(!report.is_ready());
+    }
+}
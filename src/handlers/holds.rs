@@ -0,0 +1,212 @@
+use actix_web::{HttpResponse, get, post, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    fx::FxRateProvider,
+    i18n::Locale,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::AuditContext,
+        claims::Claims,
+        hold::{CaptureHold, CreateHold, Hold, HoldFilter},
+        pagination::{Pagination, PaginationResponse},
+    },
+    services::HoldService,
+};
+
+/// reserva um valor na conta: sai do saldo disponível, mas o contábil só muda na captura
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "holds",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    request_body = CreateHold,
+    responses(
+        (status = 201, description = "Valor reservado", body = ApiResponse<Hold>),
+        (status = 400, description = "Valor, descrição ou validade inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão; titular sem KYC aprovado", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Saldo disponível insuficiente, limite excedido, conta inativa ou sem taxa de câmbio para a moeda da conta", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/holds")]
+#[tracing::instrument(name = "handler.place_hold", skip_all, fields(user_id = %claims.sub))]
+async fn place_hold(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateHold>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let hold = HoldService::place(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(hold, locale.message("HOLD_PLACED"))))
+}
+
+/// reservas da conta, mais recentes primeiro
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "holds",
+    params(("id" = Uuid, Path, description = "ID da conta"), HoldFilter),
+    responses(
+        (status = 200, description = "Reservas da conta", body = PaginationResponse<Hold>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/holds")]
+#[tracing::instrument(name = "handler.list_holds", skip_all, fields(user_id = %claims.sub))]
+async fn list_holds(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Query(filter): web::Query<HoldFilter>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (page, limit) = Pagination::bounds(filter.page, filter.limit);
+    let (holds, total) = HoldService::list(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        filter.status,
+        limit,
+        Pagination::offset(page, limit),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(PaginationResponse::new(
+        holds,
+        Pagination::new(page, limit, total as u64),
+        locale.message("HOLDS_LISTED"),
+    )))
+}
+
+/// reserva com a situação e o valor capturado, quando houver
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "holds",
+    params(
+        ("id" = Uuid, Path, description = "ID da conta"),
+        ("hold_id" = Uuid, Path, description = "ID da reserva"),
+    ),
+    responses(
+        (status = 200, description = "Reserva", body = ApiResponse<Hold>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta ou reserva não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/holds/{hold_id}")]
+#[tracing::instrument(name = "handler.get_hold", skip_all, fields(user_id = %claims.sub))]
+async fn get_hold(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (account_id, hold_id) = path.into_inner();
+    let hold = HoldService::find(&pool, claims.user_id()?, account_id, hold_id).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(hold, locale.message("HOLD_FOUND"))))
+}
+
+/// captura a reserva (toda ou parte); o restante volta ao disponível
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "holds",
+    params(
+        ("id" = Uuid, Path, description = "ID da conta"),
+        ("hold_id" = Uuid, Path, description = "ID da reserva"),
+    ),
+    request_body = CaptureHold,
+    responses(
+        (status = 200, description = "Reserva capturada e valor debitado", body = ApiResponse<Hold>),
+        (status = 400, description = "Valor inválido", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta ou reserva não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Reserva já encerrada ou expirada", body = ApiErrorResponse),
+        (status = 422, description = "Valor maior que o reservado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/holds/{hold_id}/capture")]
+#[tracing::instrument(name = "handler.capture_hold", skip_all, fields(user_id = %claims.sub))]
+async fn capture_hold(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    web::Json(request): web::Json<CaptureHold>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (account_id, hold_id) = path.into_inner();
+    let hold = HoldService::capture(
+        &pool,
+        claims.user_id()?,
+        account_id,
+        hold_id,
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(hold, locale.message("HOLD_CAPTURED"))))
+}
+
+/// libera a reserva sem debitar
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "holds",
+    params(
+        ("id" = Uuid, Path, description = "ID da conta"),
+        ("hold_id" = Uuid, Path, description = "ID da reserva"),
+    ),
+    responses(
+        (status = 200, description = "Reserva liberada", body = ApiResponse<Hold>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta ou reserva não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Reserva já encerrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/holds/{hold_id}/release")]
+#[tracing::instrument(name = "handler.release_hold", skip_all, fields(user_id = %claims.sub))]
+async fn release_hold(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (account_id, hold_id) = path.into_inner();
+    let hold = HoldService::release(&pool, claims.user_id()?, account_id, hold_id, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(hold, locale.message("HOLD_RELEASED"))))
+}
+
+/// Rotas dentro do escopo `/accounts` (a autenticação vem dele)
+pub(super) fn hold_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(place_hold)
+        .service(list_holds)
+        .service(get_hold)
+        .service(capture_hold)
+        .service(release_hold);
+}
@@ -0,0 +1,79 @@
+use actix_web::{HttpResponse, get, post, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    i18n::Locale,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        claims::Claims,
+        interest::{InterestSettlement, InterestSummary},
+    },
+    services::InterestService,
+};
+
+/// taxa em vigor e juros apurados que entram no próximo crédito mensal
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "interest",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    responses(
+        (status = 200, description = "Rendimento da conta", body = ApiResponse<InterestSummary>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/interest")]
+#[tracing::instrument(name = "handler.get_interest", skip_all, fields(user_id = %claims.sub))]
+async fn get_interest(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let summary = InterestService::summary(&pool, claims.user_id()?, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        summary,
+        locale.message("INTEREST_RETRIEVED"),
+    )))
+}
+
+/// lança agora os juros apurados e ainda não lançados; necessário antes de encerrar a conta
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "interest",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    responses(
+        (status = 200, description = "Juros lançados (sem lançamento se a soma não chegou a um centavo)", body = ApiResponse<InterestSettlement>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Conta inativa", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/interest/settle")]
+#[tracing::instrument(name = "handler.settle_interest", skip_all, fields(user_id = %claims.sub))]
+async fn settle_interest(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let settlement =
+        InterestService::settle_now(&pool, claims.user_id()?, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        settlement,
+        locale.message("INTEREST_SETTLED"),
+    )))
+}
+
+/// Rotas dentro do escopo `/accounts` (a autenticação vem dele)
+pub(super) fn interest_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_interest).service(settle_interest);
+}
@@ -0,0 +1,167 @@
+use actix_web::{
+    FromRequest, HttpRequest, HttpResponse, dev::Payload, get, http::header, post, put, web,
+};
+use futures_util::{StreamExt, future::LocalBoxFuture};
+use sqlx::PgPool;
+
+use crate::{
+    i18n::Locale,
+    middleware,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::AuditContext,
+        claims::Claims,
+        kyc::{KycDocument, KycDocumentType, KycOverview, MAX_DOCUMENT_BYTES},
+    },
+    services::KycService,
+    storage::DocumentStorage,
+    validators::FieldError,
+};
+
+/// situação do cadastro do usuário do token, com os documentos que faltam
+#[utoipa::path(
+    context_path = "/kyc",
+    tag = "kyc",
+    responses(
+        (status = 200, description = "Situação do cadastro", body = ApiResponse<KycOverview>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("")]
+#[tracing::instrument(name = "handler.get_kyc", skip_all, fields(user_id = %claims.sub))]
+async fn get_kyc(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let overview = KycService::overview(&pool, claims.user_id()?).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        overview,
+        locale.message("KYC_RETRIEVED"),
+    )))
+}
+
+/// envia um documento (corpo binário: JPEG, PNG ou PDF até 5 MiB); reenviar substitui
+#[utoipa::path(
+    context_path = "/kyc",
+    tag = "kyc",
+    params(("document_type" = KycDocumentType, Path, description = "Tipo do documento")),
+    request_body(content = Vec<u8>, content_type = "application/pdf", description = "Arquivo do documento (image/jpeg, image/png ou application/pdf)"),
+    responses(
+        (status = 201, description = "Documento recebido", body = ApiResponse<KycDocument>),
+        (status = 400, description = "Arquivo vazio, grande demais ou em formato não aceito", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 409, description = "Cadastro já enviado para revisão", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/documents/{document_type}")]
+#[tracing::instrument(name = "handler.upload_kyc_document", skip_all, fields(user_id = %claims.sub))]
+async fn upload_kyc_document(
+    pool: web::Data<PgPool>,
+    storage: web::Data<dyn DocumentStorage>,
+    path: web::Path<KycDocumentType>,
+    upload: DocumentUpload,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let document = KycService::upload(
+        &pool,
+        storage.get_ref(),
+        claims.user_id()?,
+        path.into_inner(),
+        upload.content_type.as_deref(),
+        &upload.content,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        document,
+        locale.message("KYC_DOCUMENT_UPLOADED"),
+    )))
+}
+
+/// envia o cadastro para revisão; exige os documentos obrigatórios
+#[utoipa::path(
+    context_path = "/kyc",
+    tag = "kyc",
+    responses(
+        (status = 200, description = "Cadastro enviado", body = ApiResponse<KycOverview>),
+        (status = 400, description = "Documentos obrigatórios não enviados", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 409, description = "Cadastro já enviado ou aprovado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/submit")]
+#[tracing::instrument(name = "handler.submit_kyc", skip_all, fields(user_id = %claims.sub))]
+async fn submit_kyc(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let overview = KycService::submit(&pool, claims.user_id()?, &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        overview,
+        locale.message("KYC_SUBMITTED"),
+    )))
+}
+
+/// Corpo binário do documento com o `Content-Type` informado
+struct DocumentUpload {
+    content_type: Option<String>,
+    content: Vec<u8>,
+}
+
+/// Lê o corpo até um byte além do limite; o validador rejeita o excesso
+impl FromRequest for DocumentUpload {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let mut payload = payload.take();
+
+        Box::pin(async move {
+            let mut content = Vec::new();
+            while let Some(chunk) = payload.next().await {
+                let chunk = chunk.map_err(|_| {
+                    AppError::Validation(
+                        FieldError::new("body", "INVALID_BODY", "Corpo da requisição inválido")
+                            .into(),
+                    )
+                })?;
+                let room = MAX_DOCUMENT_BYTES + 1 - content.len();
+                content.extend_from_slice(&chunk[..chunk.len().min(room)]);
+                if content.len() > MAX_DOCUMENT_BYTES {
+                    break;
+                }
+            }
+            Ok(DocumentUpload {
+                content_type,
+                content,
+            })
+        })
+    }
+}
+
+pub fn kyc_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/kyc")
+            .wrap(middleware::Authentication)
+            .service(get_kyc)
+            .service(upload_kyc_document)
+            .service(submit_kyc),
+    );
+}
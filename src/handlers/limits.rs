@@ -0,0 +1,101 @@
+use actix_web::{HttpResponse, get, put, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    fx::FxRateProvider,
+    i18n::Locale,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::AuditContext,
+        claims::Claims,
+        limit::{AccountLimitsView, UpdateLimits},
+    },
+    services::LimitService,
+};
+
+/// limites em vigor, aumentos pendentes e quanto já saiu em cada janela
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "limits",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    responses(
+        (status = 200, description = "Limites da conta", body = ApiResponse<AccountLimitsView>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Sem taxa de câmbio para converter os tetos à moeda da conta", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/limits")]
+#[tracing::instrument(name = "handler.get_limits", skip_all, fields(user_id = %claims.sub))]
+async fn get_limits(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let limits = LimitService::view(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        path.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        limits,
+        locale.message("LIMITS_RETRIEVED"),
+    )))
+}
+
+/// altera os limites: reduções valem na hora, aumentos depois da carência
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "limits",
+    params(("id" = Uuid, Path, description = "ID da conta")),
+    request_body = UpdateLimits,
+    responses(
+        (status = 200, description = "Limites alterados (aumentos ficam pendentes)", body = ApiResponse<AccountLimitsView>),
+        (status = 400, description = "Nenhum limite informado ou valor acima do teto do tipo de conta", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Conta inativa ou sem taxa de câmbio para converter os tetos", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/{id}/limits")]
+#[tracing::instrument(name = "handler.update_limits", skip_all, fields(user_id = %claims.sub))]
+async fn update_limits(
+    pool: web::Data<PgPool>,
+    provider: web::Data<dyn FxRateProvider>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<UpdateLimits>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let limits = LimitService::update(
+        &pool,
+        provider.get_ref(),
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        limits,
+        locale.message("LIMITS_UPDATED"),
+    )))
+}
+
+/// Rotas dentro do escopo `/accounts` (a autenticação vem dele)
+pub(super) fn limit_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_limits).service(update_limits);
+}
@@ -0,0 +1,48 @@
+use actix_web::{
+    HttpRequest, HttpResponse, get,
+    http::header,
+    web::{Data, ServiceConfig},
+};
+use sqlx::PgPool;
+
+use crate::{METRICS_TOKEN, metrics};
+
+/// Métricas no formato texto do Prometheus
+///
+/// Só com `Authorization: Bearer <METRICS_TOKEN>`; sem token configurado a rota
+/// não existe, para não expor rotas, volumes e o pool a qualquer um.
+#[get("/metrics")]
+#[tracing::instrument(name = "handler.metrics", skip_all)]
+async fn scrape(req: HttpRequest, pool: Data<PgPool>) -> HttpResponse {
+    let Some(expected) = METRICS_TOKEN.get() else {
+        return HttpResponse::NotFound().finish();
+    };
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !token.is_some_and(|token| same_token(token, expected)) {
+        return HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+            .finish();
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(metrics::render(&pool))
+}
+
+/// Comparação em tempo constante para o tamanho do token
+fn same_token(token: &str, expected: &str) -> bool {
+    token.len() == expected.len()
+        && token
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+pub fn metrics_routes(cfg: &mut ServiceConfig) {
+    cfg.service(scrape);
+}
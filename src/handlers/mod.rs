@@ -1,4 +1,31 @@
+mod accounts;
+mod admin;
 mod authentication;
+mod boletos;
+mod business;
+mod fx;
+mod health;
+mod holds;
+mod interest;
+mod kyc;
+mod limits;
+mod metrics;
+mod notifications;
+mod openapi;
+mod pix;
+mod scheduled_transfers;
 mod users;
+mod webhooks;
+pub use accounts::account_routes;
+pub use admin::admin_routes;
 pub use authentication::auth_routes;
+pub use boletos::boleto_routes;
+pub use business::business_routes;
+pub use health::health_routes;
+pub use kyc::kyc_routes;
+pub use metrics::metrics_routes;
+pub use notifications::notification_routes;
+pub use openapi::openapi_routes;
+pub use pix::pix_routes;
 pub use users::user_routes;
+pub use webhooks::webhook_routes;
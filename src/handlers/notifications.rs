@@ -0,0 +1,176 @@
+use std::convert::Infallible;
+
+use actix_web::{
+    HttpRequest, HttpResponse, get,
+    http::header::{AUTHORIZATION, CacheControl, CacheDirective},
+    rt,
+    web::{self, Bytes},
+};
+use actix_ws::{CloseCode, CloseReason, Message};
+use chrono::{DateTime, Utc};
+use jsonwebtoken::errors::ErrorKind;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    events::{NotificationHub, StreamItem},
+    metrics,
+    models::{
+        api_response::ApiErrorResponse, app_error::AppError, claims::Claims,
+        notification::Notification,
+    },
+    utils::verify_token,
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+struct StreamQuery {
+    /// Alternativa ao header `Authorization`, para `EventSource` e `WebSocket` do navegador
+    access_token: Option<String>,
+}
+
+/// Valida o token da conexão; aceita o header ou `?access_token=`
+///
+/// O `verify_token` tolera alguns segundos de relógio; aqui a conexão fica
+/// aberta até o vencimento, então o prazo é conferido sem folga.
+fn stream_claims(req: &HttpRequest, query: &StreamQuery) -> Result<Claims, AppError> {
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or(query.access_token.as_deref())
+        .ok_or(AppError::Unauthorized)?;
+
+    let claims = verify_token(token)
+        .map_err(|err| match err.kind() {
+            ErrorKind::ExpiredSignature => AppError::TokenExpired,
+            _ => AppError::Unauthorized,
+        })?
+        .claims;
+    if claims.exp as i64 <= Utc::now().timestamp() {
+        return Err(AppError::TokenExpired);
+    }
+    Ok(claims)
+}
+
+fn expires_at(claims: &Claims) -> DateTime<Utc> {
+    DateTime::from_timestamp(claims.exp as i64, 0).unwrap_or_else(Utc::now)
+}
+
+/// fluxo SSE com saldo, transferências recebidas e eventos de segurança do usuário
+///
+/// Eventos: `balance_changed`, `transfer_received`, `security`, `resync` (recarregue
+/// os saldos), `token_expired` e `shutdown` (reconecte); comentários são heartbeat.
+#[utoipa::path(
+    context_path = "/notifications",
+    tag = "notifications",
+    params(StreamQuery),
+    responses(
+        (status = 200, description = "Fluxo `text/event-stream`; cada `data` é uma notificação", content_type = "text/event-stream", body = Notification),
+        (status = 401, description = "Token ausente, inválido ou expirado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/stream")]
+#[tracing::instrument(name = "handler.notification_stream", skip_all)]
+async fn notification_stream(
+    req: HttpRequest,
+    web::Query(query): web::Query<StreamQuery>,
+    hub: web::Data<NotificationHub>,
+) -> Result<HttpResponse, AppError> {
+    let claims = stream_claims(&req, &query)?;
+    let subscription = hub.subscribe(claims.user_id()?, expires_at(&claims));
+    let connection = metrics::realtime_connection("sse");
+
+    let stream = futures_util::stream::unfold(
+        (subscription, connection),
+        |(mut subscription, connection)| async move {
+            let item = subscription.next().await?;
+            let frame = Bytes::from(item.sse_frame());
+            Some((Ok::<_, Infallible>(frame), (subscription, connection)))
+        },
+    );
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        // proxies como o nginx não devem segurar os eventos em buffer
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(stream))
+}
+
+/// mesmas notificações por WebSocket, uma mensagem JSON por notificação
+///
+/// Além das notificações, chega `{"type":"resync"}`; heartbeat é por ping. A conexão
+/// fecha com 1008 quando o token vence e com 1001 quando a réplica desliga.
+#[utoipa::path(
+    context_path = "/notifications",
+    tag = "notifications",
+    params(StreamQuery),
+    responses(
+        (status = 101, description = "Conexão WebSocket aberta", body = Notification),
+        (status = 401, description = "Token ausente, inválido ou expirado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/ws")]
+#[tracing::instrument(name = "handler.notification_socket", skip_all)]
+async fn notification_socket(
+    req: HttpRequest,
+    body: web::Payload,
+    web::Query(query): web::Query<StreamQuery>,
+    hub: web::Data<NotificationHub>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let claims = stream_claims(&req, &query)?;
+    let mut subscription = hub.subscribe(claims.user_id()?, expires_at(&claims));
+    let (response, mut session, mut messages) = actix_ws::handle(&req, body)?;
+
+    rt::spawn(async move {
+        let _connection = metrics::realtime_connection("websocket");
+        let reason = loop {
+            tokio::select! {
+                item = subscription.next() => {
+                    let sent = match item {
+                        Some(StreamItem::Notification(notification)) => {
+                            let text = serde_json::to_string(notification.as_ref()).unwrap_or_default();
+                            session.text(text).await
+                        }
+                        Some(StreamItem::Heartbeat) => session.ping(b"").await,
+                        Some(StreamItem::Resync) => session.text(r#"{"type":"resync"}"#).await,
+                        Some(StreamItem::Expired) => {
+                            break Some(CloseReason::from((CloseCode::Policy, "token expired")));
+                        }
+                        Some(StreamItem::ShuttingDown) => break Some(CloseCode::Away.into()),
+                        None => break Some(CloseCode::Normal.into()),
+                    };
+                    if sent.is_err() {
+                        break None;
+                    }
+                }
+                message = messages.recv() => match message {
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            break None;
+                        }
+                    }
+                    Some(Ok(Message::Close(reason))) => break reason,
+                    Some(Err(_)) | None => break None,
+                    // o canal é só de saída; o que o cliente mandar é ignorado
+                    Some(Ok(_)) => {}
+                },
+            }
+        };
+        let _ = session.close(reason).await;
+    });
+
+    Ok(response)
+}
+
+pub fn notification_routes(cfg: &mut web::ServiceConfig) {
+    // sem o middleware de autenticação: o token também pode vir na query
+    cfg.service(
+        web::scope("/notifications")
+            .service(notification_stream)
+            .service(notification_socket),
+    );
+}
@@ -0,0 +1,364 @@
+use actix_web::{HttpResponse, get, web};
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+};
+
+use crate::{
+    models::{
+        CreateUser, CustomerTier, CustomerType, LoginUserRequest, LoginUserResponse,
+        RefreshTokenRequest, UpdatePreferences, UpdateTier, UserProfile, UserRole,
+        account::{Account, AccountBalance, AccountType, CreateAccount, UpdateOverdraft},
+        audit::{AuditEvent, ChainVerification},
+        boleto::{Boleto, BoletoStatus, CreateBoleto, ParseBoleto, ParsedBoleto, PayBoleto},
+        brcode::{
+            BrCodeKind, BrCodeResponse, CreateBrCode, ParseBrCode, ParsedBrCode, QrFormat,
+            TransferPrefill,
+        },
+        business::{AddOperator, BusinessOperator, OperatorPermission, UpdateOperator},
+        currency::Currency,
+        fee::{CreateFeeWaiver, FeeOperation, FeeWaiver},
+        fx::{CreateFxQuote, FxQuote},
+        hold::{CaptureHold, CreateHold, Hold, HoldStatus},
+        interest::{InterestSettlement, InterestSummary},
+        kyc::{
+            KycDocument, KycDocumentType, KycOverview, KycProfile, KycReviewItem, KycStatus,
+            RejectKyc,
+        },
+        limit::{AccountLimitsView, LimitKind, LimitStatus, LimitUsage, UpdateLimits},
+        notification::{Notification, NotificationKind},
+        pagination::{Pagination, PaginationResponse},
+        pix::{
+            ChargeStatus, ClaimStatus, ClaimType, CreatePixClaim, CreatePixKey,
+            CreatePixKeyVerification, PixCharge, PixClaim, PixKey, PixKeyLookup, PixKeyType,
+            PixKeyVerification, VerificationProof,
+        },
+        reversal::{CreateReversal, Reversal, ReversalReason, ReversalStatus},
+        scheduled_transfer::{
+            CreateScheduledTransfer, RunStatus, ScheduleFrequency, ScheduleStatus,
+            ScheduledTransfer, ScheduledTransferDetails, ScheduledTransferRun,
+        },
+        transaction::{
+            CreateTransaction, CreateTransfer, Transaction, TransactionStatus, TransactionType,
+        },
+        webhook::{
+            CreateWebhook, CreatedWebhook, DeliveryAttempt, DeliveryStatus, WebhookDelivery,
+            WebhookSubscription,
+        },
+    },
+    validators::FieldError,
+};
+
+use super::{
+    accounts, admin, authentication, boletos, business, fx, holds, interest, kyc, limits,
+    notifications, pix, scheduled_transfers, users, webhooks,
+};
+
+/// Contrato OpenAPI 3 gerado a partir dos handlers e modelos
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Mini Bank API",
+        description = "API bancária educacional. Erros seguem o envelope `ApiResponse` com um código estável em `error`.",
+    ),
+    servers((url = "/api/v1")),
+    paths(
+        authentication::register,
+        authentication::login,
+        authentication::refresh,
+        users::get_profile,
+        users::soft_delete_user,
+        users::update_preferences,
+        accounts::create_account,
+        accounts::list_accounts,
+        accounts::get_account,
+        accounts::get_balance,
+        accounts::close_account,
+        accounts::deposit,
+        accounts::withdraw,
+        accounts::transfer,
+        accounts::list_transactions,
+        scheduled_transfers::create_scheduled_transfer,
+        scheduled_transfers::list_scheduled_transfers,
+        scheduled_transfers::get_scheduled_transfer,
+        scheduled_transfers::cancel_scheduled_transfer,
+        holds::place_hold,
+        holds::list_holds,
+        holds::get_hold,
+        holds::capture_hold,
+        holds::release_hold,
+        limits::get_limits,
+        limits::update_limits,
+        interest::get_interest,
+        interest::settle_interest,
+        fx::create_fx_quote,
+        pix::create_key_verification,
+        pix::register_key,
+        pix::list_keys,
+        pix::lookup_key,
+        pix::delete_key,
+        pix::open_claim,
+        pix::list_claims,
+        pix::confirm_claim,
+        pix::cancel_claim,
+        pix::complete_claim,
+        pix::create_qr_code,
+        pix::parse_qr_code,
+        pix::get_charge,
+        boletos::issue_boleto,
+        boletos::list_boletos,
+        boletos::get_boleto,
+        boletos::boleto_pdf,
+        boletos::cancel_boleto,
+        boletos::parse_boleto,
+        boletos::pay_boleto,
+        business::list_operators,
+        business::add_operator,
+        business::update_operator,
+        business::remove_operator,
+        kyc::get_kyc,
+        kyc::upload_kyc_document,
+        kyc::submit_kyc,
+        webhooks::create_webhook,
+        webhooks::list_webhooks,
+        webhooks::delete_webhook,
+        webhooks::list_deliveries,
+        webhooks::list_attempts,
+        webhooks::replay_delivery,
+        notifications::notification_stream,
+        notifications::notification_socket,
+        admin::list_audit_events,
+        admin::verify_audit_chain,
+        admin::list_kyc_queue,
+        admin::get_kyc_profile,
+        admin::download_kyc_document,
+        admin::start_kyc_review,
+        admin::approve_kyc,
+        admin::reject_kyc,
+        admin::reverse_transaction,
+        admin::list_transaction_reversals,
+        admin::list_reversals,
+        admin::grant_fee_waiver,
+        admin::list_fee_waivers,
+        admin::revoke_fee_waiver,
+        admin::update_user_tier,
+        admin::update_overdraft,
+    ),
+    components(schemas(
+        CreateUser,
+        LoginUserRequest,
+        LoginUserResponse,
+        RefreshTokenRequest,
+        UpdatePreferences,
+        UserRole,
+        CustomerType,
+        CustomerTier,
+        UpdateTier,
+        UserProfile,
+        FieldError,
+        Pagination,
+        PaginationResponse<Account>,
+        PaginationResponse<Transaction>,
+        PaginationResponse<AuditEvent>,
+        PaginationResponse<WebhookDelivery>,
+        PaginationResponse<KycReviewItem>,
+        PaginationResponse<ScheduledTransfer>,
+        PaginationResponse<Hold>,
+        PaginationResponse<Reversal>,
+        CreateAccount,
+        AccountType,
+        Currency,
+        Account,
+        AccountBalance,
+        UpdateOverdraft,
+        CreateTransaction,
+        CreateTransfer,
+        TransactionType,
+        TransactionStatus,
+        Transaction,
+        ScheduleFrequency,
+        ScheduleStatus,
+        CreateScheduledTransfer,
+        ScheduledTransfer,
+        RunStatus,
+        ScheduledTransferRun,
+        ScheduledTransferDetails,
+        HoldStatus,
+        CreateHold,
+        CaptureHold,
+        Hold,
+        LimitKind,
+        UpdateLimits,
+        LimitStatus,
+        LimitUsage,
+        AccountLimitsView,
+        InterestSummary,
+        InterestSettlement,
+        CreateFxQuote,
+        FxQuote,
+        PixKeyType,
+        CreatePixKeyVerification,
+        PixKeyVerification,
+        VerificationProof,
+        CreatePixKey,
+        PixKey,
+        PixKeyLookup,
+        CreatePixClaim,
+        ClaimType,
+        ClaimStatus,
+        PixClaim,
+        BrCodeKind,
+        CreateBrCode,
+        QrFormat,
+        BrCodeResponse,
+        ParseBrCode,
+        TransferPrefill,
+        ParsedBrCode,
+        ChargeStatus,
+        PixCharge,
+        BoletoStatus,
+        CreateBoleto,
+        Boleto,
+        ParseBoleto,
+        ParsedBoleto,
+        PayBoleto,
+        OperatorPermission,
+        AddOperator,
+        UpdateOperator,
+        BusinessOperator,
+        KycStatus,
+        KycDocumentType,
+        KycProfile,
+        KycDocument,
+        KycOverview,
+        KycReviewItem,
+        RejectKyc,
+        ReversalReason,
+        ReversalStatus,
+        CreateReversal,
+        Reversal,
+        FeeOperation,
+        CreateFeeWaiver,
+        FeeWaiver,
+        AuditEvent,
+        ChainVerification,
+        CreateWebhook,
+        CreatedWebhook,
+        WebhookSubscription,
+        DeliveryStatus,
+        WebhookDelivery,
+        DeliveryAttempt,
+        Notification,
+        NotificationKind,
+    )),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "auth", description = "Registro, login e renovação de tokens"),
+        (name = "users", description = "Gestão do usuário autenticado"),
+        (name = "accounts", description = "Contas do usuário autenticado"),
+        (name = "transactions", description = "Depósito, saque, transferência e extrato"),
+        (name = "holds", description = "Reservas de valor (autorizações) com captura total ou parcial, liberação e expiração"),
+        (name = "limits", description = "Limites de saída por transação, diário, mensal e noturno; aumentos só depois da carência"),
+        (name = "interest", description = "Rendimento diário das contas poupança e investimento e juros do cheque especial, lançados todo mês"),
+        (name = "fx", description = "Cotações de câmbio travadas por alguns segundos para transferências entre contas de moedas diferentes"),
+        (name = "scheduled-transfers", description = "Transferências agendadas e recorrentes (semanal/mensal) com histórico de execuções"),
+        (name = "pix", description = "Chaves PIX (cadastro, consulta, portabilidade e reivindicação de posse) e BR Code"),
+        (name = "boletos", description = "Emissão de boletos (linha digitável, código de barras e PDF) e pagamento com débito em conta"),
+        (name = "business", description = "Operadores de clientes empresa e suas permissões nas contas"),
+        (name = "kyc", description = "Cadastro (KYC): envio de documentos e acompanhamento da revisão; sem aprovação não há abertura de conta nem débitos"),
+        (name = "webhooks", description = "Notificações assinadas (HMAC-SHA256) com novas tentativas, fila morta e replay"),
+        (name = "notifications", description = "Saldo, transferências recebidas e eventos de segurança em tempo real (SSE e WebSocket)"),
+        (name = "admin", description = "Log de auditoria, revisão de cadastros (KYC), estornos, isenções de tarifa e faixas de cliente (somente administradores)"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Esquema `bearer_auth` usado pelo middleware de autenticação JWT
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+#[get("/openapi.json")]
+#[tracing::instrument(name = "handler.openapi_spec", skip_all)]
+async fn openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
+pub fn openapi_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(openapi_spec);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_documents_routes_and_security() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        assert_eq!(spec["openapi"], "3.1.0");
+        assert!(spec["paths"]["/auth/register"]["post"].is_object());
+        assert!(spec["paths"]["/auth/login"]["post"].is_object());
+        assert!(spec["paths"]["/auth/refresh"]["post"].is_object());
+        assert!(spec["paths"]["/users/account"]["delete"].is_object());
+        assert!(spec["paths"]["/accounts/{id}/transfer"]["post"].is_object());
+        assert!(
+            spec["paths"]["/accounts/{id}/scheduled-transfers/{schedule_id}"]["delete"].is_object()
+        );
+        assert!(spec["paths"]["/accounts/{id}/holds/{hold_id}/capture"]["post"].is_object());
+        assert!(spec["paths"]["/accounts/{id}/limits"]["put"].is_object());
+        assert!(spec["paths"]["/accounts/{id}/interest"]["get"].is_object());
+        assert!(spec["paths"]["/accounts/{id}/fx-quotes"]["post"].is_object());
+        assert!(spec["components"]["schemas"]["FxQuote"]["properties"]["user_id"].is_null());
+        assert!(spec["paths"]["/admin/fee-waivers/{id}"]["delete"].is_object());
+        assert!(spec["paths"]["/admin/accounts/{id}/overdraft"]["put"].is_object());
+        assert!(
+            spec["components"]["schemas"]["AccountBalance"]["properties"]["available_balance"]
+                .is_object()
+        );
+        assert!(spec["paths"]["/admin/audit"]["get"].is_object());
+        assert!(
+            spec["paths"]["/webhooks/{id}/deliveries/{delivery_id}/replay"]["post"].is_object()
+        );
+        assert!(spec["paths"]["/notifications/stream"]["get"].is_object());
+        assert!(spec["paths"]["/pix/claims/{id}/confirm"]["post"].is_object());
+        assert!(spec["paths"]["/pix/qr/parse"]["post"].is_object());
+        assert!(
+            spec["components"]["schemas"]["PixKeyVerification"]["properties"]["code_hash"]
+                .is_null()
+        );
+        assert!(spec["paths"]["/boletos/{id}/pdf"]["get"].is_object());
+        assert!(spec["paths"]["/business/operators/{id}"]["put"].is_object());
+        assert!(spec["paths"]["/kyc/documents/{document_type}"]["put"].is_object());
+        assert!(spec["paths"]["/admin/kyc/{user_id}/reject"]["post"].is_object());
+        assert!(spec["paths"]["/admin/transactions/{id}/reversals"]["post"].is_object());
+        assert!(
+            spec["components"]["schemas"]["KycDocument"]["properties"]["storage_key"].is_null()
+        );
+        assert!(
+            spec["components"]["schemas"]["WebhookSubscription"]["properties"]["secret"].is_null()
+        );
+        assert_eq!(
+            spec["paths"]["/users/preferences"]["put"]["security"][0]["bearer_auth"],
+            serde_json::json!([])
+        );
+        assert_eq!(
+            spec["components"]["securitySchemes"]["bearer_auth"]["scheme"],
+            "bearer"
+        );
+        assert!(spec["components"]["schemas"]["CreateUser"].is_object());
+    }
+}
@@ -0,0 +1,454 @@
+use actix_web::{HttpResponse, delete, get, http::header::ContentType, post, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::PixRepository,
+    i18n::Locale,
+    middleware,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::AuditContext,
+        brcode::{
+            BrCodeResponse, CreateBrCode, ParseBrCode, ParsedBrCode, QrFormat, QrFormatQuery,
+            qr_png, qr_svg,
+        },
+        claims::Claims,
+        pix::{
+            CreatePixClaim, CreatePixKey, CreatePixKeyVerification, PixCharge, PixClaim, PixKey,
+            PixKeyLookup, PixKeyQuery, PixKeyVerification, error::PixError,
+        },
+    },
+    services::PixService,
+    validators::PixKeyValidator,
+    verification::CodeSender,
+};
+
+/// envia um código de uso único para o email ou telefone que será cadastrado ou reivindicado
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    request_body = CreatePixKeyVerification,
+    responses(
+        (status = 201, description = "Código enviado", body = ApiResponse<PixKeyVerification>),
+        (status = 400, description = "Chave em formato inválido ou que não é email nem telefone", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 429, description = "Limite de códigos por hora atingido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/keys/verifications")]
+#[tracing::instrument(name = "handler.create_pix_key_verification", skip_all, fields(user_id = %claims.sub))]
+async fn create_key_verification(
+    pool: web::Data<PgPool>,
+    sender: web::Data<dyn CodeSender>,
+    web::Json(request): web::Json<CreatePixKeyVerification>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let verification =
+        PixService::send_verification(&pool, sender.get_ref(), claims.user_id()?, &request).await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        verification,
+        locale.message("PIX_VERIFICATION_SENT"),
+    )))
+}
+
+/// registra uma chave PIX (CPF, email, telefone ou aleatória) para uma conta do usuário
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    request_body = CreatePixKey,
+    responses(
+        (status = 201, description = "Chave registrada", body = ApiResponse<PixKey>),
+        (status = 400, description = "Chave em formato inválido ou CPF com dígito errado", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Chave já registrada", body = ApiErrorResponse),
+        (status = 422, description = "Limite de chaves, conta inativa, CPF de outro titular ou código de verificação ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/keys")]
+#[tracing::instrument(name = "handler.register_pix_key", skip_all, fields(user_id = %claims.sub))]
+async fn register_key(
+    pool: web::Data<PgPool>,
+    web::Json(request): web::Json<CreatePixKey>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let key = PixService::register_key(&pool, claims.user_id()?, &request, &audit).await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        key,
+        locale.message("PIX_KEY_REGISTERED"),
+    )))
+}
+
+/// lista as chaves PIX ativas do usuário, em todas as contas
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    responses(
+        (status = 200, description = "Chaves do usuário", body = ApiResponse<Vec<PixKey>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/keys")]
+#[tracing::instrument(name = "handler.list_pix_keys", skip_all, fields(user_id = %claims.sub))]
+async fn list_keys(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let keys = PixRepository::list_by_user(&pool, claims.user_id()?).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(keys, locale.message("PIX_KEYS_LISTED"))))
+}
+
+/// consulta uma chave antes do pagamento: tipo, titular abreviado e tipo de conta
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    params(PixKeyQuery),
+    responses(
+        (status = 200, description = "Dados públicos da chave", body = ApiResponse<PixKeyLookup>),
+        (status = 400, description = "Chave em formato inválido", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Chave não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/keys/lookup")]
+#[tracing::instrument(name = "handler.lookup_pix_key", skip_all, fields(user_id = %claims.sub))]
+async fn lookup_key(
+    pool: web::Data<PgPool>,
+    web::Query(query): web::Query<PixKeyQuery>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (_, key) = PixKeyValidator::parse("key", &query.key)
+        .map_err(|err| AppError::Validation(err.into()))?;
+    let lookup = PixRepository::lookup(&pool, &key)
+        .await?
+        .ok_or(PixError::KeyNotFound)?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        lookup.masked(),
+        locale.message("PIX_KEY_FOUND"),
+    )))
+}
+
+/// remove a chave; com reivindicação em aberto, resolva-a antes
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    params(("id" = Uuid, Path, description = "ID da chave")),
+    responses(
+        (status = 200, description = "Chave removida", body = ApiResponse<PixKey>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Chave não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Chave com reivindicação em aberto", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/keys/{id}")]
+#[tracing::instrument(name = "handler.delete_pix_key", skip_all, fields(user_id = %claims.sub))]
+async fn delete_key(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let key = PixService::delete_key(&pool, claims.user_id()?, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(key, locale.message("PIX_KEY_DELETED"))))
+}
+
+/// traz uma chave registrada em outra conta: portabilidade (mesmo titular) ou
+/// reivindicação de posse (outro titular, só email e telefone)
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    request_body = CreatePixClaim,
+    responses(
+        (status = 201, description = "Portabilidade concluída ou reivindicação aberta", body = ApiResponse<PixClaim>),
+        (status = 400, description = "Chave em formato inválido", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta ou chave não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Chave já tem reivindicação em aberto", body = ApiErrorResponse),
+        (status = 422, description = "Chave não reivindicável, já está na conta, limite atingido ou código de verificação ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/claims")]
+#[tracing::instrument(name = "handler.open_pix_claim", skip_all, fields(user_id = %claims.sub))]
+async fn open_claim(
+    pool: web::Data<PgPool>,
+    web::Json(request): web::Json<CreatePixClaim>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let claim = PixService::open_claim(&pool, claims.user_id()?, &request, &audit).await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        claim,
+        locale.message("PIX_CLAIM_CREATED"),
+    )))
+}
+
+/// reivindicações feitas pelo usuário e sobre chaves dele, mais recentes primeiro
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    responses(
+        (status = 200, description = "Reivindicações do usuário", body = ApiResponse<Vec<PixClaim>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/claims")]
+#[tracing::instrument(name = "handler.list_pix_claims", skip_all, fields(user_id = %claims.sub))]
+async fn list_claims(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let pix_claims = PixRepository::list_claims(&pool, claims.user_id()?).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        pix_claims,
+        locale.message("PIX_CLAIMS_LISTED"),
+    )))
+}
+
+/// o dono atual entrega a chave ao reivindicante
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    params(("id" = Uuid, Path, description = "ID da reivindicação")),
+    responses(
+        (status = 200, description = "Chave transferida", body = ApiResponse<PixClaim>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Reivindicação não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Reivindicação já resolvida", body = ApiErrorResponse),
+        (status = 422, description = "Conta do reivindicante inativa ou com o limite de chaves", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/claims/{id}/confirm")]
+#[tracing::instrument(name = "handler.confirm_pix_claim", skip_all, fields(user_id = %claims.sub))]
+async fn confirm_claim(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let claim =
+        PixService::confirm_claim(&pool, claims.user_id()?, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        claim,
+        locale.message("PIX_CLAIM_COMPLETED"),
+    )))
+}
+
+/// cancela a reivindicação (qualquer um dos lados); a chave fica onde está
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    params(("id" = Uuid, Path, description = "ID da reivindicação")),
+    responses(
+        (status = 200, description = "Reivindicação cancelada", body = ApiResponse<PixClaim>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Reivindicação não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Reivindicação já resolvida", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/claims/{id}/cancel")]
+#[tracing::instrument(name = "handler.cancel_pix_claim", skip_all, fields(user_id = %claims.sub))]
+async fn cancel_claim(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let claim =
+        PixService::cancel_claim(&pool, claims.user_id()?, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        claim,
+        locale.message("PIX_CLAIM_CANCELLED"),
+    )))
+}
+
+/// o reivindicante conclui depois do prazo sem resposta do dono atual
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    params(("id" = Uuid, Path, description = "ID da reivindicação")),
+    responses(
+        (status = 200, description = "Chave transferida", body = ApiResponse<PixClaim>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Reivindicação não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Prazo ainda não terminou, reivindicação sem posse verificada ou já resolvida", body = ApiErrorResponse),
+        (status = 422, description = "Conta inativa ou com o limite de chaves", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/claims/{id}/complete")]
+#[tracing::instrument(name = "handler.complete_pix_claim", skip_all, fields(user_id = %claims.sub))]
+async fn complete_claim(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let claim =
+        PixService::complete_claim(&pool, claims.user_id()?, path.into_inner(), &audit).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        claim,
+        locale.message("PIX_CLAIM_COMPLETED"),
+    )))
+}
+
+/// gera o BR Code ("PIX copia e cola") para receber em uma chave do usuário
+///
+/// Com `format=png` ou `format=svg` a resposta é a imagem do QR Code e o payload
+/// vai no header `X-Pix-Payload`.
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    params(QrFormatQuery),
+    request_body = CreateBrCode,
+    responses(
+        (status = 201, description = "Payload gerado", body = ApiResponse<BrCodeResponse>),
+        (status = 201, description = "Imagem do QR Code", content_type = "image/png"),
+        (status = 201, description = "Imagem do QR Code", content_type = "image/svg+xml"),
+        (status = 400, description = "Valor, txid ou descrição inválidos (o dinâmico exige valor)", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Chave não encontrada", body = ApiErrorResponse),
+        (status = 409, description = "Já existe uma cobrança com o txid informado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/qr")]
+#[tracing::instrument(name = "handler.create_pix_qr", skip_all, fields(user_id = %claims.sub))]
+async fn create_qr_code(
+    pool: web::Data<PgPool>,
+    web::Json(request): web::Json<CreateBrCode>,
+    web::Query(query): web::Query<QrFormatQuery>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let code = PixService::create_brcode(&pool, claims.user_id()?, &request).await?;
+
+    let response = match query.format.unwrap_or_default() {
+        QrFormat::Json => HttpResponse::Created()
+            .json(ApiResponse::sucess(code, locale.message("PIX_QR_CREATED"))),
+        QrFormat::Png => {
+            let image = qr_png(&code.payload)?;
+            HttpResponse::Created()
+                .content_type(ContentType::png())
+                .insert_header(("X-Pix-Payload", code.payload))
+                .body(image)
+        }
+        QrFormat::Svg => {
+            let image = qr_svg(&code.payload)?;
+            HttpResponse::Created()
+                .content_type("image/svg+xml")
+                .insert_header(("X-Pix-Payload", code.payload))
+                .body(image)
+        }
+    };
+    Ok(response)
+}
+
+/// lê um BR Code e devolve a transferência pré-preenchida
+///
+/// No dinâmico, a chave, o valor e o `pix_txid` vêm da cobrança; só as
+/// cobranças deste banco são aceitas.
+#[utoipa::path(
+    context_path = "/pix",
+    tag = "pix",
+    request_body = ParseBrCode,
+    responses(
+        (status = 200, description = "Dados do BR Code e corpo da transferência", body = ApiResponse<ParsedBrCode>),
+        (status = 400, description = "Payload malformado, CRC incorreto ou chave inválida", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Cobrança do QR dinâmico não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Cobrança já paga ou vencida", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/qr/parse")]
+#[tracing::instrument(name = "handler.parse_pix_qr", skip_all, fields(user_id = %claims.sub))]
+async fn parse_qr_code(
+    pool: web::Data<PgPool>,
+    web::Json(request): web::Json<ParseBrCode>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let parsed = PixService::parse_brcode(&pool, &request.payload).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(parsed, locale.message("PIX_QR_PARSED"))))
+}
+
+/// cobrança de um BR Code dinâmico: é a URL que vai no payload
+///
+/// Pública, como a consulta que o PSP do pagador faz ao ler o QR Code.
+#[utoipa::path(
+    tag = "pix",
+    params(("id" = Uuid, Path, description = "ID da cobrança")),
+    responses(
+        (status = 200, description = "Cobrança, com `expired` depois da validade", body = ApiResponse<PixCharge>),
+        (status = 404, description = "Cobrança não encontrada", body = ApiErrorResponse),
+    )
+)]
+#[get("/pix/cob/{id}")]
+#[tracing::instrument(name = "handler.get_pix_charge", skip_all)]
+async fn get_charge(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let charge = PixService::find_charge(&pool, path.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        charge,
+        locale.message("PIX_CHARGE_RETRIEVED"),
+    )))
+}
+
+pub fn pix_routes(cfg: &mut web::ServiceConfig) {
+    // registrada antes do escopo autenticado, que também casaria `/pix/cob/...`
+    cfg.service(get_charge).service(
+        web::scope("/pix")
+            .wrap(middleware::Authentication)
+            .service(create_key_verification)
+            .service(register_key)
+            .service(list_keys)
+            .service(lookup_key)
+            .service(delete_key)
+            .service(open_claim)
+            .service(list_claims)
+            .service(confirm_claim)
+            .service(cancel_claim)
+            .service(complete_claim)
+            .service(create_qr_code)
+            .service(parse_qr_code),
+    );
+}
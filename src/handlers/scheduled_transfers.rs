@@ -0,0 +1,180 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    i18n::Locale,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::AuditContext,
+        claims::Claims,
+        pagination::{Pagination, PaginationResponse},
+        scheduled_transfer::{
+            CreateScheduledTransfer, ScheduledTransfer, ScheduledTransferDetails,
+            ScheduledTransferFilter,
+        },
+    },
+    services::ScheduledTransferService,
+};
+
+/// agenda uma transferência única ou recorrente (semanal/mensal) a partir da conta
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "scheduled-transfers",
+    params(("id" = Uuid, Path, description = "ID da conta de origem")),
+    request_body = CreateScheduledTransfer,
+    responses(
+        (status = 201, description = "Transferência agendada", body = ApiResponse<ScheduledTransfer>),
+        (status = 400, description = "Valor, destino ou agenda inválidos", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão; titular sem KYC aprovado", body = ApiErrorResponse),
+        (status = 404, description = "Conta de origem ou destino não encontrada", body = ApiErrorResponse),
+        (status = 422, description = "Mesma conta ou conta inativa", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/scheduled-transfers")]
+#[tracing::instrument(name = "handler.create_scheduled_transfer", skip_all, fields(user_id = %claims.sub))]
+async fn create_scheduled_transfer(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Json(request): web::Json<CreateScheduledTransfer>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let schedule = ScheduledTransferService::create(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        &request,
+        &audit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        schedule,
+        locale.message("SCHEDULED_TRANSFER_CREATED"),
+    )))
+}
+
+/// agendamentos da conta, mais recentes primeiro
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "scheduled-transfers",
+    params(("id" = Uuid, Path, description = "ID da conta"), ScheduledTransferFilter),
+    responses(
+        (status = 200, description = "Agendamentos da conta", body = PaginationResponse<ScheduledTransfer>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta não encontrada", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/scheduled-transfers")]
+#[tracing::instrument(name = "handler.list_scheduled_transfers", skip_all, fields(user_id = %claims.sub))]
+async fn list_scheduled_transfers(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Query(filter): web::Query<ScheduledTransferFilter>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (page, limit) = Pagination::bounds(filter.page, filter.limit);
+    let (schedules, total) = ScheduledTransferService::list(
+        &pool,
+        claims.user_id()?,
+        path.into_inner(),
+        filter.status,
+        limit,
+        Pagination::offset(page, limit),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(PaginationResponse::new(
+        schedules,
+        Pagination::new(page, limit, total as u64),
+        locale.message("SCHEDULED_TRANSFERS_LISTED"),
+    )))
+}
+
+/// agendamento com as últimas execuções (transferência gerada ou código do erro)
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "scheduled-transfers",
+    params(
+        ("id" = Uuid, Path, description = "ID da conta"),
+        ("schedule_id" = Uuid, Path, description = "ID do agendamento"),
+    ),
+    responses(
+        (status = 200, description = "Agendamento e execuções", body = ApiResponse<ScheduledTransferDetails>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário", body = ApiErrorResponse),
+        (status = 404, description = "Conta ou agendamento não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/scheduled-transfers/{schedule_id}")]
+#[tracing::instrument(name = "handler.get_scheduled_transfer", skip_all, fields(user_id = %claims.sub))]
+async fn get_scheduled_transfer(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (account_id, schedule_id) = path.into_inner();
+    let details =
+        ScheduledTransferService::details(&pool, claims.user_id()?, account_id, schedule_id)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        details,
+        locale.message("SCHEDULED_TRANSFER_DETAILS"),
+    )))
+}
+
+/// cancela as próximas execuções do agendamento
+#[utoipa::path(
+    context_path = "/accounts",
+    tag = "scheduled-transfers",
+    params(
+        ("id" = Uuid, Path, description = "ID da conta"),
+        ("schedule_id" = Uuid, Path, description = "ID do agendamento"),
+    ),
+    responses(
+        (status = 200, description = "Agendamento cancelado", body = ApiResponse<ScheduledTransfer>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Conta de outro usuário ou operador sem permissão", body = ApiErrorResponse),
+        (status = 404, description = "Conta ou agendamento não encontrado", body = ApiErrorResponse),
+        (status = 409, description = "Agendamento já encerrado ou cancelado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/{id}/scheduled-transfers/{schedule_id}")]
+#[tracing::instrument(name = "handler.cancel_scheduled_transfer", skip_all, fields(user_id = %claims.sub))]
+async fn cancel_scheduled_transfer(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (account_id, schedule_id) = path.into_inner();
+    let schedule =
+        ScheduledTransferService::cancel(&pool, claims.user_id()?, account_id, schedule_id, &audit)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        schedule,
+        locale.message("SCHEDULED_TRANSFER_CANCELLED"),
+    )))
+}
+
+/// Rotas dentro do escopo `/accounts` (a autenticação vem dele)
+pub(super) fn scheduled_transfer_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(create_scheduled_transfer)
+        .service(list_scheduled_transfers)
+        .service(get_scheduled_transfer)
+        .service(cancel_scheduled_transfer);
+}
@@ -1,57 +1,170 @@
-use actix_web::{HttpResponse, Responder, delete, web};
+use actix_web::{HttpResponse, get, put, web};
+use serde_json::json;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
-    database::UserRepository,
+    database::{AuditRepository, NotificationRepository, OutboxRepository, UserRepository},
+    i18n::Locale,
     middleware,
-    models::{api_response::ApiResponse, claims::Claims, error::UserError},
+    models::{
+        UpdatePreferences, UserProfile,
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        claims::Claims,
+        error::UserError,
+        event::DomainEvent,
+        notification::Notification,
+    },
+    validators::UserValidator,
 };
 /// marca o usuario como inativo
-#[delete("/account")]
+///
+/// Registrada com `route` (sem macro) porque também responde em `/auth/account`.
+#[utoipa::path(
+    delete,
+    path = "/account",
+    context_path = "/users",
+    tag = "users",
+    request_body(content = String, description = "Email do usuário", example = json!("joao.silva@email.com")),
+    responses(
+        (status = 200, description = "Conta desativada", body = ApiResponse<Uuid>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 403, description = "Email não pertence ao usuário do token", body = ApiErrorResponse),
+        (status = 404, description = "Usuário não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[tracing::instrument(name = "handler.soft_delete_user", skip_all, fields(user_id = %claims.sub))]
 async fn soft_delete_user(
     pool: web::Data<PgPool>,
     web::Json(email): web::Json<String>,
     claims: Claims,
-) -> impl Responder {
-    let user = match UserRepository::find_by_email(&pool, &email).await {
-        Ok(Some(user)) => user,
-        Ok(None) => {
-            return HttpResponse::NoContent().json(ApiResponse::<()>::error(
-                "usuario nao encontrado",
-                &UserError::NotFound.to_string(),
-            ));
-        }
-        Err(err) => {
-            return HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error("database_error", &err.to_string()));
-        }
-    };
-
-    if &claims.email != &user.email {
-        return HttpResponse::Unauthorized().json(ApiResponse::<()>::error(
-            "NOT OWNER",
-            &UserError::InvalidCredentials.to_string(),
-        ));
-    }
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let user = UserRepository::find_by_email(&pool, &email)
+        .await?
+        .ok_or(UserError::NotFound)?;
 
-    if let Ok(_) = UserRepository::delete(&pool, user.id).await {
-        HttpResponse::Ok().json(ApiResponse::<Uuid>::sucess(
-            user.id,
-            "conta desativada com sucesso!",
-        ))
-    } else {
-        HttpResponse::InternalServerError().json(ApiResponse::<()>::error(
-            "Erro ao desativar usuario",
-            &UserError::DatabaseError(sqlx::Error::PoolClosed).to_string(),
-        ))
+    if claims.email != user.email {
+        return Err(AppError::Forbidden);
     }
+
+    let mut tx = pool.begin().await?;
+    let mut user = UserRepository::lock_by_id(&mut tx, user.id)
+        .await?
+        .ok_or(UserError::NotFound)?;
+    UserRepository::delete(&mut tx, user.id).await?;
+
+    let before = user.audit_snapshot();
+    user.is_active = false;
+    let event = audit
+        .event(AuditAction::UserDeactivated, "user", Some(user.id))
+        .before(before)
+        .after(user.audit_snapshot());
+    AuditRepository::append(&mut tx, &event).await?;
+    OutboxRepository::enqueue(&mut tx, DomainEvent::UserDeactivated { user_id: user.id }).await?;
+    // encerra as conexões de tempo real abertas pelo usuário
+    let notification = Notification::security(user.id, AuditAction::UserDeactivated, &audit);
+    NotificationRepository::publish(&mut tx, &notification).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::<Uuid>::sucess(
+        user.id,
+        locale.message("USER_DEACTIVATED"),
+    )))
+}
+
+/// dados do usuário do token, com o documento mascarado (RF004)
+#[utoipa::path(
+    context_path = "/users",
+    tag = "users",
+    responses(
+        (status = 200, description = "Perfil do usuário", body = ApiResponse<UserProfile>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Usuário não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/me")]
+#[tracing::instrument(name = "handler.get_profile", skip_all, fields(user_id = %claims.sub))]
+async fn get_profile(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let user = UserRepository::find_by_id(&pool, claims.user_id()?)
+        .await?
+        .ok_or(UserError::NotFound)?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        UserProfile::from(user),
+        locale.message("PROFILE_RETRIEVED"),
+    )))
+}
+
+/// salva o idioma preferido; passa a valer nos tokens emitidos a partir do próximo login
+#[utoipa::path(
+    context_path = "/users",
+    tag = "users",
+    request_body = UpdatePreferences,
+    responses(
+        (status = 200, description = "Preferências salvas", body = ApiResponse<Object>),
+        (status = 400, description = "Idioma não suportado", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/preferences")]
+#[tracing::instrument(name = "handler.update_preferences", skip_all, fields(user_id = %claims.sub))]
+async fn update_preferences(
+    pool: web::Data<PgPool>,
+    web::Json(preferences): web::Json<UpdatePreferences>,
+    claims: Claims,
+    audit: AuditContext,
+) -> Result<HttpResponse, AppError> {
+    let locale = UserValidator::validate_locale(&preferences.locale)
+        .map_err(|err| AppError::Validation(err.into()))?;
+
+    let mut tx = pool.begin().await?;
+    let mut user = UserRepository::lock_by_id(&mut tx, claims.user_id()?)
+        .await?
+        .ok_or(UserError::NotFound)?;
+    UserRepository::update_locale(&mut tx, user.id, locale.as_tag()).await?;
+
+    let before = user.audit_snapshot();
+    user.locale = Some(locale.as_tag().to_string());
+    let event = audit
+        .event(AuditAction::UserUpdated, "user", Some(user.id))
+        .before(before)
+        .after(user.audit_snapshot());
+    AuditRepository::append(&mut tx, &event).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        json!({ "locale": locale.as_tag() }),
+        locale.message("PREFERENCES_UPDATED"),
+    )))
 }
 
 pub fn user_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
-        web::scope("/auth")
+        web::scope("/users")
+            .wrap(middleware::Authentication)
+            .service(get_profile)
+            .route("/account", web::delete().to(soft_delete_user))
+            .service(update_preferences),
+    );
+}
+
+/// `DELETE /auth/account`, caminho antigo mantido para clientes que ainda não migraram
+/// para `/users`; só esse recurso exige token, e os outros caminhos de `/auth` seguem em 404
+pub(super) fn legacy_user_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/account")
             .wrap(middleware::Authentication)
-            .service(soft_delete_user),
+            .route(web::delete().to(soft_delete_user)),
     );
 }
@@ -0,0 +1,326 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::{AuditRepository, WebhookRepository},
+    i18n::Locale,
+    middleware,
+    models::{
+        api_response::{ApiErrorResponse, ApiResponse},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        claims::Claims,
+        pagination::{Pagination, PaginationResponse},
+        webhook::{
+            CreateWebhook, CreatedWebhook, DeliveryAttempt, DeliveryFilter, DeliveryStatus,
+            WebhookDelivery, WebhookSubscription, error::WebhookError, generate_secret,
+        },
+    },
+    utils::webhook_settings,
+    validators::WebhookValidator,
+};
+
+/// Assinatura ativa do usuário do token
+async fn owned_subscription(
+    pool: &PgPool,
+    claims: &Claims,
+    subscription_id: Uuid,
+) -> Result<WebhookSubscription, AppError> {
+    let mut conn = pool.acquire().await?;
+    let subscription =
+        WebhookRepository::find_subscription(&mut conn, subscription_id, claims.user_id()?)
+            .await?
+            .ok_or(WebhookError::NotFound)?;
+    Ok(subscription)
+}
+
+/// cadastra uma URL para receber os eventos escolhidos, assinados com HMAC-SHA256
+#[utoipa::path(
+    context_path = "/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhook,
+    responses(
+        (status = 201, description = "Webhook criado; o segredo só é devolvido aqui", body = ApiResponse<CreatedWebhook>),
+        (status = 400, description = "URL, tipos de evento ou segredo inválidos; o destino precisa ser https e público", body = ApiErrorResponse),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("")]
+#[tracing::instrument(name = "handler.create_webhook", skip_all, fields(user_id = %claims.sub))]
+async fn create_webhook(
+    pool: web::Data<PgPool>,
+    web::Json(request): web::Json<CreateWebhook>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let allow_local = webhook_settings().allow_local_targets;
+    WebhookValidator::validate(
+        &request.url,
+        &request.event_types,
+        request.secret.as_deref(),
+        allow_local,
+    )
+    .map_err(AppError::Validation)?;
+    WebhookValidator::validate_target(&request.url, allow_local)
+        .await
+        .map_err(|err| AppError::Validation(err.into()))?;
+
+    let mut event_types = request.event_types;
+    event_types.sort();
+    event_types.dedup();
+    let secret = request.secret.unwrap_or_else(generate_secret);
+    let subscription = WebhookSubscription::new(
+        claims.user_id()?,
+        request.url.trim().to_string(),
+        event_types,
+        secret.clone(),
+    );
+
+    let mut tx = pool.begin().await?;
+    WebhookRepository::insert_subscription(&mut tx, &subscription).await?;
+    let event = audit
+        .event(
+            AuditAction::WebhookCreated,
+            "webhook",
+            Some(subscription.id),
+        )
+        .after(subscription.audit_snapshot());
+    AuditRepository::append(&mut tx, &event).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse::sucess(
+        CreatedWebhook {
+            subscription,
+            secret,
+        },
+        locale.message("WEBHOOK_CREATED"),
+    )))
+}
+
+/// lista os webhooks ativos do usuário (sem o segredo)
+#[utoipa::path(
+    context_path = "/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Webhooks do usuário", body = ApiResponse<Vec<WebhookSubscription>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("")]
+#[tracing::instrument(name = "handler.list_webhooks", skip_all, fields(user_id = %claims.sub))]
+async fn list_webhooks(
+    pool: web::Data<PgPool>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let subscriptions = WebhookRepository::list_subscriptions(&pool, claims.user_id()?).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        subscriptions,
+        locale.message("WEBHOOKS_LISTED"),
+    )))
+}
+
+/// desativa o webhook; entregas pendentes deixam de ser enviadas
+#[utoipa::path(
+    context_path = "/webhooks",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "ID do webhook")),
+    responses(
+        (status = 200, description = "Webhook removido", body = ApiResponse<Uuid>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Webhook não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/{id}")]
+#[tracing::instrument(name = "handler.delete_webhook", skip_all, fields(user_id = %claims.sub))]
+async fn delete_webhook(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let mut tx = pool.begin().await?;
+    let mut subscription =
+        WebhookRepository::find_subscription(&mut tx, path.into_inner(), claims.user_id()?)
+            .await?
+            .ok_or(WebhookError::NotFound)?;
+    WebhookRepository::deactivate_subscription(&mut tx, subscription.id).await?;
+
+    let before = subscription.audit_snapshot();
+    subscription.is_active = false;
+    let event = audit
+        .event(
+            AuditAction::WebhookDeleted,
+            "webhook",
+            Some(subscription.id),
+        )
+        .before(before)
+        .after(subscription.audit_snapshot());
+    AuditRepository::append(&mut tx, &event).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        subscription.id,
+        locale.message("WEBHOOK_DELETED"),
+    )))
+}
+
+/// log de entregas do webhook, mais recentes primeiro; `status=dead` lista a fila morta
+#[utoipa::path(
+    context_path = "/webhooks",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "ID do webhook"), DeliveryFilter),
+    responses(
+        (status = 200, description = "Entregas do webhook", body = PaginationResponse<WebhookDelivery>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Webhook não encontrado", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/deliveries")]
+#[tracing::instrument(name = "handler.list_webhook_deliveries", skip_all, fields(user_id = %claims.sub))]
+async fn list_deliveries(
+    pool: web::Data<PgPool>,
+    path: web::Path<Uuid>,
+    web::Query(filter): web::Query<DeliveryFilter>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let subscription = owned_subscription(&pool, &claims, path.into_inner()).await?;
+
+    let (page, limit) = Pagination::bounds(filter.page, filter.limit);
+    let (deliveries, total) = WebhookRepository::list_deliveries(
+        &pool,
+        subscription.id,
+        &filter,
+        limit,
+        Pagination::offset(page, limit),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(PaginationResponse::new(
+        deliveries,
+        Pagination::new(page, limit, total as u64),
+        locale.message("WEBHOOK_DELIVERIES_LISTED"),
+    )))
+}
+
+/// tentativas de uma entrega: status HTTP, erro e duração de cada uma
+#[utoipa::path(
+    context_path = "/webhooks",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "ID do webhook"),
+        ("delivery_id" = Uuid, Path, description = "ID da entrega"),
+    ),
+    responses(
+        (status = 200, description = "Tentativas da entrega", body = ApiResponse<Vec<DeliveryAttempt>>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Webhook ou entrega não encontrados", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{id}/deliveries/{delivery_id}/attempts")]
+#[tracing::instrument(name = "handler.list_webhook_attempts", skip_all, fields(user_id = %claims.sub))]
+async fn list_attempts(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    claims: Claims,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (subscription_id, delivery_id) = path.into_inner();
+    let subscription = owned_subscription(&pool, &claims, subscription_id).await?;
+
+    let mut tx = pool.begin().await?;
+    let delivery = WebhookRepository::lock_delivery(&mut tx, subscription.id, delivery_id)
+        .await?
+        .ok_or(WebhookError::DeliveryNotFound)?;
+    tx.commit().await?;
+    let attempts = WebhookRepository::list_attempts(&pool, delivery.id).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse::sucess(
+        attempts,
+        locale.message("WEBHOOK_ATTEMPTS_LISTED"),
+    )))
+}
+
+/// devolve uma entrega (entregue ou na fila morta) à fila, com as tentativas zeradas
+#[utoipa::path(
+    context_path = "/webhooks",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "ID do webhook"),
+        ("delivery_id" = Uuid, Path, description = "ID da entrega"),
+    ),
+    responses(
+        (status = 202, description = "Entrega reenfileirada", body = ApiResponse<WebhookDelivery>),
+        (status = 401, description = "Token ausente ou inválido", body = ApiErrorResponse),
+        (status = 404, description = "Webhook ou entrega não encontrados", body = ApiErrorResponse),
+        (status = 409, description = "Entrega ainda pendente", body = ApiErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/{id}/deliveries/{delivery_id}/replay")]
+#[tracing::instrument(name = "handler.replay_webhook_delivery", skip_all, fields(user_id = %claims.sub))]
+async fn replay_delivery(
+    pool: web::Data<PgPool>,
+    path: web::Path<(Uuid, Uuid)>,
+    claims: Claims,
+    audit: AuditContext,
+    locale: Locale,
+) -> Result<HttpResponse, AppError> {
+    let (subscription_id, delivery_id) = path.into_inner();
+
+    let mut tx = pool.begin().await?;
+    let subscription =
+        WebhookRepository::find_subscription(&mut tx, subscription_id, claims.user_id()?)
+            .await?
+            .ok_or(WebhookError::NotFound)?;
+    let delivery = WebhookRepository::lock_delivery(&mut tx, subscription.id, delivery_id)
+        .await?
+        .ok_or(WebhookError::DeliveryNotFound)?;
+    if delivery.status == DeliveryStatus::Pending {
+        return Err(WebhookError::DeliveryPending.into());
+    }
+
+    let replayed = WebhookRepository::replay(&mut tx, delivery.id).await?;
+    let event = audit
+        .event(
+            AuditAction::WebhookReplayed,
+            "webhook",
+            Some(subscription.id),
+        )
+        .metadata(serde_json::json!({
+            "delivery_id": delivery.id,
+            "event_id": delivery.event_id,
+            "previous_status": delivery.status,
+            "previous_attempts": delivery.attempts,
+        }));
+    AuditRepository::append(&mut tx, &event).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Accepted().json(ApiResponse::sucess(
+        replayed,
+        locale.message("WEBHOOK_DELIVERY_REPLAYED"),
+    )))
+}
+
+pub fn webhook_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/webhooks")
+            .wrap(middleware::Authentication)
+            .service(create_webhook)
+            .service(list_webhooks)
+            .service(delete_webhook)
+            .service(list_deliveries)
+            .service(list_attempts)
+            .service(replay_delivery),
+    );
+}
@@ -0,0 +1,205 @@
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    future::{Future, Ready, ready},
+    sync::OnceLock,
+};
+
+use actix_web::{FromRequest, HttpMessage, HttpRequest, dev::Payload};
+use serde::{Deserialize, Serialize};
+
+use crate::validators::FieldError;
+
+type Catalog = HashMap<String, String>;
+
+static PT_BR: OnceLock<Catalog> = OnceLock::new();
+static EN: OnceLock<Catalog> = OnceLock::new();
+static ES: OnceLock<Catalog> = OnceLock::new();
+
+tokio::task_local! {
+    /// Idioma da requisição em andamento, usado ao renderizar erros
+    static CURRENT_LOCALE: Cell<Locale>;
+}
+
+/// Idiomas suportados pela API (pt-BR é o padrão)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "pt-BR")]
+    PtBr,
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "es")]
+    Es,
+}
+
+impl Locale {
+    /// Aceita tags como `pt-BR`, `pt`, `en-US`, `es-AR`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_lowercase();
+        match primary.as_str() {
+            "pt" => Some(Locale::PtBr),
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            _ => None,
+        }
+    }
+
+    pub fn as_tag(&self) -> &'static str {
+        match self {
+            Locale::PtBr => "pt-BR",
+            Locale::En => "en",
+            Locale::Es => "es",
+        }
+    }
+
+    /// Escolhe o idioma suportado de maior peso (`q`) no header `Accept-Language`
+    pub fn negotiate(accept_language: &str) -> Option<Self> {
+        let mut candidates: Vec<(Locale, f32)> = accept_language
+            .split(',')
+            .filter_map(|entry| {
+                let mut parts = entry.split(';');
+                let locale = Locale::from_tag(parts.next()?)?;
+                let quality = parts
+                    .find_map(|param| param.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (quality > 0.0).then_some((locale, quality))
+            })
+            .collect();
+
+        // sort estável: em empate vence a ordem do header
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidates.first().map(|(locale, _)| *locale)
+    }
+
+    fn catalog(&self) -> &'static Catalog {
+        match self {
+            Locale::PtBr => PT_BR.get_or_init(|| load(include_str!("../../locales/pt-BR.json"))),
+            Locale::En => EN.get_or_init(|| load(include_str!("../../locales/en.json"))),
+            Locale::Es => ES.get_or_init(|| load(include_str!("../../locales/es.json"))),
+        }
+    }
+
+    /// Mensagem do catálogo para a chave (código estável); cai para pt-BR e,
+    /// por último, para a própria chave
+    pub fn message(&self, key: &'static str) -> &'static str {
+        self.lookup(key).unwrap_or(key)
+    }
+
+    /// Traduz uma falha de campo usando `CODIGO.regra` e, se não houver, `CODIGO`
+    pub fn field_message(&self, error: &FieldError) -> String {
+        let (rule, arg) = match error.constraint.as_deref() {
+            Some(constraint) => match constraint.split_once('=') {
+                Some((rule, arg)) => (Some(rule), arg),
+                None => (Some(constraint), ""),
+            },
+            None => (None, ""),
+        };
+
+        let specific = rule.map(|rule| format!("{}.{}", error.code, rule));
+        let template = specific
+            .as_deref()
+            .and_then(|key| self.lookup(key))
+            .or_else(|| self.lookup(error.code))
+            .unwrap_or(&error.message);
+
+        template.replace("{0}", arg)
+    }
+
+    fn lookup(&self, key: &str) -> Option<&'static str> {
+        self.catalog()
+            .get(key)
+            .or_else(|| Locale::PtBr.catalog().get(key))
+            .map(String::as_str)
+    }
+}
+
+fn load(raw: &str) -> Catalog {
+    serde_json::from_str(raw).expect("catálogo de mensagens inválido")
+}
+
+/// Idioma da requisição atual (pt-BR fora do escopo de uma requisição)
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE
+        .try_with(|locale| locale.get())
+        .unwrap_or_default()
+}
+
+/// Executa `fut` com `locale` como idioma da requisição
+pub fn with_locale<F: Future>(locale: Locale, fut: F) -> impl Future<Output = F::Output> {
+    CURRENT_LOCALE.scope(Cell::new(locale), fut)
+}
+
+/// Troca o idioma da requisição atual, ex.: pela preferência salva do usuário
+pub fn set_current_locale(locale: Locale) {
+    let _ = CURRENT_LOCALE.try_with(|current| current.set(locale));
+}
+
+impl FromRequest for Locale {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let locale = req
+            .extensions()
+            .get::<Locale>()
+            .copied()
+            .unwrap_or_else(current_locale);
+        ready(Ok(locale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_accept_language() {
+        assert_eq!(Locale::negotiate("en-US,en;q=0.9"), Some(Locale::En));
+        assert_eq!(
+            Locale::negotiate("fr-FR, es;q=0.8, en;q=0.5"),
+            Some(Locale::Es)
+        );
+        assert_eq!(
+            Locale::negotiate("en;q=0.2, pt-BR;q=0.9"),
+            Some(Locale::PtBr)
+        );
+        assert_eq!(Locale::negotiate("de, fr"), None);
+        assert_eq!(Locale::negotiate("en;q=0"), None);
+    }
+
+    #[test]
+    fn test_catalogs_have_same_keys() {
+        let base = Locale::PtBr.catalog();
+        for locale in [Locale::En, Locale::Es] {
+            let catalog = locale.catalog();
+            for key in base.keys() {
+                assert!(
+                    catalog.contains_key(key),
+                    "{key} ausente em {}",
+                    locale.as_tag()
+                );
+            }
+            assert_eq!(
+                catalog.len(),
+                base.len(),
+                "chaves extras em {}",
+                locale.as_tag()
+            );
+        }
+    }
+
+    #[test]
+    fn test_field_message_uses_rule_and_argument() {
+        let error = FieldError::new("password", "WEAK_PASSWORD", "Senha fraca")
+            .with_constraint("min_length=8");
+        assert_eq!(
+            Locale::En.field_message(&error),
+            "Password must have at least 8 characters"
+        );
+
+        let error = FieldError::new("email", "INVALID_EMAIL", "").with_constraint("format=email");
+        assert_eq!(Locale::Es.field_message(&error), "Email inválido");
+    }
+}
@@ -1,23 +1,92 @@
-use std::sync::OnceLock;
+use std::sync::{OnceLock, atomic::AtomicBool};
 
-use crate::handlers::{auth_routes, user_routes};
+use crate::{
+    config::{
+        AuthSettings, FeesConfig, FxConfig, InterestConfig, LimitsConfig, PixConfig, WebhookConfig,
+    },
+    handlers::{
+        account_routes, admin_routes, auth_routes, boleto_routes, business_routes, health_routes,
+        kyc_routes, metrics_routes, notification_routes, openapi_routes, pix_routes, user_routes,
+        webhook_routes,
+    },
+    models::app_error::AppError,
+};
 use actix_web::web::{self, ServiceConfig};
+use sqlx::migrate::Migrator;
 
+pub mod config;
 mod database;
+pub mod events;
+pub mod fx;
 mod handlers;
+mod i18n;
+pub mod metrics;
 pub mod middleware;
 mod models;
+mod pdf;
+pub mod scheduler;
+mod services;
+pub mod storage;
+pub mod telemetry;
 mod utils;
 pub mod validators;
+pub mod verification;
 
 pub static JWT_SECRET: OnceLock<String> = OnceLock::new();
 
+/// Token do `GET /metrics`; sem definição, a rota responde 404
+pub static METRICS_TOKEN: OnceLock<String> = OnceLock::new();
+
+/// TTLs dos tokens e custo do bcrypt; sem definição, valem os padrões de `AuthSettings`
+pub static AUTH_SETTINGS: OnceLock<AuthSettings> = OnceLock::new();
+
+/// Limites de saque e transferência; sem definição, valem os padrões de `LimitsConfig`
+pub static LIMIT_SETTINGS: OnceLock<LimitsConfig> = OnceLock::new();
+
+/// Regras de tarifa; sem definição, valem os padrões de `FeesConfig`
+pub static FEE_SETTINGS: OnceLock<FeesConfig> = OnceLock::new();
+
+/// Regras de rendimento; sem definição, valem os padrões de `InterestConfig`
+pub static INTEREST_SETTINGS: OnceLock<InterestConfig> = OnceLock::new();
+
+/// Spread e validade das cotações de câmbio; sem definição, valem os padrões de `FxConfig`
+pub static FX_SETTINGS: OnceLock<FxConfig> = OnceLock::new();
+
+/// URL e validade das cobranças PIX; sem definição, valem os padrões de `PixConfig`
+pub static PIX_SETTINGS: OnceLock<PixConfig> = OnceLock::new();
+
+/// Destinos aceitos nos webhooks; sem definição, valem os padrões de `WebhookConfig`
+pub static WEBHOOK_SETTINGS: OnceLock<WebhookConfig> = OnceLock::new();
+
+/// Migrations embutidas no binário (usadas na inicialização e na readiness)
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Ligado no desligamento gracioso: a readiness passa a responder 503
+pub static DRAINING: AtomicBool = AtomicBool::new(false);
+
 pub fn app(cgf: &mut ServiceConfig) {
+    // falhas de desserialização seguem o mesmo formato de erro por campo
+    cgf.app_data(web::JsonConfig::default().error_handler(|err, _req| AppError::from(err).into()));
+
+    cgf.configure(health_routes).configure(metrics_routes);
+
     cgf.service(
-        web::scope("/api").service(
-            web::scope("/v1")
-                .configure(auth_routes)
-                .configure(user_routes), //protegido pelo middleware
-        ),
+        web::scope("/api")
+            .wrap(middleware::Localization)
+            .wrap(middleware::RequestMetrics)
+            .service(
+                web::scope("/v1")
+                    .configure(openapi_routes)
+                    .configure(auth_routes)
+                    .configure(user_routes) //protegido pelo middleware
+                    .configure(account_routes)
+                    .configure(pix_routes)
+                    .configure(boleto_routes)
+                    .configure(business_routes)
+                    .configure(kyc_routes)
+                    .configure(webhook_routes)
+                    .configure(notification_routes)
+                    .configure(admin_routes),
+            ),
     );
 }
@@ -1,9 +1,20 @@
+use std::{sync::Arc, time::Duration};
+
 use actix_files as fs;
-use actix_web::{
-    middleware::Logger,
-    web::{self, ServiceConfig},
+use actix_web::web::{self, ServiceConfig};
+use api_mini_bank::{
+    JWT_SECRET, METRICS_TOKEN, MIGRATOR, app,
+    config::{
+        FxConfig, InterestConfig, OutboxConfig, PixConfig, RealtimeConfig, SchedulerConfig,
+        StorageConfig, VerificationConfig, WebhookConfig,
+    },
+    events::{NotificationHub, OutboxDispatcher, WebhookDispatcher, WebhookFanout},
+    fx::{FileRateProvider, FxRateProvider},
+    middleware::RequestTracing,
+    scheduler::{HoldExpirer, InterestAccruer, ReversalRecovery, TransferScheduler},
+    storage::{DocumentStorage, LocalStorage},
+    verification::{CodeSender, HttpCodeSender},
 };
-use api_mini_bank::{JWT_SECRET, app};
 use shuttle_actix_web::ShuttleActixWeb;
 use shuttle_runtime::SecretStore;
 use sqlx::PgPool;
@@ -20,17 +31,78 @@ async fn main(
 
     // Define uma única vez - se tentar definir novamente, vai dar erro
     JWT_SECRET.set(secret).expect("JWT_SECRET já foi definido");
+    // sem o secret, `GET /metrics` fica desligado
+    if let Some(token) = secrets.get("METRICS_TOKEN") {
+        METRICS_TOKEN
+            .set(token)
+            .expect("METRICS_TOKEN já foi definido");
+    }
+
+    // os BR Codes dinâmicos apontam para a URL pública do serviço
+    let pix = PixConfig {
+        location_url: secrets
+            .get("PIX_LOCATION_URL")
+            .expect("PIX_LOCATION_URL não configurado"),
+        ..PixConfig::default()
+    };
+    pix.validate().expect("PIX_LOCATION_URL inválido");
+    pix.install();
 
-    sqlx::migrate!()
-        .run(&pool)
+    MIGRATOR.run(&pool).await.expect("Failed to run migrations");
+
+    // no Shuttle os eventos vão para o log e para os webhooks cadastrados
+    let dispatcher = OutboxDispatcher::from_config(pool.clone(), &OutboxConfig::default())
+        .expect("falha ao configurar os sinks de eventos")
+        .with_sink(Box::new(WebhookFanout::new(pool.clone())));
+    tokio::spawn(dispatcher.run());
+    let webhooks = WebhookDispatcher::from_config(pool.clone(), &WebhookConfig::default())
+        .expect("falha ao configurar o envio de webhooks");
+    tokio::spawn(webhooks.run());
+    let fx_rates = FileRateProvider::open(FxConfig::default().rates_file)
+        .await
+        .expect("falha ao carregar as taxas de câmbio");
+    let fx_rates: Arc<dyn FxRateProvider> = Arc::new(fx_rates);
+    tokio::spawn(
+        TransferScheduler::new(pool.clone(), fx_rates.clone(), &SchedulerConfig::default()).run(),
+    );
+    tokio::spawn(HoldExpirer::new(pool.clone(), &SchedulerConfig::default()).run());
+    tokio::spawn(ReversalRecovery::new(pool.clone(), &SchedulerConfig::default()).run());
+    let interest = InterestConfig::default();
+    if interest.enabled {
+        tokio::spawn(
+            InterestAccruer::new(pool.clone(), &SchedulerConfig::default(), &interest).run(),
+        );
+    }
+    let hub = web::Data::new(NotificationHub::new(&RealtimeConfig::default()));
+    tokio::spawn(hub.listener(pool.clone()).run());
+    let storage = LocalStorage::open(StorageConfig::default().local_dir)
         .await
-        .expect("Failed to run migrations");
+        .expect("falha ao abrir o storage de documentos");
+    let storage: web::Data<dyn DocumentStorage> =
+        web::Data::from(Arc::new(storage) as Arc<dyn DocumentStorage>);
+    let fx_rates = web::Data::from(fx_rates);
+    // no Shuttle não há modo de desenvolvimento: sem gateway de email/SMS o serviço não sobe
+    let verification = VerificationConfig::default();
+    let code_sender = HttpCodeSender::new(
+        secrets
+            .get("VERIFICATION_GATEWAY_URL")
+            .expect("VERIFICATION_GATEWAY_URL não configurado"),
+        secrets.get("VERIFICATION_GATEWAY_TOKEN"),
+        Duration::from_secs(verification.timeout_secs),
+    )
+    .expect("falha ao configurar o envio dos códigos de verificação");
+    let code_sender: web::Data<dyn CodeSender> =
+        web::Data::from(Arc::new(code_sender) as Arc<dyn CodeSender>);
 
     let config = move |cfg: &mut ServiceConfig| {
         cfg.service(
             web::scope("")
-                .wrap(Logger::default())
+                .wrap(RequestTracing)
                 .app_data(web::Data::new(pool.clone()))
+                .app_data(hub.clone())
+                .app_data(storage.clone())
+                .app_data(fx_rates.clone())
+                .app_data(code_sender.clone())
                 .configure(app)
                 .service(fs::Files::new("/", "templates").index_file("index.html")),
         );
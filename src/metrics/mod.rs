@@ -0,0 +1,277 @@
+//! Métricas da API no formato texto do Prometheus, expostas em `GET /metrics`
+use std::sync::LazyLock;
+
+use prometheus::{
+    CounterVec, Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use sqlx::PgPool;
+
+use crate::models::{
+    currency::Currency, scheduled_transfer::RunStatus, transaction::TransactionType,
+};
+
+/// Faixas do histograma de latência, em segundos
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_duration: HistogramVec,
+    login_attempts: IntCounterVec,
+    transactions: IntCounterVec,
+    transaction_amount: CounterVec,
+    rate_limit_hits: IntCounterVec,
+    outbox_deliveries: IntCounterVec,
+    scheduled_runs: IntCounterVec,
+    realtime_connections: IntGaugeVec,
+    pool_size: IntGauge,
+    pool_idle: IntGauge,
+    pool_in_use: IntGauge,
+    pool_max: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("minibank".into()), None)
+            .expect("prefixo de métricas inválido");
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "Requisições HTTP por rota e status"),
+            &["method", "route", "status"],
+        )
+        .expect("métrica inválida");
+        let http_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Latência das requisições HTTP por rota e status",
+            )
+            .buckets(LATENCY_BUCKETS.to_vec()),
+            &["method", "route", "status"],
+        )
+        .expect("métrica inválida");
+        let login_attempts = IntCounterVec::new(
+            Opts::new("login_attempts_total", "Tentativas de login por resultado"),
+            &["outcome"],
+        )
+        .expect("métrica inválida");
+        let transactions = IntCounterVec::new(
+            Opts::new(
+                "transactions_total",
+                "Transações concluídas por tipo e moeda",
+            ),
+            &["type", "currency"],
+        )
+        .expect("métrica inválida");
+        let transaction_amount = CounterVec::new(
+            Opts::new(
+                "transaction_amount_total",
+                "Valor movimentado pelas transações concluídas, por tipo e moeda",
+            ),
+            &["type", "currency"],
+        )
+        .expect("métrica inválida");
+        let rate_limit_hits = IntCounterVec::new(
+            Opts::new(
+                "rate_limit_hits_total",
+                "Requisições recusadas por limite de taxa (429)",
+            ),
+            &["route"],
+        )
+        .expect("métrica inválida");
+        let outbox_deliveries = IntCounterVec::new(
+            Opts::new(
+                "outbox_deliveries_total",
+                "Entregas de eventos do outbox por tipo e resultado",
+            ),
+            &["event_type", "outcome"],
+        )
+        .expect("métrica inválida");
+        let scheduled_runs = IntCounterVec::new(
+            Opts::new(
+                "scheduled_transfer_runs_total",
+                "Execuções de transferências agendadas por resultado",
+            ),
+            &["outcome"],
+        )
+        .expect("métrica inválida");
+        let realtime_connections = IntGaugeVec::new(
+            Opts::new(
+                "realtime_connections",
+                "Conexões abertas de notificações em tempo real por transporte",
+            ),
+            &["transport"],
+        )
+        .expect("métrica inválida");
+        let pool_size = IntGauge::new("db_pool_connections", "Conexões abertas no pool")
+            .expect("métrica inválida");
+        let pool_idle = IntGauge::new("db_pool_idle_connections", "Conexões ociosas no pool")
+            .expect("métrica inválida");
+        let pool_in_use = IntGauge::new("db_pool_in_use_connections", "Conexões em uso no pool")
+            .expect("métrica inválida");
+        let pool_max = IntGauge::new("db_pool_max_connections", "Limite de conexões do pool")
+            .expect("métrica inválida");
+
+        let metrics = Metrics {
+            registry,
+            http_requests,
+            http_duration,
+            login_attempts,
+            transactions,
+            transaction_amount,
+            rate_limit_hits,
+            outbox_deliveries,
+            scheduled_runs,
+            realtime_connections,
+            pool_size,
+            pool_idle,
+            pool_in_use,
+            pool_max,
+        };
+        metrics.register_all();
+        metrics
+    }
+
+    fn register_all(&self) {
+        let collectors: Vec<Box<dyn prometheus::core::Collector>> = vec![
+            Box::new(self.http_requests.clone()),
+            Box::new(self.http_duration.clone()),
+            Box::new(self.login_attempts.clone()),
+            Box::new(self.transactions.clone()),
+            Box::new(self.transaction_amount.clone()),
+            Box::new(self.rate_limit_hits.clone()),
+            Box::new(self.outbox_deliveries.clone()),
+            Box::new(self.scheduled_runs.clone()),
+            Box::new(self.realtime_connections.clone()),
+            Box::new(self.pool_size.clone()),
+            Box::new(self.pool_idle.clone()),
+            Box::new(self.pool_in_use.clone()),
+            Box::new(self.pool_max.clone()),
+        ];
+        for collector in collectors {
+            self.registry
+                .register(collector)
+                .expect("métrica registrada duas vezes");
+        }
+    }
+}
+
+/// Contabiliza uma requisição HTTP; `route` é o padrão da rota (ex.: `/api/v1/auth/login`)
+pub fn record_request(method: &str, route: &str, status: u16, seconds: f64) {
+    let status = status.to_string();
+    let labels = [method, route, status.as_str()];
+    METRICS.http_requests.with_label_values(&labels).inc();
+    METRICS
+        .http_duration
+        .with_label_values(&labels)
+        .observe(seconds);
+    // hoje só o envio de códigos de verificação do PIX responde 429
+    if status == "429" {
+        METRICS.rate_limit_hits.with_label_values(&[route]).inc();
+    }
+}
+
+pub fn record_login(success: bool) {
+    let outcome = if success { "success" } else { "failure" };
+    METRICS.login_attempts.with_label_values(&[outcome]).inc();
+}
+
+/// O valor fica na moeda da transação: somar moedas diferentes não faz sentido
+pub fn record_transaction(kind: &TransactionType, amount: Decimal, currency: Currency) {
+    let labels = [kind.as_str(), currency.code()];
+    METRICS.transactions.with_label_values(&labels).inc();
+    METRICS
+        .transaction_amount
+        .with_label_values(&labels)
+        .inc_by(amount.abs().to_f64().unwrap_or_default());
+}
+
+pub fn record_outbox_delivery(event_type: &str, delivered: bool) {
+    let outcome = if delivered { "delivered" } else { "failed" };
+    METRICS
+        .outbox_deliveries
+        .with_label_values(&[event_type, outcome])
+        .inc();
+}
+
+pub fn record_scheduled_run(status: RunStatus) {
+    let outcome = match status {
+        RunStatus::Completed => "completed",
+        RunStatus::Failed => "failed",
+    };
+    METRICS.scheduled_runs.with_label_values(&[outcome]).inc();
+}
+
+/// Conta uma conexão de tempo real enquanto o valor devolvido existir
+pub fn realtime_connection(transport: &'static str) -> RealtimeConnection {
+    METRICS
+        .realtime_connections
+        .with_label_values(&[transport])
+        .inc();
+    RealtimeConnection { transport }
+}
+
+pub struct RealtimeConnection {
+    transport: &'static str,
+}
+
+impl Drop for RealtimeConnection {
+    fn drop(&mut self) {
+        METRICS
+            .realtime_connections
+            .with_label_values(&[self.transport])
+            .dec();
+    }
+}
+
+/// Atualiza os gauges do pool e serializa todas as métricas
+pub fn render(pool: &PgPool) -> String {
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    METRICS.pool_size.set(i64::from(size));
+    METRICS.pool_idle.set(i64::from(idle));
+    METRICS
+        .pool_in_use
+        .set(i64::from(size.saturating_sub(idle)));
+    METRICS
+        .pool_max
+        .set(i64::from(pool.options().get_max_connections()));
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&METRICS.registry.gather(), &mut buffer)
+        .expect("falha ao serializar métricas");
+    String::from_utf8(buffer).expect("métricas devem ser UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_and_rate_limit_hits_are_counted() {
+        record_request("POST", "/api/v1/test-metrics", 429, 0.02);
+
+        let labels = ["POST", "/api/v1/test-metrics", "429"];
+        assert_eq!(METRICS.http_requests.with_label_values(&labels).get(), 1);
+        assert_eq!(
+            METRICS
+                .http_duration
+                .with_label_values(&labels)
+                .get_sample_count(),
+            1
+        );
+        assert_eq!(
+            METRICS
+                .rate_limit_hits
+                .with_label_values(&["/api/v1/test-metrics"])
+                .get(),
+            1
+        );
+    }
+}
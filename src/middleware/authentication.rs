@@ -3,11 +3,15 @@ use std::{future::Future, pin::Pin, rc::Rc};
 
 use actix_web::{
     HttpMessage,
+    body::EitherBody,
     dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
-    error::ErrorUnauthorized,
 };
 
-use crate::{models::error::UserError, utils::verify_token};
+use crate::{
+    i18n::{self, Locale},
+    models::app_error::AppError,
+    utils::verify_token,
+};
 
 pub struct Authentication;
 
@@ -18,7 +22,7 @@ where
     B: 'static,
 {
     type Error = actix_web::Error;
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
 
     type Transform = AuthenticationMiddleware<S>;
 
@@ -43,7 +47,7 @@ where
     S::Future: 'static,
     B: 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<EitherBody<B>>;
     type Error = actix_web::Error;
     type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
 
@@ -53,22 +57,32 @@ where
         let service = self.service.clone();
 
         Box::pin(async move {
-            let auth_header = req.headers().get("Authorization");
-
-            if let Some(auth_value) = auth_header {
-                if let Ok(auth_str) = auth_value.to_str() {
-                    let token = &auth_str[7..];
-                    match verify_token(token) {
-                        Ok(token) => {
-                            req.extensions_mut().insert(token.claims);
-                            return service.call(req).await;
-                        }
-                        Err(_) => return Err(ErrorUnauthorized(UserError::InvalidCredentials)),
-                    }
-                }
+            let claims = req
+                .headers()
+                .get("Authorization")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .and_then(|token| verify_token(token).ok())
+                .map(|token| token.claims);
+
+            // responde aqui (e não com Err) para o erro sair no idioma da requisição
+            let Some(claims) = claims else {
+                return Ok(req
+                    .error_response(AppError::Unauthorized)
+                    .map_into_right_body());
+            };
+
+            // a preferência salva do usuário tem prioridade sobre o Accept-Language
+            if let Some(locale) = claims.locale.as_deref().and_then(Locale::from_tag) {
+                i18n::set_current_locale(locale);
+                req.extensions_mut().insert(locale);
             }
+            req.extensions_mut().insert(claims);
 
-            Err(ErrorUnauthorized(UserError::InvalidCredentials))
+            service
+                .call(req)
+                .await
+                .map(ServiceResponse::map_into_left_body)
         })
     }
 }
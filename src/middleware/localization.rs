@@ -0,0 +1,77 @@
+use futures_util::future::{Ready, ready};
+use std::{future::Future, pin::Pin, rc::Rc};
+
+use actix_web::{
+    HttpMessage,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    http::header::{ACCEPT_LANGUAGE, CONTENT_LANGUAGE, HeaderValue},
+};
+
+use crate::i18n::{Locale, current_locale, with_locale};
+
+/// Resolve o idioma da requisição pelo `Accept-Language` (padrão pt-BR)
+///
+/// Handlers e middlewares internos rodam dentro do escopo do idioma, então os
+/// erros que eles renderizam já saem traduzidos.
+pub struct Localization;
+
+impl<S, B> Transform<S, ServiceRequest> for Localization
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Error = actix_web::Error;
+    type Response = ServiceResponse<B>;
+
+    type Transform = LocalizationMiddleware<S>;
+
+    type InitError = ();
+
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(LocalizationMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct LocalizationMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for LocalizationMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        let locale = req
+            .headers()
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Locale::negotiate)
+            .unwrap_or_default();
+        req.extensions_mut().insert(locale);
+
+        Box::pin(with_locale(locale, async move {
+            let mut res = service.call(req).await?;
+
+            res.headers_mut().insert(
+                CONTENT_LANGUAGE,
+                HeaderValue::from_static(current_locale().as_tag()),
+            );
+            Ok(res)
+        }))
+    }
+}
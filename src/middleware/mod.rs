@@ -1,2 +1,8 @@
 mod authentication;
+mod localization;
+mod metrics;
+mod request_tracing;
 pub use authentication::Authentication;
+pub use localization::Localization;
+pub use metrics::RequestMetrics;
+pub use request_tracing::RequestTracing;
@@ -0,0 +1,111 @@
+use futures_util::future::{Ready, ready};
+use std::{future::Future, pin::Pin, rc::Rc, time::Instant};
+use tracing::{Instrument, field::Empty};
+
+use actix_web::{
+    HttpMessage,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    http::header::{HeaderName, HeaderValue},
+};
+
+use crate::telemetry::{REQUEST_ID_HEADER, RequestContext, TRACEPARENT_HEADER, with_request};
+
+/// Substitui o `Logger` do actix: abre um span por requisição, propaga
+/// `X-Request-Id`/`traceparent` e registra um evento estruturado ao final
+///
+/// Só método, rota, status e ids entram no log; headers e corpo nunca, para
+/// não vazar senhas e tokens.
+pub struct RequestTracing;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTracing
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Error = actix_web::Error;
+    type Response = ServiceResponse<B>;
+
+    type Transform = RequestTracingMiddleware<S>;
+
+    type InitError = ();
+
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestTracingMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestTracingMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTracingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let started = Instant::now();
+
+        #[allow(unused_mut)]
+        let mut context = RequestContext::from_headers(req.headers());
+        let span = tracing::info_span!(
+            "http_request",
+            http.method = %req.method(),
+            http.route = req.match_pattern().as_deref().unwrap_or("unmatched"),
+            request_id = %context.request_id,
+            trace_id = Empty,
+            http.status = Empty,
+            latency_ms = Empty,
+        );
+        #[cfg(feature = "otlp")]
+        crate::telemetry::link_span(&span, &mut context);
+        span.record("trace_id", context.trace_id.as_str());
+        req.extensions_mut().insert(context.clone());
+
+        let request_id = HeaderValue::from_str(&context.request_id).ok();
+        let traceparent = HeaderValue::from_str(&context.traceparent()).ok();
+
+        Box::pin(
+            with_request(context, async move {
+                let result = service.call(req).await;
+                let status = match &result {
+                    Ok(res) => res.status(),
+                    Err(err) => err.as_response_error().status_code(),
+                };
+
+                let span = tracing::Span::current();
+                span.record("http.status", status.as_u16());
+                span.record("latency_ms", started.elapsed().as_secs_f64() * 1000.0);
+                if status.is_server_error() {
+                    tracing::error!("request failed");
+                } else {
+                    tracing::info!("request completed");
+                }
+
+                let mut res = result?;
+                let headers = res.headers_mut();
+                if let Some(value) = request_id {
+                    headers.insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+                }
+                if let Some(value) = traceparent {
+                    headers.insert(HeaderName::from_static(TRACEPARENT_HEADER), value);
+                }
+                Ok(res)
+            })
+            .instrument(span),
+        )
+    }
+}
@@ -1,17 +1,25 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::models::currency::Currency;
+
 /// Dados para criação de conta bancária
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateAccount {
     pub account_type: AccountType,
+    /// Padrão: BRL
+    #[serde(default)]
+    pub currency: Currency,
 }
 
 /// Tipos de conta permitidos
-#[derive(Debug, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "account_type", rename_all = "lowercase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "account_type_enum", rename_all = "lowercase")]
 pub enum AccountType {
     Checking,
     Savings,
@@ -19,18 +27,97 @@ pub enum AccountType {
 }
 
 /// Entidade Account
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
 pub struct Account {
     pub id: Uuid,
     pub user_id: Uuid,
     pub account_number: String,
     pub account_type: AccountType,
+    /// Moeda de todos os valores da conta; não muda depois da abertura
+    pub currency: Currency,
+    /// Saldo contábil: só muda com lançamentos concluídos; negativo quando a
+    /// conta corrente usa o cheque especial
     pub balance: Decimal,
+    /// Soma das reservas ativas
+    pub held_balance: Decimal,
+    /// Limite do cheque especial (só conta corrente)
+    pub overdraft_limit: Decimal,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Account {
+    /// Nova conta ativa com saldo zero (RF008)
+    pub fn open(user_id: Uuid, account_type: AccountType, currency: Currency) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            account_number: generate_account_number(),
+            account_type,
+            currency,
+            balance: Decimal::ZERO,
+            held_balance: Decimal::ZERO,
+            overdraft_limit: Decimal::ZERO,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Saldo que pode ser debitado: o contábil mais o cheque especial, menos as reservas
+    pub fn available_balance(&self) -> Decimal {
+        self.balance + self.overdraft_limit - self.held_balance
+    }
+
+    /// Quanto do cheque especial o saldo negativo e as reservas ativas já
+    /// comprometem; o limite não pode ficar abaixo disso
+    pub fn overdraft_committed(&self) -> Decimal {
+        (self.held_balance - self.balance).max(Decimal::ZERO)
+    }
+
+    /// Estado relevante para auditoria
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "account_number": self.account_number,
+            "account_type": self.account_type,
+            "currency": self.currency,
+            "balance": self.balance,
+            "held_balance": self.held_balance,
+            "overdraft_limit": self.overdraft_limit,
+            "is_active": self.is_active,
+        })
+    }
+}
+
+/// Número de conta com 10 dígitos; colisões são tratadas no insert
+pub fn generate_account_number() -> String {
+    format!("{:010}", Uuid::new_v4().as_u128() % 10_000_000_000)
+}
+
+/// Saldo da conta (`GET /accounts/{id}/balance`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountBalance {
+    pub account_id: Uuid,
+    pub account_number: String,
+    pub currency: Currency,
+    /// Saldo contábil
+    pub balance: Decimal,
+    /// Reservado por autorizações ainda não capturadas
+    pub held_balance: Decimal,
+    pub overdraft_limit: Decimal,
+    /// Disponível para saque, transferência e novas reservas (inclui o cheque especial)
+    pub available_balance: Decimal,
+}
+
+/// Novo limite do cheque especial (`PUT /admin/accounts/{id}/overdraft`)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateOverdraft {
+    /// Zero desliga o cheque especial
+    pub limit: Decimal,
+}
+
 pub mod error {
     #[derive(Debug, thiserror::Error)]
     pub enum AccountError {
@@ -46,7 +133,47 @@ pub mod error {
         #[error("Número de conta já existe")]
         DuplicateAccountNumber,
 
+        #[error("Conta com saldo não pode ser desativada")]
+        NonZeroBalance,
+
+        #[error("Conta com valor bloqueado não pode ser desativada")]
+        HeldBalance,
+
+        #[error("Conta com estorno a recuperar não pode ser desativada")]
+        PendingRecovery,
+
+        #[error("Conta com juros a lançar não pode ser desativada")]
+        PendingInterest,
+
+        #[error("Cheque especial só existe em conta corrente")]
+        OverdraftNotAllowed,
+
+        #[error("Limite menor que o cheque especial em uso")]
+        OverdraftLimitBelowUsage,
+
         #[error("Erro no banco de dados: {0}")]
         DatabaseError(#[from] sqlx::Error),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overdraft_committed_counts_holds() {
+        let mut account = Account::open(Uuid::new_v4(), AccountType::Checking, Currency::Brl);
+        account.overdraft_limit = Decimal::from(1_000);
+        account.balance = Decimal::from(100);
+        account.held_balance = Decimal::from(400);
+        assert_eq!(account.overdraft_committed(), Decimal::from(300));
+
+        account.balance = Decimal::from(-200);
+        assert_eq!(account.overdraft_committed(), Decimal::from(600));
+
+        account.held_balance = Decimal::ZERO;
+        assert_eq!(account.overdraft_committed(), Decimal::from(200));
+        account.balance = Decimal::from(50);
+        assert_eq!(account.overdraft_committed(), Decimal::ZERO);
+    }
+}
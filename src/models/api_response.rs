@@ -1,16 +1,30 @@
 use chrono::Utc;
 use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{telemetry::RequestContext, validators::FieldError};
 
 /// Padronização do tipo de resposta
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub sucess: bool,
     pub data: Option<T>,
     pub message: String,
     pub error: Option<String>,
+    /// Falhas por campo, presente apenas em erros de validação
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+    /// Correlação com os logs, presente apenas em erros
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
     pub timestamp: String,
 }
 
+/// Envelope de erro (`data` sempre nulo), usado na documentação OpenAPI
+pub type ApiErrorResponse = ApiResponse<()>;
+
 impl<T> ApiResponse<T> {
     pub fn sucess(data: T, message: &str) -> Self {
         Self {
@@ -18,6 +32,9 @@ impl<T> ApiResponse<T> {
             data: Some(data),
             message: message.into(),
             error: None,
+            errors: None,
+            request_id: None,
+            trace_id: None,
             timestamp: Utc::now().to_rfc3339(),
         }
     }
@@ -27,7 +44,21 @@ impl<T> ApiResponse<T> {
             data: None,
             message: message.into(),
             error: Some(error.into()),
+            errors: None,
+            request_id: None,
+            trace_id: None,
             timestamp: Utc::now().to_rfc3339(),
         }
     }
+
+    pub fn with_errors(mut self, errors: &[FieldError]) -> Self {
+        self.errors = Some(errors.to_vec());
+        self
+    }
+
+    pub fn with_request(mut self, context: &RequestContext) -> Self {
+        self.request_id = Some(context.request_id.clone());
+        self.trace_id = Some(context.trace_id.clone());
+        self
+    }
 }
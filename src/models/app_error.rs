@@ -0,0 +1,506 @@
+use actix_web::{HttpResponse, ResponseError, error::JsonPayloadError, http::StatusCode};
+use serde_json::error::Category;
+
+use crate::{
+    i18n::current_locale,
+    models::{
+        account::error::AccountError, api_response::ApiResponse, boleto::error::BoletoError,
+        business::error::BusinessError, error::UserError, fee::error::FeeError, fx::error::FxError,
+        hold::error::HoldError, kyc::error::KycError, pix::error::PixError,
+        reversal::error::ReversalError, scheduled_transfer::error::ScheduledTransferError,
+        transaction::error::TransactionError, webhook::error::WebhookError,
+    },
+    storage::StorageError,
+    telemetry::current_request,
+    validators::{FieldError, ValidationErrors},
+};
+
+/// Erro único da aplicação
+///
+/// Os handlers retornam `Result<HttpResponse, AppError>` e usam `?`; a conversão
+/// para o envelope `ApiResponse` e o status HTTP acontece aqui.
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error(transparent)]
+    User(#[from] UserError),
+
+    #[error(transparent)]
+    Account(#[from] AccountError),
+
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+
+    #[error(transparent)]
+    Webhook(#[from] WebhookError),
+
+    #[error(transparent)]
+    Pix(#[from] PixError),
+
+    #[error(transparent)]
+    Boleto(#[from] BoletoError),
+
+    #[error(transparent)]
+    Business(#[from] BusinessError),
+
+    #[error(transparent)]
+    Kyc(#[from] KycError),
+
+    #[error(transparent)]
+    ScheduledTransfer(#[from] ScheduledTransferError),
+
+    #[error(transparent)]
+    Hold(#[from] HoldError),
+
+    #[error(transparent)]
+    Reversal(#[from] ReversalError),
+
+    #[error(transparent)]
+    Fee(#[from] FeeError),
+
+    #[error(transparent)]
+    Fx(#[from] FxError),
+
+    #[error("Dados inválidos: {0}")]
+    Validation(ValidationErrors),
+
+    #[error("Token de autenticação ausente ou inválido")]
+    Unauthorized,
+
+    #[error("Token de autenticação expirado")]
+    TokenExpired,
+
+    #[error("Acesso negado ao recurso")]
+    Forbidden,
+
+    #[error("Erro no banco de dados: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Erro interno: {0}")]
+    Internal(String),
+}
+
+impl AppError {
+    /// Código estável do erro, enviado no campo `error` da resposta
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::User(err) => match err {
+                UserError::Validation(_) => "VALIDATION_ERROR",
+                UserError::InvalidEmail(_) => "INVALID_EMAIL",
+                UserError::WeakPassword => "WEAK_PASSWORD",
+                UserError::InvalidName => "INVALID_NAME",
+                UserError::EmailAlreadyExists => "EMAIL_ALREADY_EXISTS",
+                UserError::DocumentAlreadyExists => "DOCUMENT_ALREADY_EXISTS",
+                UserError::NotFound => "USER_NOT_FOUND",
+                UserError::InvalidCredentials => "INVALID_CREDENTIALS",
+                UserError::InvalidRefreshToken => "INVALID_REFRESH_TOKEN",
+                UserError::DatabaseError(_) => "DATABASE_ERROR",
+            },
+            AppError::Account(err) => match err {
+                AccountError::NotFound => "ACCOUNT_NOT_FOUND",
+                AccountError::Unauthorized => "ACCOUNT_NOT_OWNED",
+                AccountError::Inactive => "ACCOUNT_INACTIVE",
+                AccountError::DuplicateAccountNumber => "DUPLICATE_ACCOUNT_NUMBER",
+                AccountError::NonZeroBalance => "ACCOUNT_HAS_BALANCE",
+                AccountError::HeldBalance => "ACCOUNT_HAS_HELD_BALANCE",
+                AccountError::PendingRecovery => "ACCOUNT_HAS_PENDING_RECOVERY",
+                AccountError::PendingInterest => "ACCOUNT_HAS_PENDING_INTEREST",
+                AccountError::OverdraftNotAllowed => "OVERDRAFT_NOT_ALLOWED",
+                AccountError::OverdraftLimitBelowUsage => "OVERDRAFT_LIMIT_BELOW_USAGE",
+                AccountError::DatabaseError(_) => "DATABASE_ERROR",
+            },
+            AppError::Transaction(err) => match err {
+                TransactionError::InvalidAmount => "INVALID_AMOUNT",
+                TransactionError::InsufficientFunds => "INSUFFICIENT_FUNDS",
+                TransactionError::SourceAccountNotFound => "SOURCE_ACCOUNT_NOT_FOUND",
+                TransactionError::DestinationAccountNotFound => "DESTINATION_ACCOUNT_NOT_FOUND",
+                TransactionError::SameAccountTransfer => "SAME_ACCOUNT_TRANSFER",
+                TransactionError::PerTransactionLimitExceeded => "TRANSACTION_LIMIT_EXCEEDED",
+                TransactionError::DailyLimitExceeded => "DAILY_LIMIT_EXCEEDED",
+                TransactionError::MonthlyLimitExceeded => "MONTHLY_LIMIT_EXCEEDED",
+                TransactionError::NightlyLimitExceeded => "NIGHTLY_LIMIT_EXCEEDED",
+                TransactionError::UserDailyLimitExceeded => "USER_DAILY_LIMIT_EXCEEDED",
+                TransactionError::UserMonthlyLimitExceeded => "USER_MONTHLY_LIMIT_EXCEEDED",
+                TransactionError::UserNightlyLimitExceeded => "USER_NIGHTLY_LIMIT_EXCEEDED",
+                TransactionError::DatabaseError(_) => "DATABASE_ERROR",
+            },
+            AppError::Webhook(err) => match err {
+                WebhookError::NotFound => "WEBHOOK_NOT_FOUND",
+                WebhookError::DeliveryNotFound => "WEBHOOK_DELIVERY_NOT_FOUND",
+                WebhookError::DeliveryPending => "WEBHOOK_DELIVERY_PENDING",
+            },
+            AppError::Pix(err) => match err {
+                PixError::KeyNotFound => "PIX_KEY_NOT_FOUND",
+                PixError::KeyAlreadyRegistered => "PIX_KEY_ALREADY_REGISTERED",
+                PixError::KeyLimitReached => "PIX_KEY_LIMIT_REACHED",
+                PixError::NotClaimable => "PIX_KEY_NOT_CLAIMABLE",
+                PixError::ClaimNotFound => "PIX_CLAIM_NOT_FOUND",
+                PixError::ClaimOpen => "PIX_CLAIM_OPEN",
+                PixError::ClaimNotOpen => "PIX_CLAIM_NOT_OPEN",
+                PixError::ClaimDeadlinePending => "PIX_CLAIM_DEADLINE_PENDING",
+                PixError::SameAccount => "PIX_KEY_SAME_ACCOUNT",
+                PixError::DocumentMismatch => "PIX_KEY_DOCUMENT_MISMATCH",
+                PixError::VerificationRequired => "PIX_VERIFICATION_REQUIRED",
+                PixError::VerificationInvalid => "PIX_VERIFICATION_INVALID",
+                PixError::TooManyVerifications => "PIX_VERIFICATION_LIMIT",
+                PixError::ClaimNotVerified => "PIX_CLAIM_NOT_VERIFIED",
+                PixError::ChargeNotFound => "PIX_CHARGE_NOT_FOUND",
+                PixError::ChargeNotPayable => "PIX_CHARGE_NOT_PAYABLE",
+                PixError::ChargeMismatch => "PIX_CHARGE_MISMATCH",
+                PixError::ChargeAlreadyExists => "PIX_CHARGE_ALREADY_EXISTS",
+            },
+            AppError::Boleto(err) => match err {
+                BoletoError::NotFound => "BOLETO_NOT_FOUND",
+                BoletoError::NotOpen => "BOLETO_NOT_OPEN",
+                BoletoError::Overdue => "BOLETO_OVERDUE",
+                BoletoError::SameAccount => "BOLETO_SAME_ACCOUNT",
+            },
+            AppError::Business(err) => match err {
+                BusinessError::NotBusiness => "BUSINESS_ONLY",
+                BusinessError::OperatorNotFound => "OPERATOR_NOT_FOUND",
+                BusinessError::OperatorAlreadyExists => "OPERATOR_ALREADY_EXISTS",
+                BusinessError::InvalidOperator => "INVALID_OPERATOR",
+                BusinessError::PermissionDenied => "OPERATOR_PERMISSION_DENIED",
+            },
+            AppError::Kyc(err) => match err {
+                KycError::NotFound => "KYC_NOT_FOUND",
+                KycError::NotApproved => "KYC_REQUIRED",
+                KycError::InvalidStatus => "KYC_INVALID_STATUS",
+                KycError::DocumentNotFound => "KYC_DOCUMENT_NOT_FOUND",
+            },
+            AppError::ScheduledTransfer(err) => match err {
+                ScheduledTransferError::NotFound => "SCHEDULED_TRANSFER_NOT_FOUND",
+                ScheduledTransferError::NotActive => "SCHEDULED_TRANSFER_NOT_ACTIVE",
+            },
+            AppError::Hold(err) => match err {
+                HoldError::NotFound => "HOLD_NOT_FOUND",
+                HoldError::NotActive => "HOLD_NOT_ACTIVE",
+                HoldError::Expired => "HOLD_EXPIRED",
+                HoldError::CaptureExceedsHold => "CAPTURE_EXCEEDS_HOLD",
+            },
+            AppError::Reversal(err) => match err {
+                ReversalError::TransactionNotFound => "TRANSACTION_NOT_FOUND",
+                ReversalError::NotReversible => "TRANSACTION_NOT_REVERSIBLE",
+                ReversalError::AlreadyReversed => "TRANSACTION_ALREADY_REVERSED",
+                ReversalError::ExceedsRemaining => "REVERSAL_EXCEEDS_REMAINING",
+            },
+            AppError::Fee(err) => match err {
+                FeeError::WaiverNotFound => "FEE_WAIVER_NOT_FOUND",
+                FeeError::WaiverRevoked => "FEE_WAIVER_ALREADY_REVOKED",
+            },
+            AppError::Fx(err) => match err {
+                FxError::QuoteNotFound => "FX_QUOTE_NOT_FOUND",
+                FxError::QuoteExpired => "FX_QUOTE_EXPIRED",
+                FxError::QuoteAlreadyUsed => "FX_QUOTE_ALREADY_USED",
+                FxError::QuoteMismatch => "FX_QUOTE_MISMATCH",
+                FxError::QuoteRequired => "FX_QUOTE_REQUIRED",
+                FxError::SameCurrency => "FX_SAME_CURRENCY",
+                FxError::RateUnavailable => "FX_RATE_UNAVAILABLE",
+                FxError::AmountTooSmall => "FX_AMOUNT_TOO_SMALL",
+                FxError::CurrencyNotSupported => "CURRENCY_NOT_SUPPORTED",
+            },
+            AppError::Validation(_) => "VALIDATION_ERROR",
+            AppError::Unauthorized => "UNAUTHORIZED",
+            AppError::TokenExpired => "TOKEN_EXPIRED",
+            AppError::Forbidden => "FORBIDDEN",
+            AppError::Database(_) => "DATABASE_ERROR",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Falhas por campo, quando o erro é de validação
+    pub fn field_errors(&self) -> Option<&ValidationErrors> {
+        match self {
+            AppError::Validation(errors) | AppError::User(UserError::Validation(errors)) => {
+                Some(errors)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Falha do storage de documentos: o detalhe fica no log, o cliente recebe erro interno
+impl From<StorageError> for AppError {
+    fn from(err: StorageError) -> Self {
+        AppError::Internal(err.to_string())
+    }
+}
+
+/// Converte falhas do extractor `Json` no mesmo formato de erro por campo
+impl From<JsonPayloadError> for AppError {
+    fn from(err: JsonPayloadError) -> Self {
+        let error = match &err {
+            JsonPayloadError::ContentType => FieldError::new(
+                "body",
+                "UNSUPPORTED_CONTENT_TYPE",
+                "Content-Type deve ser application/json",
+            )
+            .with_constraint("content_type=application/json"),
+            JsonPayloadError::OverflowKnownLength { limit, .. }
+            | JsonPayloadError::Overflow { limit } => FieldError::new(
+                "body",
+                "PAYLOAD_TOO_LARGE",
+                "Corpo da requisição muito grande",
+            )
+            .with_constraint(format!("max_bytes={limit}")),
+            JsonPayloadError::Deserialize(json_err) => json_field_error(json_err),
+            _ => FieldError::new("body", "INVALID_BODY", "Corpo da requisição inválido"),
+        };
+
+        AppError::Validation(error.into())
+    }
+}
+
+/// Extrai o campo e a regra violada da mensagem do serde
+fn json_field_error(err: &serde_json::Error) -> FieldError {
+    let detail = err.to_string();
+    let field = detail
+        .split('`')
+        .nth(1)
+        .filter(|_| detail.starts_with("missing field") || detail.starts_with("unknown field"))
+        .unwrap_or("body");
+
+    match err.classify() {
+        Category::Data if detail.starts_with("missing field") => {
+            FieldError::new(field, "REQUIRED", "Campo obrigatório ausente")
+                .with_constraint("required")
+        }
+        Category::Data => {
+            let expected = detail
+                .split(", expected ")
+                .nth(1)
+                .and_then(|rest| rest.split(" at line").next())
+                .unwrap_or("valid value");
+            FieldError::new(field, "INVALID_TYPE", "Tipo de valor inválido")
+                .with_constraint(format!("expected={expected}"))
+        }
+        Category::Syntax | Category::Eof | Category::Io => {
+            FieldError::new("body", "MALFORMED_JSON", "JSON malformado")
+        }
+    }
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::User(err) => match err {
+                UserError::Validation(_)
+                | UserError::InvalidEmail(_)
+                | UserError::WeakPassword
+                | UserError::InvalidName => StatusCode::BAD_REQUEST,
+                UserError::EmailAlreadyExists | UserError::DocumentAlreadyExists => {
+                    StatusCode::CONFLICT
+                }
+                UserError::NotFound => StatusCode::NOT_FOUND,
+                UserError::InvalidCredentials | UserError::InvalidRefreshToken => {
+                    StatusCode::UNAUTHORIZED
+                }
+                UserError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            AppError::Account(err) => match err {
+                AccountError::NotFound => StatusCode::NOT_FOUND,
+                AccountError::Unauthorized => StatusCode::FORBIDDEN,
+                AccountError::Inactive
+                | AccountError::NonZeroBalance
+                | AccountError::HeldBalance
+                | AccountError::PendingRecovery
+                | AccountError::PendingInterest
+                | AccountError::OverdraftNotAllowed => StatusCode::UNPROCESSABLE_ENTITY,
+                AccountError::DuplicateAccountNumber | AccountError::OverdraftLimitBelowUsage => {
+                    StatusCode::CONFLICT
+                }
+                AccountError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            AppError::Transaction(err) => match err {
+                TransactionError::InvalidAmount => StatusCode::BAD_REQUEST,
+                TransactionError::InsufficientFunds
+                | TransactionError::SameAccountTransfer
+                | TransactionError::PerTransactionLimitExceeded
+                | TransactionError::DailyLimitExceeded
+                | TransactionError::MonthlyLimitExceeded
+                | TransactionError::NightlyLimitExceeded
+                | TransactionError::UserDailyLimitExceeded
+                | TransactionError::UserMonthlyLimitExceeded
+                | TransactionError::UserNightlyLimitExceeded => StatusCode::UNPROCESSABLE_ENTITY,
+                TransactionError::SourceAccountNotFound
+                | TransactionError::DestinationAccountNotFound => StatusCode::NOT_FOUND,
+                TransactionError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            AppError::Webhook(err) => match err {
+                WebhookError::NotFound | WebhookError::DeliveryNotFound => StatusCode::NOT_FOUND,
+                WebhookError::DeliveryPending => StatusCode::CONFLICT,
+            },
+            AppError::Pix(err) => match err {
+                PixError::KeyNotFound | PixError::ClaimNotFound | PixError::ChargeNotFound => {
+                    StatusCode::NOT_FOUND
+                }
+                PixError::KeyAlreadyRegistered
+                | PixError::ChargeAlreadyExists
+                | PixError::ClaimOpen
+                | PixError::ClaimNotOpen
+                | PixError::ClaimDeadlinePending
+                | PixError::ClaimNotVerified => StatusCode::CONFLICT,
+                PixError::KeyLimitReached
+                | PixError::NotClaimable
+                | PixError::SameAccount
+                | PixError::DocumentMismatch
+                | PixError::VerificationRequired
+                | PixError::VerificationInvalid
+                | PixError::ChargeNotPayable
+                | PixError::ChargeMismatch => StatusCode::UNPROCESSABLE_ENTITY,
+                PixError::TooManyVerifications => StatusCode::TOO_MANY_REQUESTS,
+            },
+            AppError::Boleto(err) => match err {
+                BoletoError::NotFound => StatusCode::NOT_FOUND,
+                BoletoError::NotOpen => StatusCode::CONFLICT,
+                BoletoError::Overdue | BoletoError::SameAccount => StatusCode::UNPROCESSABLE_ENTITY,
+            },
+            AppError::Business(err) => match err {
+                BusinessError::NotBusiness | BusinessError::PermissionDenied => {
+                    StatusCode::FORBIDDEN
+                }
+                BusinessError::OperatorNotFound => StatusCode::NOT_FOUND,
+                BusinessError::OperatorAlreadyExists => StatusCode::CONFLICT,
+                BusinessError::InvalidOperator => StatusCode::UNPROCESSABLE_ENTITY,
+            },
+            AppError::Kyc(err) => match err {
+                KycError::NotFound | KycError::DocumentNotFound => StatusCode::NOT_FOUND,
+                KycError::NotApproved => StatusCode::FORBIDDEN,
+                KycError::InvalidStatus => StatusCode::CONFLICT,
+            },
+            AppError::ScheduledTransfer(err) => match err {
+                ScheduledTransferError::NotFound => StatusCode::NOT_FOUND,
+                ScheduledTransferError::NotActive => StatusCode::CONFLICT,
+            },
+            AppError::Hold(err) => match err {
+                HoldError::NotFound => StatusCode::NOT_FOUND,
+                HoldError::NotActive | HoldError::Expired => StatusCode::CONFLICT,
+                HoldError::CaptureExceedsHold => StatusCode::UNPROCESSABLE_ENTITY,
+            },
+            AppError::Reversal(err) => match err {
+                ReversalError::TransactionNotFound => StatusCode::NOT_FOUND,
+                ReversalError::AlreadyReversed => StatusCode::CONFLICT,
+                ReversalError::NotReversible | ReversalError::ExceedsRemaining => {
+                    StatusCode::UNPROCESSABLE_ENTITY
+                }
+            },
+            AppError::Fee(err) => match err {
+                FeeError::WaiverNotFound => StatusCode::NOT_FOUND,
+                FeeError::WaiverRevoked => StatusCode::CONFLICT,
+            },
+            AppError::Fx(err) => match err {
+                FxError::QuoteNotFound => StatusCode::NOT_FOUND,
+                FxError::QuoteExpired | FxError::QuoteAlreadyUsed => StatusCode::CONFLICT,
+                FxError::QuoteMismatch
+                | FxError::QuoteRequired
+                | FxError::SameCurrency
+                | FxError::RateUnavailable
+                | FxError::AmountTooSmall
+                | FxError::CurrencyNotSupported => StatusCode::UNPROCESSABLE_ENTITY,
+            },
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized | AppError::TokenExpired => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden => StatusCode::FORBIDDEN,
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        // o detalhe de erros internos fica só no log; o cliente recebe o código
+        if self.status_code().is_server_error() {
+            tracing::error!(code = self.code(), error = %self, "internal error");
+        }
+
+        // a mensagem vem do catálogo do idioma da requisição; o código é estável
+        let locale = current_locale();
+        let mut body = ApiResponse::<()>::error(locale.message(self.code()), self.code());
+
+        if let Some(errors) = self.field_errors() {
+            let localized: Vec<FieldError> = errors
+                .errors()
+                .iter()
+                .map(|err| FieldError {
+                    message: locale.field_message(err),
+                    ..err.clone()
+                })
+                .collect();
+            body = body.with_errors(&localized);
+        }
+        if let Some(context) = current_request() {
+            body = body.with_request(&context);
+        }
+
+        HttpResponse::build(self.status_code()).json(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::{Locale, with_locale};
+    use actix_web::body::to_bytes;
+
+    #[test]
+    fn test_status_codes() {
+        assert_eq!(
+            AppError::from(UserError::InvalidName).status_code(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            AppError::from(UserError::EmailAlreadyExists).status_code(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            AppError::from(UserError::NotFound).status_code(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            AppError::from(TransactionError::InsufficientFunds).status_code(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            AppError::Unauthorized.status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_response_envelope() {
+        let response = AppError::from(UserError::EmailAlreadyExists).error_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["sucess"], false);
+        assert_eq!(json["error"], "EMAIL_ALREADY_EXISTS");
+        assert_eq!(json["message"], "Email já existe no sistema");
+    }
+
+    #[tokio::test]
+    async fn test_json_missing_field_is_reported() {
+        let json_err = serde_json::from_str::<crate::models::CreateUser>(r#"{"email": "a@b.com"}"#)
+            .unwrap_err();
+        let error = AppError::from(JsonPayloadError::Deserialize(json_err));
+        let response = with_locale(Locale::En, async { error.error_response() }).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "VALIDATION_ERROR");
+        assert_eq!(json["errors"][0]["field"], "name");
+        assert_eq!(json["errors"][0]["code"], "REQUIRED");
+        assert_eq!(json["errors"][0]["constraint"], "required");
+        assert_eq!(json["errors"][0]["message"], "Required field is missing");
+        assert_eq!(json["message"], "Invalid data");
+    }
+
+    #[tokio::test]
+    async fn test_internal_error_hides_details() {
+        let response = AppError::Database(sqlx::Error::PoolTimedOut).error_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "DATABASE_ERROR");
+        assert_eq!(json["message"], "Erro interno do servidor");
+    }
+}
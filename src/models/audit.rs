@@ -0,0 +1,435 @@
+use std::{
+    fmt::Write as _,
+    future::{Ready, ready},
+};
+
+use actix_web::{FromRequest, HttpMessage, HttpRequest, dev::Payload, http::header::USER_AGENT};
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{models::claims::Claims, telemetry::RequestContext};
+
+/// `prev_hash` do primeiro evento da cadeia
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Partições da cadeia de hashes; cada uma tem o próprio lock de escrita
+pub const AUDIT_CHAINS: i16 = 16;
+
+/// Ações auditadas; o texto (`as_str`) é o que fica gravado em `action`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    LoginSucceeded,
+    LoginFailed,
+    TokenRefreshed,
+    UserUpdated,
+    UserDeactivated,
+    AccountOpened,
+    AccountDeactivated,
+    LimitsUpdated,
+    Deposit,
+    Withdraw,
+    Transfer,
+    WebhookCreated,
+    WebhookDeleted,
+    WebhookReplayed,
+    PixKeyRegistered,
+    PixKeyDeleted,
+    PixClaimOpened,
+    PixClaimCompleted,
+    PixClaimCancelled,
+    BoletoIssued,
+    BoletoCancelled,
+    BoletoPaid,
+    OperatorAdded,
+    OperatorUpdated,
+    OperatorRemoved,
+    KycDocumentUploaded,
+    KycSubmitted,
+    KycReviewStarted,
+    KycApproved,
+    KycRejected,
+    TransferScheduled,
+    ScheduledTransferCancelled,
+    HoldPlaced,
+    HoldCaptured,
+    HoldReleased,
+    HoldExpired,
+    TransactionReversed,
+    ReversalRecovered,
+    FeeWaiverGranted,
+    FeeWaiverRevoked,
+    InterestCredited,
+    OverdraftInterestCharged,
+    OverdraftLimitUpdated,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::LoginSucceeded => "auth.login_succeeded",
+            AuditAction::LoginFailed => "auth.login_failed",
+            AuditAction::TokenRefreshed => "auth.token_refreshed",
+            AuditAction::UserUpdated => "user.updated",
+            AuditAction::UserDeactivated => "user.deactivated",
+            AuditAction::AccountOpened => "account.opened",
+            AuditAction::AccountDeactivated => "account.deactivated",
+            AuditAction::LimitsUpdated => "account.limits_updated",
+            AuditAction::Deposit => "money.deposit",
+            AuditAction::Withdraw => "money.withdraw",
+            AuditAction::Transfer => "money.transfer",
+            AuditAction::WebhookCreated => "webhook.created",
+            AuditAction::WebhookDeleted => "webhook.deleted",
+            AuditAction::WebhookReplayed => "webhook.replayed",
+            AuditAction::PixKeyRegistered => "pix.key_registered",
+            AuditAction::PixKeyDeleted => "pix.key_deleted",
+            AuditAction::PixClaimOpened => "pix.claim_opened",
+            AuditAction::PixClaimCompleted => "pix.claim_completed",
+            AuditAction::PixClaimCancelled => "pix.claim_cancelled",
+            AuditAction::BoletoIssued => "boleto.issued",
+            AuditAction::BoletoCancelled => "boleto.cancelled",
+            AuditAction::BoletoPaid => "boleto.paid",
+            AuditAction::OperatorAdded => "business.operator_added",
+            AuditAction::OperatorUpdated => "business.operator_updated",
+            AuditAction::OperatorRemoved => "business.operator_removed",
+            AuditAction::KycDocumentUploaded => "kyc.document_uploaded",
+            AuditAction::KycSubmitted => "kyc.submitted",
+            AuditAction::KycReviewStarted => "kyc.review_started",
+            AuditAction::KycApproved => "kyc.approved",
+            AuditAction::KycRejected => "kyc.rejected",
+            AuditAction::TransferScheduled => "schedule.created",
+            AuditAction::ScheduledTransferCancelled => "schedule.cancelled",
+            AuditAction::HoldPlaced => "hold.placed",
+            AuditAction::HoldCaptured => "hold.captured",
+            AuditAction::HoldReleased => "hold.released",
+            AuditAction::HoldExpired => "hold.expired",
+            AuditAction::TransactionReversed => "money.reversed",
+            AuditAction::ReversalRecovered => "money.reversal_recovered",
+            AuditAction::FeeWaiverGranted => "fee.waiver_granted",
+            AuditAction::FeeWaiverRevoked => "fee.waiver_revoked",
+            AuditAction::InterestCredited => "account.interest_credited",
+            AuditAction::OverdraftInterestCharged => "account.overdraft_interest_charged",
+            AuditAction::OverdraftLimitUpdated => "account.overdraft_limit_updated",
+        }
+    }
+}
+
+/// Quem fez e de onde: extraído da requisição pelos handlers
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub actor_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<String>,
+}
+
+impl AuditContext {
+    /// Usado quando o ator só é conhecido depois (ex.: login)
+    pub fn with_actor(mut self, actor_id: Uuid) -> Self {
+        self.actor_id = Some(actor_id);
+        self
+    }
+
+    pub fn event(
+        &self,
+        action: AuditAction,
+        target_type: &'static str,
+        target_id: Option<Uuid>,
+    ) -> NewAuditEvent {
+        NewAuditEvent {
+            id: Uuid::new_v4(),
+            // o Postgres guarda microssegundos; o hash precisa ver o mesmo valor
+            occurred_at: Utc::now().trunc_subsecs(6),
+            actor_id: self.actor_id,
+            action: action.as_str(),
+            target_type,
+            target_id,
+            ip: self.ip.clone(),
+            user_agent: self.user_agent.clone(),
+            request_id: self.request_id.clone(),
+            before: None,
+            after: None,
+            metadata: None,
+        }
+    }
+}
+
+impl FromRequest for AuditContext {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let extensions = req.extensions();
+        let actor_id = extensions
+            .get::<Claims>()
+            .and_then(|claims| Uuid::parse_str(&claims.sub).ok());
+        let request_id = extensions
+            .get::<RequestContext>()
+            .map(|context| context.request_id.clone());
+
+        ready(Ok(AuditContext {
+            actor_id,
+            // endereço do socket: headers como X-Forwarded-For podem ser forjados
+            ip: req.peer_addr().map(|addr| addr.ip().to_string()),
+            user_agent: req
+                .headers()
+                .get(USER_AGENT)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+            request_id,
+        }))
+    }
+}
+
+/// Evento ainda não gravado; o hash é calculado na inserção
+#[derive(Debug, Clone)]
+pub struct NewAuditEvent {
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub actor_id: Option<Uuid>,
+    pub action: &'static str,
+    pub target_type: &'static str,
+    pub target_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub metadata: Option<Value>,
+}
+
+impl NewAuditEvent {
+    pub fn before(mut self, snapshot: Value) -> Self {
+        self.before = Some(snapshot);
+        self
+    }
+
+    pub fn after(mut self, snapshot: Value) -> Self {
+        self.after = Some(snapshot);
+        self
+    }
+
+    pub fn metadata(mut self, metadata: Value) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub fn hash(&self, prev_hash: &str) -> String {
+        HashedFields {
+            id: self.id,
+            occurred_at: self.occurred_at,
+            actor_id: self.actor_id,
+            action: self.action,
+            target_type: self.target_type,
+            target_id: self.target_id,
+            ip: self.ip.as_deref(),
+            user_agent: self.user_agent.as_deref(),
+            request_id: self.request_id.as_deref(),
+            before: self.before.as_ref(),
+            after: self.after.as_ref(),
+            metadata: self.metadata.as_ref(),
+        }
+        .hash(prev_hash)
+    }
+}
+
+/// Evento gravado, como devolvido pela API de consulta
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AuditEvent {
+    pub seq: i64,
+    /// Partição da cadeia; `prev_hash` é o do evento anterior nela
+    pub chain: i16,
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<String>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub metadata: Option<Value>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEvent {
+    /// Recalcula o hash a partir do conteúdo gravado
+    pub fn recompute_hash(&self) -> String {
+        HashedFields {
+            id: self.id,
+            occurred_at: self.occurred_at,
+            actor_id: self.actor_id,
+            action: &self.action,
+            target_type: &self.target_type,
+            target_id: self.target_id,
+            ip: self.ip.as_deref(),
+            user_agent: self.user_agent.as_deref(),
+            request_id: self.request_id.as_deref(),
+            before: self.before.as_ref(),
+            after: self.after.as_ref(),
+            metadata: self.metadata.as_ref(),
+        }
+        .hash(&self.prev_hash)
+    }
+}
+
+/// Campos cobertos pelo hash, iguais na gravação e na verificação
+struct HashedFields<'a> {
+    id: Uuid,
+    occurred_at: DateTime<Utc>,
+    actor_id: Option<Uuid>,
+    action: &'a str,
+    target_type: &'a str,
+    target_id: Option<Uuid>,
+    ip: Option<&'a str>,
+    user_agent: Option<&'a str>,
+    request_id: Option<&'a str>,
+    before: Option<&'a Value>,
+    after: Option<&'a Value>,
+    metadata: Option<&'a Value>,
+}
+
+impl HashedFields<'_> {
+    /// `sha256(prev_hash || "\n" || json canônico)`, em hexadecimal
+    fn hash(&self, prev_hash: &str) -> String {
+        let payload = json!({
+            "id": self.id,
+            "occurred_at": self.occurred_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            "actor_id": self.actor_id,
+            "action": self.action,
+            "target_type": self.target_type,
+            "target_id": self.target_id,
+            "ip": self.ip,
+            "user_agent": self.user_agent,
+            "request_id": self.request_id,
+            "before": self.before,
+            "after": self.after,
+            "metadata": self.metadata,
+        });
+
+        let mut hasher = Sha256::new();
+        hasher.update(prev_hash.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(canonical_json(&payload).as_bytes());
+        hasher
+            .finalize()
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            })
+    }
+}
+
+/// JSON com chaves ordenadas em todos os níveis: o JSONB do Postgres não
+/// preserva a ordem original, então o hash não pode depender dela
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|key| {
+                    format!(
+                        "{}:{}",
+                        Value::String(key.clone()),
+                        canonical_json(&map[key])
+                    )
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Filtros da consulta administrativa (`GET /admin/audit`)
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct AuditQuery {
+    pub actor_id: Option<Uuid>,
+    /// Ex.: `auth.login_failed`, `money.transfer`
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<Uuid>,
+    /// Início do período (inclusivo)
+    pub from: Option<DateTime<Utc>>,
+    /// Fim do período (exclusivo)
+    pub to: Option<DateTime<Utc>>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+/// Resultado da verificação da cadeia de hashes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChainVerification {
+    pub valid: bool,
+    pub checked: u64,
+    /// Primeiro evento (`seq`) cujo hash ou encadeamento não confere
+    pub broken_at: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored(event: &NewAuditEvent, prev_hash: &str, seq: i64) -> AuditEvent {
+        AuditEvent {
+            seq,
+            chain: 0,
+            id: event.id,
+            occurred_at: event.occurred_at,
+            actor_id: event.actor_id,
+            action: event.action.to_string(),
+            target_type: event.target_type.to_string(),
+            target_id: event.target_id,
+            ip: event.ip.clone(),
+            user_agent: event.user_agent.clone(),
+            request_id: event.request_id.clone(),
+            before: event.before.clone(),
+            after: event.after.clone(),
+            metadata: event.metadata.clone(),
+            prev_hash: prev_hash.to_string(),
+            hash: event.hash(prev_hash),
+        }
+    }
+
+    #[test]
+    fn test_hash_is_stable_and_detects_tampering() {
+        let context = AuditContext {
+            actor_id: Some(Uuid::new_v4()),
+            ip: Some("127.0.0.1".into()),
+            ..Default::default()
+        };
+        let event = context
+            .event(AuditAction::Deposit, "account", Some(Uuid::new_v4()))
+            .before(json!({ "balance": "0.00" }))
+            .after(json!({ "balance": "10.00", "account_number": "0000000001" }));
+
+        let mut row = stored(&event, GENESIS_HASH, 1);
+        assert_eq!(row.hash.len(), 64);
+        assert_eq!(row.recompute_hash(), row.hash);
+
+        row.after = Some(json!({ "balance": "9999.00", "account_number": "0000000001" }));
+        assert_ne!(row.recompute_hash(), row.hash);
+    }
+
+    #[test]
+    fn test_canonical_json_ignores_key_order() {
+        let a: Value = serde_json::from_str(r#"{"b":1,"a":{"d":[1,2],"c":"x"}}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"a":{"c":"x","d":[1,2]},"b":1}"#).unwrap();
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+        assert_eq!(canonical_json(&a), r#"{"a":{"c":"x","d":[1,2]},"b":1}"#);
+    }
+}
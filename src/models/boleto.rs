@@ -0,0 +1,415 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{models::account::Account, validators::FieldError};
+
+/// Código de compensação do banco (fictício, fora da tabela da Febraban)
+pub const BANK_CODE: &str = "999";
+
+/// Código de moeda do boleto para o real
+const CURRENCY_CODE: &str = "9";
+
+/// Maior valor que cabe nos 10 dígitos do código de barras (R$ 99.999.999,99)
+pub const MAX_AMOUNT: Decimal = Decimal::from_parts(1_410_065_407, 2, 0, false, 2);
+
+/// Vencimento mais distante aceito na emissão
+pub const MAX_DUE_DAYS: i64 = 5 * 365;
+
+/// O fator de vencimento volta a 1000 depois de 9999: 1000 é 22/02/2025
+const FACTOR_MIN: i64 = 1000;
+const FACTOR_CYCLE: i64 = 9000;
+
+fn factor_base() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1997, 10, 7).expect("data-base do fator de vencimento")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum BoletoStatus {
+    Open,
+    Paid,
+    Cancelled,
+}
+
+/// Emissão de boleto para receber em uma conta do usuário
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateBoleto {
+    pub account_id: Uuid,
+    pub amount: Decimal,
+    #[schema(example = "2025-10-10")]
+    pub due_date: NaiveDate,
+    #[schema(example = "Maria da Silva")]
+    pub payer_name: String,
+    /// Instruções impressas no boleto
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Boleto {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub user_id: Uuid,
+    /// "Nosso número": sequencial do banco, parte do campo livre
+    pub our_number: String,
+    pub amount: Decimal,
+    pub due_date: NaiveDate,
+    pub payer_name: String,
+    pub description: Option<String>,
+    /// 44 dígitos, na ordem do código de barras
+    pub barcode: String,
+    /// 47 dígitos, sem a pontuação
+    pub digitable_line: String,
+    pub status: BoletoStatus,
+    pub payment_transaction_id: Option<Uuid>,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Boleto {
+    /// Monta o boleto; o campo livre é o nosso número seguido do número da conta
+    pub fn issue(account: &Account, request: &CreateBoleto, our_number: i64) -> Self {
+        let now = Utc::now();
+        let our_number = format!("{our_number:015}");
+        let barcode = BoletoBarcode::new(
+            BANK_CODE,
+            Some(request.due_date),
+            Some(request.amount),
+            &format!("{our_number}{}", account.account_number),
+        );
+        Self {
+            id: Uuid::new_v4(),
+            account_id: account.id,
+            user_id: account.user_id,
+            our_number,
+            amount: request.amount,
+            due_date: request.due_date,
+            payer_name: request.payer_name.trim().to_string(),
+            description: request
+                .description
+                .as_deref()
+                .map(str::trim)
+                .filter(|description| !description.is_empty())
+                .map(str::to_string),
+            digitable_line: barcode.digitable_line(),
+            barcode: barcode.0,
+            status: BoletoStatus::Open,
+            payment_transaction_id: None,
+            paid_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn audit_snapshot(&self) -> Value {
+        serde_json::json!({
+            "account_id": self.account_id,
+            "our_number": self.our_number,
+            "amount": self.amount,
+            "due_date": self.due_date,
+            "status": self.status,
+        })
+    }
+}
+
+/// Linha digitável ou código de barras lido/colado pelo pagador
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ParseBoleto {
+    #[schema(example = "00190.50095 40144.816069 06809.350314 3 37370000000100")]
+    pub line: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ParsedBoleto {
+    pub bank_code: String,
+    pub barcode: String,
+    /// Com a pontuação usual: `AAABC.CCCCX DDDDD.DDDDDY EEEEE.EEEEEZ K UUUUVVVVVVVVVV`
+    pub digitable_line: String,
+    /// Ausente quando o boleto não tem vencimento (fator zero)
+    pub due_date: Option<NaiveDate>,
+    /// Ausente quando o valor é informado pelo pagador
+    pub amount: Option<Decimal>,
+    /// Emitido por este banco: o pagamento credita a conta do beneficiário na hora
+    pub issued_here: bool,
+}
+
+/// Pagamento de boleto com débito na conta; `amount` só é usado quando o boleto não traz valor
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PayBoleto {
+    pub account_id: Uuid,
+    #[schema(example = "00190500954014481606906809350314337370000000100")]
+    pub line: String,
+    pub amount: Option<Decimal>,
+    pub description: Option<String>,
+}
+
+/// Código de barras de 44 dígitos no padrão Febraban (boleto de cobrança)
+///
+/// Posições: banco (3), moeda (1), DV geral (1), fator de vencimento (4),
+/// valor em centavos (10) e campo livre (25).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoletoBarcode(String);
+
+impl BoletoBarcode {
+    /// `None` em vencimento ou valor grava zeros (sem vencimento / valor livre);
+    /// `free_field` tem 25 dígitos
+    pub fn new(
+        bank_code: &str,
+        due_date: Option<NaiveDate>,
+        amount: Option<Decimal>,
+        free_field: &str,
+    ) -> Self {
+        let factor = due_date.map(due_date_factor).unwrap_or_default();
+        let cents = amount
+            .and_then(|amount| (amount.round_dp(2) * Decimal::ONE_HUNDRED).to_u64())
+            .unwrap_or_default();
+        let without_dv = format!("{bank_code}{CURRENCY_CODE}{factor:04}{cents:010}{free_field}");
+        let dv = modulo11(&without_dv);
+        Self(format!("{}{dv}{}", &without_dv[..4], &without_dv[4..]))
+    }
+
+    /// Aceita a linha digitável (47 dígitos) ou o código de barras (44), com ou
+    /// sem pontuação, conferindo os dígitos verificadores
+    pub fn parse(raw: &str) -> Result<Self, FieldError> {
+        let error = |constraint: &str| invalid_boleto().with_constraint(constraint.to_string());
+
+        if raw
+            .chars()
+            .any(|c| !c.is_ascii_digit() && !matches!(c, ' ' | '.' | '-'))
+        {
+            return Err(error("format"));
+        }
+        let digits: String = raw.chars().filter(char::is_ascii_digit).collect();
+        // boletos de arrecadação (contas de consumo, tributos) começam com 8 e têm outro layout
+        if digits.starts_with('8') {
+            return Err(error("unsupported"));
+        }
+
+        let barcode = match digits.len() {
+            44 => digits,
+            47 => {
+                let fields = [(0, 9), (10, 20), (21, 31)];
+                for (start, end) in fields {
+                    let dv = modulo10(&digits[start..end]);
+                    if digits.as_bytes()[end] != b'0' + dv {
+                        return Err(error("check_digit"));
+                    }
+                }
+                format!(
+                    "{}{}{}{}{}{}",
+                    &digits[0..4],
+                    &digits[32..33],
+                    &digits[33..47],
+                    &digits[4..9],
+                    &digits[10..20],
+                    &digits[21..31],
+                )
+            }
+            _ => return Err(error("format")),
+        };
+
+        if &barcode[3..4] != CURRENCY_CODE {
+            return Err(error("currency"));
+        }
+        let without_dv = format!("{}{}", &barcode[..4], &barcode[5..]);
+        if barcode.as_bytes()[4] != b'0' + modulo11(&without_dv) {
+            return Err(error("check_digit"));
+        }
+        Ok(Self(barcode))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    pub fn bank_code(&self) -> &str {
+        &self.0[..3]
+    }
+
+    pub fn amount(&self) -> Option<Decimal> {
+        let cents: i64 = self.0[9..19].parse().ok()?;
+        (cents > 0).then(|| Decimal::new(cents, 2))
+    }
+
+    /// O fator se repete a cada 9000 dias; vale a data mais próxima de `today`
+    pub fn due_date(&self, today: NaiveDate) -> Option<NaiveDate> {
+        let factor: i64 = self.0[5..9].parse().ok()?;
+        if factor < FACTOR_MIN {
+            return None;
+        }
+        let first = factor_base() + Duration::days(factor);
+        let cycles = ((today - first).num_days() as f64 / FACTOR_CYCLE as f64).round() as i64;
+        Some(first + Duration::days(cycles.max(0) * FACTOR_CYCLE))
+    }
+
+    /// Linha digitável: três campos do campo livre com DV módulo 10, o DV geral,
+    /// o fator e o valor
+    pub fn digitable_line(&self) -> String {
+        let code = &self.0;
+        let field1 = format!("{}{}", &code[0..4], &code[19..24]);
+        let field2 = &code[24..34];
+        let field3 = &code[34..44];
+        format!(
+            "{field1}{}{field2}{}{field3}{}{}{}",
+            modulo10(&field1),
+            modulo10(field2),
+            modulo10(field3),
+            &code[4..5],
+            &code[5..19],
+        )
+    }
+
+    /// Linha digitável pontuada, como impressa no boleto
+    pub fn formatted_line(&self) -> String {
+        let line = self.digitable_line();
+        format!(
+            "{}.{} {}.{} {}.{} {} {}",
+            &line[0..5],
+            &line[5..10],
+            &line[10..15],
+            &line[15..21],
+            &line[21..26],
+            &line[26..32],
+            &line[32..33],
+            &line[33..47],
+        )
+    }
+
+    pub fn into_parsed(self, today: NaiveDate) -> ParsedBoleto {
+        ParsedBoleto {
+            bank_code: self.bank_code().to_string(),
+            digitable_line: self.formatted_line(),
+            due_date: self.due_date(today),
+            amount: self.amount(),
+            issued_here: self.bank_code() == BANK_CODE,
+            barcode: self.0,
+        }
+    }
+}
+
+/// Dias desde 07/10/1997; a partir de 22/02/2025 recomeça em 1000
+pub fn due_date_factor(due_date: NaiveDate) -> i64 {
+    let days = (due_date - factor_base()).num_days();
+    if days < FACTOR_MIN + FACTOR_CYCLE {
+        days
+    } else {
+        (days - FACTOR_MIN - FACTOR_CYCLE) % FACTOR_CYCLE + FACTOR_MIN
+    }
+}
+
+fn invalid_boleto() -> FieldError {
+    FieldError::new("line", "INVALID_BOLETO", "Linha digitável inválida")
+}
+
+/// DV módulo 10: pesos 2 e 1 da direita para a esquerda, somando os dígitos dos produtos
+fn modulo10(digits: &str) -> u8 {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .zip([2, 1].into_iter().cycle())
+        .map(|(digit, weight)| {
+            let product = (digit - b'0') as u32 * weight;
+            product / 10 + product % 10
+        })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// DV geral módulo 11: pesos 2 a 9 da direita para a esquerda; 0, 10 e 11 viram 1
+fn modulo11(digits: &str) -> u8 {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .zip((2..=9).cycle())
+        .map(|(digit, weight)| (digit - b'0') as u32 * weight)
+        .sum();
+    match 11 - sum % 11 {
+        0 | 10 | 11 => 1,
+        dv => dv as u8,
+    }
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum BoletoError {
+        #[error("Boleto não encontrado")]
+        NotFound,
+
+        #[error("Boleto já foi pago ou cancelado")]
+        NotOpen,
+
+        #[error("Boleto vencido")]
+        Overdue,
+
+        #[error("Boleto não pode ser pago com a própria conta beneficiária")]
+        SameAccount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Boleto do Banco do Brasil usado como exemplo em documentações da Febraban
+    const BARCODE: &str = "00193373700000001000500940144816060680935031";
+    const LINE: &str = "00190.50095 40144.816069 06809.350314 3 37370000000100";
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parses_line_and_barcode() {
+        let from_line = BoletoBarcode::parse(LINE).unwrap();
+        assert_eq!(from_line.as_str(), BARCODE);
+        assert_eq!(from_line, BoletoBarcode::parse(BARCODE).unwrap());
+        assert_eq!(from_line.formatted_line(), LINE);
+        assert_eq!(from_line.bank_code(), "001");
+        assert_eq!(from_line.amount(), Some(Decimal::new(100, 2)));
+        assert_eq!(
+            from_line.due_date(date(2007, 12, 1)),
+            Some(date(2007, 12, 31))
+        );
+    }
+
+    #[test]
+    fn test_rejects_wrong_check_digits() {
+        let constraint = |raw: &str| BoletoBarcode::parse(raw).unwrap_err().constraint.unwrap();
+        assert_eq!(
+            constraint("00190.50095 40144.816069 06809.350315 3 37370000000100"),
+            "check_digit"
+        );
+        assert_eq!(
+            constraint("00190.50095 40144.816069 06809.350314 3 37370000000200"),
+            "check_digit"
+        );
+        assert_eq!(constraint("0019050095"), "format");
+        assert_eq!(
+            constraint("836200000005 667800481000 180975657313 001589636081"),
+            "unsupported"
+        );
+    }
+
+    #[test]
+    fn test_due_date_factor_rolls_over() {
+        assert_eq!(due_date_factor(date(2025, 2, 21)), 9999);
+        assert_eq!(due_date_factor(date(2025, 2, 22)), 1000);
+
+        let code = BoletoBarcode::new(BANK_CODE, Some(date(2025, 10, 10)), None, &"0".repeat(25));
+        assert_eq!(code.due_date(date(2025, 9, 1)), Some(date(2025, 10, 10)));
+        assert_eq!(code.amount(), None);
+        assert_eq!(BoletoBarcode::parse(&code.digitable_line()), Ok(code));
+    }
+}
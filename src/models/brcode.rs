@@ -0,0 +1,472 @@
+use std::{fmt::Write as _, io::Cursor};
+
+use chrono::{DateTime, Utc};
+use image::{ImageFormat, Luma};
+use qrcode::{QrCode, render::svg};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    models::app_error::AppError,
+    validators::{FieldError, PixKeyValidator},
+};
+
+/// Identificador do arranjo PIX (GUI) no campo 26
+const PIX_GUI: &str = "br.gov.bcb.pix";
+
+/// Código ISO 4217 do real
+const CURRENCY_BRL: &str = "986";
+
+/// Limites de tamanho do padrão EMV MPM
+const MAX_NAME_LEN: usize = 25;
+const MAX_CITY_LEN: usize = 15;
+pub const MAX_TXID_LEN: usize = 25;
+const MAX_FIELD_LEN: usize = 99;
+
+/// Tamanho fixo do campo 26 sem a chave e a descrição (GUI e cabeçalhos dos subcampos)
+const ACCOUNT_INFO_OVERHEAD: usize = 4 + PIX_GUI.len() + 4 + 4;
+
+/// URL da cobrança no subcampo 26-25, sem o `https://`
+pub const MAX_LOCATION_LEN: usize = 77;
+
+/// O cadastro não tem endereço; a cidade do recebedor é a da sede do banco
+pub const MERCHANT_CITY: &str = "SAO PAULO";
+
+/// Estático (ponto de iniciação `11`) pode ser pago várias vezes; dinâmico
+/// (`12`) aponta para uma cobrança que vale para um único pagamento
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BrCodeKind {
+    #[default]
+    Static,
+    Dynamic,
+}
+
+/// Pedido de QR Code para receber em uma chave PIX do usuário
+///
+/// O dinâmico cria uma cobrança com valor e validade; o payload leva a URL
+/// dela (`GET /pix/cob/{id}`) no lugar da chave e a transferência que a paga
+/// informa o `txid`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateBrCode {
+    pub pix_key_id: Uuid,
+    #[serde(default)]
+    pub kind: BrCodeKind,
+    /// Sem valor, o pagador digita; obrigatório no dinâmico
+    pub amount: Option<Decimal>,
+    /// Identificador da cobrança (até 25 letras e dígitos); no dinâmico, gerado se omitido
+    #[schema(example = "PEDIDO12345")]
+    pub txid: Option<String>,
+    /// Mensagem mostrada ao pagador
+    pub description: Option<String>,
+}
+
+/// Formato da resposta de `POST /pix/qr`
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Json,
+    Png,
+    Svg,
+}
+
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct QrFormatQuery {
+    /// `json` (padrão) devolve o payload; `png` e `svg` devolvem a imagem
+    #[param(value_type = Option<QrFormat>)]
+    pub format: Option<QrFormat>,
+}
+
+/// Payload gerado ("PIX copia e cola")
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BrCodeResponse {
+    #[schema(example = "00020101021126...6304ABCD")]
+    pub payload: String,
+    pub kind: BrCodeKind,
+    pub key: String,
+    pub amount: Option<Decimal>,
+    pub txid: Option<String>,
+    /// URL da cobrança, só no dinâmico
+    pub location: Option<String>,
+    /// Fim da validade da cobrança, só no dinâmico
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Payload lido da câmera ou colado pelo usuário
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ParseBrCode {
+    pub payload: String,
+}
+
+/// Transferência pré-preenchida; sem `amount`, o pagador informa o valor
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferPrefill {
+    pub to_pix_key: String,
+    pub amount: Option<Decimal>,
+    pub description: String,
+    /// Cobrança paga pela transferência, só no dinâmico
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pix_txid: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ParsedBrCode {
+    pub kind: BrCodeKind,
+    pub merchant_name: String,
+    pub merchant_city: String,
+    pub txid: Option<String>,
+    /// Corpo para `POST /accounts/{id}/transfer`
+    pub transfer: TransferPrefill,
+}
+
+/// BR Code do PIX: payload EMV MPM (campos TLV) terminado pelo CRC16
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrCode {
+    pub kind: BrCodeKind,
+    /// No dinâmico lido de um payload fica vazia até a cobrança ser consultada
+    pub key: String,
+    /// URL da cobrança (subcampo 26-25), só no dinâmico
+    pub location: Option<String>,
+    pub amount: Option<Decimal>,
+    pub merchant_name: String,
+    pub merchant_city: String,
+    pub txid: Option<String>,
+    pub description: Option<String>,
+}
+
+impl BrCode {
+    /// Espaço que sobra para a descrição no campo 26 depois da chave
+    pub fn max_description_len(key: &str) -> usize {
+        MAX_FIELD_LEN.saturating_sub(ACCOUNT_INFO_OVERHEAD + key.len())
+    }
+
+    /// No dinâmico a chave, a descrição e o `txid` ficam na cobrança; o campo
+    /// 62-05 leva `***`, como pede o manual
+    pub fn payload(&self) -> String {
+        let mut account_info = tlv("00", PIX_GUI);
+        match (&self.kind, &self.location) {
+            (BrCodeKind::Dynamic, Some(location)) => account_info += &tlv("25", location),
+            _ => {
+                account_info += &tlv("01", &self.key);
+                if let Some(description) = &self.description {
+                    account_info += &tlv("02", &ascii_fold(description));
+                }
+            }
+        }
+        let (initiation, txid) = match self.kind {
+            BrCodeKind::Static => ("11", self.txid.as_deref().unwrap_or("***")),
+            BrCodeKind::Dynamic => ("12", "***"),
+        };
+
+        let mut payload = tlv("00", "01");
+        payload += &tlv("01", initiation);
+        payload += &tlv("26", &account_info);
+        payload += &tlv("52", "0000");
+        payload += &tlv("53", CURRENCY_BRL);
+        if let Some(amount) = self.amount {
+            payload += &tlv("54", &format!("{:.2}", amount.round_dp(2)));
+        }
+        payload += &tlv("58", "BR");
+        payload += &tlv(
+            "59",
+            &truncate(&ascii_fold(&self.merchant_name), MAX_NAME_LEN),
+        );
+        payload += &tlv(
+            "60",
+            &truncate(&ascii_fold(&self.merchant_city), MAX_CITY_LEN),
+        );
+        payload += &tlv("62", &tlv("05", txid));
+
+        payload += "6304";
+        let _ = write!(payload, "{:04X}", crc16(payload.as_bytes()));
+        payload
+    }
+
+    /// Lê um payload, conferindo o CRC, a moeda e a chave PIX (ou, no dinâmico,
+    /// a URL da cobrança)
+    pub fn parse(payload: &str) -> Result<Self, FieldError> {
+        let payload = payload.trim();
+        let error = |constraint: &str| invalid_brcode().with_constraint(constraint.to_string());
+
+        if !payload.is_ascii() || payload.len() < 8 {
+            return Err(error("format"));
+        }
+        let (body, crc) = payload.split_at(payload.len() - 4);
+        if !body.ends_with("6304") {
+            return Err(error("format"));
+        }
+        if !crc.eq_ignore_ascii_case(&format!("{:04X}", crc16(body.as_bytes()))) {
+            return Err(error("crc"));
+        }
+
+        // o próprio campo 63 fica de fora: o valor dele é o CRC já conferido
+        let fields = parse_tlv(&body[..body.len() - 4]).ok_or_else(|| error("format"))?;
+        let field = |id: &str| {
+            fields
+                .iter()
+                .find(|(field_id, _)| *field_id == id)
+                .map(|(_, value)| *value)
+        };
+        if field("00") != Some("01") {
+            return Err(error("format"));
+        }
+        if field("53").is_some_and(|currency| currency != CURRENCY_BRL) {
+            return Err(error("currency"));
+        }
+
+        // o campo 26 pode aparecer junto de outros arranjos (26 a 51); vale o do PIX
+        let account_info = fields
+            .iter()
+            .filter(|(id, _)| ("26"..="51").contains(id))
+            .filter_map(|(_, value)| parse_tlv(value))
+            .find(|sub| {
+                sub.iter()
+                    .any(|(id, value)| *id == "00" && value.eq_ignore_ascii_case(PIX_GUI))
+            })
+            .ok_or_else(|| error("pix"))?;
+        let sub = |id: &str| {
+            account_info
+                .iter()
+                .find(|(field_id, _)| *field_id == id)
+                .map(|(_, value)| value.to_string())
+        };
+        let kind = match field("01") {
+            Some("12") => BrCodeKind::Dynamic,
+            None | Some("11") => BrCodeKind::Static,
+            Some(_) => return Err(error("format")),
+        };
+        let (key, location) = match kind {
+            // a chave e o valor vêm da cobrança, consultada por quem lê
+            BrCodeKind::Dynamic => {
+                let location = sub("25")
+                    .filter(|location| !location.is_empty() && location.len() <= MAX_LOCATION_LEN)
+                    .ok_or_else(|| error("location"))?;
+                (String::new(), Some(location))
+            }
+            BrCodeKind::Static => {
+                let raw_key = sub("01").ok_or_else(|| error("key"))?;
+                let (_, key) =
+                    PixKeyValidator::parse("payload", &raw_key).map_err(|_| error("key"))?;
+                (key, None)
+            }
+        };
+
+        let amount = field("54")
+            .map(|raw| raw.parse::<Decimal>().map_err(|_| error("amount")))
+            .transpose()?;
+        let txid = field("62")
+            .and_then(parse_tlv)
+            .and_then(|additional| {
+                additional
+                    .into_iter()
+                    .find(|(id, _)| *id == "05")
+                    .map(|(_, value)| value.to_string())
+            })
+            .filter(|txid| txid != "***");
+
+        Ok(Self {
+            kind,
+            key,
+            location,
+            amount,
+            merchant_name: field("59").unwrap_or_default().to_string(),
+            merchant_city: field("60").unwrap_or_default().to_string(),
+            txid,
+            description: sub("02"),
+        })
+    }
+
+    pub fn into_parsed(self) -> ParsedBrCode {
+        let description = self
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("PIX {}", self.merchant_name).trim().to_string());
+        let pix_txid = match self.kind {
+            BrCodeKind::Dynamic => self.txid.clone(),
+            BrCodeKind::Static => None,
+        };
+        ParsedBrCode {
+            kind: self.kind,
+            merchant_name: self.merchant_name,
+            merchant_city: self.merchant_city,
+            txid: self.txid,
+            transfer: TransferPrefill {
+                to_pix_key: self.key,
+                amount: self.amount,
+                description,
+                pix_txid,
+            },
+        }
+    }
+}
+
+/// `txid` de uma cobrança dinâmica quando o recebedor não informa um
+pub fn generate_txid() -> String {
+    Uuid::new_v4().simple().to_string()[..MAX_TXID_LEN].to_uppercase()
+}
+
+/// QR Code do payload em PNG (escala de cinza, com margem)
+pub fn qr_png(payload: &str) -> Result<Vec<u8>, AppError> {
+    let code =
+        QrCode::new(payload.as_bytes()).map_err(|err| AppError::Internal(err.to_string()))?;
+    let image = code.render::<Luma<u8>>().min_dimensions(256, 256).build();
+
+    let mut png = Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|err| AppError::Internal(err.to_string()))?;
+    Ok(png.into_inner())
+}
+
+/// QR Code do payload em SVG
+pub fn qr_svg(payload: &str) -> Result<String, AppError> {
+    let code =
+        QrCode::new(payload.as_bytes()).map_err(|err| AppError::Internal(err.to_string()))?;
+    Ok(code.render::<svg::Color>().min_dimensions(256, 256).build())
+}
+
+fn invalid_brcode() -> FieldError {
+    FieldError::new("payload", "INVALID_BRCODE", "BR Code inválido")
+}
+
+fn tlv(id: &str, value: &str) -> String {
+    format!("{id}{:02}{value}", value.len())
+}
+
+/// Campos `ID(2) + tamanho(2) + valor`; `None` se a estrutura estiver quebrada
+fn parse_tlv(raw: &str) -> Option<Vec<(&str, &str)>> {
+    let mut fields = Vec::new();
+    let mut rest = raw;
+    while !rest.is_empty() {
+        let id = rest.get(..2)?;
+        let len: usize = rest.get(2..4)?.parse().ok()?;
+        let value = rest.get(4..4 + len)?;
+        fields.push((id, value));
+        rest = &rest[4 + len..];
+    }
+    Some(fields)
+}
+
+/// CRC16-CCITT (polinômio 0x1021, valor inicial 0xFFFF), como pede o BR Code
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in data {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// O payload só aceita ASCII: tira os acentos e descarta o que sobrar
+fn ascii_fold(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => Some('a'),
+            'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => Some('A'),
+            'é' | 'è' | 'ê' | 'ë' => Some('e'),
+            'É' | 'È' | 'Ê' | 'Ë' => Some('E'),
+            'í' | 'ì' | 'î' | 'ï' => Some('i'),
+            'Í' | 'Ì' | 'Î' | 'Ï' => Some('I'),
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => Some('o'),
+            'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => Some('O'),
+            'ú' | 'ù' | 'û' | 'ü' => Some('u'),
+            'Ú' | 'Ù' | 'Û' | 'Ü' => Some('U'),
+            'ç' => Some('c'),
+            'Ç' => Some('C'),
+            'ñ' => Some('n'),
+            'Ñ' => Some('N'),
+            c if c.is_ascii() && !c.is_ascii_control() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    text.chars()
+        .take(max)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exemplo do manual do BR Code do Banco Central
+    const MANUAL_EXAMPLE: &str = "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-4266554400005204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D";
+
+    #[test]
+    fn test_parses_manual_example() {
+        let code = BrCode::parse(MANUAL_EXAMPLE).unwrap();
+        assert_eq!(code.key, "123e4567-e12b-12d1-a456-426655440000");
+        assert_eq!(code.merchant_name, "Fulano de Tal");
+        assert_eq!(code.merchant_city, "BRASILIA");
+        assert_eq!(code.amount, None);
+        assert_eq!(code.txid, None);
+    }
+
+    #[test]
+    fn test_roundtrip_and_crc() {
+        let code = BrCode {
+            kind: BrCodeKind::Static,
+            key: "+5511987654321".into(),
+            location: None,
+            amount: Some("10.5".parse().unwrap()),
+            merchant_name: "João da Conceição".into(),
+            merchant_city: MERCHANT_CITY.into(),
+            txid: Some("PEDIDO123".into()),
+            description: Some("Almoço".into()),
+        };
+        let payload = code.payload();
+        assert!(payload.contains("010211"));
+        assert!(payload.contains("540510.50"));
+        assert!(payload.contains("5917Joao da Conceicao"));
+
+        let parsed = BrCode::parse(&payload).unwrap();
+        assert_eq!(parsed.amount, code.amount);
+        assert_eq!(parsed.txid.as_deref(), Some("PEDIDO123"));
+        assert_eq!(parsed.description.as_deref(), Some("Almoco"));
+
+        let mut tampered = payload.replace("10.50", "90.50");
+        let err = BrCode::parse(&tampered).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("crc"));
+        tampered.truncate(10);
+        assert!(BrCode::parse(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_dynamic_carries_location_instead_of_key() {
+        let location = "pix.exemplo.com.br/api/v1/pix/cob/0f1e2d3c4b5a69788796a5b4c3d2e1f0";
+        let code = BrCode {
+            kind: BrCodeKind::Dynamic,
+            key: "+5511987654321".into(),
+            location: Some(location.into()),
+            amount: Some("25".parse().unwrap()),
+            merchant_name: "Maria".into(),
+            merchant_city: MERCHANT_CITY.into(),
+            txid: Some(generate_txid()),
+            description: Some("Pedido".into()),
+        };
+        let payload = code.payload();
+        assert!(payload.contains("010212"));
+        assert!(payload.contains(&format!("2566{location}")));
+        assert!(!payload.contains("+5511987654321"));
+        assert!(payload.contains("62070503***"));
+
+        let parsed = BrCode::parse(&payload).unwrap();
+        assert_eq!(parsed.kind, BrCodeKind::Dynamic);
+        assert_eq!(parsed.location.as_deref(), Some(location));
+        assert_eq!(parsed.key, "");
+        assert_eq!(parsed.txid, None);
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::Document;
+
+/// O que um operador pode fazer nas contas da empresa
+///
+/// Qualquer permissão já dá acesso de leitura (`view`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum OperatorPermission {
+    /// Saldo, detalhes e extrato
+    View,
+    /// Depósito, saque, transferência e boletos
+    Transact,
+    /// Encerrar contas e gerenciar chaves PIX
+    Manage,
+}
+
+impl OperatorPermission {
+    /// Se as permissões concedidas cobrem a exigida
+    pub fn allows(granted: &[OperatorPermission], required: OperatorPermission) -> bool {
+        match required {
+            OperatorPermission::View => !granted.is_empty(),
+            required => granted.contains(&required),
+        }
+    }
+}
+
+/// Autoriza uma pessoa física, pelo email de login, a operar as contas da empresa
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddOperator {
+    #[schema(example = "maria.souza@email.com")]
+    pub email: String,
+    #[schema(example = json!(["view", "transact"]))]
+    pub permissions: Vec<OperatorPermission>,
+}
+
+/// Troca as permissões de um operador
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateOperator {
+    #[schema(example = json!(["view"]))]
+    pub permissions: Vec<OperatorPermission>,
+}
+
+/// Operador de uma empresa, com os dados de login dele
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct BusinessOperator {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    #[schema(value_type = Option<String>, example = "***.982.247-**")]
+    pub document: Option<Document>,
+    pub permissions: Vec<OperatorPermission>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BusinessOperator {
+    /// Estado relevante para auditoria
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "business_id": self.business_id,
+            "user_id": self.user_id,
+            "permissions": self.permissions,
+        })
+    }
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum BusinessError {
+        #[error("Operação disponível só para clientes empresa")]
+        NotBusiness,
+
+        #[error("Operador não encontrado")]
+        OperatorNotFound,
+
+        #[error("Usuário já é operador da empresa")]
+        OperatorAlreadyExists,
+
+        #[error("Operador deve ser uma pessoa física ativa")]
+        InvalidOperator,
+
+        #[error("Operador sem permissão para esta operação")]
+        PermissionDenied,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_permission_allows_view() {
+        use OperatorPermission::*;
+
+        assert!(OperatorPermission::allows(&[Transact], View));
+        assert!(OperatorPermission::allows(&[View, Manage], Manage));
+        assert!(!OperatorPermission::allows(&[View], Transact));
+        assert!(!OperatorPermission::allows(&[], View));
+    }
+}
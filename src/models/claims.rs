@@ -2,6 +2,9 @@ use std::future::{Ready, ready};
 
 use actix_web::{FromRequest, HttpMessage};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::{UserRole, app_error::AppError};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
@@ -9,15 +12,43 @@ pub struct Claims {
     pub exp: usize,
     pub iat: usize,
     pub email: String,
+    /// Idioma preferido do usuário, se ele escolheu um
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Tokens emitidos antes do papel existir valem como `customer`
+    #[serde(default)]
+    pub role: UserRole,
 }
 
 impl Claims {
-    pub fn new(sub: String, exp: usize, iat: usize, email: String) -> Self {
+    pub fn new(
+        sub: String,
+        exp: usize,
+        iat: usize,
+        email: String,
+        locale: Option<String>,
+        role: UserRole,
+    ) -> Self {
         Self {
             sub,
             exp,
             iat,
             email,
+            locale,
+            role,
+        }
+    }
+
+    /// Id do usuário dono do token
+    pub fn user_id(&self) -> Result<Uuid, AppError> {
+        Uuid::parse_str(&self.sub).map_err(|_| AppError::Unauthorized)
+    }
+
+    /// Barra quem não é administrador (403)
+    pub fn require_admin(&self) -> Result<(), AppError> {
+        match self.role {
+            UserRole::Admin => Ok(()),
+            UserRole::Customer => Err(AppError::Forbidden),
         }
     }
 }
@@ -32,7 +63,7 @@ impl FromRequest for Claims {
     ) -> Self::Future {
         match req.extensions().get::<Claims>() {
             Some(claims) => ready(Ok(claims.clone())),
-            None => ready(Err(actix_web::error::ErrorUnauthorized("Ivalid Claims"))),
+            None => ready(Err(AppError::Unauthorized.into())),
         }
     }
 }
@@ -0,0 +1,112 @@
+use std::{fmt, str::FromStr};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Moeda ISO 4217 de contas e lançamentos
+///
+/// As colunas de valor guardam 2 casas, então só há moedas com até 2 unidades
+/// menores. Tarifas e boletos existem só em reais.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "UPPERCASE")]
+#[sqlx(type_name = "varchar", rename_all = "UPPERCASE")]
+pub enum Currency {
+    #[default]
+    Brl,
+    Usd,
+    Eur,
+    Gbp,
+    Chf,
+    Cad,
+    Mxn,
+    Ars,
+    Jpy,
+    Clp,
+}
+
+impl Currency {
+    pub const ALL: [Currency; 10] = [
+        Currency::Brl,
+        Currency::Usd,
+        Currency::Eur,
+        Currency::Gbp,
+        Currency::Chf,
+        Currency::Cad,
+        Currency::Mxn,
+        Currency::Ars,
+        Currency::Jpy,
+        Currency::Clp,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Brl => "BRL",
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Chf => "CHF",
+            Currency::Cad => "CAD",
+            Currency::Mxn => "MXN",
+            Currency::Ars => "ARS",
+            Currency::Jpy => "JPY",
+            Currency::Clp => "CLP",
+        }
+    }
+
+    /// Casas decimais da moeda (ISO 4217)
+    pub fn minor_units(&self) -> u32 {
+        match self {
+            Currency::Jpy | Currency::Clp => 0,
+            _ => 2,
+        }
+    }
+
+    /// Arredonda para a menor unidade da moeda (arredondamento bancário)
+    pub fn round(&self, amount: Decimal) -> Decimal {
+        amount.round_dp(self.minor_units())
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let code = raw.trim().to_ascii_uppercase();
+        Currency::ALL
+            .into_iter()
+            .find(|currency| currency.code() == code)
+            .ok_or_else(|| format!("moeda não suportada: {raw}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minor_units_and_parsing() {
+        assert_eq!(
+            Currency::Jpy.round(Decimal::new(15055, 2)),
+            Decimal::from(151)
+        );
+        assert_eq!(
+            Currency::Usd.round(Decimal::new(12345, 3)),
+            Decimal::new(1234, 2)
+        );
+        assert_eq!(" usd ".parse::<Currency>(), Ok(Currency::Usd));
+        assert!("KWD".parse::<Currency>().is_err());
+        assert_eq!(
+            serde_json::to_value(Currency::Brl).unwrap(),
+            serde_json::json!("BRL")
+        );
+    }
+}
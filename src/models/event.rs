@@ -0,0 +1,309 @@
+use chrono::{DateTime, NaiveDate, SubsecRound, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::prelude::FromRow;
+use uuid::Uuid;
+
+use crate::models::{
+    account::AccountType, currency::Currency, fee::FeeOperation, kyc::KycStatus,
+    reversal::ReversalReason, transaction::Transaction,
+};
+
+/// Todos os valores possíveis de `DomainEvent::event_type`
+pub const EVENT_TYPES: &[&str] = &[
+    "UserRegistered",
+    "UserDeactivated",
+    "AccountOpened",
+    "AccountClosed",
+    "DepositCompleted",
+    "WithdrawalCompleted",
+    "TransferCompleted",
+    "BoletoPaid",
+    "KycReviewed",
+    "HoldCaptured",
+    "TransactionReversed",
+    "ReversalRecovered",
+    "FeeCharged",
+    "InterestCredited",
+    "OverdraftInterestCharged",
+    "OverdraftEntered",
+    "OverdraftLeft",
+];
+
+/// Fatos de negócio publicados para outros serviços via outbox
+///
+/// O nome da variante é o `event_type` entregue aos sinks; os campos viram o `payload`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum DomainEvent {
+    UserRegistered {
+        user_id: Uuid,
+        email: String,
+        name: String,
+    },
+    UserDeactivated {
+        user_id: Uuid,
+    },
+    AccountOpened {
+        account_id: Uuid,
+        user_id: Uuid,
+        account_number: String,
+        account_type: AccountType,
+        currency: Currency,
+    },
+    AccountClosed {
+        account_id: Uuid,
+        user_id: Uuid,
+    },
+    DepositCompleted {
+        transaction_id: Uuid,
+        account_id: Uuid,
+        amount: Decimal,
+        balance: Decimal,
+    },
+    WithdrawalCompleted {
+        transaction_id: Uuid,
+        account_id: Uuid,
+        amount: Decimal,
+        balance: Decimal,
+    },
+    /// Publicado no agregado da conta de origem; `amount` na moeda da origem e
+    /// `credited_amount` na do destino (iguais sem câmbio)
+    TransferCompleted {
+        debit_transaction_id: Uuid,
+        credit_transaction_id: Uuid,
+        from_account_id: Uuid,
+        to_account_id: Uuid,
+        amount: Decimal,
+        credited_amount: Decimal,
+        /// Taxa aplicada, só entre moedas diferentes
+        fx_rate: Option<Decimal>,
+    },
+    /// Publicado no agregado da conta pagadora; `boleto_id` e a conta do
+    /// beneficiário só existem quando o boleto foi emitido aqui
+    BoletoPaid {
+        transaction_id: Uuid,
+        account_id: Uuid,
+        barcode: String,
+        amount: Decimal,
+        balance: Decimal,
+        boleto_id: Option<Uuid>,
+        beneficiary_account_id: Option<Uuid>,
+    },
+    /// Revisão do KYC concluída; `status` é `approved` ou `rejected`
+    KycReviewed {
+        user_id: Uuid,
+        status: KycStatus,
+    },
+    /// Reserva capturada: `amount` foi debitado do saldo contábil (`balance`)
+    HoldCaptured {
+        hold_id: Uuid,
+        transaction_id: Uuid,
+        account_id: Uuid,
+        amount: Decimal,
+        balance: Decimal,
+    },
+    /// Estorno feito pela operação; `outstanding_amount` é o que a conta
+    /// debitada ainda deve devolver
+    TransactionReversed {
+        reversal_id: Uuid,
+        transaction_id: Uuid,
+        amount: Decimal,
+        reason: ReversalReason,
+        credit_account_id: Option<Uuid>,
+        debit_account_id: Option<Uuid>,
+        outstanding_amount: Decimal,
+    },
+    /// Parte pendente de um estorno cobrada pelo agendador da conta debitada;
+    /// `outstanding_amount` é o que ainda falta
+    ReversalRecovered {
+        reversal_id: Uuid,
+        transaction_id: Uuid,
+        recovery_transaction_id: Uuid,
+        account_id: Uuid,
+        amount: Decimal,
+        balance: Decimal,
+        outstanding_amount: Decimal,
+    },
+    /// Tarifa debitada; `reference_id` é a operação tarifada (transação ou boleto)
+    FeeCharged {
+        transaction_id: Uuid,
+        account_id: Uuid,
+        operation: FeeOperation,
+        amount: Decimal,
+        balance: Decimal,
+        reference_id: Uuid,
+    },
+    /// Juros creditados; o período são os dias apurados incluídos no crédito
+    InterestCredited {
+        transaction_id: Uuid,
+        account_id: Uuid,
+        amount: Decimal,
+        balance: Decimal,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    },
+    /// Juros do cheque especial debitados, pelos dias apurados no período
+    OverdraftInterestCharged {
+        transaction_id: Uuid,
+        account_id: Uuid,
+        amount: Decimal,
+        balance: Decimal,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    },
+    /// O saldo passou de zero ou positivo para negativo
+    OverdraftEntered {
+        account_id: Uuid,
+        user_id: Uuid,
+        balance: Decimal,
+        overdraft_limit: Decimal,
+    },
+    /// O saldo voltou a zero ou positivo
+    OverdraftLeft {
+        account_id: Uuid,
+        user_id: Uuid,
+        balance: Decimal,
+    },
+}
+
+impl DomainEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::UserRegistered { .. } => "UserRegistered",
+            DomainEvent::UserDeactivated { .. } => "UserDeactivated",
+            DomainEvent::AccountOpened { .. } => "AccountOpened",
+            DomainEvent::AccountClosed { .. } => "AccountClosed",
+            DomainEvent::DepositCompleted { .. } => "DepositCompleted",
+            DomainEvent::WithdrawalCompleted { .. } => "WithdrawalCompleted",
+            DomainEvent::TransferCompleted { .. } => "TransferCompleted",
+            DomainEvent::BoletoPaid { .. } => "BoletoPaid",
+            DomainEvent::KycReviewed { .. } => "KycReviewed",
+            DomainEvent::HoldCaptured { .. } => "HoldCaptured",
+            DomainEvent::TransactionReversed { .. } => "TransactionReversed",
+            DomainEvent::ReversalRecovered { .. } => "ReversalRecovered",
+            DomainEvent::FeeCharged { .. } => "FeeCharged",
+            DomainEvent::InterestCredited { .. } => "InterestCredited",
+            DomainEvent::OverdraftInterestCharged { .. } => "OverdraftInterestCharged",
+            DomainEvent::OverdraftEntered { .. } => "OverdraftEntered",
+            DomainEvent::OverdraftLeft { .. } => "OverdraftLeft",
+        }
+    }
+
+    /// Agregado dono do evento: a ordem de entrega é garantida dentro dele
+    pub fn aggregate(&self) -> (&'static str, Uuid) {
+        match self {
+            DomainEvent::UserRegistered { user_id, .. }
+            | DomainEvent::UserDeactivated { user_id }
+            | DomainEvent::KycReviewed { user_id, .. } => ("user", *user_id),
+            DomainEvent::AccountOpened { account_id, .. }
+            | DomainEvent::AccountClosed { account_id, .. }
+            | DomainEvent::DepositCompleted { account_id, .. }
+            | DomainEvent::WithdrawalCompleted { account_id, .. }
+            | DomainEvent::BoletoPaid { account_id, .. }
+            | DomainEvent::HoldCaptured { account_id, .. }
+            | DomainEvent::FeeCharged { account_id, .. }
+            | DomainEvent::InterestCredited { account_id, .. }
+            | DomainEvent::OverdraftInterestCharged { account_id, .. }
+            | DomainEvent::OverdraftEntered { account_id, .. }
+            | DomainEvent::OverdraftLeft { account_id, .. } => ("account", *account_id),
+            DomainEvent::TransferCompleted {
+                from_account_id, ..
+            } => ("account", *from_account_id),
+            DomainEvent::TransactionReversed { transaction_id, .. }
+            | DomainEvent::ReversalRecovered { transaction_id, .. } => {
+                ("transaction", *transaction_id)
+            }
+        }
+    }
+
+    /// Evento de depósito ou saque a partir do lançamento gravado
+    pub fn money_movement(transaction: &Transaction, account_id: Uuid, balance: Decimal) -> Self {
+        if transaction.from_account_id.is_some() {
+            DomainEvent::WithdrawalCompleted {
+                transaction_id: transaction.id,
+                account_id,
+                amount: transaction.amount,
+                balance,
+            }
+        } else {
+            DomainEvent::DepositCompleted {
+                transaction_id: transaction.id,
+                account_id,
+                amount: transaction.amount,
+                balance,
+            }
+        }
+    }
+
+    /// Linha do outbox pronta para inserir
+    pub fn into_outbox(self) -> NewOutboxEvent {
+        let (aggregate_type, aggregate_id) = self.aggregate();
+        let event_type = self.event_type();
+        let payload = match serde_json::to_value(&self) {
+            Ok(Value::Object(mut tagged)) => tagged.remove("data").unwrap_or(Value::Null),
+            _ => Value::Null,
+        };
+
+        NewOutboxEvent {
+            id: Uuid::new_v4(),
+            aggregate_type,
+            aggregate_id,
+            event_type,
+            payload,
+            occurred_at: Utc::now().trunc_subsecs(6),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewOutboxEvent {
+    pub id: Uuid,
+    pub aggregate_type: &'static str,
+    pub aggregate_id: Uuid,
+    pub event_type: &'static str,
+    pub payload: Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Evento pendente lido do outbox; é também o envelope entregue aos sinks
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct OutboxEvent {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub seq: i64,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+    pub occurred_at: DateTime<Utc>,
+    /// Tentativas anteriores; consumidores devem deduplicar pelo `id`
+    pub attempts: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outbox_row_carries_type_aggregate_and_payload() {
+        let from = Uuid::new_v4();
+        let event = DomainEvent::TransferCompleted {
+            debit_transaction_id: Uuid::new_v4(),
+            credit_transaction_id: Uuid::new_v4(),
+            from_account_id: from,
+            to_account_id: Uuid::new_v4(),
+            amount: Decimal::new(4000, 2),
+            credited_amount: Decimal::new(4000, 2),
+            fx_rate: None,
+        }
+        .into_outbox();
+
+        assert_eq!(event.event_type, "TransferCompleted");
+        assert_eq!(event.aggregate_type, "account");
+        assert_eq!(event.aggregate_id, from);
+        assert_eq!(event.payload["amount"], "40.00");
+        assert!(event.payload.get("type").is_none());
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::currency::Currency;
+
+/// Operação tarifada
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum FeeOperation {
+    Withdraw,
+    /// Transferência para conta de outro cliente; entre contas do mesmo titular não há tarifa
+    Transfer,
+    BoletoIssue,
+}
+
+impl FeeOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeeOperation::Withdraw => "withdraw",
+            FeeOperation::Transfer => "transfer",
+            FeeOperation::BoletoIssue => "boleto_issue",
+        }
+    }
+
+    /// Descrição do lançamento `fee` no extrato
+    pub fn description(&self) -> &'static str {
+        match self {
+            FeeOperation::Withdraw => "Tarifa de saque",
+            FeeOperation::Transfer => "Tarifa de transferência",
+            FeeOperation::BoletoIssue => "Tarifa de emissão de boleto",
+        }
+    }
+}
+
+/// Tarifa apurada para uma operação, ainda não lançada
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fee {
+    pub operation: FeeOperation,
+    pub amount: Decimal,
+}
+
+/// Fixo mais o percentual sobre o valor da operação, na menor unidade da moeda
+/// (arredondamento bancário); `fixed` já convertido para a moeda
+pub fn fee_amount(
+    fixed: Decimal,
+    percent: Decimal,
+    amount: Decimal,
+    currency: Currency,
+) -> Decimal {
+    currency.round(fixed + amount * percent / Decimal::ONE_HUNDRED)
+}
+
+/// Isenção de tarifas concedida pela operação a uma conta
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct FeeWaiver {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    /// Sem operação, isenta todas
+    pub operation: Option<FeeOperation>,
+    pub reason: String,
+    /// Sem validade, vale até ser revogada
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FeeWaiver {
+    pub fn new(account_id: Uuid, created_by: Uuid, request: &CreateFeeWaiver) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            account_id,
+            operation: request.operation,
+            reason: request.reason.trim().to_string(),
+            expires_at: request.expires_at,
+            created_by,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "account_id": self.account_id,
+            "operation": self.operation,
+            "reason": self.reason,
+            "expires_at": self.expires_at,
+            "revoked_at": self.revoked_at,
+        })
+    }
+}
+
+/// Isenção de tarifas (`POST /admin/accounts/{id}/fee-waivers`)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateFeeWaiver {
+    /// Padrão: todas as operações
+    pub operation: Option<FeeOperation>,
+    #[schema(example = "Campanha de boas-vindas")]
+    pub reason: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum FeeError {
+        #[error("Isenção não encontrada")]
+        WaiverNotFound,
+
+        #[error("Isenção já revogada")]
+        WaiverRevoked,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_amount_uses_bankers_rounding() {
+        // 0,5% de 101,00 = 0,505 → 0,50 (par mais próximo)
+        assert_eq!(
+            fee_amount(
+                Decimal::ZERO,
+                Decimal::new(5, 1),
+                Decimal::from(101),
+                Currency::Brl
+            ),
+            Decimal::new(50, 2)
+        );
+        // 0,5% de 103,00 = 0,515 → 0,52
+        assert_eq!(
+            fee_amount(
+                Decimal::ZERO,
+                Decimal::new(5, 1),
+                Decimal::from(103),
+                Currency::Brl
+            ),
+            Decimal::new(52, 2)
+        );
+        assert_eq!(
+            fee_amount(
+                Decimal::new(650, 2),
+                Decimal::ZERO,
+                Decimal::from(1_000),
+                Currency::Brl
+            ),
+            Decimal::new(650, 2)
+        );
+        // R$ 6,50 a 29,1 ienes por real: sem casas no iene
+        assert_eq!(
+            fee_amount(
+                Decimal::new(650, 2) * Decimal::new(291, 1),
+                Decimal::ZERO,
+                Decimal::from(1_000),
+                Currency::Jpy
+            ),
+            Decimal::from(189)
+        );
+    }
+}
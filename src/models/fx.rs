@@ -0,0 +1,199 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{account::Account, currency::Currency, fx::error::FxError};
+
+/// Casas guardadas nas taxas (coluna DECIMAL(20,10))
+const RATE_SCALE: u32 = 10;
+
+/// Pedido de cotação (`POST /accounts/{id}/fx-quotes`)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateFxQuote {
+    pub to_account_number: String,
+    /// Valor a debitar, na moeda da conta de origem
+    pub amount: Decimal,
+}
+
+/// Taxa de um par como veio do provedor: unidades da moeda de destino por
+/// unidade da de origem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketRate {
+    pub mid_rate: Decimal,
+    pub provider: &'static str,
+}
+
+/// Cotação travada para uma transferência entre moedas
+///
+/// Vale para uma única transferência da conta de origem para a de destino, com
+/// o mesmo valor, até `expires_at`.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct FxQuote {
+    pub id: Uuid,
+    #[serde(skip)]
+    pub user_id: Uuid,
+    pub from_account_id: Uuid,
+    pub to_account_id: Uuid,
+    pub from_currency: Currency,
+    pub to_currency: Currency,
+    /// Debitado na origem
+    pub amount: Decimal,
+    /// Creditado no destino, arredondado para a moeda de destino
+    pub converted_amount: Decimal,
+    /// Taxa do provedor: unidades da moeda de destino por unidade da de origem
+    pub mid_rate: Decimal,
+    /// Spread em %
+    pub spread: Decimal,
+    /// Taxa aplicada, já com o spread
+    pub rate: Decimal,
+    pub provider: String,
+    pub expires_at: DateTime<Utc>,
+    /// Perna de débito da transferência que usou a cotação
+    pub transaction_id: Option<Uuid>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FxQuote {
+    /// Cotação de `amount` da moeda de `source` para a de `destination`; o
+    /// spread sai da taxa, então o destino recebe menos que pela taxa do provedor
+    pub fn new(
+        user_id: Uuid,
+        source: &Account,
+        destination: &Account,
+        amount: Decimal,
+        market: MarketRate,
+        spread: Decimal,
+        ttl: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        let rate = applied_rate(market.mid_rate, spread);
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            from_account_id: source.id,
+            to_account_id: destination.id,
+            from_currency: source.currency,
+            to_currency: destination.currency,
+            amount,
+            converted_amount: destination.currency.round(amount * rate),
+            mid_rate: market.mid_rate.round_dp(RATE_SCALE),
+            spread,
+            rate,
+            provider: market.provider.to_string(),
+            expires_at: now + ttl,
+            transaction_id: None,
+            used_at: None,
+            created_at: now,
+        }
+    }
+
+    /// Confere se a cotação serve para a transferência pedida
+    pub fn ensure_usable(
+        &self,
+        source: &Account,
+        destination: &Account,
+        amount: Decimal,
+        now: DateTime<Utc>,
+    ) -> Result<(), FxError> {
+        if self.from_account_id != source.id
+            || self.to_account_id != destination.id
+            || self.from_currency != source.currency
+            || self.to_currency != destination.currency
+            || self.amount != amount
+        {
+            return Err(FxError::QuoteMismatch);
+        }
+        if self.used_at.is_some() {
+            return Err(FxError::QuoteAlreadyUsed);
+        }
+        if self.expires_at <= now {
+            return Err(FxError::QuoteExpired);
+        }
+        Ok(())
+    }
+}
+
+/// Taxa do provedor menos o spread (%)
+pub fn applied_rate(mid_rate: Decimal, spread: Decimal) -> Decimal {
+    (mid_rate * (Decimal::ONE - spread / Decimal::ONE_HUNDRED)).round_dp(RATE_SCALE)
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum FxError {
+        #[error("Cotação não encontrada")]
+        QuoteNotFound,
+
+        #[error("Cotação expirada")]
+        QuoteExpired,
+
+        #[error("Cotação já usada")]
+        QuoteAlreadyUsed,
+
+        #[error("Cotação não corresponde à transferência")]
+        QuoteMismatch,
+
+        #[error("Transferência entre moedas exige uma cotação")]
+        QuoteRequired,
+
+        #[error("As contas estão na mesma moeda")]
+        SameCurrency,
+
+        #[error("Sem taxa de câmbio para o par de moedas")]
+        RateUnavailable,
+
+        #[error("Valor pequeno demais para a conversão")]
+        AmountTooSmall,
+
+        #[error("Operação disponível só para contas em reais")]
+        CurrencyNotSupported,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::AccountType;
+
+    #[test]
+    fn test_quote_applies_spread_and_destination_minor_units() {
+        let user_id = Uuid::new_v4();
+        let source = Account::open(user_id, AccountType::Checking, Currency::Usd);
+        let destination = Account::open(user_id, AccountType::Checking, Currency::Jpy);
+        // 1 USD = 150 JPY, spread de 1%: 148,50
+        let quote = FxQuote::new(
+            user_id,
+            &source,
+            &destination,
+            Decimal::new(1001, 2),
+            MarketRate {
+                mid_rate: Decimal::from(150),
+                provider: "file",
+            },
+            Decimal::ONE,
+            Duration::seconds(30),
+        );
+        assert_eq!(quote.rate, Decimal::new(14850, 2));
+        // 10,01 × 148,50 = 1486,485 → 1486 ienes
+        assert_eq!(quote.converted_amount, Decimal::from(1486));
+
+        let now = Utc::now();
+        assert!(
+            quote
+                .ensure_usable(&source, &destination, quote.amount, now)
+                .is_ok()
+        );
+        assert!(matches!(
+            quote.ensure_usable(&source, &destination, Decimal::TEN, now),
+            Err(FxError::QuoteMismatch)
+        ));
+        assert!(matches!(
+            quote.ensure_usable(&source, &destination, quote.amount, quote.expires_at),
+            Err(FxError::QuoteExpired)
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Estado de um componente verificado
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    /// Funcionando, mas perto do limite (não derruba a readiness)
+    Degraded,
+    Down,
+    /// Servidor em desligamento gracioso, recusando tráfego novo
+    Draining,
+}
+
+/// Resultado de uma verificação individual
+#[derive(Debug, Serialize)]
+pub struct HealthCheck {
+    pub status: HealthStatus,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Resposta dos endpoints de health
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<NamedCheck>,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NamedCheck {
+    pub name: &'static str,
+    #[serde(flatten)]
+    pub check: HealthCheck,
+}
+
+impl HealthReport {
+    /// Agrega as verificações: qualquer `Down` derruba o relatório
+    pub fn new(checks: Vec<NamedCheck>) -> Self {
+        let status = if checks.iter().any(|c| c.check.status == HealthStatus::Down) {
+            HealthStatus::Down
+        } else if checks
+            .iter()
+            .any(|c| c.check.status == HealthStatus::Degraded)
+        {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Up
+        };
+
+        Self {
+            status,
+            checks,
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+
+    pub fn with_status(status: HealthStatus) -> Self {
+        Self {
+            status,
+            checks: Vec::new(),
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Pronto para receber tráfego
+    pub fn is_ready(&self) -> bool {
+        matches!(self.status, HealthStatus::Up | HealthStatus::Degraded)
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Validade da reserva quando o pedido não informa `ttl_secs` (7 dias)
+pub const DEFAULT_HOLD_TTL_SECS: u32 = 7 * 24 * 60 * 60;
+
+/// Maior validade aceita para uma reserva (30 dias)
+pub const MAX_HOLD_TTL_SECS: u32 = 30 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum HoldStatus {
+    /// Valor reservado, fora do saldo disponível
+    Active,
+    Captured,
+    Released,
+    /// Passou de `expires_at` sem captura; o valor voltou ao disponível
+    Expired,
+}
+
+/// Reserva de valor (autorização) na conta
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateHold {
+    pub amount: Decimal,
+    pub description: String,
+    /// Validade em segundos; padrão de 7 dias, no máximo 30
+    pub ttl_secs: Option<u32>,
+}
+
+/// Captura de uma reserva; sem `amount`, captura o valor todo
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CaptureHold {
+    /// Até o valor reservado; o restante é liberado
+    pub amount: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Hold {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub created_by: Uuid,
+    /// Valor reservado
+    pub amount: Decimal,
+    /// Valor debitado na captura
+    pub captured_amount: Option<Decimal>,
+    pub description: String,
+    pub status: HoldStatus,
+    /// Lançamento `hold_capture`: pendente enquanto a reserva está ativa
+    pub transaction_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Hold {
+    pub fn new(
+        account_id: Uuid,
+        created_by: Uuid,
+        request: &CreateHold,
+        transaction_id: Uuid,
+    ) -> Self {
+        let now = Utc::now();
+        let ttl = request.ttl_secs.unwrap_or(DEFAULT_HOLD_TTL_SECS);
+        Self {
+            id: Uuid::new_v4(),
+            account_id,
+            created_by,
+            amount: request.amount,
+            captured_amount: None,
+            description: request.description.trim().to_string(),
+            status: HoldStatus::Active,
+            transaction_id,
+            expires_at: now + Duration::seconds(i64::from(ttl)),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    pub fn capture(&mut self, amount: Decimal, now: DateTime<Utc>) {
+        self.captured_amount = Some(amount);
+        self.finish(HoldStatus::Captured, now);
+    }
+
+    /// Encerra a reserva sem captura (`Released` ou `Expired`)
+    pub fn finish(&mut self, status: HoldStatus, now: DateTime<Utc>) {
+        self.status = status;
+        self.updated_at = now;
+    }
+
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "account_id": self.account_id,
+            "amount": self.amount,
+            "captured_amount": self.captured_amount,
+            "status": self.status,
+            "expires_at": self.expires_at,
+        })
+    }
+}
+
+/// Filtros de `GET /accounts/{id}/holds`
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct HoldFilter {
+    #[param(value_type = Option<HoldStatus>)]
+    pub status: Option<HoldStatus>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum HoldError {
+        #[error("Reserva não encontrada")]
+        NotFound,
+
+        #[error("Reserva já capturada, liberada ou expirada")]
+        NotActive,
+
+        #[error("Reserva expirada")]
+        Expired,
+
+        #[error("Valor da captura maior que o reservado")]
+        CaptureExceedsHold,
+    }
+}
@@ -0,0 +1,144 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{
+    account::{Account, AccountType},
+    currency::Currency,
+    transaction::Transaction,
+};
+
+/// Base anual dos juros diários
+const DAYS_IN_YEAR: i64 = 365;
+
+/// Casas guardadas nos juros do dia; centavos só na soma lançada
+const ACCRUAL_SCALE: u32 = 8;
+
+/// Juros simples de um dia sobre o saldo, à taxa anual (%); negativos no saldo
+/// negativo (cheque especial)
+pub fn daily_interest(balance: Decimal, annual_rate: Decimal) -> Decimal {
+    (balance * annual_rate / Decimal::ONE_HUNDRED / Decimal::from(DAYS_IN_YEAR))
+        .round_dp(ACCRUAL_SCALE)
+}
+
+fn offset(utc_offset_hours: i32) -> FixedOffset {
+    FixedOffset::east_opt(utc_offset_hours * 3600).unwrap_or(FixedOffset::east_opt(0).unwrap())
+}
+
+/// Data local de `now` com o fuso de `[limits]`
+pub fn local_date(now: DateTime<Utc>, utc_offset_hours: i32) -> NaiveDate {
+    now.with_timezone(&offset(utc_offset_hours)).date_naive()
+}
+
+/// Meia-noite local do dia, em UTC
+pub fn local_midnight(date: NaiveDate, utc_offset_hours: i32) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN)
+        .and_local_timezone(offset(utc_offset_hours))
+        .single()
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+/// Juros de uma conta em um dia (`interest_accruals`); o lançamento mensal
+/// preenche `transaction_id` e `credited_at` direto no banco
+#[derive(Debug, Clone)]
+pub struct InterestAccrual {
+    pub account_id: Uuid,
+    pub accrual_date: NaiveDate,
+    /// Saldo contábil na apuração
+    pub balance: Decimal,
+    /// % ao ano
+    pub annual_rate: Decimal,
+    pub amount: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InterestAccrual {
+    pub fn new(account: &Account, accrual_date: NaiveDate, annual_rate: Decimal) -> Self {
+        Self {
+            account_id: account.id,
+            accrual_date,
+            balance: account.balance,
+            annual_rate,
+            amount: daily_interest(account.balance, annual_rate),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Juros apurados e ainda não lançados; a soma é negativa no cheque especial
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingInterest {
+    pub amount: Decimal,
+    pub days: i64,
+    pub first_date: Option<NaiveDate>,
+    pub last_date: Option<NaiveDate>,
+}
+
+impl PendingInterest {
+    /// Valor do lançamento: a soma dos dias na menor unidade da moeda da conta
+    /// (arredondamento bancário)
+    pub fn credit_amount(&self, currency: Currency) -> Decimal {
+        currency.round(self.amount)
+    }
+}
+
+/// Rendimento da conta (`GET /accounts/{id}/interest`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InterestSummary {
+    pub account_id: Uuid,
+    pub account_type: AccountType,
+    /// Taxa anual (%) em vigor hoje; com saldo negativo, a do cheque especial.
+    /// Ausente quando o saldo não rende nem paga juros
+    pub annual_rate: Option<Decimal>,
+    /// Taxa de referência (% a.a.) em vigor hoje
+    pub reference_rate: Option<Decimal>,
+    /// Apurado e ainda não lançado, em centavos; negativo no cheque especial
+    pub accrued: Decimal,
+    pub accrued_days: i64,
+    pub last_accrual_date: Option<NaiveDate>,
+}
+
+/// Lançamento antecipado dos juros apurados (`POST /accounts/{id}/interest/settle`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InterestSettlement {
+    pub account_id: Uuid,
+    /// Saldo contábil depois do lançamento
+    pub balance: Decimal,
+    /// `interest` ou `overdraft_interest`; ausente quando a soma não chegou a um centavo
+    pub transaction: Option<Transaction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_interest_and_monthly_rounding() {
+        // 1.000,00 a 6% a.a.: 60/365 por dia
+        let day = daily_interest(Decimal::from(1_000), Decimal::from(6));
+        assert_eq!(day, Decimal::new(16438356, 8));
+        // cheque especial: 1.000,00 negativos a 96% a.a.
+        assert_eq!(
+            daily_interest(Decimal::from(-1_000), Decimal::from(96)),
+            Decimal::new(-263013699, 8)
+        );
+
+        let pending = PendingInterest {
+            amount: day * Decimal::from(30),
+            days: 30,
+            ..Default::default()
+        };
+        assert_eq!(pending.credit_amount(Currency::Brl), Decimal::new(493, 2));
+        assert_eq!(pending.credit_amount(Currency::Jpy), Decimal::from(5));
+
+        // meio centavo vai para o par mais próximo
+        let half = PendingInterest {
+            amount: Decimal::new(12345, 3),
+            ..Default::default()
+        };
+        assert_eq!(half.credit_amount(Currency::Brl), Decimal::new(1234, 2));
+    }
+}
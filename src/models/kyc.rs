@@ -0,0 +1,330 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{CustomerType, Document};
+
+/// Tamanho máximo de cada documento enviado
+pub const MAX_DOCUMENT_BYTES: usize = 5 * 1024 * 1024;
+
+/// Formatos aceitos nos documentos, com a assinatura (magic bytes) de cada um
+pub const ALLOWED_CONTENT_TYPES: [(&str, &[u8]); 3] = [
+    ("image/jpeg", &[0xFF, 0xD8, 0xFF]),
+    (
+        "image/png",
+        &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A],
+    ),
+    ("application/pdf", b"%PDF-"),
+];
+
+/// Situação do cadastro (KYC) do usuário
+///
+/// `pending` → `submitted` → `under_review` → `approved` | `rejected`; quem foi
+/// rejeitado corrige os documentos e envia de novo. Só `approved` abre contas e
+/// movimenta dinheiro para fora.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum KycStatus {
+    /// Aguardando os documentos
+    Pending,
+    /// Documentos enviados, aguardando um revisor
+    Submitted,
+    UnderReview,
+    Approved,
+    Rejected,
+}
+
+impl KycStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KycStatus::Pending => "pending",
+            KycStatus::Submitted => "submitted",
+            KycStatus::UnderReview => "under_review",
+            KycStatus::Approved => "approved",
+            KycStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn can_transition_to(&self, next: KycStatus) -> bool {
+        use KycStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending | Rejected, Submitted)
+                | (Submitted, UnderReview)
+                | (UnderReview, Approved | Rejected)
+        )
+    }
+
+    /// Documentos só podem ser trocados antes do envio (ou depois de uma rejeição)
+    pub fn accepts_documents(&self) -> bool {
+        matches!(self, KycStatus::Pending | KycStatus::Rejected)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum KycDocumentType {
+    /// RG, CNH ou passaporte (do titular ou do representante da empresa)
+    IdDocument,
+    Selfie,
+    ProofOfAddress,
+    /// Contrato social ou cartão CNPJ
+    CompanyRegistration,
+}
+
+impl KycDocumentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KycDocumentType::IdDocument => "id_document",
+            KycDocumentType::Selfie => "selfie",
+            KycDocumentType::ProofOfAddress => "proof_of_address",
+            KycDocumentType::CompanyRegistration => "company_registration",
+        }
+    }
+
+    /// Documentos exigidos para enviar o cadastro; os demais são opcionais
+    pub fn required_for(customer_type: CustomerType) -> &'static [KycDocumentType] {
+        match customer_type {
+            CustomerType::Individual => &[KycDocumentType::IdDocument, KycDocumentType::Selfie],
+            CustomerType::Business => &[
+                KycDocumentType::CompanyRegistration,
+                KycDocumentType::IdDocument,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct KycProfile {
+    pub user_id: Uuid,
+    pub status: KycStatus,
+    pub submitted_at: Option<DateTime<Utc>>,
+    /// Admin que assumiu ou concluiu a revisão
+    pub reviewer_id: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// Motivo informado ao usuário quando o cadastro é rejeitado
+    pub rejection_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl KycProfile {
+    /// Novo estado após a transição; erro se ela não é permitida a partir do atual
+    pub fn transition(
+        &self,
+        next: KycStatus,
+        reviewer_id: Option<Uuid>,
+        now: DateTime<Utc>,
+    ) -> Result<KycProfile, error::KycError> {
+        if !self.status.can_transition_to(next) {
+            return Err(error::KycError::InvalidStatus);
+        }
+        let mut profile = self.clone();
+        profile.status = next;
+        profile.updated_at = now;
+        match next {
+            KycStatus::Submitted => {
+                profile.submitted_at = Some(now);
+                profile.reviewer_id = None;
+                profile.reviewed_at = None;
+                profile.rejection_reason = None;
+            }
+            KycStatus::UnderReview => profile.reviewer_id = reviewer_id,
+            KycStatus::Approved | KycStatus::Rejected => {
+                profile.reviewer_id = reviewer_id;
+                profile.reviewed_at = Some(now);
+            }
+            KycStatus::Pending => {}
+        }
+        Ok(profile)
+    }
+
+    /// Estado relevante para auditoria
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": self.status,
+            "reviewer_id": self.reviewer_id,
+            "rejection_reason": self.rejection_reason,
+        })
+    }
+}
+
+/// Metadados de um documento; o conteúdo fica no storage
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct KycDocument {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub document_type: KycDocumentType,
+    #[schema(example = "image/jpeg")]
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// SHA-256 do conteúdo, em hexadecimal
+    pub sha256: String,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub storage_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl KycDocument {
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "document_type": self.document_type,
+            "content_type": self.content_type,
+            "size_bytes": self.size_bytes,
+            "sha256": self.sha256,
+        })
+    }
+}
+
+/// Situação do cadastro com os documentos enviados e os que ainda faltam
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KycOverview {
+    pub profile: KycProfile,
+    pub documents: Vec<KycDocument>,
+    pub missing_documents: Vec<KycDocumentType>,
+}
+
+impl KycOverview {
+    pub fn new(
+        profile: KycProfile,
+        documents: Vec<KycDocument>,
+        customer_type: CustomerType,
+    ) -> Self {
+        let missing_documents = KycDocumentType::required_for(customer_type)
+            .iter()
+            .filter(|required| {
+                !documents
+                    .iter()
+                    .any(|document| document.document_type == **required)
+            })
+            .copied()
+            .collect();
+        Self {
+            profile,
+            documents,
+            missing_documents,
+        }
+    }
+}
+
+/// Cadastro na fila de revisão, com os dados do usuário
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct KycReviewItem {
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub customer_type: CustomerType,
+    #[schema(value_type = Option<String>, example = "***.982.247-**")]
+    pub document: Option<Document>,
+    pub status: KycStatus,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub reviewer_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RejectKyc {
+    #[schema(example = "Foto do documento ilegível")]
+    pub reason: String,
+}
+
+/// Filtros da fila de revisão (`GET /admin/kyc`); sem `status`, lista os enviados
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct KycQuery {
+    #[param(value_type = Option<KycStatus>)]
+    pub status: Option<KycStatus>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum KycError {
+        #[error("Cadastro não encontrado")]
+        NotFound,
+
+        #[error("Cadastro ainda não aprovado")]
+        NotApproved,
+
+        #[error("Operação não permitida na situação atual do cadastro")]
+        InvalidStatus,
+
+        #[error("Documento não encontrado")]
+        DocumentNotFound,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(status: KycStatus) -> KycProfile {
+        let now = Utc::now();
+        KycProfile {
+            user_id: Uuid::new_v4(),
+            status,
+            submitted_at: None,
+            reviewer_id: None,
+            reviewed_at: None,
+            rejection_reason: Some("Selfie escura".into()),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        use KycStatus::*;
+
+        assert!(Pending.can_transition_to(Submitted));
+        assert!(Rejected.can_transition_to(Submitted));
+        assert!(Submitted.can_transition_to(UnderReview));
+        assert!(UnderReview.can_transition_to(Approved));
+        assert!(UnderReview.can_transition_to(Rejected));
+        assert!(!Pending.can_transition_to(Approved));
+        assert!(!Submitted.can_transition_to(Approved));
+        assert!(!Approved.can_transition_to(Rejected));
+        assert!(!Approved.can_transition_to(Submitted));
+    }
+
+    #[test]
+    fn test_resubmission_clears_previous_review() {
+        let reviewer = Uuid::new_v4();
+        let rejected = profile(KycStatus::Rejected);
+
+        let submitted = rejected
+            .transition(KycStatus::Submitted, None, Utc::now())
+            .unwrap();
+        assert!(submitted.submitted_at.is_some());
+        assert!(submitted.rejection_reason.is_none());
+
+        let approved = submitted
+            .transition(KycStatus::UnderReview, Some(reviewer), Utc::now())
+            .and_then(|p| p.transition(KycStatus::Approved, Some(reviewer), Utc::now()))
+            .unwrap();
+        assert_eq!(approved.reviewer_id, Some(reviewer));
+        assert!(approved.reviewed_at.is_some());
+        assert!(matches!(
+            approved.transition(KycStatus::Rejected, Some(reviewer), Utc::now()),
+            Err(error::KycError::InvalidStatus)
+        ));
+    }
+
+    #[test]
+    fn test_missing_documents_by_customer_type() {
+        let overview =
+            KycOverview::new(profile(KycStatus::Pending), vec![], CustomerType::Business);
+        assert_eq!(
+            overview.missing_documents,
+            vec![
+                KycDocumentType::CompanyRegistration,
+                KycDocumentType::IdDocument
+            ]
+        );
+    }
+}
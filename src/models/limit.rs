@@ -0,0 +1,442 @@
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, Timelike, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    config::{AccountLimits, LimitsConfig, UserLimits},
+    models::{account::AccountType, transaction::error::TransactionError},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum LimitKind {
+    /// Valor máximo de um saque ou transferência
+    PerTransaction,
+    /// Total de saída no dia (hora local)
+    Daily,
+    /// Total de saída no mês (hora local)
+    Monthly,
+    /// Total de saída no período noturno
+    Nightly,
+}
+
+impl LimitKind {
+    pub const ALL: [LimitKind; 4] = [
+        LimitKind::PerTransaction,
+        LimitKind::Daily,
+        LimitKind::Monthly,
+        LimitKind::Nightly,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LimitKind::PerTransaction => "per_transaction",
+            LimitKind::Daily => "daily",
+            LimitKind::Monthly => "monthly",
+            LimitKind::Nightly => "nightly",
+        }
+    }
+
+    /// Teto do tipo de conta, que também é o valor padrão
+    pub fn max(&self, limits: &AccountLimits) -> Decimal {
+        match self {
+            LimitKind::PerTransaction => limits.per_transaction,
+            LimitKind::Daily => limits.daily,
+            LimitKind::Monthly => limits.monthly,
+            LimitKind::Nightly => limits.nightly,
+        }
+    }
+}
+
+/// Novos limites da conta; os ausentes não mudam
+///
+/// Reduções valem na hora; aumentos só depois do período de carência.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateLimits {
+    pub per_transaction: Option<Decimal>,
+    pub daily: Option<Decimal>,
+    pub monthly: Option<Decimal>,
+    pub nightly: Option<Decimal>,
+}
+
+impl UpdateLimits {
+    pub fn requested(&self) -> impl Iterator<Item = (LimitKind, Decimal)> + '_ {
+        LimitKind::ALL.into_iter().filter_map(|kind| {
+            let amount = match kind {
+                LimitKind::PerTransaction => self.per_transaction,
+                LimitKind::Daily => self.daily,
+                LimitKind::Monthly => self.monthly,
+                LimitKind::Nightly => self.nightly,
+            };
+            amount.map(|amount| (kind, amount))
+        })
+    }
+}
+
+/// Limite personalizado pelo titular (`account_limits`)
+#[derive(Debug, Clone, FromRow)]
+pub struct AccountLimit {
+    pub account_id: Uuid,
+    pub kind: LimitKind,
+    pub amount: Decimal,
+    /// Aumento pedido, aguardando a carência
+    pub pending_amount: Option<Decimal>,
+    pub pending_effective_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AccountLimit {
+    pub fn new(account_id: Uuid, kind: LimitKind, amount: Decimal, now: DateTime<Utc>) -> Self {
+        Self {
+            account_id,
+            kind,
+            amount,
+            pending_amount: None,
+            pending_effective_at: None,
+            updated_at: now,
+        }
+    }
+
+    /// Valor em vigor: o aumento pendente passa a valer quando vence a carência
+    pub fn effective(&self, now: DateTime<Utc>) -> Decimal {
+        match (self.pending_amount, self.pending_effective_at) {
+            (Some(pending), Some(effective_at)) if effective_at <= now => pending,
+            _ => self.amount,
+        }
+    }
+
+    /// Redução (ou o mesmo valor) vale na hora e cancela um aumento pendente;
+    /// aumento fica pendente até `now + cooling_off`
+    pub fn change(&mut self, amount: Decimal, now: DateTime<Utc>, cooling_off: Duration) {
+        let current = self.effective(now);
+        if amount <= current {
+            self.amount = amount;
+            self.pending_amount = None;
+            self.pending_effective_at = None;
+        } else {
+            self.amount = current;
+            self.pending_amount = Some(amount);
+            self.pending_effective_at = Some(now + cooling_off);
+        }
+        self.updated_at = now;
+    }
+}
+
+/// Limites em vigor de uma conta
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EffectiveLimits {
+    pub per_transaction: Decimal,
+    pub daily: Decimal,
+    pub monthly: Decimal,
+    pub nightly: Decimal,
+}
+
+impl EffectiveLimits {
+    /// Padrões do tipo de conta com os personalizados por cima
+    pub fn resolve(defaults: &AccountLimits, custom: &[AccountLimit], now: DateTime<Utc>) -> Self {
+        let value = |kind: LimitKind| {
+            custom
+                .iter()
+                .find(|limit| limit.kind == kind)
+                .map(|limit| limit.effective(now))
+                .unwrap_or_else(|| kind.max(defaults))
+        };
+        Self {
+            per_transaction: value(LimitKind::PerTransaction),
+            daily: value(LimitKind::Daily),
+            monthly: value(LimitKind::Monthly),
+            nightly: value(LimitKind::Nightly),
+        }
+    }
+
+    /// Confere um novo débito de `amount` contra o já usado em cada janela
+    pub fn check(&self, usage: &LimitUsage, amount: Decimal) -> Result<(), TransactionError> {
+        if amount > self.per_transaction {
+            return Err(TransactionError::PerTransactionLimitExceeded);
+        }
+        if let Some(nightly) = usage.nightly
+            && nightly + amount > self.nightly
+        {
+            return Err(TransactionError::NightlyLimitExceeded);
+        }
+        if usage.daily + amount > self.daily {
+            return Err(TransactionError::DailyLimitExceeded);
+        }
+        if usage.monthly + amount > self.monthly {
+            return Err(TransactionError::MonthlyLimitExceeded);
+        }
+        Ok(())
+    }
+}
+
+/// Início das janelas que contam para os limites, em UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitWindows {
+    pub day_start: DateTime<Utc>,
+    pub month_start: DateTime<Utc>,
+    /// Só durante o período noturno
+    pub night_start: Option<DateTime<Utc>>,
+}
+
+impl LimitWindows {
+    pub fn at(now: DateTime<Utc>, config: &LimitsConfig) -> Self {
+        let offset = FixedOffset::east_opt(config.utc_offset_hours * 3600)
+            .unwrap_or(FixedOffset::east_opt(0).unwrap());
+        let local = now.with_timezone(&offset);
+        let today = local.date_naive();
+        let at = |date: NaiveDate, hour: u32| {
+            date.and_hms_opt(hour, 0, 0)
+                .and_then(|time| time.and_local_timezone(offset).single())
+                .map(|time| time.with_timezone(&Utc))
+                .unwrap_or(now)
+        };
+
+        let (start, end, hour) = (config.night_start_hour, config.night_end_hour, local.hour());
+        let night_start = if start > end {
+            // atravessa a meia-noite (ex.: 20h–6h)
+            if hour >= start {
+                Some(at(today, start))
+            } else if hour < end {
+                today.pred_opt().map(|yesterday| at(yesterday, start))
+            } else {
+                None
+            }
+        } else {
+            (start..end).contains(&hour).then(|| at(today, start))
+        };
+
+        Self {
+            day_start: at(today, 0),
+            month_start: at(today.with_day(1).unwrap_or(today), 0),
+            night_start,
+        }
+    }
+}
+
+/// Total já debitado em saques e transferências em cada janela
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LimitUsage {
+    pub daily: Decimal,
+    pub monthly: Decimal,
+    /// Ausente fora do período noturno
+    pub nightly: Option<Decimal>,
+}
+
+impl LimitUsage {
+    /// Soma o uso de várias contas em reais; cada uma vem com quanto vale um
+    /// real na moeda dela
+    pub fn sum_in_brl(usages: &[(LimitUsage, Decimal)]) -> Self {
+        usages
+            .iter()
+            .fold(LimitUsage::default(), |total, (usage, rate)| LimitUsage {
+                daily: total.daily + usage.daily / rate,
+                monthly: total.monthly + usage.monthly / rate,
+                nightly: match (total.nightly, usage.nightly) {
+                    (None, None) => None,
+                    (total, usage) => {
+                        Some(total.unwrap_or_default() + usage.unwrap_or_default() / rate)
+                    }
+                },
+            })
+    }
+
+    /// Confere um novo débito de `amount` (em reais) contra os limites do
+    /// titular, com `self` sendo a soma de todas as contas dele
+    pub fn check_user(&self, limits: &UserLimits, amount: Decimal) -> Result<(), TransactionError> {
+        if let Some(nightly) = self.nightly
+            && nightly + amount > limits.nightly
+        {
+            return Err(TransactionError::UserNightlyLimitExceeded);
+        }
+        if self.daily + amount > limits.daily {
+            return Err(TransactionError::UserDailyLimitExceeded);
+        }
+        if self.monthly + amount > limits.monthly {
+            return Err(TransactionError::UserMonthlyLimitExceeded);
+        }
+        Ok(())
+    }
+}
+
+/// Um limite da conta como o titular vê
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LimitStatus {
+    pub kind: LimitKind,
+    /// Valor em vigor
+    pub amount: Decimal,
+    /// Teto do tipo de conta
+    pub max: Decimal,
+    pub pending_amount: Option<Decimal>,
+    pub pending_effective_at: Option<DateTime<Utc>>,
+}
+
+/// Limites e uso da conta (`GET /accounts/{id}/limits`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountLimitsView {
+    pub account_id: Uuid,
+    pub account_type: AccountType,
+    pub limits: Vec<LimitStatus>,
+    pub usage: LimitUsage,
+}
+
+impl AccountLimitsView {
+    pub fn new(
+        account_id: Uuid,
+        account_type: AccountType,
+        defaults: &AccountLimits,
+        custom: &[AccountLimit],
+        usage: LimitUsage,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let limits = LimitKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let limit = custom.iter().find(|limit| limit.kind == kind);
+                // aumento já vencido aparece como valor em vigor, não como pendente
+                let pending = limit
+                    .filter(|limit| limit.effective(now) == limit.amount)
+                    .and_then(|limit| limit.pending_amount.zip(limit.pending_effective_at));
+                LimitStatus {
+                    kind,
+                    amount: limit.map_or_else(|| kind.max(defaults), |limit| limit.effective(now)),
+                    max: kind.max(defaults),
+                    pending_amount: pending.map(|(amount, _)| amount),
+                    pending_effective_at: pending.map(|(_, at)| at),
+                }
+            })
+            .collect();
+        Self {
+            account_id,
+            account_type,
+            limits,
+            usage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(raw: &str) -> DateTime<Utc> {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn test_windows_in_local_time() {
+        let config = LimitsConfig::default();
+
+        // 23h30 de 31/jan em Brasília
+        let windows = LimitWindows::at(utc("2025-02-01T02:30:00Z"), &config);
+        assert_eq!(windows.day_start, utc("2025-01-31T03:00:00Z"));
+        assert_eq!(windows.month_start, utc("2025-01-01T03:00:00Z"));
+        assert_eq!(windows.night_start, Some(utc("2025-01-31T23:00:00Z")));
+
+        // 5h da manhã: a noite começou ontem às 20h
+        let windows = LimitWindows::at(utc("2025-02-01T08:00:00Z"), &config);
+        assert_eq!(windows.night_start, Some(utc("2025-01-31T23:00:00Z")));
+        assert_eq!(windows.month_start, utc("2025-02-01T03:00:00Z"));
+
+        let windows = LimitWindows::at(utc("2025-02-01T15:00:00Z"), &config);
+        assert_eq!(windows.night_start, None);
+    }
+
+    #[test]
+    fn test_check_reports_the_limit_hit() {
+        let limits = EffectiveLimits::resolve(&LimitsConfig::default().checking, &[], Utc::now());
+        let mut usage = LimitUsage {
+            daily: Decimal::from(9_000),
+            monthly: Decimal::from(9_000),
+            nightly: None,
+        };
+
+        assert!(limits.check(&usage, Decimal::from(1_000)).is_ok());
+        assert!(matches!(
+            limits.check(&usage, Decimal::from(6_000)),
+            Err(TransactionError::PerTransactionLimitExceeded)
+        ));
+        assert!(matches!(
+            limits.check(&usage, Decimal::from(1_500)),
+            Err(TransactionError::DailyLimitExceeded)
+        ));
+        usage.nightly = Some(Decimal::from(500));
+        assert!(matches!(
+            limits.check(&usage, Decimal::from(600)),
+            Err(TransactionError::NightlyLimitExceeded)
+        ));
+    }
+
+    #[test]
+    fn test_user_limits_add_up_all_accounts() {
+        let limits = UserLimits {
+            daily: Decimal::from(14_000),
+            ..LimitsConfig::default().user
+        };
+        // conta em reais com R$ 8.000 no dia e conta em dólar com US$ 1.000 (R$ 5.000)
+        let brl = LimitUsage {
+            daily: Decimal::from(8_000),
+            monthly: Decimal::from(8_000),
+            nightly: None,
+        };
+        let usd = LimitUsage {
+            daily: Decimal::from(1_000),
+            monthly: Decimal::from(1_000),
+            nightly: None,
+        };
+        let usd_rate = Decimal::new(2, 1);
+        let total = LimitUsage::sum_in_brl(&[(brl, Decimal::ONE), (usd, usd_rate)]);
+        assert_eq!(total.daily, Decimal::from(13_000));
+        assert!(total.check_user(&limits, Decimal::from(500)).is_ok());
+
+        // a conta sozinha ainda comporta R$ 1.500, o titular não
+        let checking = EffectiveLimits::resolve(&LimitsConfig::default().checking, &[], Utc::now());
+        assert!(checking.check(&brl, Decimal::from(1_500)).is_ok());
+        assert!(matches!(
+            total.check_user(&limits, Decimal::from(1_500)),
+            Err(TransactionError::UserDailyLimitExceeded)
+        ));
+
+        let total = LimitUsage::sum_in_brl(&[
+            (
+                LimitUsage {
+                    nightly: Some(Decimal::from(400)),
+                    ..brl
+                },
+                Decimal::ONE,
+            ),
+            (
+                LimitUsage {
+                    nightly: Some(Decimal::from(100)),
+                    ..usd
+                },
+                usd_rate,
+            ),
+        ]);
+        assert_eq!(total.nightly, Some(Decimal::from(900)));
+        assert!(matches!(
+            total.check_user(&limits, Decimal::from(200)),
+            Err(TransactionError::UserNightlyLimitExceeded)
+        ));
+    }
+
+    #[test]
+    fn test_raise_waits_for_cooling_off() {
+        let now = Utc::now();
+        let cooling_off = Duration::hours(24);
+        let mut limit =
+            AccountLimit::new(Uuid::new_v4(), LimitKind::Daily, Decimal::from(2_000), now);
+
+        limit.change(Decimal::from(8_000), now, cooling_off);
+        assert_eq!(limit.effective(now), Decimal::from(2_000));
+        assert_eq!(limit.effective(now + cooling_off), Decimal::from(8_000));
+
+        // reduzir vale na hora e descarta o aumento pendente
+        limit.change(Decimal::from(1_000), now, cooling_off);
+        assert_eq!(limit.effective(now + cooling_off), Decimal::from(1_000));
+        assert!(limit.pending_amount.is_none());
+    }
+}
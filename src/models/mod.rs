@@ -1,9 +1,28 @@
-mod account;
+pub mod account;
 pub mod api_response;
+pub mod app_error;
+pub mod audit;
+pub mod boleto;
+pub mod brcode;
+pub mod business;
 pub mod claims;
+pub mod currency;
+pub mod event;
+pub mod fee;
+pub mod fx;
+pub mod health;
+pub mod hold;
+pub mod interest;
+pub mod kyc;
+pub mod limit;
+pub mod notification;
 pub mod pagination;
+pub mod pix;
 mod refresh_token;
+pub mod reversal;
+pub mod scheduled_transfer;
 pub mod transaction;
 mod user;
+pub mod webhook;
 
 pub use user::*;
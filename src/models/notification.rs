@@ -0,0 +1,220 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{
+    audit::{AuditAction, AuditContext},
+    kyc::{KycProfile, KycStatus},
+    pix::PixClaim,
+    scheduled_transfer::ScheduledTransfer,
+    transaction::Transaction,
+};
+
+/// Canal do `LISTEN/NOTIFY` por onde as notificações passam entre as réplicas
+pub const NOTIFICATION_CHANNEL: &str = "minibank_notifications";
+
+/// Aviso em tempo real para um usuário (SSE e WebSocket)
+///
+/// É publicado com `pg_notify` na transação da mudança, então só chega aos
+/// clientes depois do commit. Não há histórico: quem estava desconectado
+/// consulta saldo e extrato normalmente.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Notification {
+    pub id: Uuid,
+    /// Destinatário; cada conexão só recebe as próprias notificações
+    pub user_id: Uuid,
+    #[serde(flatten)]
+    pub kind: NotificationKind,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// `amount` é a variação do saldo: negativa em saques e transferências enviadas
+    BalanceChanged {
+        account_id: Uuid,
+        transaction_id: Uuid,
+        amount: Decimal,
+        balance: Decimal,
+    },
+    TransferReceived {
+        account_id: Uuid,
+        from_account_id: Uuid,
+        transaction_id: Uuid,
+        amount: Decimal,
+        description: String,
+    },
+    /// `action` é a mesma gravada na auditoria (ex.: `auth.login_failed`)
+    Security {
+        action: String,
+        ip: Option<String>,
+        user_agent: Option<String>,
+    },
+    /// Outro titular reivindicou uma chave PIX do usuário; sem resposta até
+    /// `resolution_deadline`, a chave passa para ele
+    PixKeyClaimed {
+        claim_id: Uuid,
+        pix_key_id: Uuid,
+        resolution_deadline: DateTime<Utc>,
+    },
+    /// O cadastro mudou de situação na revisão; `rejection_reason` só quando rejeitado
+    KycStatusChanged {
+        status: KycStatus,
+        rejection_reason: Option<String>,
+    },
+    /// Uma execução agendada falhou; `retry_at` vazio quando a ocorrência foi
+    /// abandonada (tentativas esgotadas)
+    ScheduledTransferFailed {
+        scheduled_transfer_id: Uuid,
+        account_id: Uuid,
+        /// Código do erro, o mesmo das respostas da API (ex.: `INSUFFICIENT_FUNDS`)
+        error: String,
+        attempt: i32,
+        retry_at: Option<DateTime<Utc>>,
+    },
+}
+
+impl Notification {
+    fn new(user_id: Uuid, kind: NotificationKind) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            kind,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    pub fn balance_changed(
+        user_id: Uuid,
+        account_id: Uuid,
+        transaction: &Transaction,
+        amount: Decimal,
+        balance: Decimal,
+    ) -> Self {
+        Self::new(
+            user_id,
+            NotificationKind::BalanceChanged {
+                account_id,
+                transaction_id: transaction.id,
+                amount,
+                balance,
+            },
+        )
+    }
+
+    /// Para o dono da conta de destino; `credit_leg` é a perna de crédito
+    pub fn transfer_received(
+        user_id: Uuid,
+        account_id: Uuid,
+        from_account_id: Uuid,
+        credit_leg: &Transaction,
+    ) -> Self {
+        Self::new(
+            user_id,
+            NotificationKind::TransferReceived {
+                account_id,
+                from_account_id,
+                transaction_id: credit_leg.id,
+                amount: credit_leg.amount,
+                description: credit_leg.description.clone(),
+            },
+        )
+    }
+
+    pub fn security(user_id: Uuid, action: AuditAction, audit: &AuditContext) -> Self {
+        Self::new(
+            user_id,
+            NotificationKind::Security {
+                action: action.as_str().to_string(),
+                ip: audit.ip.clone(),
+                user_agent: audit.user_agent.clone(),
+            },
+        )
+    }
+
+    /// Para o dono atual da chave reivindicada
+    pub fn pix_key_claimed(claim: &PixClaim) -> Self {
+        Self::new(
+            claim.donor_user_id,
+            NotificationKind::PixKeyClaimed {
+                claim_id: claim.id,
+                pix_key_id: claim.pix_key_id,
+                resolution_deadline: claim.resolution_deadline,
+            },
+        )
+    }
+
+    pub fn kyc_status_changed(profile: &KycProfile) -> Self {
+        Self::new(
+            profile.user_id,
+            NotificationKind::KycStatusChanged {
+                status: profile.status,
+                rejection_reason: profile.rejection_reason.clone(),
+            },
+        )
+    }
+
+    /// Para quem agendou; `attempt` é a tentativa que acabou de falhar
+    pub fn scheduled_transfer_failed(
+        schedule: &ScheduledTransfer,
+        error: &str,
+        attempt: i32,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self::new(
+            schedule.created_by,
+            NotificationKind::ScheduledTransferFailed {
+                scheduled_transfer_id: schedule.id,
+                account_id: schedule.account_id,
+                error: error.to_string(),
+                attempt,
+                retry_at,
+            },
+        )
+    }
+
+    /// Nome do evento no SSE e campo `type` no WebSocket
+    pub fn event_name(&self) -> &'static str {
+        match self.kind {
+            NotificationKind::BalanceChanged { .. } => "balance_changed",
+            NotificationKind::TransferReceived { .. } => "transfer_received",
+            NotificationKind::Security { .. } => "security",
+            NotificationKind::PixKeyClaimed { .. } => "pix_key_claimed",
+            NotificationKind::KycStatusChanged { .. } => "kyc_status_changed",
+            NotificationKind::ScheduledTransferFailed { .. } => "scheduled_transfer_failed",
+        }
+    }
+
+    /// Depois desta notificação a conexão é encerrada (o usuário foi desativado)
+    pub fn ends_session(&self) -> bool {
+        matches!(
+            &self.kind,
+            NotificationKind::Security { action, .. } if action == AuditAction::UserDeactivated.as_str()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_roundtrip() {
+        let notification = Notification::security(
+            Uuid::new_v4(),
+            AuditAction::LoginFailed,
+            &AuditContext::default(),
+        );
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["type"], "security");
+        assert_eq!(json["data"]["action"], "auth.login_failed");
+
+        let parsed: Notification = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, notification);
+        assert_eq!(parsed.event_name(), "security");
+        assert!(!parsed.ends_session());
+    }
+}
@@ -1,7 +1,8 @@
 use chrono::Utc;
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct Pagination {
     pub page: u32,
     pub limit: u32,
@@ -9,7 +10,33 @@ pub struct Pagination {
     pub pages: u32,
 }
 
-#[derive(Debug, Serialize)]
+/// Registros por página quando `limit` não é informado (RF016)
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+impl Pagination {
+    /// Normaliza `page`/`limit` vindos da query string (página começa em 1)
+    pub fn bounds(page: Option<u32>, limit: Option<u32>) -> (u32, u32) {
+        let page = page.unwrap_or(1).max(1);
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        (page, limit)
+    }
+
+    pub fn offset(page: u32, limit: u32) -> i64 {
+        i64::from(page - 1) * i64::from(limit)
+    }
+
+    pub fn new(page: u32, limit: u32, total: u64) -> Self {
+        Self {
+            page,
+            limit,
+            total,
+            pages: total.div_ceil(u64::from(limit)) as u32,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PaginationResponse<T> {
     pub sucess: bool,
     pub data: Vec<T>,
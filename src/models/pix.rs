@@ -0,0 +1,499 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::account::AccountType;
+
+/// Chaves ativas por conta (limite do regulamento do PIX para pessoa física)
+pub const MAX_KEYS_PER_ACCOUNT: i64 = 5;
+
+/// Prazo para o dono atual responder a uma reivindicação de posse
+pub const CLAIM_RESOLUTION_DAYS: i64 = 7;
+
+/// Validade do código de verificação de chave
+pub const VERIFICATION_TTL_MINUTES: i64 = 10;
+
+/// Tentativas erradas antes de o código deixar de valer
+pub const MAX_VERIFICATION_ATTEMPTS: i32 = 5;
+
+/// Códigos que um usuário pode pedir por hora
+pub const MAX_VERIFICATIONS_PER_HOUR: i64 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum PixKeyType {
+    Cpf,
+    Email,
+    Phone,
+    /// Chave aleatória (UUID) gerada pelo banco
+    Evp,
+}
+
+impl PixKeyType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PixKeyType::Cpf => "cpf",
+            PixKeyType::Email => "email",
+            PixKeyType::Phone => "phone",
+            PixKeyType::Evp => "evp",
+        }
+    }
+
+    /// Só email e telefone mudam de dono; CPF e chave aleatória são do titular
+    pub fn is_claimable(&self) -> bool {
+        matches!(self, PixKeyType::Email | PixKeyType::Phone)
+    }
+}
+
+/// Cadastro de chave; `key` é omitido quando o tipo é `evp`
+///
+/// Email e telefone exigem `verification` com o código enviado para a chave;
+/// CPF precisa ser o documento do titular da conta.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePixKey {
+    pub account_id: Uuid,
+    pub key_type: PixKeyType,
+    #[schema(example = "+5511987654321")]
+    pub key: Option<String>,
+    pub verification: Option<VerificationProof>,
+}
+
+/// Código recebido em `POST /pix/keys/verifications`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerificationProof {
+    pub verification_id: Uuid,
+    #[schema(example = "482913")]
+    pub code: String,
+}
+
+/// Pede o envio de um código para o email ou telefone informado
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePixKeyVerification {
+    #[schema(example = "+5511987654321")]
+    pub key: String,
+}
+
+/// Código de uso único enviado para a chave; só o hash fica guardado
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PixKeyVerification {
+    pub id: Uuid,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub user_id: Uuid,
+    pub key_type: PixKeyType,
+    #[sqlx(rename = "key_value")]
+    pub key: String,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub code_hash: String,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub attempts: i32,
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub consumed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PixKeyVerification {
+    /// Novo pedido e o código de 6 dígitos a enviar
+    pub fn new(user_id: Uuid, key_type: PixKeyType, key: String) -> (Self, String) {
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+        let code = format!("{:06}", Uuid::new_v4().as_u128() % 1_000_000);
+        let verification = Self {
+            id,
+            user_id,
+            key_type,
+            key,
+            code_hash: hash_code(id, &code),
+            attempts: 0,
+            expires_at: now + chrono::Duration::minutes(VERIFICATION_TTL_MINUTES),
+            consumed_at: None,
+            created_at: now,
+        };
+        (verification, code)
+    }
+
+    /// Ainda pode ser usado: não consumido, no prazo e abaixo do limite de tentativas
+    pub fn is_usable(&self) -> bool {
+        self.consumed_at.is_none()
+            && Utc::now() < self.expires_at
+            && self.attempts < MAX_VERIFICATION_ATTEMPTS
+    }
+
+    pub fn matches(&self, code: &str) -> bool {
+        hash_code(self.id, code.trim()) == self.code_hash
+    }
+}
+
+/// O ID entra no hash para o mesmo código não ter o mesmo hash em pedidos diferentes
+fn hash_code(id: Uuid, code: &str) -> String {
+    Sha256::digest(format!("{id}:{code}")).iter().fold(
+        String::with_capacity(64),
+        |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        },
+    )
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PixKey {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub user_id: Uuid,
+    pub key_type: PixKeyType,
+    /// Valor normalizado: CPF só com dígitos, email em minúsculas, telefone `+55…`
+    #[sqlx(rename = "key_value")]
+    pub key: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PixKey {
+    pub fn new(user_id: Uuid, account_id: Uuid, key_type: PixKeyType, key: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            account_id,
+            user_id,
+            key_type,
+            key,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn audit_snapshot(&self) -> Value {
+        serde_json::json!({
+            "account_id": self.account_id,
+            "key_type": self.key_type,
+            "key": self.key,
+        })
+    }
+}
+
+/// Resultado da consulta de chave: o suficiente para o pagador confirmar o destino
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PixKeyLookup {
+    pub key_type: PixKeyType,
+    #[sqlx(rename = "key_value")]
+    pub key: String,
+    /// Nome do titular com os sobrenomes abreviados
+    pub owner_name: String,
+    pub account_type: AccountType,
+}
+
+impl PixKeyLookup {
+    /// `Maria da Silva Souza` → `Maria D. S. S.`
+    pub fn masked(mut self) -> Self {
+        let mut parts = self.owner_name.split_whitespace();
+        let first = parts.next().unwrap_or_default().to_string();
+        let initials: Vec<String> = parts
+            .filter_map(|part| part.chars().next())
+            .map(|c| format!("{}.", c.to_uppercase()))
+            .collect();
+        self.owner_name = std::iter::once(first)
+            .chain(initials)
+            .collect::<Vec<_>>()
+            .join(" ");
+        self
+    }
+}
+
+/// Consulta por chave (`GET /pix/keys/lookup`)
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct PixKeyQuery {
+    /// CPF, email, telefone (`+55…`) ou chave aleatória; o tipo é detectado
+    pub key: String,
+}
+
+/// Traz para `account_id` uma chave hoje registrada em outra conta
+///
+/// A reivindicação de posse (chave de outro titular) exige `verification`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePixClaim {
+    #[schema(example = "maria@exemplo.com")]
+    pub key: String,
+    pub account_id: Uuid,
+    pub verification: Option<VerificationProof>,
+}
+
+/// `Portability`: a chave é do mesmo titular e muda de conta na hora.
+/// `Ownership`: a chave está com outro titular, que tem o prazo para responder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum ClaimType {
+    Portability,
+    Ownership,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum ClaimStatus {
+    Open,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PixClaim {
+    pub id: Uuid,
+    pub pix_key_id: Uuid,
+    pub claim_type: ClaimType,
+    pub status: ClaimStatus,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub claimer_user_id: Uuid,
+    pub claimer_account_id: Uuid,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub donor_user_id: Uuid,
+    pub donor_account_id: Uuid,
+    /// Depois dele, o reivindicante pode concluir sem resposta do dono atual
+    pub resolution_deadline: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Quando o reivindicante provou a posse da chave com o código
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+impl PixClaim {
+    pub fn open(key: &PixKey, claimer_user_id: Uuid, claimer_account_id: Uuid) -> Self {
+        let now = Utc::now();
+        let claim_type = if key.user_id == claimer_user_id {
+            ClaimType::Portability
+        } else {
+            ClaimType::Ownership
+        };
+        Self {
+            id: Uuid::new_v4(),
+            pix_key_id: key.id,
+            claim_type,
+            status: ClaimStatus::Open,
+            claimer_user_id,
+            claimer_account_id,
+            donor_user_id: key.user_id,
+            donor_account_id: key.account_id,
+            resolution_deadline: now + chrono::Duration::days(CLAIM_RESOLUTION_DAYS),
+            created_at: now,
+            updated_at: now,
+            resolved_at: None,
+            verified_at: None,
+        }
+    }
+
+    pub fn audit_snapshot(&self) -> Value {
+        serde_json::json!({
+            "pix_key_id": self.pix_key_id,
+            "claim_type": self.claim_type,
+            "status": self.status,
+            "claimer_account_id": self.claimer_account_id,
+            "donor_account_id": self.donor_account_id,
+        })
+    }
+}
+
+/// Situação gravada da cobrança; `expired` só aparece na consulta, pelo prazo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum ChargeStatus {
+    Active,
+    Completed,
+    Expired,
+}
+
+/// Cobrança de um BR Code dinâmico: valor fixo, validade e um único pagamento,
+/// identificado pelo `txid`
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PixCharge {
+    pub id: Uuid,
+    pub txid: String,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub user_id: Uuid,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub account_id: Uuid,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub pix_key_id: Uuid,
+    #[sqlx(rename = "key_value")]
+    pub key: String,
+    pub amount: Decimal,
+    pub description: Option<String>,
+    pub status: ChargeStatus,
+    pub expires_at: DateTime<Utc>,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PixCharge {
+    pub fn new(
+        key: &PixKey,
+        txid: String,
+        amount: Decimal,
+        description: Option<String>,
+        ttl: chrono::Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            txid,
+            user_id: key.user_id,
+            account_id: key.account_id,
+            pix_key_id: key.id,
+            key: key.key.clone(),
+            amount,
+            description,
+            status: ChargeStatus::Active,
+            expires_at: now + ttl,
+            paid_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Ainda pode ser paga: ativa e dentro da validade
+    pub fn is_payable(&self, now: DateTime<Utc>) -> bool {
+        self.status == ChargeStatus::Active && now < self.expires_at
+    }
+
+    /// Como a cobrança aparece na consulta, com o prazo já aplicado
+    pub fn with_current_status(mut self, now: DateTime<Utc>) -> Self {
+        if self.status == ChargeStatus::Active && now >= self.expires_at {
+            self.status = ChargeStatus::Expired;
+        }
+        self
+    }
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum PixError {
+        #[error("Chave PIX não encontrada")]
+        KeyNotFound,
+
+        #[error("Chave PIX já registrada")]
+        KeyAlreadyRegistered,
+
+        #[error("Limite de chaves PIX da conta atingido")]
+        KeyLimitReached,
+
+        #[error("Chave PIX deste tipo não pode ser reivindicada")]
+        NotClaimable,
+
+        #[error("Reivindicação não encontrada")]
+        ClaimNotFound,
+
+        #[error("Chave PIX já tem uma reivindicação em aberto")]
+        ClaimOpen,
+
+        #[error("Reivindicação não está em aberto")]
+        ClaimNotOpen,
+
+        #[error("Prazo de resposta da reivindicação ainda não terminou")]
+        ClaimDeadlinePending,
+
+        #[error("Chave PIX já está registrada nesta conta")]
+        SameAccount,
+
+        #[error("CPF da chave não é o documento do titular da conta")]
+        DocumentMismatch,
+
+        #[error("Chave PIX exige o código de verificação")]
+        VerificationRequired,
+
+        #[error("Código de verificação inválido ou expirado")]
+        VerificationInvalid,
+
+        #[error("Limite de códigos de verificação atingido")]
+        TooManyVerifications,
+
+        #[error("Reivindicação sem verificação de posse não conclui pelo prazo")]
+        ClaimNotVerified,
+
+        #[error("Cobrança PIX não encontrada")]
+        ChargeNotFound,
+
+        #[error("Cobrança PIX já paga ou vencida")]
+        ChargeNotPayable,
+
+        #[error("Chave ou valor diferentes dos da cobrança PIX")]
+        ChargeMismatch,
+
+        #[error("Já existe uma cobrança PIX com este txid")]
+        ChargeAlreadyExists,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_type_depends_on_owner() {
+        let owner = Uuid::new_v4();
+        let key = PixKey::new(
+            owner,
+            Uuid::new_v4(),
+            PixKeyType::Email,
+            "maria@exemplo.com".into(),
+        );
+
+        let claim = PixClaim::open(&key, owner, Uuid::new_v4());
+        assert_eq!(claim.claim_type, ClaimType::Portability);
+        let claim = PixClaim::open(&key, Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(claim.claim_type, ClaimType::Ownership);
+        assert_eq!(claim.donor_account_id, key.account_id);
+    }
+
+    #[test]
+    fn test_lookup_masks_surnames() {
+        let lookup = PixKeyLookup {
+            key_type: PixKeyType::Cpf,
+            key: "52998224725".into(),
+            owner_name: "Maria da Silva Souza".into(),
+            account_type: AccountType::Checking,
+        };
+        assert_eq!(lookup.masked().owner_name, "Maria D. S. S.");
+    }
+
+    #[test]
+    fn test_verification_code_matches_only_its_own_hash() {
+        let (verification, code) =
+            PixKeyVerification::new(Uuid::new_v4(), PixKeyType::Phone, "+5511987654321".into());
+        assert_eq!(code.len(), 6);
+        assert!(verification.matches(&code));
+        assert!(!verification.matches("1234567"));
+        assert!(verification.is_usable());
+
+        let (other, _) =
+            PixKeyVerification::new(Uuid::new_v4(), PixKeyType::Phone, "+5511987654321".into());
+        assert_ne!(verification.code_hash, other.code_hash);
+        let exhausted = PixKeyVerification {
+            attempts: MAX_VERIFICATION_ATTEMPTS,
+            ..verification
+        };
+        assert!(!exhausted.is_usable());
+    }
+}
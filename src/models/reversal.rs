@@ -0,0 +1,229 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{
+    reversal::error::ReversalError,
+    transaction::{Transaction, TransactionStatus, TransactionType},
+};
+
+/// Motivo do estorno, obrigatório
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReversalReason {
+    /// Lançamento em duplicidade
+    Duplicate,
+    Fraud,
+    /// Erro da operação (valor ou conta errados)
+    OperationalError,
+    /// Contestação do cliente aceita
+    Chargeback,
+    /// Exige `note`
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReversalStatus {
+    Completed,
+    /// A conta debitada não tinha saldo; `outstanding_amount` ainda será cobrado
+    PendingRecovery,
+}
+
+/// Estorno (total ou parcial) de uma transação concluída
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateReversal {
+    /// Padrão: todo o valor ainda não estornado
+    pub amount: Option<Decimal>,
+    pub reason: ReversalReason,
+    #[schema(example = "Transferência feita para a conta errada")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct Reversal {
+    pub id: Uuid,
+    /// Transação estornada; as pernas `reversal` apontam para ela em `reference_id`
+    pub transaction_id: Uuid,
+    pub amount: Decimal,
+    pub reason: ReversalReason,
+    pub note: Option<String>,
+    /// Administrador que estornou
+    pub created_by: Uuid,
+    /// Conta que recebe o valor de volta (a que foi debitada na original)
+    pub credit_account_id: Option<Uuid>,
+    /// Conta que devolve o valor (a que foi creditada na original)
+    pub debit_account_id: Option<Uuid>,
+    /// Parte ainda não debitada de `debit_account_id`
+    pub outstanding_amount: Decimal,
+    pub status: ReversalStatus,
+    pub next_recovery_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Reversal {
+    /// `outstanding` é o que não coube no saldo disponível da conta debitada
+    pub fn new(
+        original: &Transaction,
+        created_by: Uuid,
+        request: &CreateReversal,
+        amount: Decimal,
+        outstanding: Decimal,
+    ) -> Self {
+        let now = Utc::now();
+        let mut reversal = Self {
+            id: Uuid::new_v4(),
+            transaction_id: original.id,
+            amount,
+            reason: request.reason,
+            note: request
+                .note
+                .as_deref()
+                .map(str::trim)
+                .filter(|note| !note.is_empty())
+                .map(str::to_string),
+            created_by,
+            credit_account_id: original.from_account_id,
+            debit_account_id: original.to_account_id,
+            outstanding_amount: amount,
+            status: ReversalStatus::PendingRecovery,
+            next_recovery_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        reversal.recover(amount - outstanding, now, now);
+        reversal
+    }
+
+    /// Abate `recovered` do pendente; o que sobrar volta a ser cobrado em `retry_at`
+    pub fn recover(&mut self, recovered: Decimal, now: DateTime<Utc>, retry_at: DateTime<Utc>) {
+        self.outstanding_amount -= recovered;
+        if self.outstanding_amount.is_zero() {
+            self.status = ReversalStatus::Completed;
+            self.next_recovery_at = None;
+        } else {
+            self.next_recovery_at = Some(retry_at);
+        }
+        self.updated_at = now;
+    }
+
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "transaction_id": self.transaction_id,
+            "amount": self.amount,
+            "reason": self.reason,
+            "outstanding_amount": self.outstanding_amount,
+            "status": self.status,
+        })
+    }
+}
+
+/// Só lançamentos concluídos, e nunca um estorno nem uma transferência com câmbio
+///
+/// A perna de crédito da transferência e do boleto não chega aqui: o serviço
+/// estorna sempre pela perna de débito, a que carrega o total estornado.
+pub fn ensure_reversible(transaction: &Transaction) -> Result<(), ReversalError> {
+    if transaction.status != TransactionStatus::Completed
+        || transaction.transaction_type == TransactionType::Reversal
+        || transaction.fx_rate.is_some()
+    {
+        return Err(ReversalError::NotReversible);
+    }
+    Ok(())
+}
+
+/// Quanto sai agora da conta debitada e quanto fica pendente, dado o saldo disponível
+pub fn split_debit(amount: Decimal, available: Decimal) -> (Decimal, Decimal) {
+    let debited = amount.min(available.max(Decimal::ZERO));
+    (debited, amount - debited)
+}
+
+/// Filtros de `GET /admin/reversals`
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct ReversalQuery {
+    #[param(value_type = Option<ReversalStatus>)]
+    pub status: Option<ReversalStatus>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum ReversalError {
+        #[error("Transação não encontrada")]
+        TransactionNotFound,
+
+        #[error("Transação não pode ser estornada")]
+        NotReversible,
+
+        #[error("Transação já estornada por completo")]
+        AlreadyReversed,
+
+        #[error("Valor maior que o saldo a estornar da transação")]
+        ExceedsRemaining,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::currency::Currency;
+
+    #[test]
+    fn test_debit_shortfall_becomes_outstanding() {
+        let amount = Decimal::new(10000, 2);
+        assert_eq!(
+            split_debit(amount, Decimal::new(25000, 2)),
+            (amount, Decimal::ZERO)
+        );
+        assert_eq!(
+            split_debit(amount, Decimal::new(3000, 2)),
+            (Decimal::new(3000, 2), Decimal::new(7000, 2))
+        );
+        // reservas acima do saldo deixam o disponível negativo: nada sai agora
+        assert_eq!(
+            split_debit(amount, Decimal::new(-500, 2)),
+            (Decimal::ZERO, amount)
+        );
+
+        let original = Transaction::completed(
+            TransactionType::Deposit,
+            None,
+            Some(Uuid::new_v4()),
+            amount,
+            Currency::Brl,
+            "Depósito",
+        );
+        let request = CreateReversal {
+            amount: None,
+            reason: ReversalReason::Duplicate,
+            note: Some("  ".into()),
+        };
+        let mut reversal = Reversal::new(
+            &original,
+            Uuid::new_v4(),
+            &request,
+            amount,
+            Decimal::new(7000, 2),
+        );
+        assert_eq!(reversal.status, ReversalStatus::PendingRecovery);
+        assert!(reversal.note.is_none());
+        assert_eq!(reversal.debit_account_id, original.to_account_id);
+
+        let now = Utc::now();
+        reversal.recover(Decimal::new(7000, 2), now, now);
+        assert_eq!(reversal.status, ReversalStatus::Completed);
+        assert!(reversal.next_recovery_at.is_none());
+
+        let mut reversal_leg = original.clone();
+        reversal_leg.transaction_type = TransactionType::Reversal;
+        assert!(ensure_reversible(&original).is_ok());
+        assert!(ensure_reversible(&reversal_leg).is_err());
+    }
+}
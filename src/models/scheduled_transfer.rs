@@ -0,0 +1,403 @@
+use chrono::{DateTime, Duration, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::transaction::CreateTransfer;
+
+/// Primeira execução mais distante aceita no agendamento
+pub const MAX_SCHEDULE_DAYS: i64 = 365;
+
+/// Maior quantidade de ocorrências de uma recorrência
+pub const MAX_RUNS: u32 = 360;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum ScheduleFrequency {
+    /// Uma única execução na data agendada
+    #[default]
+    Once,
+    Weekly,
+    /// Mesmo dia do mês da primeira execução (ou o último dia, em meses mais curtos)
+    Monthly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum ScheduleStatus {
+    Active,
+    /// Todas as ocorrências foram processadas
+    Completed,
+    Cancelled,
+    /// Agendamento único que esgotou as tentativas
+    Failed,
+}
+
+/// Novas tentativas de uma ocorrência que falhou (ex.: saldo insuficiente)
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Tentativas por ocorrência, contando a primeira
+    pub max_attempts: i32,
+    pub retry_delay: Duration,
+}
+
+/// Agendamento de transferência; o destino segue as regras de `CreateTransfer`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateScheduledTransfer {
+    pub to_account_number: Option<String>,
+    #[schema(example = "maria@exemplo.com")]
+    pub to_pix_key: Option<String>,
+    pub amount: Decimal,
+    pub description: String,
+    /// Primeira execução; precisa estar no futuro
+    #[schema(example = "2025-10-05T09:00:00Z")]
+    pub scheduled_for: DateTime<Utc>,
+    #[serde(default)]
+    pub frequency: ScheduleFrequency,
+    /// Última data em que a recorrência pode executar (inclusiva, em UTC)
+    pub end_date: Option<NaiveDate>,
+    /// Quantidade de ocorrências da recorrência; alternativa a `end_date`
+    pub max_runs: Option<u32>,
+}
+
+impl CreateScheduledTransfer {
+    pub fn transfer_request(&self) -> CreateTransfer {
+        CreateTransfer {
+            to_account_number: self.to_account_number.clone(),
+            to_pix_key: self.to_pix_key.clone(),
+            amount: self.amount,
+            description: self.description.clone(),
+            fx_quote_id: None,
+            pix_txid: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ScheduledTransfer {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    /// Quem agendou; cada execução confere as permissões dele na conta
+    pub created_by: Uuid,
+    pub to_account_number: Option<String>,
+    /// Chave normalizada, resolvida de novo a cada execução
+    pub to_pix_key: Option<String>,
+    pub amount: Decimal,
+    pub description: String,
+    pub frequency: ScheduleFrequency,
+    /// Primeira ocorrência; as seguintes são contadas a partir dela
+    pub start_at: DateTime<Utc>,
+    pub end_date: Option<NaiveDate>,
+    pub max_runs: Option<i32>,
+    /// Índice da ocorrência atual (0 = `start_at`)
+    pub occurrence: i32,
+    /// Próxima execução ou nova tentativa; vazio depois de encerrado
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Tentativas que falharam na ocorrência atual
+    pub attempts: i32,
+    /// Código do último erro (ex.: `INSUFFICIENT_FUNDS`)
+    pub last_error: Option<String>,
+    pub status: ScheduleStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ScheduledTransfer {
+    pub fn new(account_id: Uuid, created_by: Uuid, request: &CreateScheduledTransfer) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            account_id,
+            created_by,
+            to_account_number: request
+                .to_account_number
+                .as_deref()
+                .map(|number| number.trim().to_string()),
+            to_pix_key: request.to_pix_key.clone(),
+            amount: request.amount,
+            description: request.description.trim().to_string(),
+            frequency: request.frequency,
+            start_at: request.scheduled_for,
+            end_date: request.end_date,
+            max_runs: request.max_runs.map(|max| max as i32),
+            occurrence: 0,
+            next_run_at: Some(request.scheduled_for),
+            attempts: 0,
+            last_error: None,
+            status: ScheduleStatus::Active,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Pedido repassado ao mesmo caminho de `POST /accounts/{id}/transfer`
+    pub fn transfer_request(&self) -> CreateTransfer {
+        CreateTransfer {
+            to_account_number: self.to_account_number.clone(),
+            to_pix_key: self.to_pix_key.clone(),
+            amount: self.amount,
+            description: self.description.clone(),
+            fx_quote_id: None,
+            pix_txid: None,
+        }
+    }
+
+    /// Data da ocorrência `index`; `None` depois da última (`max_runs` ou `end_date`)
+    pub fn occurrence_at(&self, index: i32) -> Option<DateTime<Utc>> {
+        let at = match self.frequency {
+            ScheduleFrequency::Once if index == 0 => self.start_at,
+            ScheduleFrequency::Once => return None,
+            ScheduleFrequency::Weekly => self.start_at + Duration::weeks(i64::from(index)),
+            ScheduleFrequency::Monthly => self
+                .start_at
+                .checked_add_months(Months::new(u32::try_from(index).ok()?))?,
+        };
+        if self.max_runs.is_some_and(|max| index >= max)
+            || self.end_date.is_some_and(|end| at.date_naive() > end)
+        {
+            return None;
+        }
+        Some(at)
+    }
+
+    /// Ocorrência atual executada
+    pub fn succeed(&mut self, now: DateTime<Utc>) {
+        self.last_error = None;
+        self.advance(now);
+    }
+
+    /// Registra a falha da ocorrência atual e devolve quando será a nova tentativa
+    ///
+    /// Sem tentativas restantes (ou se a próxima cairia depois da ocorrência
+    /// seguinte) a ocorrência é abandonada: `None`. Um agendamento único
+    /// termina em `failed`.
+    pub fn fail(
+        &mut self,
+        error: &str,
+        now: DateTime<Utc>,
+        policy: &RetryPolicy,
+    ) -> Option<DateTime<Utc>> {
+        self.attempts += 1;
+        self.last_error = Some(error.to_string());
+
+        let retry_at = now + policy.retry_delay;
+        let before_next = self
+            .occurrence_at(self.occurrence + 1)
+            .is_none_or(|following| retry_at < following);
+        if self.attempts < policy.max_attempts && before_next {
+            self.next_run_at = Some(retry_at);
+            self.updated_at = now;
+            return Some(retry_at);
+        }
+
+        self.advance(now);
+        if self.frequency == ScheduleFrequency::Once {
+            self.status = ScheduleStatus::Failed;
+        }
+        None
+    }
+
+    pub fn cancel(&mut self, now: DateTime<Utc>) {
+        self.status = ScheduleStatus::Cancelled;
+        self.next_run_at = None;
+        self.updated_at = now;
+    }
+
+    /// Passa para a próxima ocorrência ou encerra o agendamento
+    fn advance(&mut self, now: DateTime<Utc>) {
+        self.attempts = 0;
+        self.occurrence += 1;
+        // ocorrências perdidas com o agendador parado não se acumulam: só a mais recente roda
+        while self
+            .occurrence_at(self.occurrence + 1)
+            .is_some_and(|following| following <= now)
+        {
+            self.occurrence += 1;
+        }
+        self.next_run_at = self.occurrence_at(self.occurrence);
+        if self.next_run_at.is_none() {
+            self.status = ScheduleStatus::Completed;
+        }
+        self.updated_at = now;
+    }
+
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "account_id": self.account_id,
+            "to_account_number": self.to_account_number,
+            "to_pix_key": self.to_pix_key,
+            "amount": self.amount,
+            "frequency": self.frequency,
+            "start_at": self.start_at,
+            "end_date": self.end_date,
+            "max_runs": self.max_runs,
+            "status": self.status,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum RunStatus {
+    Completed,
+    Failed,
+}
+
+/// Uma tentativa de execução de um agendamento
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ScheduledTransferRun {
+    pub id: Uuid,
+    pub scheduled_transfer_id: Uuid,
+    pub occurrence: i32,
+    pub attempt: i32,
+    pub status: RunStatus,
+    /// Perna de débito da transferência, quando executada
+    pub transaction_id: Option<Uuid>,
+    /// Código do erro, quando falhou
+    pub error: Option<String>,
+    pub executed_at: DateTime<Utc>,
+}
+
+impl ScheduledTransferRun {
+    /// Tentativa da ocorrência atual, antes de o agendamento avançar
+    pub fn new(
+        schedule: &ScheduledTransfer,
+        transaction_id: Option<Uuid>,
+        error: Option<&str>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            scheduled_transfer_id: schedule.id,
+            occurrence: schedule.occurrence,
+            attempt: schedule.attempts + 1,
+            status: if error.is_some() {
+                RunStatus::Failed
+            } else {
+                RunStatus::Completed
+            },
+            transaction_id,
+            error: error.map(str::to_string),
+            executed_at: Utc::now(),
+        }
+    }
+}
+
+/// Agendamento com as últimas execuções
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScheduledTransferDetails {
+    pub scheduled_transfer: ScheduledTransfer,
+    pub runs: Vec<ScheduledTransferRun>,
+}
+
+/// Filtros de `GET /accounts/{id}/scheduled-transfers`
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct ScheduledTransferFilter {
+    #[param(value_type = Option<ScheduleStatus>)]
+    pub status: Option<ScheduleStatus>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum ScheduledTransferError {
+        #[error("Agendamento não encontrado")]
+        NotFound,
+
+        #[error("Agendamento já encerrado ou cancelado")]
+        NotActive,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn schedule(
+        frequency: ScheduleFrequency,
+        start_at: DateTime<Utc>,
+        max_runs: Option<u32>,
+    ) -> ScheduledTransfer {
+        let request = CreateScheduledTransfer {
+            to_account_number: Some("12345678".into()),
+            to_pix_key: None,
+            amount: Decimal::ONE_HUNDRED,
+            description: "Aluguel".into(),
+            scheduled_for: start_at,
+            frequency,
+            end_date: None,
+            max_runs,
+        };
+        ScheduledTransfer::new(Uuid::new_v4(), Uuid::new_v4(), &request)
+    }
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            retry_delay: Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn test_monthly_occurrences_keep_the_day_of_month() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 31, 9, 0, 0).unwrap();
+        let mut schedule = schedule(ScheduleFrequency::Monthly, start, Some(3));
+
+        let day = |index| schedule.occurrence_at(index).map(|at| at.date_naive());
+        assert_eq!(day(1), NaiveDate::from_ymd_opt(2025, 2, 28));
+        assert_eq!(day(2), NaiveDate::from_ymd_opt(2025, 3, 31));
+        assert_eq!(day(3), None);
+
+        schedule.end_date = NaiveDate::from_ymd_opt(2025, 3, 30);
+        assert_eq!(schedule.occurrence_at(2), None);
+    }
+
+    #[test]
+    fn test_missed_occurrences_do_not_pile_up() {
+        let start = Utc.with_ymd_and_hms(2025, 9, 1, 9, 0, 0).unwrap();
+        let mut schedule = schedule(ScheduleFrequency::Weekly, start, None);
+
+        // agendador parado por três semanas: só a ocorrência mais recente roda
+        let now = start + Duration::weeks(3) + Duration::hours(1);
+        schedule.succeed(now);
+        assert_eq!(schedule.occurrence, 3);
+        assert_eq!(schedule.next_run_at, Some(start + Duration::weeks(3)));
+        assert_eq!(schedule.status, ScheduleStatus::Active);
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        let start = Utc.with_ymd_and_hms(2025, 9, 1, 9, 0, 0).unwrap();
+        let mut once = schedule(ScheduleFrequency::Once, start, None);
+
+        assert_eq!(
+            once.fail("INSUFFICIENT_FUNDS", start, &policy()),
+            Some(start + Duration::hours(1))
+        );
+        assert_eq!(once.attempts, 1);
+        once.fail("INSUFFICIENT_FUNDS", start, &policy());
+        assert_eq!(once.fail("INSUFFICIENT_FUNDS", start, &policy()), None);
+        assert_eq!(once.status, ScheduleStatus::Failed);
+        assert_eq!(once.next_run_at, None);
+
+        // a nova tentativa não pode passar da ocorrência seguinte
+        let mut weekly = schedule(ScheduleFrequency::Weekly, start, Some(2));
+        let late = start + Duration::weeks(1) - Duration::minutes(30);
+        assert_eq!(weekly.fail("INSUFFICIENT_FUNDS", late, &policy()), None);
+        assert_eq!(weekly.occurrence, 1);
+        assert_eq!(weekly.attempts, 0);
+        assert_eq!(weekly.last_error.as_deref(), Some("INSUFFICIENT_FUNDS"));
+
+        weekly.succeed(start + Duration::weeks(1));
+        assert_eq!(weekly.status, ScheduleStatus::Completed);
+        assert_eq!(weekly.last_error, None);
+    }
+}
@@ -1,36 +1,84 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::models::currency::Currency;
+
 /// Dados para operações financeiras
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTransaction {
     pub amount: Decimal,
     pub description: String,
 }
 
-/// Dados para transferência
-#[derive(Debug, Deserialize)]
+/// Dados para transferência; o destino é o número da conta ou uma chave PIX
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTransfer {
-    pub to_account_number: String,
+    pub to_account_number: Option<String>,
+    /// CPF, email, telefone (`+55…`) ou chave aleatória
+    #[schema(example = "maria@exemplo.com")]
+    pub to_pix_key: Option<String>,
     pub amount: Decimal,
     pub description: String,
+    /// Obrigatória quando as contas estão em moedas diferentes (`POST /accounts/{id}/fx-quotes`)
+    #[serde(default)]
+    pub fx_quote_id: Option<Uuid>,
+    /// Cobrança de um BR Code dinâmico paga pela transferência (`POST /pix/qr/parse`)
+    #[serde(default)]
+    #[schema(example = "7D9F0335A7B34A6C8E2F0B1D2")]
+    pub pix_txid: Option<String>,
 }
 
 /// Tipos de transação
-#[derive(Debug, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "transaction_type", rename_all = "snake_case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "transaction_type_enum", rename_all = "snake_case")]
 pub enum TransactionType {
     Deposit,
     Withdraw,
     TransferDebit,
     TransferCredit,
+    /// Pagamento de boleto (débito do pagador)
+    BoletoPayment,
+    /// Boleto emitido aqui e pago (crédito do beneficiário)
+    BoletoCredit,
+    /// Captura de uma reserva; fica pendente enquanto a reserva está ativa
+    HoldCapture,
+    /// Perna de estorno; `reference_id` aponta para a transação estornada
+    Reversal,
+    /// Tarifa; `reference_id` aponta para a operação tarifada
+    Fee,
+    /// Crédito mensal dos juros apurados
+    Interest,
+    /// Débito mensal dos juros do cheque especial
+    OverdraftInterest,
+}
+
+impl TransactionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionType::Deposit => "deposit",
+            TransactionType::Withdraw => "withdraw",
+            TransactionType::TransferDebit => "transfer_debit",
+            TransactionType::TransferCredit => "transfer_credit",
+            TransactionType::BoletoPayment => "boleto_payment",
+            TransactionType::BoletoCredit => "boleto_credit",
+            TransactionType::HoldCapture => "hold_capture",
+            TransactionType::Reversal => "reversal",
+            TransactionType::Fee => "fee",
+            TransactionType::Interest => "interest",
+            TransactionType::OverdraftInterest => "overdraft_interest",
+        }
+    }
 }
 
 /// Status da transação
-#[derive(Debug, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "transaction_status", rename_all = "lowercase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "transaction_status_enum", rename_all = "lowercase")]
 pub enum TransactionStatus {
     Pending,
     Completed,
@@ -38,19 +86,90 @@ pub enum TransactionStatus {
 }
 
 /// Entidade Transaction
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
 pub struct Transaction {
     pub id: Uuid,
     pub from_account_id: Option<Uuid>,
     pub to_account_id: Option<Uuid>,
     pub amount: Decimal,
+    /// Moeda da conta lançada (a de origem no débito, a de destino no crédito)
+    pub currency: Currency,
     pub transaction_type: TransactionType,
     pub description: String,
-    pub reference_id: Option<Uuid>, // Para linking de transferências
+    pub reference_id: Option<Uuid>, // Para linking de transferências e estornos
     pub status: TransactionStatus,
+    /// Taxa aplicada numa transferência entre moedas, já com o spread (nas duas pernas)
+    pub fx_rate: Option<Decimal>,
+    /// Spread da conversão, em %
+    pub fx_spread: Option<Decimal>,
     pub created_at: DateTime<Utc>,
 }
 
+impl Transaction {
+    /// Lançamento já concluído (depósito, saque e as pernas da transferência)
+    pub fn completed(
+        transaction_type: TransactionType,
+        from_account_id: Option<Uuid>,
+        to_account_id: Option<Uuid>,
+        amount: Decimal,
+        currency: Currency,
+        description: &str,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            from_account_id,
+            to_account_id,
+            amount,
+            currency,
+            transaction_type,
+            description: description.trim().to_string(),
+            reference_id: None,
+            status: TransactionStatus::Completed,
+            fx_rate: None,
+            fx_spread: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Lançamento que só movimenta o saldo quando concluído (captura de reserva)
+    pub fn pending(
+        transaction_type: TransactionType,
+        from_account_id: Option<Uuid>,
+        to_account_id: Option<Uuid>,
+        amount: Decimal,
+        currency: Currency,
+        description: &str,
+    ) -> Self {
+        Self {
+            status: TransactionStatus::Pending,
+            ..Self::completed(
+                transaction_type,
+                from_account_id,
+                to_account_id,
+                amount,
+                currency,
+                description,
+            )
+        }
+    }
+}
+
+/// Filtros do histórico (`GET /accounts/{id}/transactions`)
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct TransactionFilter {
+    #[serde(rename = "type")]
+    #[param(rename = "type", value_type = Option<TransactionType>)]
+    pub transaction_type: Option<TransactionType>,
+    /// Início do período (inclusivo)
+    pub from: Option<DateTime<Utc>>,
+    /// Fim do período (exclusivo)
+    pub to: Option<DateTime<Utc>>,
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
 pub mod error {
     #[derive(Debug, thiserror::Error)]
     pub enum TransactionError {
@@ -69,6 +188,27 @@ pub mod error {
         #[error("Não é possível transferir para a mesma conta")]
         SameAccountTransfer,
 
+        #[error("Valor acima do limite por transação")]
+        PerTransactionLimitExceeded,
+
+        #[error("Limite diário excedido")]
+        DailyLimitExceeded,
+
+        #[error("Limite mensal excedido")]
+        MonthlyLimitExceeded,
+
+        #[error("Limite noturno excedido")]
+        NightlyLimitExceeded,
+
+        #[error("Limite diário do titular excedido")]
+        UserDailyLimitExceeded,
+
+        #[error("Limite mensal do titular excedido")]
+        UserMonthlyLimitExceeded,
+
+        #[error("Limite noturno do titular excedido")]
+        UserNightlyLimitExceeded,
+
         #[error("Erro no banco de dados: {0}")]
         DatabaseError(#[from] sqlx::Error),
     }
@@ -1,26 +1,55 @@
+use std::fmt;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::{models::error::UserError, utils::hash_password, validators::UserValidator};
+use crate::{
+    models::error::UserError,
+    utils::hash_password,
+    validators::{DocumentValidator, UserValidator, ValidationErrors},
+};
 
 /// Dados que chegam do endpoint de registro
-#[derive(Debug, Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateUser {
+    #[schema(example = "joao.silva@email.com")]
     pub email: String,
+    #[schema(example = "João Silva")]
     pub name: String,
+    #[schema(format = Password, example = "Senha123")]
     pub password: String, // senha em texto claro
+    /// Pessoa física (padrão) ou empresa
+    #[serde(default)]
+    pub customer_type: CustomerType,
+    /// CPF para pessoa física, CNPJ para empresa; com ou sem pontuação
+    #[schema(example = "529.982.247-25")]
+    pub document: String,
+    /// Idioma preferido para as mensagens da API (pt-BR, en, es)
+    #[serde(default)]
+    #[schema(example = "pt-BR")]
+    pub locale: Option<String>,
+}
+
+/// Preferências do usuário que podem ser alteradas depois do cadastro
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePreferences {
+    #[schema(example = "en")]
+    pub locale: String,
 }
 
 ///login request
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, ToSchema)]
 pub struct LoginUserRequest {
+    #[schema(example = "joao.silva@email.com")]
     pub email: String,
+    #[schema(format = Password)]
     pub password: String,
 }
 ///Resposta de login
-#[derive(Debug, Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct LoginUserResponse {
     pub refresh_token: String,
     pub token: String,
@@ -28,18 +57,226 @@ pub struct LoginUserResponse {
     pub email: String,
 }
 
+/// Troca de refresh token (`POST /auth/refresh`)
+#[derive(Deserialize, ToSchema)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+/// Papel do usuário; `Admin` libera as rotas administrativas
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum UserRole {
+    #[default]
+    Customer,
+    Admin,
+}
+
+/// Pessoa física (CPF) ou empresa (CNPJ); empresa pode ter operadores
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum CustomerType {
+    #[default]
+    Individual,
+    Business,
+}
+
+/// Faixa de relacionamento; define as tarifas cobradas (`[fees]`)
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum CustomerTier {
+    #[default]
+    Standard,
+    Premium,
+}
+
+/// Nova faixa do cliente (`PUT /admin/users/{id}/tier`)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTier {
+    pub tier: CustomerTier,
+}
+
+/// CPF ou CNPJ só com os dígitos
+///
+/// Serializa e aparece em logs sempre mascarado; o número completo só sai por `as_str`.
+#[derive(Clone, PartialEq, Eq, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct Document(String);
+
+impl Document {
+    pub fn new(digits: String) -> Self {
+        Self(digits)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// `***.982.247-**` para CPF, `**.222.333/0001-**` para CNPJ
+    pub fn masked(&self) -> String {
+        let digits = &self.0;
+        match digits.len() {
+            11 => format!("***.{}.{}-**", &digits[3..6], &digits[6..9]),
+            14 => format!(
+                "**.{}.{}/{}-**",
+                &digits[2..5],
+                &digits[5..8],
+                &digits[8..12]
+            ),
+            _ => "*".repeat(digits.len()),
+        }
+    }
+}
+
+impl Serialize for Document {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.masked())
+    }
+}
+
+impl fmt::Debug for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.masked())
+    }
+}
+
 /// Entidade User final - pronta para persistência
-#[derive(Debug, Serialize, FromRow)]
+#[derive(Serialize, FromRow)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
     pub name: String,
     pub password_hash: String,
+    pub locale: Option<String>,
+    pub role: UserRole,
+    pub customer_type: CustomerType,
+    pub tier: CustomerTier,
+    /// Usuários cadastrados antes da identificação não têm documento
+    pub document: Option<Document>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Dados do próprio usuário (`GET /users/me`)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserProfile {
+    pub id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub customer_type: CustomerType,
+    pub tier: CustomerTier,
+    #[schema(value_type = Option<String>, example = "***.982.247-**")]
+    pub document: Option<Document>,
+    pub locale: Option<String>,
+    pub role: UserRole,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<User> for UserProfile {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            customer_type: user.customer_type,
+            tier: user.tier,
+            document: user.document,
+            locale: user.locale,
+            role: user.role,
+            created_at: user.created_at,
+        }
+    }
+}
+
+// Debug manual: senhas, hashes e tokens não podem aparecer em logs
+const REDACTED: &str = "[REDACTED]";
+
+impl fmt::Debug for CreateUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateUser")
+            .field("email", &self.email)
+            .field("name", &self.name)
+            .field("password", &REDACTED)
+            .field("customer_type", &self.customer_type)
+            .field("document", &REDACTED)
+            .field("locale", &self.locale)
+            .finish()
+    }
+}
+
+impl fmt::Debug for LoginUserRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoginUserRequest")
+            .field("email", &self.email)
+            .field("password", &REDACTED)
+            .finish()
+    }
+}
+
+impl fmt::Debug for LoginUserResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoginUserResponse")
+            .field("refresh_token", &REDACTED)
+            .field("token", &REDACTED)
+            .field("user_id", &self.user_id)
+            .field("email", &self.email)
+            .finish()
+    }
+}
+
+impl fmt::Debug for RefreshTokenRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefreshTokenRequest")
+            .field("refresh_token", &REDACTED)
+            .finish()
+    }
+}
+
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("email", &self.email)
+            .field("name", &self.name)
+            .field("password_hash", &REDACTED)
+            .field("locale", &self.locale)
+            .field("role", &self.role)
+            .field("customer_type", &self.customer_type)
+            .field("tier", &self.tier)
+            .field("document", &self.document)
+            .field("is_active", &self.is_active)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .finish()
+    }
+}
+
+impl User {
+    /// Estado relevante para auditoria (nunca inclui o hash da senha)
+    pub fn audit_snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "email": self.email,
+            "name": self.name,
+            "locale": self.locale,
+            "role": self.role,
+            "customer_type": self.customer_type,
+            "tier": self.tier,
+            "document": self.document,
+            "is_active": self.is_active,
+        })
+    }
+}
+
 impl TryFrom<CreateUser> for User {
     type Error = UserError;
     fn try_from(create_user: CreateUser) -> Result<Self, Self::Error> {
@@ -47,7 +284,30 @@ impl TryFrom<CreateUser> for User {
             &create_user.name,
             &create_user.email,
             &create_user.password,
-        )?;
+        );
+        let locale = create_user
+            .locale
+            .as_deref()
+            .map(UserValidator::validate_locale)
+            .transpose();
+        let document =
+            DocumentValidator::validate(create_user.customer_type, &create_user.document);
+
+        let (validated, locale, document) = match (validated, locale, document) {
+            (Ok(validated), Ok(locale), Ok(document)) => (validated, locale, document),
+            (validated, locale, document) => {
+                // falhas de idioma e documento se juntam às dos outros campos
+                let mut errors = match validated {
+                    Err(UserError::Validation(errors)) => errors,
+                    Err(err) => return Err(err),
+                    Ok(_) => ValidationErrors::new(),
+                };
+                for err in [locale.err(), document.err()].into_iter().flatten() {
+                    errors.push(err);
+                }
+                return Err(UserError::Validation(errors));
+            }
+        };
 
         let password_hash =
             hash_password(&validated.password).map_err(|_| UserError::WeakPassword)?;
@@ -59,6 +319,11 @@ impl TryFrom<CreateUser> for User {
             email: validated.email,
             name: validated.name,
             password_hash: password_hash,
+            locale: locale.map(|locale| locale.as_tag().to_string()),
+            role: UserRole::Customer,
+            customer_type: create_user.customer_type,
+            tier: CustomerTier::Standard,
+            document: Some(document),
             is_active: true,
             created_at: now,
             updated_at: now,
@@ -67,8 +332,13 @@ impl TryFrom<CreateUser> for User {
 }
 
 pub mod error {
+    use crate::validators::ValidationErrors;
+
     #[derive(Debug, thiserror::Error)]
     pub enum UserError {
+        #[error("Dados inválidos: {0}")]
+        Validation(ValidationErrors),
+
         #[error("Email inválido: {0}")]
         InvalidEmail(String),
 
@@ -81,13 +351,65 @@ pub mod error {
         #[error("Email já existe no sistema")]
         EmailAlreadyExists,
 
+        #[error("Documento já cadastrado no sistema")]
+        DocumentAlreadyExists,
+
         #[error("Usuário não encontrado")]
         NotFound,
 
         #[error("Credenciais inválidas")]
         InvalidCredentials,
 
+        #[error("Refresh token inválido ou expirado")]
+        InvalidRefreshToken,
+
         #[error("Erro no banco de dados: {0}")]
         DatabaseError(#[from] sqlx::Error),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_secrets() {
+        let request = LoginUserRequest {
+            email: "joao.silva@email.com".into(),
+            password: "Senha123".into(),
+        };
+        let user = User {
+            id: Uuid::new_v4(),
+            email: "joao.silva@email.com".into(),
+            name: "João Silva".into(),
+            password_hash: "$2b$12$hashdeexemplo".into(),
+            locale: None,
+            role: UserRole::Customer,
+            customer_type: CustomerType::Individual,
+            tier: CustomerTier::Standard,
+            document: Some(Document::new("52998224725".into())),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let logged = format!("{request:?} {user:?}");
+        assert!(!logged.contains("Senha123"));
+        assert!(!logged.contains("hashdeexemplo"));
+        assert!(!logged.contains("52998224725"));
+        assert!(logged.contains("joao.silva@email.com"));
+    }
+
+    #[test]
+    fn test_document_is_masked_when_serialized() {
+        let cpf = Document::new("52998224725".into());
+        let cnpj = Document::new("11222333000181".into());
+
+        assert_eq!(
+            serde_json::to_value(&cpf).unwrap(),
+            serde_json::json!("***.982.247-**")
+        );
+        assert_eq!(cnpj.masked(), "**.222.333/0001-**");
+        assert_eq!(cpf.as_str(), "52998224725");
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::prelude::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Assinatura de webhook enviada pelo usuário
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWebhook {
+    #[schema(example = "https://parceiro.com/minibank/webhook")]
+    pub url: String,
+    /// Tipos de evento entregues, ex.: `["DepositCompleted", "TransferCompleted"]`
+    pub event_types: Vec<String>,
+    /// Segredo do HMAC; se omitido, um é gerado e devolvido uma única vez
+    pub secret: Option<String>,
+}
+
+impl fmt::Debug for CreateWebhook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateWebhook")
+            .field("url", &self.url)
+            .field("event_types", &self.event_types)
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+#[derive(Clone, Serialize, FromRow, ToSchema)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    /// Nunca serializado; só aparece na resposta de criação (`CreatedWebhook`)
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub secret: String,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    pub fn new(user_id: Uuid, url: String, event_types: Vec<String>, secret: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            url,
+            event_types,
+            secret,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Dados gravados na auditoria (sem o segredo)
+    pub fn audit_snapshot(&self) -> Value {
+        serde_json::json!({
+            "url": self.url,
+            "event_types": self.event_types,
+            "is_active": self.is_active,
+        })
+    }
+}
+
+impl fmt::Debug for WebhookSubscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebhookSubscription")
+            .field("id", &self.id)
+            .field("user_id", &self.user_id)
+            .field("url", &self.url)
+            .field("event_types", &self.event_types)
+            .field("secret", &"[REDACTED]")
+            .field("is_active", &self.is_active)
+            .finish()
+    }
+}
+
+/// Resposta da criação: a única vez em que o segredo é devolvido
+#[derive(Serialize, ToSchema)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+/// Segredo aleatório (256 bits) para assinar as entregas
+pub fn generate_secret() -> String {
+    format!(
+        "whsec_{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// `Dead` é a fila de mensagens mortas: esgotou as tentativas e só volta por replay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Dead,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    /// Corpo enviado (envelope do evento)
+    pub payload: Value,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Entrega reservada pelo dispatcher, com o destino e o segredo da assinatura
+#[derive(Clone, FromRow)]
+pub struct DueDelivery {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: Value,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+/// Uma tentativa de entrega, como registrada no log
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DeliveryAttempt {
+    pub attempt: i32,
+    /// Status HTTP recebido; ausente em falhas de conexão ou timeout
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// Filtros do log de entregas (`GET /webhooks/{id}/deliveries`)
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct DeliveryFilter {
+    pub status: Option<DeliveryStatus>,
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+pub mod error {
+    #[derive(Debug, thiserror::Error)]
+    pub enum WebhookError {
+        #[error("Webhook não encontrado")]
+        NotFound,
+
+        #[error("Entrega de webhook não encontrada")]
+        DeliveryNotFound,
+
+        #[error("Entrega ainda pendente; aguarde a próxima tentativa")]
+        DeliveryPending,
+    }
+}
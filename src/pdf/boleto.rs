@@ -0,0 +1,297 @@
+//! Boleto em PDF: recibo do pagador e ficha de compensação com o código de barras
+
+use crate::{
+    models::boleto::{BANK_CODE, Boleto, BoletoBarcode, BoletoStatus},
+    pdf::{A4_WIDTH, Font, MM, Page, PdfDocument, format_brl},
+};
+
+/// Nome impresso no cabeçalho das duas partes
+const BANK_NAME: &str = "Mini Bank";
+
+const MARGIN: f32 = 15.0 * MM;
+const CONTENT_WIDTH: f32 = A4_WIDTH - 2.0 * MARGIN;
+const ROW_HEIGHT: f32 = 24.0;
+/// Coluna da direita (vencimento, valores) da ficha de compensação
+const SIDE_WIDTH: f32 = 130.0;
+
+/// Módulo estreito do ITF: 405 módulos ocupam ~103 mm, como pede a Febraban
+const BARCODE_NARROW: f32 = 0.72;
+const BARCODE_HEIGHT: f32 = 13.0 * MM;
+
+/// Quem recebe: titular e número da conta de crédito
+pub struct Beneficiary<'a> {
+    pub name: &'a str,
+    pub account_number: &'a str,
+}
+
+pub fn render(boleto: &Boleto, beneficiary: &Beneficiary) -> Vec<u8> {
+    let barcode = BoletoBarcode::parse(&boleto.barcode)
+        .expect("boleto gravado sempre tem código de barras válido");
+    let line = barcode.formatted_line();
+    let due_date = boleto.due_date.format("%d/%m/%Y").to_string();
+    let issued_at = boleto.created_at.format("%d/%m/%Y").to_string();
+    let amount = format_brl(boleto.amount);
+
+    let mut document = PdfDocument::new().with_title(format!("Boleto {}", boleto.our_number));
+    let page = document.add_page();
+
+    // recibo do pagador
+    let mut y = MARGIN;
+    header(page, y, &line);
+    y += 26.0;
+    field(
+        page,
+        MARGIN,
+        y,
+        CONTENT_WIDTH - 2.0 * SIDE_WIDTH,
+        "Beneficiário",
+        beneficiary.name,
+    );
+    field(
+        page,
+        A4_WIDTH - MARGIN - 2.0 * SIDE_WIDTH,
+        y,
+        SIDE_WIDTH,
+        "Agência/Conta",
+        &format!("0001 / {}", beneficiary.account_number),
+    );
+    field(
+        page,
+        A4_WIDTH - MARGIN - SIDE_WIDTH,
+        y,
+        SIDE_WIDTH,
+        "Nosso número",
+        &boleto.our_number,
+    );
+    y += ROW_HEIGHT;
+    field(
+        page,
+        MARGIN,
+        y,
+        CONTENT_WIDTH - 2.0 * SIDE_WIDTH,
+        "Pagador",
+        &boleto.payer_name,
+    );
+    field(
+        page,
+        A4_WIDTH - MARGIN - 2.0 * SIDE_WIDTH,
+        y,
+        SIDE_WIDTH,
+        "Vencimento",
+        &due_date,
+    );
+    field(
+        page,
+        A4_WIDTH - MARGIN - SIDE_WIDTH,
+        y,
+        SIDE_WIDTH,
+        "Valor do documento",
+        &amount,
+    );
+    y += ROW_HEIGHT + 4.0;
+    page.text_right(
+        A4_WIDTH - MARGIN,
+        y + 6.0,
+        6.0,
+        Font::Regular,
+        "Recibo do Pagador - Autenticação mecânica",
+    );
+    if let Some(stamp) = stamp(boleto.status) {
+        page.text(MARGIN, y + 8.0, 14.0, Font::Bold, stamp);
+    }
+
+    y += 30.0;
+    page.dashed_line(MARGIN, y, A4_WIDTH - MARGIN, 0.5);
+    page.text_right(
+        A4_WIDTH - MARGIN,
+        y - 3.0,
+        6.0,
+        Font::Regular,
+        "Corte na linha pontilhada",
+    );
+
+    // ficha de compensação
+    y += 20.0;
+    header(page, y, &line);
+    y += 26.0;
+    let main_width = CONTENT_WIDTH - SIDE_WIDTH;
+    let side_x = A4_WIDTH - MARGIN - SIDE_WIDTH;
+    field(
+        page,
+        MARGIN,
+        y,
+        main_width,
+        "Local de pagamento",
+        "Pagável em qualquer banco até o vencimento",
+    );
+    field(page, side_x, y, SIDE_WIDTH, "Vencimento", &due_date);
+    y += ROW_HEIGHT;
+    field(
+        page,
+        MARGIN,
+        y,
+        main_width,
+        "Beneficiário",
+        beneficiary.name,
+    );
+    field(
+        page,
+        side_x,
+        y,
+        SIDE_WIDTH,
+        "Agência/Código do beneficiário",
+        &format!("0001 / {}", beneficiary.account_number),
+    );
+    y += ROW_HEIGHT;
+    let cells = [
+        ("Data do documento", issued_at.as_str()),
+        ("Nº do documento", boleto.our_number.as_str()),
+        ("Espécie doc.", "DM"),
+        ("Aceite", "N"),
+        ("Data processamento", issued_at.as_str()),
+    ];
+    row(page, y, main_width, &cells);
+    field(
+        page,
+        side_x,
+        y,
+        SIDE_WIDTH,
+        "Nosso número",
+        &boleto.our_number,
+    );
+    y += ROW_HEIGHT;
+    let cells = [
+        ("Uso do banco", ""),
+        ("Carteira", "09"),
+        ("Espécie", "R$"),
+        ("Quantidade", ""),
+        ("Valor", ""),
+    ];
+    row(page, y, main_width, &cells);
+    field(
+        page,
+        side_x,
+        y,
+        SIDE_WIDTH,
+        "(=) Valor do documento",
+        &amount,
+    );
+    y += ROW_HEIGHT;
+
+    // instruções à esquerda; descontos e acréscimos (em branco) à direita
+    let instructions_height = 4.0 * ROW_HEIGHT;
+    page.rect(MARGIN, y, main_width, instructions_height, 0.5);
+    page.text(
+        MARGIN + 3.0,
+        y + 7.0,
+        6.0,
+        Font::Regular,
+        "Instruções (texto de responsabilidade do beneficiário)",
+    );
+    let instructions = boleto.description.as_deref().unwrap_or_default();
+    for (index, text) in wrap(instructions, 95).iter().take(6).enumerate() {
+        page.text(
+            MARGIN + 3.0,
+            y + 18.0 + index as f32 * 10.0,
+            8.0,
+            Font::Regular,
+            text,
+        );
+    }
+    for (index, label) in [
+        "(-) Desconto/Abatimento",
+        "(+) Mora/Multa",
+        "(+) Outros acréscimos",
+        "(=) Valor cobrado",
+    ]
+    .iter()
+    .enumerate()
+    {
+        field(
+            page,
+            side_x,
+            y + index as f32 * ROW_HEIGHT,
+            SIDE_WIDTH,
+            label,
+            "",
+        );
+    }
+    y += instructions_height;
+
+    page.rect(MARGIN, y, CONTENT_WIDTH, 30.0, 0.5);
+    page.text(MARGIN + 3.0, y + 7.0, 6.0, Font::Regular, "Pagador");
+    page.text(
+        MARGIN + 3.0,
+        y + 18.0,
+        9.0,
+        Font::Regular,
+        &boleto.payer_name,
+    );
+    y += 36.0;
+
+    page.itf_barcode(MARGIN, y, barcode.as_str(), BARCODE_NARROW, BARCODE_HEIGHT);
+    page.text_right(
+        A4_WIDTH - MARGIN,
+        y + 6.0,
+        6.0,
+        Font::Regular,
+        "Ficha de Compensação - Autenticação mecânica",
+    );
+
+    document.to_bytes()
+}
+
+/// Nome do banco, código e linha digitável, separados por traços verticais
+fn header(page: &mut Page, y: f32, line: &str) {
+    page.text(MARGIN, y + 14.0, 12.0, Font::Bold, BANK_NAME);
+    page.line(MARGIN + 80.0, y, MARGIN + 80.0, y + 20.0, 1.0);
+    page.text(MARGIN + 88.0, y + 15.0, 14.0, Font::Bold, BANK_CODE);
+    page.line(MARGIN + 124.0, y, MARGIN + 124.0, y + 20.0, 1.0);
+    page.text_right(A4_WIDTH - MARGIN, y + 15.0, 10.5, Font::Bold, line);
+    page.line(MARGIN, y + 20.0, A4_WIDTH - MARGIN, y + 20.0, 1.5);
+}
+
+/// Caixa com rótulo pequeno em cima e o valor embaixo
+fn field(page: &mut Page, x: f32, y: f32, width: f32, label: &str, value: &str) {
+    page.rect(x, y, width, ROW_HEIGHT, 0.5);
+    page.text(x + 3.0, y + 7.0, 6.0, Font::Regular, label);
+    page.text(x + 3.0, y + 18.0, 9.0, Font::Regular, value);
+}
+
+/// Linha dividida em colunas iguais
+fn row(page: &mut Page, y: f32, width: f32, cells: &[(&str, &str)]) {
+    let cell_width = width / cells.len() as f32;
+    for (index, (label, value)) in cells.iter().enumerate() {
+        field(
+            page,
+            MARGIN + index as f32 * cell_width,
+            y,
+            cell_width,
+            label,
+            value,
+        );
+    }
+}
+
+fn stamp(status: BoletoStatus) -> Option<&'static str> {
+    match status {
+        BoletoStatus::Open => None,
+        BoletoStatus::Paid => Some("PAGO"),
+        BoletoStatus::Cancelled => Some("CANCELADO"),
+    }
+}
+
+/// Quebra por palavras em linhas de até `max` caracteres
+fn wrap(text: &str, max: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
@@ -0,0 +1,378 @@
+//! PDF sem dependências externas: páginas A4 com texto em Helvetica, linhas,
+//! retângulos e código de barras ITF (intercalado 2 de 5)
+//!
+//! As coordenadas são em pontos (1/72"), a partir do canto superior esquerdo.
+//! Os documentos (boleto, extrato) montam o layout em cima de `Page`.
+
+use std::fmt::Write as _;
+
+use rust_decimal::Decimal;
+
+pub mod boleto;
+
+pub const A4_WIDTH: f32 = 595.28;
+pub const A4_HEIGHT: f32 = 841.89;
+
+/// Pontos por milímetro
+pub const MM: f32 = 72.0 / 25.4;
+
+/// Fontes padrão do PDF (não são embutidas)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PdfDocument {
+    title: Option<String>,
+    pages: Vec<Page>,
+}
+
+#[derive(Debug, Default)]
+pub struct Page {
+    content: Vec<u8>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn add_page(&mut self) -> &mut Page {
+        self.pages.push(Page::default());
+        self.pages.last_mut().expect("página recém-criada")
+    }
+
+    /// Arquivo completo: catálogo, fontes, páginas e tabela de referências cruzadas
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // 1 catálogo, 2 árvore de páginas, 3 e 4 fontes, 5 info; depois página + conteúdo
+        let page_id = |index: usize| 6 + index * 2;
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        let kids: Vec<String> = (0..self.pages.len())
+            .map(|index| format!("{} 0 R", page_id(index)))
+            .collect();
+        objects.push(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                self.pages.len()
+            )
+            .into_bytes(),
+        );
+        for base_font in ["Helvetica", "Helvetica-Bold"] {
+            objects.push(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{base_font} /Encoding /WinAnsiEncoding >>"
+                )
+                .into_bytes(),
+            );
+        }
+        let mut info = b"<< /Producer (api_mini_bank)".to_vec();
+        if let Some(title) = &self.title {
+            info.extend_from_slice(b" /Title (");
+            info.extend(encode_text(title));
+            info.push(b')');
+        }
+        info.extend_from_slice(b" >>");
+        objects.push(info);
+
+        for (index, page) in self.pages.iter().enumerate() {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {A4_WIDTH} {A4_HEIGHT}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    page_id(index) + 1
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.content.len()).into_bytes();
+            stream.extend_from_slice(&page.content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut pdf = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n", index + 1).into_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = pdf.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(table, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            table,
+            "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        );
+        pdf.extend(table.into_bytes());
+        pdf
+    }
+}
+
+impl Page {
+    pub fn text(&mut self, x: f32, y: f32, size: f32, font: Font, text: &str) -> &mut Self {
+        self.push(&format!(
+            "BT /{} {size:.1} Tf {x:.2} {:.2} Td (",
+            font.resource(),
+            A4_HEIGHT - y
+        ));
+        self.content.extend(encode_text(text));
+        self.push(") Tj ET\n");
+        self
+    }
+
+    /// Texto alinhado à direita em `right` (colunas de valores)
+    pub fn text_right(
+        &mut self,
+        right: f32,
+        y: f32,
+        size: f32,
+        font: Font,
+        text: &str,
+    ) -> &mut Self {
+        self.text(right - text_width(text, size), y, size, font, text)
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) -> &mut Self {
+        self.push(&format!(
+            "{width:.2} w {x1:.2} {:.2} m {x2:.2} {:.2} l S\n",
+            A4_HEIGHT - y1,
+            A4_HEIGHT - y2
+        ))
+    }
+
+    /// Linha tracejada (ex.: linha de corte)
+    pub fn dashed_line(&mut self, x1: f32, y: f32, x2: f32, width: f32) -> &mut Self {
+        self.push("[3 2] 0 d\n");
+        self.line(x1, y, x2, y, width);
+        self.push("[] 0 d\n")
+    }
+
+    /// Contorno de um retângulo com o canto superior esquerdo em (`x`, `y`)
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, line_width: f32) -> &mut Self {
+        self.push(&format!(
+            "{line_width:.2} w {x:.2} {:.2} {width:.2} {height:.2} re S\n",
+            A4_HEIGHT - y - height
+        ))
+    }
+
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32) -> &mut Self {
+        self.push(&format!(
+            "{x:.3} {:.2} {width:.3} {height:.2} re f\n",
+            A4_HEIGHT - y - height
+        ))
+    }
+
+    /// Código de barras ITF; a barra larga tem três vezes a largura da estreita
+    pub fn itf_barcode(
+        &mut self,
+        x: f32,
+        y: f32,
+        digits: &str,
+        narrow: f32,
+        height: f32,
+    ) -> &mut Self {
+        let mut cursor = x;
+        for (index, modules) in itf_widths(digits).into_iter().enumerate() {
+            let width = modules as f32 * narrow;
+            if index % 2 == 0 {
+                self.fill_rect(cursor, y, width, height);
+            }
+            cursor += width;
+        }
+        self
+    }
+
+    fn push(&mut self, operators: &str) -> &mut Self {
+        self.content.extend_from_slice(operators.as_bytes());
+        self
+    }
+}
+
+/// Padrão de cada dígito no ITF: `true` é elemento largo
+const ITF_PATTERNS: [[bool; 5]; 10] = [
+    [false, false, true, true, false],
+    [true, false, false, false, true],
+    [false, true, false, false, true],
+    [true, true, false, false, false],
+    [false, false, true, false, true],
+    [true, false, true, false, false],
+    [false, true, true, false, false],
+    [false, false, false, true, true],
+    [true, false, false, true, false],
+    [false, true, false, true, false],
+];
+
+/// Larguras em módulos, alternando barra e espaço (começa por barra)
+///
+/// Os dígitos vão em pares: o primeiro nas barras, o segundo nos espaços.
+/// Quantidade ímpar ganha um zero à esquerda.
+pub fn itf_widths(digits: &str) -> Vec<u8> {
+    let mut digits: Vec<usize> = digits
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|digit| (digit - b'0') as usize)
+        .collect();
+    if digits.len() % 2 == 1 {
+        digits.insert(0, 0);
+    }
+
+    let width = |wide: bool| if wide { 3 } else { 1 };
+    let mut widths = vec![1, 1, 1, 1];
+    for pair in digits.chunks(2) {
+        let bars = ITF_PATTERNS[pair[0]];
+        let spaces = ITF_PATTERNS[pair[1]];
+        for (bar, space) in bars.into_iter().zip(spaces) {
+            widths.push(width(bar));
+            widths.push(width(space));
+        }
+    }
+    widths.extend([3, 1, 1]);
+    widths
+}
+
+/// Largura aproximada do texto em Helvetica (a negrito usa a mesma tabela)
+pub fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = text.chars().map(char_width).sum();
+    units as f32 * size / 1000.0
+}
+
+/// Larguras da Helvetica (AFM) para o ASCII imprimível; fora dele, a de uma letra comum
+fn char_width(c: char) -> u32 {
+    const WIDTHS: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556,
+        556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722,
+        722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722,
+        667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556,
+        556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500,
+        500, 334, 260, 334, 584,
+    ];
+    match c {
+        ' '..='~' => WIDTHS[c as usize - 32] as u32,
+        _ => 556,
+    }
+}
+
+/// Texto em WinAnsi (Latin-1 mais aspas e travessões), com `(`, `)` e `\` escapados
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            ' '..='~' | '\u{A0}'..='\u{FF}' => c as u32 as u8,
+            '€' => 0x80,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+    bytes
+}
+
+/// `1234.5` → `R$ 1.234,50`
+pub fn format_brl(amount: Decimal) -> String {
+    let formatted = format!("{:.2}", amount.abs().round_dp(2));
+    let (integer, cents) = formatted.split_once('.').unwrap_or((&formatted, "00"));
+    let mut grouped = String::new();
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push('.');
+        }
+        grouped.push(digit);
+    }
+    let sign = if amount.is_sign_negative() && !amount.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{sign}R$ {grouped},{cents}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_structure() {
+        let mut document = PdfDocument::new().with_title("Extrato (teste)");
+        document
+            .add_page()
+            .text(10.0, 10.0, 9.0, Font::Regular, "Pagável em qualquer banco");
+        let pdf = document.to_bytes();
+
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        // "á" em WinAnsi é 0xE1; parênteses escapados
+        assert!(pdf.windows(4).any(|window| window == b"Pag\xE1"));
+        assert!(pdf.windows(8).any(|window| window == b"\\(teste\\"));
+
+        // a tabela xref aponta para o início de cada objeto
+        let start = pdf
+            .windows(10)
+            .rposition(|window| window == b"startxref\n")
+            .unwrap();
+        let tail = std::str::from_utf8(&pdf[start + 10..]).unwrap();
+        let xref: usize = tail.lines().next().unwrap().parse().unwrap();
+        let table = std::str::from_utf8(&pdf[xref..]).unwrap();
+        assert!(table.starts_with("xref"));
+        let first: usize = table.lines().nth(3).unwrap()[..10].parse().unwrap();
+        assert!(pdf[first..].starts_with(b"1 0 obj"));
+    }
+
+    #[test]
+    fn test_itf_encodes_pairs() {
+        // início (4) + 1 par (10) + fim (3)
+        let widths = itf_widths("12");
+        assert_eq!(widths.len(), 17);
+        assert_eq!(&widths[4..14], &[3, 1, 1, 3, 1, 1, 1, 1, 3, 3]);
+        assert_eq!(itf_widths("2"), itf_widths("02"));
+        // 44 dígitos do boleto: 4 + 22 pares × 18 módulos + 5
+        assert_eq!(
+            itf_widths(&"0".repeat(44))
+                .iter()
+                .map(|&w| w as u32)
+                .sum::<u32>(),
+            405
+        );
+    }
+
+    #[test]
+    fn test_format_brl() {
+        assert_eq!(format_brl(Decimal::new(123_456_789, 2)), "R$ 1.234.567,89");
+        assert_eq!(format_brl(Decimal::new(5, 1)), "R$ 0,50");
+        assert_eq!(format_brl(Decimal::new(-100_000, 2)), "-R$ 1.000,00");
+    }
+}
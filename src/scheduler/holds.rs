@@ -0,0 +1,54 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use sqlx::PgPool;
+
+use crate::{
+    DRAINING, config::SchedulerConfig, models::app_error::AppError, services::HoldService,
+};
+
+/// Expira as reservas vencidas, devolvendo o valor ao saldo disponível
+///
+/// Roda com o agendador e usa os mesmos intervalos; a captura já recusa reservas
+/// vencidas, então um atraso aqui só adia a volta do valor ao disponível.
+pub struct HoldExpirer {
+    pool: PgPool,
+    batch_size: u32,
+    poll_interval: Duration,
+}
+
+impl HoldExpirer {
+    pub fn new(pool: PgPool, config: &SchedulerConfig) -> Self {
+        Self {
+            pool,
+            batch_size: config.batch_size,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Expiração de reservas iniciada");
+        while !DRAINING.load(Ordering::SeqCst) {
+            match self.run_batch().await {
+                Ok(expired) if expired == self.batch_size as usize => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!("Falha ao expirar reservas: {err}"),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        tracing::info!("Expiração de reservas encerrada");
+    }
+
+    /// Expira até `batch_size` reservas e devolve quantas
+    #[tracing::instrument(name = "HoldExpirer::run_batch", skip_all, fields(expired = tracing::field::Empty))]
+    pub async fn run_batch(&self) -> Result<usize, AppError> {
+        let mut expired = 0;
+        while expired < self.batch_size as usize && !DRAINING.load(Ordering::SeqCst) {
+            if !HoldService::expire_due(&self.pool).await? {
+                break;
+            }
+            expired += 1;
+        }
+        tracing::Span::current().record("expired", expired);
+        Ok(expired)
+    }
+}
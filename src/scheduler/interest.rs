@@ -0,0 +1,94 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use chrono::{Datelike, Days, Utc};
+use sqlx::PgPool;
+
+use crate::{
+    DRAINING,
+    config::{InterestConfig, SchedulerConfig},
+    database::InterestRepository,
+    models::{app_error::AppError, interest::local_date},
+    services::InterestService,
+    utils::limit_settings,
+};
+
+/// Apura os juros do dia anterior e lança os do mês anterior (rendimento ou
+/// juros do cheque especial)
+///
+/// A apuração de um dia usa o saldo com que a conta terminou o dia, refeito a
+/// partir dos lançamentos seguintes. Depois de uma parada, retoma do último dia
+/// apurado (até `catch_up_days` para trás), do mais antigo ao mais recente, com o
+/// saldo e a taxa de cada dia. Cada conta é travada com `SKIP LOCKED` e cada dia
+/// tem uma linha só, então várias réplicas podem rodar juntas.
+pub struct InterestAccruer {
+    pool: PgPool,
+    batch_size: u32,
+    poll_interval: Duration,
+    catch_up_days: u32,
+}
+
+impl InterestAccruer {
+    pub fn new(pool: PgPool, scheduler: &SchedulerConfig, config: &InterestConfig) -> Self {
+        Self {
+            pool,
+            batch_size: scheduler.batch_size,
+            poll_interval: Duration::from_secs(config.poll_interval_secs),
+            catch_up_days: config.catch_up_days,
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Apuração de rendimentos iniciada");
+        while !DRAINING.load(Ordering::SeqCst) {
+            match self.run_batch().await {
+                Ok(processed) if processed == self.batch_size as usize => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!("Falha ao apurar rendimentos: {err}"),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        tracing::info!("Apuração de rendimentos encerrada");
+    }
+
+    /// Apura e depois lança até `batch_size` contas; devolve quantas foram processadas
+    #[tracing::instrument(name = "InterestAccruer::run_batch", skip_all, fields(processed = tracing::field::Empty))]
+    pub async fn run_batch(&self) -> Result<usize, AppError> {
+        let today = local_date(Utc::now(), limit_settings().utc_offset_hours);
+        let mut processed = 0;
+
+        if let Some(yesterday) = today.pred_opt() {
+            let mut conn = self.pool.acquire().await?;
+            let last = InterestRepository::last_accrual_date(&mut conn).await?;
+            drop(conn);
+            let earliest = yesterday
+                .checked_sub_days(Days::new(u64::from(self.catch_up_days.saturating_sub(1))))
+                .unwrap_or(yesterday);
+            // o último dia apurado entra de novo: a parada pode ter sido no meio dele
+            let mut accrual_date = last.map_or(yesterday, |last| last.clamp(earliest, yesterday));
+            while accrual_date <= yesterday
+                && processed < self.batch_size as usize
+                && !DRAINING.load(Ordering::SeqCst)
+            {
+                if InterestService::accrue_due(&self.pool, accrual_date).await? {
+                    processed += 1;
+                } else if let Some(next_day) = accrual_date.succ_opt() {
+                    accrual_date = next_day;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        // o lançamento do mês só sai depois de apurado o último dia dele
+        let month_start = today.with_day(1).unwrap_or(today);
+        while processed < self.batch_size as usize && !DRAINING.load(Ordering::SeqCst) {
+            if !InterestService::settle_due(&self.pool, month_start).await? {
+                break;
+            }
+            processed += 1;
+        }
+
+        tracing::Span::current().record("processed", processed);
+        Ok(processed)
+    }
+}
@@ -0,0 +1,90 @@
+//! Execução das transferências agendadas, expiração das reservas, cobrança
+//! dos estornos pendentes e rendimentos dentro do próprio serviço
+mod holds;
+mod interest;
+mod reversals;
+
+use std::{
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
+};
+
+use sqlx::PgPool;
+
+pub use holds::HoldExpirer;
+pub use interest::InterestAccruer;
+pub use reversals::ReversalRecovery;
+
+use crate::{
+    DRAINING,
+    config::SchedulerConfig,
+    fx::FxRateProvider,
+    models::{app_error::AppError, scheduled_transfer::RetryPolicy},
+    services::ScheduledTransferService,
+};
+
+/// Consulta os agendamentos vencidos e executa um por vez
+///
+/// Várias réplicas podem rodar o agendador: cada execução trava o agendamento
+/// com `SKIP LOCKED`, então a mesma ocorrência nunca sai duas vezes.
+pub struct TransferScheduler {
+    pool: PgPool,
+    /// Tarifas e limites de contas fora do real dependem da taxa de câmbio
+    provider: Arc<dyn FxRateProvider>,
+    batch_size: u32,
+    poll_interval: Duration,
+    policy: RetryPolicy,
+}
+
+impl TransferScheduler {
+    pub fn new(pool: PgPool, provider: Arc<dyn FxRateProvider>, config: &SchedulerConfig) -> Self {
+        Self {
+            pool,
+            provider,
+            batch_size: config.batch_size,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            policy: RetryPolicy {
+                max_attempts: config.max_attempts as i32,
+                retry_delay: chrono::Duration::seconds(config.retry_delay_secs as i64),
+            },
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!(
+            max_attempts = self.policy.max_attempts,
+            retry_delay_secs = self.policy.retry_delay.num_seconds(),
+            "Agendador de transferências iniciado"
+        );
+        while !DRAINING.load(Ordering::SeqCst) {
+            match self.run_batch().await {
+                // lote cheio: provavelmente há mais vencidos
+                Ok(executed) if executed == self.batch_size as usize => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!("Falha ao executar transferências agendadas: {err}"),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        tracing::info!("Agendador de transferências encerrado");
+    }
+
+    /// Executa até `batch_size` agendamentos vencidos e devolve quantos rodaram
+    #[tracing::instrument(name = "TransferScheduler::run_batch", skip_all, fields(executed = tracing::field::Empty))]
+    pub async fn run_batch(&self) -> Result<usize, AppError> {
+        let mut executed = 0;
+        while executed < self.batch_size as usize && !DRAINING.load(Ordering::SeqCst) {
+            match ScheduledTransferService::execute_due(
+                &self.pool,
+                self.provider.as_ref(),
+                &self.policy,
+            )
+            .await?
+            {
+                Some(_) => executed += 1,
+                None => break,
+            }
+        }
+        tracing::Span::current().record("executed", executed);
+        Ok(executed)
+    }
+}
@@ -0,0 +1,56 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use sqlx::PgPool;
+
+use crate::{
+    DRAINING, config::SchedulerConfig, models::app_error::AppError, services::ReversalService,
+};
+
+/// Cobra os estornos pendentes com o saldo que entrar nas contas debitadas
+///
+/// Roda com o agendador; cada estorno é tentado de novo a cada
+/// `retry_delay_secs` até ser cobrado por completo.
+pub struct ReversalRecovery {
+    pool: PgPool,
+    batch_size: u32,
+    poll_interval: Duration,
+    retry_delay: chrono::Duration,
+}
+
+impl ReversalRecovery {
+    pub fn new(pool: PgPool, config: &SchedulerConfig) -> Self {
+        Self {
+            pool,
+            batch_size: config.batch_size,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            retry_delay: chrono::Duration::seconds(config.retry_delay_secs as i64),
+        }
+    }
+
+    pub async fn run(self) {
+        tracing::info!("Cobrança de estornos iniciada");
+        while !DRAINING.load(Ordering::SeqCst) {
+            match self.run_batch().await {
+                Ok(attempted) if attempted == self.batch_size as usize => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!("Falha ao cobrar estornos: {err}"),
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+        tracing::info!("Cobrança de estornos encerrada");
+    }
+
+    /// Tenta cobrar até `batch_size` estornos e devolve quantos
+    #[tracing::instrument(name = "ReversalRecovery::run_batch", skip_all, fields(attempted = tracing::field::Empty))]
+    pub async fn run_batch(&self) -> Result<usize, AppError> {
+        let mut attempted = 0;
+        while attempted < self.batch_size as usize && !DRAINING.load(Ordering::SeqCst) {
+            if !ReversalService::recover_due(&self.pool, self.retry_delay).await? {
+                break;
+            }
+            attempted += 1;
+        }
+        tracing::Span::current().record("attempted", attempted);
+        Ok(attempted)
+    }
+}
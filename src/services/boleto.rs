@@ -0,0 +1,288 @@
+use chrono::Utc;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AuditRepository, BoletoRepository, NotificationRepository, OutboxRepository,
+        TransactionRepository,
+    },
+    fx::FxRateProvider,
+    metrics,
+    models::{
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        boleto::{BANK_CODE, Boleto, BoletoStatus, CreateBoleto, PayBoleto, error::BoletoError},
+        business::OperatorPermission,
+        currency::Currency,
+        event::DomainEvent,
+        fee::FeeOperation,
+        fx::error::FxError,
+        interest::local_date,
+        notification::Notification,
+        transaction::{Transaction, TransactionType, error::TransactionError},
+    },
+    services::{
+        FeeService, KycService, LimitService,
+        fees::fee_metadata,
+        set_balance,
+        transactions::{debit, lock_owned_account, lock_owned_and_destination},
+    },
+    utils::limit_settings,
+    validators::BoletoValidator,
+};
+
+/// Descrição do lançamento quando o pagador não informa uma
+const DEFAULT_PAYMENT_DESCRIPTION: &str = "Pagamento de boleto";
+
+/// Emissão, cancelamento e pagamento de boletos
+///
+/// Boleto de outro banco só debita o pagador (a liquidação fica fora do sistema);
+/// boleto emitido aqui credita o beneficiário na mesma transação e é baixado.
+pub struct BoletoService;
+
+impl BoletoService {
+    #[tracing::instrument(name = "BoletoService::issue", skip_all, fields(account_id = %request.account_id))]
+    pub async fn issue(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        owner_id: Uuid,
+        request: &CreateBoleto,
+        audit: &AuditContext,
+    ) -> Result<Boleto, AppError> {
+        BoletoValidator::validate(
+            request,
+            local_date(Utc::now(), limit_settings().utc_offset_hours),
+        )
+        .map_err(AppError::Validation)?;
+
+        let mut tx = pool.begin().await?;
+        let account = lock_owned_account(
+            &mut tx,
+            owner_id,
+            request.account_id,
+            OperatorPermission::Transact,
+        )
+        .await?;
+        if account.currency != Currency::Brl {
+            return Err(FxError::CurrencyNotSupported.into());
+        }
+        let fee = FeeService::assess(
+            &mut tx,
+            provider,
+            &account,
+            FeeOperation::BoletoIssue,
+            request.amount,
+        )
+        .await?;
+        let mut fee_leg = None;
+        if let Some(fee) = &fee {
+            let balance = debit(&account, fee.amount)?;
+            set_balance(&mut tx, &account, balance).await?;
+            fee_leg = Some((fee, balance));
+        }
+        let our_number = BoletoRepository::next_our_number(&mut tx).await?;
+        let boleto = Boleto::issue(&account, request, our_number);
+        BoletoRepository::insert(&mut tx, &boleto).await?;
+        // a tarifa aponta para o boleto, que não tem lançamento próprio na emissão
+        let fee_leg = match fee_leg {
+            Some((fee, balance)) => {
+                Some(FeeService::post(&mut tx, &account, fee, boleto.id, balance).await?)
+            }
+            None => None,
+        };
+
+        let event = audit
+            .event(AuditAction::BoletoIssued, "boleto", Some(boleto.id))
+            .after(boleto.audit_snapshot())
+            .metadata(json!({ "fee": fee_metadata(fee_leg.as_ref()) }));
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        if let Some(fee_leg) = &fee_leg {
+            metrics::record_transaction(
+                &fee_leg.transaction_type,
+                fee_leg.amount,
+                fee_leg.currency,
+            );
+        }
+        Ok(boleto)
+    }
+
+    #[tracing::instrument(name = "BoletoService::cancel", skip_all, fields(boleto_id = %boleto_id))]
+    pub async fn cancel(
+        pool: &PgPool,
+        owner_id: Uuid,
+        boleto_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<Boleto, AppError> {
+        let mut tx = pool.begin().await?;
+        let boleto = BoletoRepository::lock_owned(&mut tx, boleto_id, owner_id)
+            .await?
+            .ok_or(BoletoError::NotFound)?;
+        if boleto.status != BoletoStatus::Open {
+            return Err(BoletoError::NotOpen.into());
+        }
+
+        let cancelled = BoletoRepository::cancel(&mut tx, boleto.id).await?;
+        let event = audit
+            .event(AuditAction::BoletoCancelled, "boleto", Some(boleto.id))
+            .before(boleto.audit_snapshot())
+            .after(cancelled.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(cancelled)
+    }
+
+    /// Debita a conta pelo valor do boleto; devolve o lançamento de débito
+    #[tracing::instrument(name = "BoletoService::pay", skip_all, fields(account_id = %request.account_id))]
+    pub async fn pay(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        owner_id: Uuid,
+        request: &PayBoleto,
+        audit: &AuditContext,
+    ) -> Result<Transaction, AppError> {
+        let (barcode, amount) =
+            BoletoValidator::validate_payment(request).map_err(AppError::Validation)?;
+        let today = local_date(Utc::now(), limit_settings().utc_offset_hours);
+        if barcode
+            .due_date(today)
+            .is_some_and(|due_date| due_date < today)
+        {
+            return Err(BoletoError::Overdue.into());
+        }
+        let description = request
+            .description
+            .as_deref()
+            .map(str::trim)
+            .filter(|description| !description.is_empty())
+            .unwrap_or(DEFAULT_PAYMENT_DESCRIPTION);
+
+        let mut tx = pool.begin().await?;
+        let (source, boleto, beneficiary) = if barcode.bank_code() == BANK_CODE {
+            let boleto = BoletoRepository::lock_by_barcode(&mut tx, barcode.as_str())
+                .await?
+                .ok_or(BoletoError::NotFound)?;
+            if boleto.status != BoletoStatus::Open {
+                return Err(BoletoError::NotOpen.into());
+            }
+            if boleto.account_id == request.account_id {
+                return Err(BoletoError::SameAccount.into());
+            }
+            let (source, beneficiary) = lock_owned_and_destination(
+                &mut tx,
+                owner_id,
+                request.account_id,
+                boleto.account_id,
+                OperatorPermission::Transact,
+            )
+            .await?;
+            let beneficiary = beneficiary
+                .filter(|account| account.is_active)
+                .ok_or(TransactionError::DestinationAccountNotFound)?;
+            (source, Some(boleto), Some(beneficiary))
+        } else {
+            let source = lock_owned_account(
+                &mut tx,
+                owner_id,
+                request.account_id,
+                OperatorPermission::Transact,
+            )
+            .await?;
+            (source, None, None)
+        };
+        // o boleto é sempre em reais (o emitido aqui só sai de conta em reais)
+        if source.currency != Currency::Brl {
+            return Err(FxError::CurrencyNotSupported.into());
+        }
+
+        KycService::ensure_approved(&mut tx, source.user_id).await?;
+        let balance = debit(&source, amount)?;
+        LimitService::enforce(&mut tx, provider, &source, amount).await?;
+        set_balance(&mut tx, &source, balance).await?;
+        let mut payment = Transaction::completed(
+            TransactionType::BoletoPayment,
+            Some(source.id),
+            beneficiary.as_ref().map(|account| account.id),
+            amount,
+            source.currency,
+            description,
+        );
+        let mut notifications = vec![Notification::balance_changed(
+            source.user_id,
+            source.id,
+            &payment,
+            -amount,
+            balance,
+        )];
+
+        let credit = match (&boleto, &beneficiary) {
+            (Some(boleto), Some(beneficiary)) => {
+                let beneficiary_balance = beneficiary.balance + amount;
+                set_balance(&mut tx, beneficiary, beneficiary_balance).await?;
+                let mut credit = Transaction::completed(
+                    TransactionType::BoletoCredit,
+                    Some(source.id),
+                    Some(beneficiary.id),
+                    amount,
+                    beneficiary.currency,
+                    &format!("Boleto {} pago", boleto.our_number),
+                );
+                credit.reference_id = Some(payment.id);
+                payment.reference_id = Some(credit.id);
+                notifications.push(Notification::balance_changed(
+                    beneficiary.user_id,
+                    beneficiary.id,
+                    &credit,
+                    amount,
+                    beneficiary_balance,
+                ));
+                Some(credit)
+            }
+            _ => None,
+        };
+        TransactionRepository::insert(&mut tx, &payment).await?;
+        if let Some(credit) = &credit {
+            TransactionRepository::insert(&mut tx, credit).await?;
+        }
+        if let Some(boleto) = &boleto {
+            BoletoRepository::mark_paid(&mut tx, boleto.id, payment.id).await?;
+        }
+
+        let event = audit
+            .event(AuditAction::BoletoPaid, "account", Some(source.id))
+            .before(json!({ "balance": source.balance }))
+            .after(json!({ "balance": balance }))
+            .metadata(json!({
+                "transaction_id": payment.id,
+                "credit_transaction_id": credit.as_ref().map(|credit| credit.id),
+                "boleto_id": boleto.as_ref().map(|boleto| boleto.id),
+                "barcode": barcode.as_str(),
+                "amount": amount,
+            }));
+        AuditRepository::append(&mut tx, &event).await?;
+        let event = DomainEvent::BoletoPaid {
+            transaction_id: payment.id,
+            account_id: source.id,
+            barcode: barcode.into_string(),
+            amount,
+            balance,
+            boleto_id: boleto.as_ref().map(|boleto| boleto.id),
+            beneficiary_account_id: beneficiary.as_ref().map(|account| account.id),
+        };
+        OutboxRepository::enqueue(&mut tx, event).await?;
+        for notification in &notifications {
+            NotificationRepository::publish(&mut tx, notification).await?;
+        }
+        tx.commit().await?;
+
+        metrics::record_transaction(&payment.transaction_type, payment.amount, payment.currency);
+        if let Some(credit) = &credit {
+            metrics::record_transaction(&credit.transaction_type, credit.amount, credit.currency);
+        }
+        Ok(payment)
+    }
+}
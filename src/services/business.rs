@@ -0,0 +1,146 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{AuditRepository, NotificationRepository, OperatorRepository, UserRepository},
+    models::{
+        CustomerType, User,
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        business::{AddOperator, BusinessOperator, UpdateOperator, error::BusinessError},
+        error::UserError,
+        notification::Notification,
+    },
+    validators::BusinessValidator,
+};
+
+/// Operadores de clientes empresa
+///
+/// O login da empresa autoriza pessoas físicas já cadastradas; cada mudança de
+/// acesso é auditada e avisada ao operador como notificação de segurança.
+pub struct BusinessService;
+
+impl BusinessService {
+    #[tracing::instrument(name = "BusinessService::add_operator", skip_all, fields(business_id = %business_id))]
+    pub async fn add_operator(
+        pool: &PgPool,
+        business_id: Uuid,
+        request: &AddOperator,
+        audit: &AuditContext,
+    ) -> Result<BusinessOperator, AppError> {
+        let permissions = BusinessValidator::validate_permissions(&request.permissions)
+            .map_err(|err| AppError::Validation(err.into()))?;
+        let user = UserRepository::find_by_email(pool, request.email.trim())
+            .await?
+            .ok_or(UserError::NotFound)?;
+        if !user.is_active || user.customer_type != CustomerType::Individual {
+            return Err(BusinessError::InvalidOperator.into());
+        }
+
+        let mut tx = pool.begin().await?;
+        let business = lock_business(&mut tx, business_id).await?;
+        let operator_id = Uuid::new_v4();
+        if !OperatorRepository::insert(&mut tx, operator_id, business.id, user.id, &permissions)
+            .await?
+        {
+            return Err(BusinessError::OperatorAlreadyExists.into());
+        }
+        let operator = OperatorRepository::lock(&mut tx, operator_id, business.id)
+            .await?
+            .ok_or(BusinessError::OperatorNotFound)?;
+
+        let event = audit
+            .event(
+                AuditAction::OperatorAdded,
+                "business_operator",
+                Some(operator.id),
+            )
+            .after(operator.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        let notification = Notification::security(user.id, AuditAction::OperatorAdded, audit);
+        NotificationRepository::publish(&mut tx, &notification).await?;
+        tx.commit().await?;
+
+        Ok(operator)
+    }
+
+    #[tracing::instrument(name = "BusinessService::update_operator", skip_all, fields(operator_id = %operator_id))]
+    pub async fn update_operator(
+        pool: &PgPool,
+        business_id: Uuid,
+        operator_id: Uuid,
+        request: &UpdateOperator,
+        audit: &AuditContext,
+    ) -> Result<BusinessOperator, AppError> {
+        let permissions = BusinessValidator::validate_permissions(&request.permissions)
+            .map_err(|err| AppError::Validation(err.into()))?;
+
+        let mut tx = pool.begin().await?;
+        let business = lock_business(&mut tx, business_id).await?;
+        let operator = OperatorRepository::lock(&mut tx, operator_id, business.id)
+            .await?
+            .ok_or(BusinessError::OperatorNotFound)?;
+        OperatorRepository::update_permissions(&mut tx, operator.id, &permissions).await?;
+        let updated = BusinessOperator {
+            permissions,
+            ..operator.clone()
+        };
+
+        let event = audit
+            .event(
+                AuditAction::OperatorUpdated,
+                "business_operator",
+                Some(operator.id),
+            )
+            .before(operator.audit_snapshot())
+            .after(updated.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        let notification =
+            Notification::security(operator.user_id, AuditAction::OperatorUpdated, audit);
+        NotificationRepository::publish(&mut tx, &notification).await?;
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    #[tracing::instrument(name = "BusinessService::remove_operator", skip_all, fields(operator_id = %operator_id))]
+    pub async fn remove_operator(
+        pool: &PgPool,
+        business_id: Uuid,
+        operator_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<BusinessOperator, AppError> {
+        let mut tx = pool.begin().await?;
+        let business = lock_business(&mut tx, business_id).await?;
+        let operator = OperatorRepository::lock(&mut tx, operator_id, business.id)
+            .await?
+            .ok_or(BusinessError::OperatorNotFound)?;
+        OperatorRepository::delete(&mut tx, operator.id).await?;
+
+        let event = audit
+            .event(
+                AuditAction::OperatorRemoved,
+                "business_operator",
+                Some(operator.id),
+            )
+            .before(operator.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        let notification =
+            Notification::security(operator.user_id, AuditAction::OperatorRemoved, audit);
+        NotificationRepository::publish(&mut tx, &notification).await?;
+        tx.commit().await?;
+
+        Ok(operator)
+    }
+}
+
+/// Trava o usuário da empresa; serializa as mudanças de operadores dela
+async fn lock_business(conn: &mut PgConnection, business_id: Uuid) -> Result<User, AppError> {
+    let business = UserRepository::lock_by_id(conn, business_id)
+        .await?
+        .ok_or(UserError::NotFound)?;
+    if business.customer_type != CustomerType::Business {
+        return Err(BusinessError::NotBusiness.into());
+    }
+    Ok(business)
+}
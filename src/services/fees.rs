@@ -0,0 +1,178 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AccountRepository, AuditRepository, FeeRepository, NotificationRepository,
+        OutboxRepository, TransactionRepository, UserRepository,
+    },
+    fx::{FxRateProvider, brl_rate},
+    models::{
+        account::{Account, error::AccountError},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        event::DomainEvent,
+        fee::{CreateFeeWaiver, Fee, FeeOperation, FeeWaiver, error::FeeError, fee_amount},
+        limit::LimitWindows,
+        notification::Notification,
+        transaction::{Transaction, TransactionType},
+    },
+    utils::{fee_settings, limit_settings},
+    validators::FeeWaiverValidator,
+};
+
+/// Tarifas de saque, transferência para outro cliente e emissão de boleto
+///
+/// A regra vem de `[fees]` pelo tipo de conta e pela faixa do titular; a parte
+/// fixa é em reais e vai para a moeda da conta pela taxa do provedor. A tarifa
+/// é apurada antes do débito (operação e tarifa precisam caber juntas no saldo
+/// disponível) e lançada como `fee` na mesma transação do banco da operação.
+pub struct FeeService;
+
+impl FeeService {
+    /// Tarifa da operação na conta já travada; `None` sem regra, dentro da
+    /// franquia do mês ou com isenção em vigor
+    pub(crate) async fn assess(
+        conn: &mut PgConnection,
+        provider: &dyn FxRateProvider,
+        account: &Account,
+        operation: FeeOperation,
+        amount: Decimal,
+    ) -> Result<Option<Fee>, AppError> {
+        let tier = UserRepository::find_tier(conn, account.user_id).await?;
+        let Some(rule) = fee_settings().rule_for(operation, account.account_type, tier) else {
+            return Ok(None);
+        };
+        let fixed = if rule.fixed.is_zero() {
+            Decimal::ZERO
+        } else {
+            rule.fixed * brl_rate(provider, account.currency).await?
+        };
+        let fee = fee_amount(fixed, rule.percent, amount, account.currency);
+        if fee <= Decimal::ZERO {
+            return Ok(None);
+        }
+        if rule.free_per_month > 0 {
+            let month_start = LimitWindows::at(Utc::now(), limit_settings()).month_start;
+            let used = FeeRepository::count_since(conn, account.id, operation, month_start).await?;
+            if used < i64::from(rule.free_per_month) {
+                return Ok(None);
+            }
+        }
+        if let Some(waiver) = FeeRepository::active_waiver(conn, account.id, operation).await? {
+            tracing::debug!(waiver_id = %waiver.id, operation = operation.as_str(), "Tarifa isenta");
+            return Ok(None);
+        }
+        Ok(Some(Fee {
+            operation,
+            amount: fee,
+        }))
+    }
+
+    /// Lança a tarifa que o chamador já descontou do saldo; `balance` é o saldo final
+    pub(crate) async fn post(
+        conn: &mut PgConnection,
+        account: &Account,
+        fee: &Fee,
+        reference_id: Uuid,
+        balance: Decimal,
+    ) -> Result<Transaction, AppError> {
+        let mut transaction = Transaction::completed(
+            TransactionType::Fee,
+            Some(account.id),
+            None,
+            fee.amount,
+            account.currency,
+            fee.operation.description(),
+        );
+        transaction.reference_id = Some(reference_id);
+        TransactionRepository::insert(conn, &transaction).await?;
+
+        let event = DomainEvent::FeeCharged {
+            transaction_id: transaction.id,
+            account_id: account.id,
+            operation: fee.operation,
+            amount: fee.amount,
+            balance,
+            reference_id,
+        };
+        OutboxRepository::enqueue(conn, event).await?;
+        let notification = Notification::balance_changed(
+            account.user_id,
+            account.id,
+            &transaction,
+            -fee.amount,
+            balance,
+        );
+        NotificationRepository::publish(conn, &notification).await?;
+        Ok(transaction)
+    }
+
+    #[tracing::instrument(name = "FeeService::grant_waiver", skip_all, fields(account_id = %account_id))]
+    pub async fn grant_waiver(
+        pool: &PgPool,
+        admin_id: Uuid,
+        account_id: Uuid,
+        request: &CreateFeeWaiver,
+        audit: &AuditContext,
+    ) -> Result<FeeWaiver, AppError> {
+        FeeWaiverValidator::validate(request, Utc::now()).map_err(AppError::Validation)?;
+
+        let mut tx = pool.begin().await?;
+        let account = AccountRepository::lock_by_id(&mut tx, account_id)
+            .await?
+            .ok_or(AccountError::NotFound)?;
+        let waiver = FeeWaiver::new(account.id, admin_id, request);
+        FeeRepository::insert_waiver(&mut tx, &waiver).await?;
+
+        let event = audit
+            .event(AuditAction::FeeWaiverGranted, "fee_waiver", Some(waiver.id))
+            .after(waiver.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+        Ok(waiver)
+    }
+
+    #[tracing::instrument(name = "FeeService::list_waivers", skip_all, fields(account_id = %account_id))]
+    pub async fn list_waivers(pool: &PgPool, account_id: Uuid) -> Result<Vec<FeeWaiver>, AppError> {
+        AccountRepository::find_by_id(pool, account_id)
+            .await?
+            .ok_or(AccountError::NotFound)?;
+        Ok(FeeRepository::list_waivers(pool, account_id).await?)
+    }
+
+    #[tracing::instrument(name = "FeeService::revoke_waiver", skip_all, fields(waiver_id = %waiver_id))]
+    pub async fn revoke_waiver(
+        pool: &PgPool,
+        waiver_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<FeeWaiver, AppError> {
+        let mut tx = pool.begin().await?;
+        let waiver = FeeRepository::lock_waiver(&mut tx, waiver_id)
+            .await?
+            .ok_or(FeeError::WaiverNotFound)?;
+        if waiver.revoked_at.is_some() {
+            return Err(FeeError::WaiverRevoked.into());
+        }
+        let revoked = FeeRepository::revoke_waiver(&mut tx, waiver.id).await?;
+
+        let event = audit
+            .event(AuditAction::FeeWaiverRevoked, "fee_waiver", Some(waiver.id))
+            .before(waiver.audit_snapshot())
+            .after(revoked.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+        Ok(revoked)
+    }
+}
+
+/// Tarifa para os metadados de auditoria da operação tarifada
+pub(crate) fn fee_metadata(fee: Option<&Transaction>) -> serde_json::Value {
+    fee.map_or(
+        serde_json::Value::Null,
+        |fee| json!({ "transaction_id": fee.id, "amount": fee.amount }),
+    )
+}
@@ -0,0 +1,85 @@
+use chrono::Duration;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    database::{AccountRepository, FxRepository},
+    fx::FxRateProvider,
+    models::{
+        account::error::AccountError,
+        app_error::AppError,
+        business::OperatorPermission,
+        fx::{CreateFxQuote, FxQuote, MarketRate, error::FxError},
+        transaction::error::TransactionError,
+    },
+    services::transactions::accessible_account,
+    utils::fx_settings,
+    validators::TransactionValidator,
+};
+
+/// Cotações de câmbio para transferências entre contas de moedas diferentes
+///
+/// A cotação aplica o spread de `[fx]` à taxa média do provedor e fica travada
+/// por `quote_ttl_secs`; a transferência que a informa credita exatamente o
+/// valor convertido.
+pub struct FxService;
+
+impl FxService {
+    #[tracing::instrument(name = "FxService::quote", skip_all, fields(account_id = %account_id, provider = provider.name()))]
+    pub async fn quote(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        user_id: Uuid,
+        account_id: Uuid,
+        request: &CreateFxQuote,
+    ) -> Result<FxQuote, AppError> {
+        TransactionValidator::validate_amount(request.amount)
+            .map_err(|err| AppError::Validation(err.into()))?;
+
+        let source =
+            accessible_account(pool, user_id, account_id, OperatorPermission::Transact).await?;
+        if !source.is_active {
+            return Err(AccountError::Inactive.into());
+        }
+        TransactionValidator::validate_minor_units(request.amount, source.currency)
+            .map_err(AppError::Validation)?;
+
+        let mut conn = pool.acquire().await?;
+        let destination_id =
+            AccountRepository::find_id_by_number(&mut conn, &request.to_account_number)
+                .await?
+                .ok_or(TransactionError::DestinationAccountNotFound)?;
+        if destination_id == source.id {
+            return Err(TransactionError::SameAccountTransfer.into());
+        }
+        let destination = AccountRepository::find_by_id(pool, destination_id)
+            .await?
+            .filter(|account| account.is_active)
+            .ok_or(TransactionError::DestinationAccountNotFound)?;
+        if destination.currency == source.currency {
+            return Err(FxError::SameCurrency.into());
+        }
+
+        let mid_rate = provider.rate(source.currency, destination.currency).await?;
+        let settings = fx_settings();
+        let quote = FxQuote::new(
+            user_id,
+            &source,
+            &destination,
+            request.amount,
+            MarketRate {
+                mid_rate,
+                provider: provider.name(),
+            },
+            settings.spread_percent,
+            Duration::seconds(settings.quote_ttl_secs as i64),
+        );
+        if quote.converted_amount <= Decimal::ZERO {
+            return Err(FxError::AmountTooSmall.into());
+        }
+        FxRepository::insert_quote(&mut conn, &quote).await?;
+
+        Ok(quote)
+    }
+}
@@ -0,0 +1,275 @@
+use chrono::Utc;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AccountRepository, AuditRepository, HoldRepository, NotificationRepository,
+        OutboxRepository, TransactionRepository,
+    },
+    fx::FxRateProvider,
+    metrics,
+    models::{
+        account::error::AccountError,
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        business::OperatorPermission,
+        event::DomainEvent,
+        hold::{CaptureHold, CreateHold, Hold, HoldStatus, error::HoldError},
+        notification::Notification,
+        transaction::{Transaction, TransactionStatus, TransactionType},
+    },
+    services::{
+        KycService, LimitService, set_balance,
+        transactions::{accessible_account, debit, lock_owned_account},
+    },
+    validators::{HoldValidator, TransactionValidator},
+};
+
+/// Reservas de valor com captura em duas etapas
+///
+/// A reserva tira o valor do saldo disponível (`held_balance`) sem mexer no
+/// contábil; só a captura debita. As operações numa reserva existente travam
+/// a reserva antes da conta, a mesma ordem da expiração.
+pub struct HoldService;
+
+impl HoldService {
+    #[tracing::instrument(name = "HoldService::place", skip_all, fields(account_id = %account_id))]
+    pub async fn place(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        user_id: Uuid,
+        account_id: Uuid,
+        request: &CreateHold,
+        audit: &AuditContext,
+    ) -> Result<Hold, AppError> {
+        HoldValidator::validate(request).map_err(AppError::Validation)?;
+
+        let mut tx = pool.begin().await?;
+        let account =
+            lock_owned_account(&mut tx, user_id, account_id, OperatorPermission::Transact).await?;
+        TransactionValidator::validate_minor_units(request.amount, account.currency)
+            .map_err(AppError::Validation)?;
+        KycService::ensure_approved(&mut tx, account.user_id).await?;
+        debit(&account, request.amount)?;
+        // a reserva conta para os limites já aqui (lançamento pendente); a captura não confere de novo
+        LimitService::enforce(&mut tx, provider, &account, request.amount).await?;
+        let held_balance = account.held_balance + request.amount;
+        AccountRepository::update_held(&mut tx, account.id, held_balance).await?;
+
+        let transaction = Transaction::pending(
+            TransactionType::HoldCapture,
+            Some(account.id),
+            None,
+            request.amount,
+            account.currency,
+            &request.description,
+        );
+        TransactionRepository::insert(&mut tx, &transaction).await?;
+        let hold = Hold::new(account.id, user_id, request, transaction.id);
+        HoldRepository::insert(&mut tx, &hold).await?;
+
+        let event = audit
+            .event(AuditAction::HoldPlaced, "hold", Some(hold.id))
+            .before(json!({ "held_balance": account.held_balance }))
+            .after(json!({ "held_balance": held_balance, "hold": hold.audit_snapshot() }));
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(hold)
+    }
+
+    #[tracing::instrument(name = "HoldService::list", skip_all, fields(account_id = %account_id))]
+    pub async fn list(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        status: Option<HoldStatus>,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<Hold>, i64), AppError> {
+        accessible_account(pool, user_id, account_id, OperatorPermission::View).await?;
+        let page = HoldRepository::list_by_account(pool, account_id, status, limit, offset).await?;
+        Ok(page)
+    }
+
+    #[tracing::instrument(name = "HoldService::find", skip_all, fields(account_id = %account_id))]
+    pub async fn find(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        hold_id: Uuid,
+    ) -> Result<Hold, AppError> {
+        accessible_account(pool, user_id, account_id, OperatorPermission::View).await?;
+        let hold = HoldRepository::find(pool, account_id, hold_id)
+            .await?
+            .ok_or(HoldError::NotFound)?;
+        Ok(hold)
+    }
+
+    /// Debita o valor capturado (todo o reservado, se não informado) e libera o restante
+    #[tracing::instrument(name = "HoldService::capture", skip_all, fields(account_id = %account_id))]
+    pub async fn capture(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        hold_id: Uuid,
+        request: &CaptureHold,
+        audit: &AuditContext,
+    ) -> Result<Hold, AppError> {
+        HoldValidator::validate_capture(request).map_err(AppError::Validation)?;
+        accessible_account(pool, user_id, account_id, OperatorPermission::Transact).await?;
+
+        let now = Utc::now();
+        let mut tx = pool.begin().await?;
+        let mut hold = HoldRepository::lock(&mut tx, account_id, hold_id)
+            .await?
+            .ok_or(HoldError::NotFound)?;
+        if hold.status != HoldStatus::Active {
+            return Err(HoldError::NotActive.into());
+        }
+        // vencida e ainda não varrida pela expiração: não captura mais
+        if hold.is_expired(now) {
+            return Err(HoldError::Expired.into());
+        }
+        let amount = request.amount.unwrap_or(hold.amount);
+        if amount > hold.amount {
+            return Err(HoldError::CaptureExceedsHold.into());
+        }
+        let account =
+            lock_owned_account(&mut tx, user_id, account_id, OperatorPermission::Transact).await?;
+        TransactionValidator::validate_minor_units(amount, account.currency)
+            .map_err(AppError::Validation)?;
+
+        // o valor estava reservado: o contábil cobre a captura sem passar pelo disponível
+        let balance = account.balance - amount;
+        let held_balance = account.held_balance - hold.amount;
+        set_balance(&mut tx, &account, balance).await?;
+        AccountRepository::update_held(&mut tx, account.id, held_balance).await?;
+        let transaction = TransactionRepository::settle(
+            &mut tx,
+            hold.transaction_id,
+            TransactionStatus::Completed,
+            amount,
+        )
+        .await?;
+
+        let before = hold.audit_snapshot();
+        hold.capture(amount, now);
+        HoldRepository::save(&mut tx, &hold).await?;
+
+        let event = audit
+            .event(AuditAction::HoldCaptured, "hold", Some(hold.id))
+            .before(json!({
+                "balance": account.balance,
+                "held_balance": account.held_balance,
+                "hold": before,
+            }))
+            .after(json!({
+                "balance": balance,
+                "held_balance": held_balance,
+                "hold": hold.audit_snapshot(),
+            }))
+            .metadata(json!({ "transaction_id": transaction.id, "amount": amount }));
+        AuditRepository::append(&mut tx, &event).await?;
+        let event = DomainEvent::HoldCaptured {
+            hold_id: hold.id,
+            transaction_id: transaction.id,
+            account_id: account.id,
+            amount,
+            balance,
+        };
+        OutboxRepository::enqueue(&mut tx, event).await?;
+        let notification = Notification::balance_changed(
+            account.user_id,
+            account.id,
+            &transaction,
+            -amount,
+            balance,
+        );
+        NotificationRepository::publish(&mut tx, &notification).await?;
+        tx.commit().await?;
+
+        metrics::record_transaction(
+            &transaction.transaction_type,
+            transaction.amount,
+            transaction.currency,
+        );
+        Ok(hold)
+    }
+
+    /// Devolve o valor ao disponível sem debitar nada
+    #[tracing::instrument(name = "HoldService::release", skip_all, fields(account_id = %account_id))]
+    pub async fn release(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        hold_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<Hold, AppError> {
+        accessible_account(pool, user_id, account_id, OperatorPermission::Transact).await?;
+
+        let mut tx = pool.begin().await?;
+        let hold = HoldRepository::lock(&mut tx, account_id, hold_id)
+            .await?
+            .ok_or(HoldError::NotFound)?;
+        if hold.status != HoldStatus::Active {
+            return Err(HoldError::NotActive.into());
+        }
+        let hold = Self::finish(&mut tx, hold, HoldStatus::Released, audit).await?;
+        tx.commit().await?;
+
+        Ok(hold)
+    }
+
+    /// Expira a reserva vencida mais antiga; `false` quando não há nenhuma
+    #[tracing::instrument(name = "HoldService::expire_due", skip_all, fields(hold_id = tracing::field::Empty))]
+    pub async fn expire_due(pool: &PgPool) -> Result<bool, AppError> {
+        let mut tx = pool.begin().await?;
+        let Some(hold) = HoldRepository::claim_expired(&mut tx).await? else {
+            return Ok(false);
+        };
+        tracing::Span::current().record("hold_id", tracing::field::display(hold.id));
+
+        Self::finish(&mut tx, hold, HoldStatus::Expired, &AuditContext::default()).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Encerra a reserva sem captura: devolve o valor ao disponível e cancela o lançamento
+    async fn finish(
+        conn: &mut PgConnection,
+        mut hold: Hold,
+        status: HoldStatus,
+        audit: &AuditContext,
+    ) -> Result<Hold, AppError> {
+        let account = AccountRepository::lock_by_id(conn, hold.account_id)
+            .await?
+            .ok_or(AccountError::NotFound)?;
+        let held_balance = account.held_balance - hold.amount;
+        AccountRepository::update_held(conn, account.id, held_balance).await?;
+        TransactionRepository::settle(
+            conn,
+            hold.transaction_id,
+            TransactionStatus::Failed,
+            hold.amount,
+        )
+        .await?;
+
+        let before = hold.audit_snapshot();
+        hold.finish(status, Utc::now());
+        HoldRepository::save(conn, &hold).await?;
+
+        let action = match status {
+            HoldStatus::Expired => AuditAction::HoldExpired,
+            _ => AuditAction::HoldReleased,
+        };
+        let event = audit
+            .event(action, "hold", Some(hold.id))
+            .before(json!({ "held_balance": account.held_balance, "hold": before }))
+            .after(json!({ "held_balance": held_balance, "hold": hold.audit_snapshot() }));
+        AuditRepository::append(conn, &event).await?;
+        Ok(hold)
+    }
+}
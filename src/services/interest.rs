@@ -0,0 +1,278 @@
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AuditRepository, InterestRepository, NotificationRepository, OutboxRepository,
+        TransactionRepository,
+    },
+    metrics,
+    models::{
+        account::{Account, AccountType},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        business::OperatorPermission,
+        event::DomainEvent,
+        interest::{
+            InterestAccrual, InterestSettlement, InterestSummary, local_date, local_midnight,
+        },
+        notification::Notification,
+        transaction::{Transaction, TransactionType},
+    },
+    services::{
+        set_balance,
+        transactions::{accessible_account, lock_owned_account},
+    },
+    utils::{interest_settings, limit_settings},
+};
+
+/// Rendimento das contas poupança e investimento e juros do cheque especial
+///
+/// A apuração grava os juros de cada conta em cada dia (uma linha por dia, então
+/// rodar de novo não duplica; no saldo negativo o valor é negativo); o lançamento
+/// soma os dias do mês anterior e credita um `interest` ou debita um
+/// `overdraft_interest` só com o valor em centavos.
+pub struct InterestService;
+
+impl InterestService {
+    /// Apura o dia `accrual_date` de uma conta que ainda não tem apuração nele;
+    /// `false` quando não resta nenhuma
+    #[tracing::instrument(name = "InterestService::accrue_due", skip_all, fields(accrual_date = %accrual_date, account_id = tracing::field::Empty))]
+    pub async fn accrue_due(pool: &PgPool, accrual_date: NaiveDate) -> Result<bool, AppError> {
+        let settings = interest_settings();
+        let account_types: Vec<AccountType> = [AccountType::Savings, AccountType::Investment]
+            .into_iter()
+            .filter(|account_type| settings.schedule_for(*account_type).is_some())
+            .collect();
+        let overdraft = settings.overdraft.is_some();
+        let Some(next_day) = accrual_date.succ_opt() else {
+            return Ok(false);
+        };
+        if account_types.is_empty() && !overdraft {
+            return Ok(false);
+        }
+        // contas abertas depois do fim do dia não rendem nele
+        let created_before = local_midnight(next_day, limit_settings().utc_offset_hours);
+
+        let mut tx = pool.begin().await?;
+        let Some(mut account) = InterestRepository::claim_unaccrued(
+            &mut tx,
+            accrual_date,
+            created_before,
+            &account_types,
+            overdraft,
+        )
+        .await?
+        else {
+            return Ok(false);
+        };
+        tracing::Span::current().record("account_id", tracing::field::display(account.id));
+        // o dia vale pelo saldo com que terminou, mesmo apurado dias depois
+        account.balance =
+            TransactionRepository::balance_at(&mut tx, account.id, account.balance, created_before)
+                .await?;
+
+        let reference = settings
+            .reference_rate(accrual_date)
+            .unwrap_or(Decimal::ZERO);
+        let annual_rate = settings
+            .schedule_for_balance(account.account_type, account.balance)
+            .map_or(Decimal::ZERO, |schedule| schedule.annual_rate(reference));
+        let accrual = InterestAccrual::new(&account, accrual_date, annual_rate);
+        InterestRepository::insert_accrual(&mut tx, &accrual).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Lança numa conta os juros apurados antes de `before` (o primeiro dia do
+    /// mês corrente): credita o rendimento ou debita os juros do cheque especial;
+    /// `false` quando não resta nenhuma
+    #[tracing::instrument(name = "InterestService::settle_due", skip_all, fields(account_id = tracing::field::Empty))]
+    pub async fn settle_due(pool: &PgPool, before: NaiveDate) -> Result<bool, AppError> {
+        let mut tx = pool.begin().await?;
+        let Some(account) = InterestRepository::claim_creditable(&mut tx, before).await? else {
+            return Ok(false);
+        };
+        tracing::Span::current().record("account_id", tracing::field::display(account.id));
+
+        let settled = Self::settle(&mut tx, &account, before).await?;
+        tx.commit().await?;
+
+        if let Some(transaction) = settled {
+            metrics::record_transaction(
+                &transaction.transaction_type,
+                transaction.amount,
+                transaction.currency,
+            );
+        }
+        Ok(true)
+    }
+
+    /// Lança os juros apurados antes de `before` na conta já travada, dentro da
+    /// transação de quem chama; devolve o lançamento, se a soma deu ao menos um centavo
+    pub(crate) async fn settle(
+        conn: &mut PgConnection,
+        account: &Account,
+        before: NaiveDate,
+    ) -> Result<Option<Transaction>, AppError> {
+        let pending = InterestRepository::pending(&mut *conn, account.id, before).await?;
+        let amount = pending.credit_amount(account.currency);
+        let now = Utc::now();
+
+        // menos de meio centavo no período: marca os dias sem lançar nada
+        let mut settled = None;
+        if let (false, Some(period_start), Some(period_end)) =
+            (amount.is_zero(), pending.first_date, pending.last_date)
+        {
+            // juros do cheque especial podem levar o saldo além do limite
+            let balance = account.balance + amount;
+            set_balance(&mut *conn, account, balance).await?;
+            let period = format!(
+                "{} a {}",
+                period_start.format("%d/%m/%Y"),
+                period_end.format("%d/%m/%Y")
+            );
+            let (transaction, action, event) = if amount > Decimal::ZERO {
+                let transaction = Transaction::completed(
+                    TransactionType::Interest,
+                    None,
+                    Some(account.id),
+                    amount,
+                    account.currency,
+                    &format!("Rendimento de {period}"),
+                );
+                let event = DomainEvent::InterestCredited {
+                    transaction_id: transaction.id,
+                    account_id: account.id,
+                    amount,
+                    balance,
+                    period_start,
+                    period_end,
+                };
+                (transaction, AuditAction::InterestCredited, event)
+            } else {
+                let transaction = Transaction::completed(
+                    TransactionType::OverdraftInterest,
+                    Some(account.id),
+                    None,
+                    -amount,
+                    account.currency,
+                    &format!("Juros do cheque especial de {period}"),
+                );
+                let event = DomainEvent::OverdraftInterestCharged {
+                    transaction_id: transaction.id,
+                    account_id: account.id,
+                    amount: -amount,
+                    balance,
+                    period_start,
+                    period_end,
+                };
+                (transaction, AuditAction::OverdraftInterestCharged, event)
+            };
+            TransactionRepository::insert(&mut *conn, &transaction).await?;
+
+            let audit = AuditContext::default()
+                .event(action, "account", Some(account.id))
+                .before(json!({ "balance": account.balance }))
+                .after(json!({ "balance": balance }))
+                .metadata(json!({
+                    "transaction_id": transaction.id,
+                    "amount": transaction.amount,
+                    "days": pending.days,
+                    "period_start": period_start,
+                    "period_end": period_end,
+                }));
+            AuditRepository::append(&mut *conn, &audit).await?;
+            OutboxRepository::enqueue(&mut *conn, event).await?;
+            let notification = Notification::balance_changed(
+                account.user_id,
+                account.id,
+                &transaction,
+                amount,
+                balance,
+            );
+            NotificationRepository::publish(&mut *conn, &notification).await?;
+            settled = Some(transaction);
+        }
+        InterestRepository::mark_credited(
+            conn,
+            account.id,
+            before,
+            settled.as_ref().map(|transaction| transaction.id),
+            now,
+        )
+        .await?;
+        Ok(settled)
+    }
+
+    /// Lança agora os juros apurados até hoje, sem esperar o mês virar; é o passo
+    /// antes de encerrar a conta, que não lança nada por conta própria
+    #[tracing::instrument(name = "InterestService::settle_now", skip_all, fields(account_id = %account_id))]
+    pub async fn settle_now(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<InterestSettlement, AppError> {
+        let mut tx = pool.begin().await?;
+        let account =
+            lock_owned_account(&mut tx, user_id, account_id, OperatorPermission::Manage).await?;
+        let today = local_date(Utc::now(), limit_settings().utc_offset_hours);
+        let until = today.succ_opt().unwrap_or(today);
+        let settled = Self::settle(&mut tx, &account, until).await?;
+        tx.commit().await?;
+
+        let balance = match &settled {
+            Some(transaction) => {
+                metrics::record_transaction(
+                    &transaction.transaction_type,
+                    transaction.amount,
+                    transaction.currency,
+                );
+                if transaction.transaction_type == TransactionType::Interest {
+                    account.balance + transaction.amount
+                } else {
+                    account.balance - transaction.amount
+                }
+            }
+            None => account.balance,
+        };
+        Ok(InterestSettlement {
+            account_id: account.id,
+            balance,
+            transaction: settled,
+        })
+    }
+
+    /// Taxa em vigor e o que a conta já apurou para o próximo crédito
+    #[tracing::instrument(name = "InterestService::summary", skip_all, fields(account_id = %account_id))]
+    pub async fn summary(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<InterestSummary, AppError> {
+        let account =
+            accessible_account(pool, user_id, account_id, OperatorPermission::View).await?;
+        let settings = interest_settings();
+        let today = local_date(Utc::now(), limit_settings().utc_offset_hours);
+        let schedule = settings.schedule_for_balance(account.account_type, account.balance);
+        let reference_rate = settings.reference_rate(today);
+
+        let mut conn = pool.acquire().await?;
+        let before = today.succ_opt().unwrap_or(today);
+        let pending = InterestRepository::pending(&mut conn, account.id, before).await?;
+
+        Ok(InterestSummary {
+            account_id: account.id,
+            account_type: account.account_type,
+            annual_rate: schedule
+                .map(|schedule| schedule.annual_rate(reference_rate.unwrap_or(Decimal::ZERO))),
+            reference_rate,
+            accrued: pending.credit_amount(account.currency),
+            accrued_days: pending.days,
+            last_accrual_date: pending.last_date,
+        })
+    }
+}
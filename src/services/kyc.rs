@@ -0,0 +1,266 @@
+use std::fmt::Write;
+
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AuditRepository, KycRepository, NotificationRepository, OutboxRepository, UserRepository,
+    },
+    models::{
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        error::UserError,
+        event::DomainEvent,
+        kyc::{
+            KycDocument, KycDocumentType, KycOverview, KycProfile, KycStatus, RejectKyc,
+            error::KycError,
+        },
+        notification::Notification,
+    },
+    storage::DocumentStorage,
+    validators::KycValidator,
+};
+
+/// Cadastro (KYC): envio de documentos, envio para revisão e decisão dos admins
+///
+/// Enquanto o cadastro não é aprovado o usuário não abre contas nem tira
+/// dinheiro delas (`ensure_approved`).
+pub struct KycService;
+
+impl KycService {
+    #[tracing::instrument(name = "KycService::overview", skip_all, fields(user_id = %user_id))]
+    pub async fn overview(pool: &PgPool, user_id: Uuid) -> Result<KycOverview, AppError> {
+        let user = UserRepository::find_by_id(pool, user_id)
+            .await?
+            .ok_or(UserError::NotFound)?;
+        let profile = KycRepository::find_profile(pool, user_id)
+            .await?
+            .ok_or(KycError::NotFound)?;
+        let mut conn = pool.acquire().await?;
+        let documents = KycRepository::list_documents(&mut conn, user_id).await?;
+
+        Ok(KycOverview::new(profile, documents, user.customer_type))
+    }
+
+    /// Grava o arquivo e registra o documento, substituindo o do mesmo tipo
+    ///
+    /// O arquivo novo vai para o storage antes do commit (e é apagado se a
+    /// transação falhar); o substituído só é apagado depois do commit.
+    #[tracing::instrument(name = "KycService::upload", skip_all, fields(user_id = %user_id, document_type = document_type.as_str()))]
+    pub async fn upload(
+        pool: &PgPool,
+        storage: &dyn DocumentStorage,
+        user_id: Uuid,
+        document_type: KycDocumentType,
+        content_type: Option<&str>,
+        content: &[u8],
+        audit: &AuditContext,
+    ) -> Result<KycDocument, AppError> {
+        let content_type = KycValidator::validate_document(content_type, content)
+            .map_err(|err| AppError::Validation(err.into()))?;
+
+        let id = Uuid::new_v4();
+        let document = KycDocument {
+            id,
+            user_id,
+            document_type,
+            content_type: content_type.to_string(),
+            size_bytes: content.len() as i64,
+            sha256: sha256_hex(content),
+            storage_key: format!("kyc/{user_id}/{}-{id}", document_type.as_str()),
+            created_at: Utc::now(),
+        };
+
+        let mut tx = pool.begin().await?;
+        let profile = KycRepository::lock_profile(&mut tx, user_id)
+            .await?
+            .ok_or(KycError::NotFound)?;
+        if !profile.status.accepts_documents() {
+            return Err(KycError::InvalidStatus.into());
+        }
+
+        storage.put(&document.storage_key, content).await?;
+        let result = async {
+            let previous = KycRepository::replace_document(&mut tx, &document).await?;
+            let mut event = audit
+                .event(AuditAction::KycDocumentUploaded, "kyc_document", Some(id))
+                .after(document.audit_snapshot());
+            if let Some(previous) = &previous {
+                event = event.before(previous.audit_snapshot());
+            }
+            AuditRepository::append(&mut tx, &event).await?;
+            tx.commit().await?;
+            Ok::<_, AppError>(previous)
+        }
+        .await;
+
+        match result {
+            Ok(previous) => {
+                if let Some(previous) = previous
+                    && let Err(err) = storage.delete(&previous.storage_key).await
+                {
+                    tracing::warn!(storage = storage.name(), key = %previous.storage_key, "Falha ao apagar documento substituído: {err}");
+                }
+                Ok(document)
+            }
+            Err(err) => {
+                if let Err(cleanup) = storage.delete(&document.storage_key).await {
+                    tracing::warn!(storage = storage.name(), key = %document.storage_key, "Falha ao apagar documento órfão: {cleanup}");
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Envia o cadastro para revisão; exige os documentos do tipo de cliente
+    #[tracing::instrument(name = "KycService::submit", skip_all, fields(user_id = %user_id))]
+    pub async fn submit(
+        pool: &PgPool,
+        user_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<KycOverview, AppError> {
+        let user = UserRepository::find_by_id(pool, user_id)
+            .await?
+            .ok_or(UserError::NotFound)?;
+
+        let mut tx = pool.begin().await?;
+        let profile = KycRepository::lock_profile(&mut tx, user_id)
+            .await?
+            .ok_or(KycError::NotFound)?;
+        let documents = KycRepository::list_documents(&mut tx, user_id).await?;
+        let overview = KycOverview::new(profile, documents, user.customer_type);
+        KycValidator::validate_submission(&overview.missing_documents)
+            .map_err(|err| AppError::Validation(err.into()))?;
+
+        let submitted = overview
+            .profile
+            .transition(KycStatus::Submitted, None, Utc::now())?;
+        KycRepository::save_profile(&mut tx, &submitted).await?;
+        let event = audit
+            .event(AuditAction::KycSubmitted, "kyc_profile", Some(user_id))
+            .before(overview.profile.audit_snapshot())
+            .after(submitted.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(KycOverview {
+            profile: submitted,
+            ..overview
+        })
+    }
+
+    /// Admin assume o cadastro enviado; a decisão vem depois em `approve`/`reject`
+    #[tracing::instrument(name = "KycService::start_review", skip_all, fields(user_id = %user_id))]
+    pub async fn start_review(
+        pool: &PgPool,
+        reviewer_id: Uuid,
+        user_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<KycProfile, AppError> {
+        Self::review(
+            pool,
+            reviewer_id,
+            user_id,
+            KycStatus::UnderReview,
+            None,
+            audit,
+        )
+        .await
+    }
+
+    #[tracing::instrument(name = "KycService::approve", skip_all, fields(user_id = %user_id))]
+    pub async fn approve(
+        pool: &PgPool,
+        reviewer_id: Uuid,
+        user_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<KycProfile, AppError> {
+        Self::review(pool, reviewer_id, user_id, KycStatus::Approved, None, audit).await
+    }
+
+    #[tracing::instrument(name = "KycService::reject", skip_all, fields(user_id = %user_id))]
+    pub async fn reject(
+        pool: &PgPool,
+        reviewer_id: Uuid,
+        user_id: Uuid,
+        request: &RejectKyc,
+        audit: &AuditContext,
+    ) -> Result<KycProfile, AppError> {
+        let reason = KycValidator::validate_reason(&request.reason)
+            .map_err(|err| AppError::Validation(err.into()))?;
+        Self::review(
+            pool,
+            reviewer_id,
+            user_id,
+            KycStatus::Rejected,
+            Some(reason),
+            audit,
+        )
+        .await
+    }
+
+    async fn review(
+        pool: &PgPool,
+        reviewer_id: Uuid,
+        user_id: Uuid,
+        next: KycStatus,
+        rejection_reason: Option<String>,
+        audit: &AuditContext,
+    ) -> Result<KycProfile, AppError> {
+        // ninguém revisa o próprio cadastro
+        if reviewer_id == user_id {
+            return Err(AppError::Forbidden);
+        }
+
+        let mut tx = pool.begin().await?;
+        let profile = KycRepository::lock_profile(&mut tx, user_id)
+            .await?
+            .ok_or(KycError::NotFound)?;
+        let mut reviewed = profile.transition(next, Some(reviewer_id), Utc::now())?;
+        reviewed.rejection_reason = rejection_reason;
+        KycRepository::save_profile(&mut tx, &reviewed).await?;
+
+        let action = match next {
+            KycStatus::Approved => AuditAction::KycApproved,
+            KycStatus::Rejected => AuditAction::KycRejected,
+            _ => AuditAction::KycReviewStarted,
+        };
+        let event = audit
+            .event(action, "kyc_profile", Some(user_id))
+            .before(profile.audit_snapshot())
+            .after(reviewed.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        if matches!(next, KycStatus::Approved | KycStatus::Rejected) {
+            let event = DomainEvent::KycReviewed {
+                user_id,
+                status: next,
+            };
+            OutboxRepository::enqueue(&mut tx, event).await?;
+        }
+        let notification = Notification::kyc_status_changed(&reviewed);
+        NotificationRepository::publish(&mut tx, &notification).await?;
+        tx.commit().await?;
+
+        Ok(reviewed)
+    }
+
+    /// Bloqueia abertura de conta e débitos de quem ainda não foi aprovado
+    pub async fn ensure_approved(conn: &mut PgConnection, user_id: Uuid) -> Result<(), AppError> {
+        match KycRepository::status(conn, user_id).await? {
+            Some(KycStatus::Approved) => Ok(()),
+            _ => Err(KycError::NotApproved.into()),
+        }
+    }
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
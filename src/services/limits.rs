@@ -0,0 +1,174 @@
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    config::AccountLimits,
+    database::{AuditRepository, LimitRepository, TransactionRepository, UserRepository},
+    fx::{FxRateProvider, brl_rate},
+    models::{
+        account::Account,
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        business::OperatorPermission,
+        limit::{
+            AccountLimit, AccountLimitsView, EffectiveLimits, LimitUsage, LimitWindows,
+            UpdateLimits,
+        },
+    },
+    services::transactions::{accessible_account, lock_owned_account},
+    utils::limit_settings,
+    validators::LimitValidator,
+};
+
+/// Limites de saída das contas (por transação, diário, mensal e noturno)
+///
+/// Sem personalização vale o teto do tipo de conta, configurado em reais e
+/// convertido para a moeda da conta pela taxa do provedor. O titular reduz na hora e
+/// só volta a subir depois da carência (`raise_cooling_off_hours`), para quem
+/// tomar a conta não conseguir aumentar o limite e sacar logo em seguida.
+pub struct LimitService;
+
+impl LimitService {
+    #[tracing::instrument(name = "LimitService::view", skip_all, fields(account_id = %account_id))]
+    pub async fn view(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<AccountLimitsView, AppError> {
+        let account =
+            accessible_account(pool, user_id, account_id, OperatorPermission::View).await?;
+        let defaults = defaults_for(provider, &account).await?;
+        let mut conn = pool.acquire().await?;
+        load_view(&mut conn, &account, &defaults).await
+    }
+
+    #[tracing::instrument(name = "LimitService::update", skip_all, fields(account_id = %account_id))]
+    pub async fn update(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        user_id: Uuid,
+        account_id: Uuid,
+        request: &UpdateLimits,
+        audit: &AuditContext,
+    ) -> Result<AccountLimitsView, AppError> {
+        let settings = limit_settings();
+        let mut tx = pool.begin().await?;
+        let account =
+            lock_owned_account(&mut tx, user_id, account_id, OperatorPermission::Manage).await?;
+        let defaults = &defaults_for(provider, &account).await?;
+        LimitValidator::validate(request, defaults).map_err(AppError::Validation)?;
+
+        let mut custom = LimitRepository::list(&mut tx, account.id).await?;
+        let now = Utc::now();
+        let before = EffectiveLimits::resolve(defaults, &custom, now);
+        let cooling_off = Duration::hours(i64::from(settings.raise_cooling_off_hours));
+        let mut changes = Vec::new();
+        for (kind, amount) in request.requested() {
+            let index = match custom.iter().position(|limit| limit.kind == kind) {
+                Some(index) => index,
+                None => {
+                    custom.push(AccountLimit::new(account.id, kind, kind.max(defaults), now));
+                    custom.len() - 1
+                }
+            };
+            let limit = &mut custom[index];
+            limit.change(amount, now, cooling_off);
+            LimitRepository::upsert(&mut tx, limit).await?;
+            changes.push(json!({
+                "kind": kind,
+                "amount": limit.amount,
+                "pending_amount": limit.pending_amount,
+                "pending_effective_at": limit.pending_effective_at,
+            }));
+        }
+
+        let event = audit
+            .event(AuditAction::LimitsUpdated, "account", Some(account.id))
+            .before(limits_snapshot(&before))
+            .after(limits_snapshot(&EffectiveLimits::resolve(
+                defaults, &custom, now,
+            )))
+            .metadata(json!({ "changes": changes }));
+        AuditRepository::append(&mut tx, &event).await?;
+        let view = load_view(&mut tx, &account, defaults).await?;
+        tx.commit().await?;
+        Ok(view)
+    }
+
+    /// Confere os limites da conta e os do titular (todas as contas dele) para
+    /// um novo débito de `amount`; chame com a conta travada, assim duas
+    /// operações simultâneas não passam juntas do limite
+    pub(crate) async fn enforce(
+        conn: &mut PgConnection,
+        provider: &dyn FxRateProvider,
+        account: &Account,
+        amount: Decimal,
+    ) -> Result<(), AppError> {
+        let settings = limit_settings();
+        let now = Utc::now();
+        let rate = brl_rate(provider, account.currency).await?;
+        let defaults = settings
+            .for_type(account.account_type)
+            .in_currency(account.currency, rate);
+        let custom = LimitRepository::list(conn, account.id).await?;
+        let limits = EffectiveLimits::resolve(&defaults, &custom, now);
+        let windows = LimitWindows::at(now, settings);
+        let usage = TransactionRepository::outgoing_usage(conn, account.id, &windows).await?;
+        limits.check(&usage, amount)?;
+
+        // a trava do titular vem depois da conta (mesma ordem em todo débito) e
+        // segura débitos simultâneos nas outras contas dele até o commit
+        UserRepository::lock_for_limits(conn, account.user_id).await?;
+        let by_currency =
+            TransactionRepository::outgoing_usage_by_user(conn, account.user_id, &windows).await?;
+        let mut usages = Vec::with_capacity(by_currency.len());
+        for (currency, usage) in by_currency {
+            usages.push((usage, brl_rate(provider, currency).await?));
+        }
+        LimitUsage::sum_in_brl(&usages).check_user(&settings.user, amount / rate)?;
+        Ok(())
+    }
+}
+
+/// Teto do tipo de conta na moeda dela
+async fn defaults_for(
+    provider: &dyn FxRateProvider,
+    account: &Account,
+) -> Result<AccountLimits, AppError> {
+    let defaults = limit_settings().for_type(account.account_type);
+    let rate = brl_rate(provider, account.currency).await?;
+    Ok(defaults.in_currency(account.currency, rate))
+}
+
+async fn load_view(
+    conn: &mut PgConnection,
+    account: &Account,
+    defaults: &AccountLimits,
+) -> Result<AccountLimitsView, AppError> {
+    let settings = limit_settings();
+    let now = Utc::now();
+    let custom = LimitRepository::list(conn, account.id).await?;
+    let windows = LimitWindows::at(now, settings);
+    let usage = TransactionRepository::outgoing_usage(conn, account.id, &windows).await?;
+    Ok(AccountLimitsView::new(
+        account.id,
+        account.account_type,
+        defaults,
+        &custom,
+        usage,
+        now,
+    ))
+}
+
+fn limits_snapshot(limits: &EffectiveLimits) -> serde_json::Value {
+    json!({
+        "per_transaction": limits.per_transaction,
+        "daily": limits.daily,
+        "monthly": limits.monthly,
+        "nightly": limits.nightly,
+    })
+}
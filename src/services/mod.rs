@@ -0,0 +1,30 @@
+//! Regras de negócio que envolvem mais de um repositório na mesma transação
+mod boleto;
+mod business;
+mod fees;
+mod fx;
+mod holds;
+mod interest;
+mod kyc;
+mod limits;
+mod overdraft;
+mod pix;
+mod reversals;
+mod scheduled_transfers;
+mod transactions;
+
+pub use boleto::BoletoService;
+pub use business::BusinessService;
+pub use fees::FeeService;
+pub use fx::FxService;
+pub use holds::HoldService;
+pub use interest::InterestService;
+pub use kyc::KycService;
+pub use limits::LimitService;
+pub use overdraft::OverdraftService;
+pub(crate) use overdraft::set_balance;
+pub use pix::PixService;
+pub use reversals::ReversalService;
+pub use scheduled_transfers::ScheduledTransferService;
+pub use transactions::TransactionService;
+pub(crate) use transactions::authorize_account;
@@ -0,0 +1,98 @@
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{AccountRepository, AuditRepository, OutboxRepository},
+    fx::{FxRateProvider, brl_rate},
+    models::{
+        account::{Account, AccountType, UpdateOverdraft, error::AccountError},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        event::DomainEvent,
+    },
+    utils::limit_settings,
+    validators::OverdraftValidator,
+};
+
+/// Cheque especial da conta corrente
+///
+/// O saldo negativo é o valor em uso; como o saldo é um só, qualquer crédito
+/// na conta já amortiza o cheque especial. Os juros são apurados junto com os
+/// rendimentos (`InterestService`).
+pub struct OverdraftService;
+
+impl OverdraftService {
+    /// Troca o limite da conta; zero desliga o cheque especial. O teto
+    /// (`overdraft_max`, em reais) vai para a moeda da conta pela taxa do provedor
+    #[tracing::instrument(name = "OverdraftService::update_limit", skip_all, fields(account_id = %account_id))]
+    pub async fn update_limit(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        account_id: Uuid,
+        request: &UpdateOverdraft,
+        audit: &AuditContext,
+    ) -> Result<Account, AppError> {
+        let mut tx = pool.begin().await?;
+        let mut account = AccountRepository::lock_by_id(&mut tx, account_id)
+            .await?
+            .ok_or(AccountError::NotFound)?;
+        let rate = brl_rate(provider, account.currency).await?;
+        let max = account
+            .currency
+            .round(limit_settings().overdraft_max * rate);
+        OverdraftValidator::validate(request, max).map_err(AppError::Validation)?;
+        if account.account_type != AccountType::Checking {
+            return Err(AccountError::OverdraftNotAllowed.into());
+        }
+        if !account.is_active {
+            return Err(AccountError::Inactive.into());
+        }
+        if request.limit < account.overdraft_committed() {
+            return Err(AccountError::OverdraftLimitBelowUsage.into());
+        }
+        AccountRepository::update_overdraft_limit(&mut tx, account.id, request.limit).await?;
+
+        let before = account.audit_snapshot();
+        account.overdraft_limit = request.limit;
+        let event = audit
+            .event(
+                AuditAction::OverdraftLimitUpdated,
+                "account",
+                Some(account.id),
+            )
+            .before(before)
+            .after(account.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+        Ok(account)
+    }
+}
+
+/// Grava o novo saldo contábil e publica a entrada ou a saída do cheque especial
+/// quando o saldo cruza o zero; todo lançamento que mexe no saldo passa por aqui
+pub(crate) async fn set_balance(
+    conn: &mut PgConnection,
+    account: &Account,
+    balance: Decimal,
+) -> Result<(), AppError> {
+    AccountRepository::update_balance(conn, account.id, balance).await?;
+
+    let was_negative = account.balance < Decimal::ZERO;
+    let event = match (was_negative, balance < Decimal::ZERO) {
+        (false, true) => DomainEvent::OverdraftEntered {
+            account_id: account.id,
+            user_id: account.user_id,
+            balance,
+            overdraft_limit: account.overdraft_limit,
+        },
+        (true, false) => DomainEvent::OverdraftLeft {
+            account_id: account.id,
+            user_id: account.user_id,
+            balance,
+        },
+        _ => return Ok(()),
+    };
+    OutboxRepository::enqueue(conn, event).await?;
+    Ok(())
+}
@@ -0,0 +1,485 @@
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{AuditRepository, NotificationRepository, PixRepository, UserRepository},
+    models::{
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        brcode::{
+            BrCode, BrCodeKind, BrCodeResponse, CreateBrCode, MERCHANT_CITY, ParsedBrCode,
+            generate_txid,
+        },
+        business::OperatorPermission,
+        error::UserError,
+        notification::Notification,
+        pix::{
+            ClaimStatus, ClaimType, CreatePixClaim, CreatePixKey, CreatePixKeyVerification,
+            MAX_KEYS_PER_ACCOUNT, MAX_VERIFICATIONS_PER_HOUR, PixCharge, PixClaim, PixKey,
+            PixKeyType, PixKeyVerification, VerificationProof, error::PixError,
+        },
+    },
+    services::transactions::lock_owned_account,
+    utils::pix_settings,
+    validators::{BrCodeValidator, PixKeyValidator},
+    verification::{Channel, CodeSender},
+};
+
+/// Diretório de chaves PIX: cadastro, remoção, portabilidade e reivindicação de posse
+///
+/// A conta é travada antes de contar as chaves, para o limite valer com
+/// cadastros concorrentes; a unicidade entre contas fica com o índice do banco.
+/// Quem cadastra ou reivindica email e telefone prova a posse com o código
+/// enviado para a chave; CPF só entra na conta do próprio titular.
+pub struct PixService;
+
+impl PixService {
+    /// Envia um código de uso único para o email ou telefone informado
+    #[tracing::instrument(name = "PixService::send_verification", skip_all, fields(sender = sender.name()))]
+    pub async fn send_verification(
+        pool: &PgPool,
+        sender: &dyn CodeSender,
+        user_id: Uuid,
+        request: &CreatePixKeyVerification,
+    ) -> Result<PixKeyVerification, AppError> {
+        let (key_type, key) = PixKeyValidator::parse("key", &request.key)
+            .map_err(|err| AppError::Validation(err.into()))?;
+        let channel = match key_type {
+            PixKeyType::Email => Channel::Email,
+            PixKeyType::Phone => Channel::Sms,
+            PixKeyType::Cpf | PixKeyType::Evp => {
+                return Err(AppError::Validation(
+                    PixKeyValidator::not_verifiable("key").into(),
+                ));
+            }
+        };
+
+        let since = Utc::now() - chrono::Duration::hours(1);
+        if PixRepository::count_recent_verifications(pool, user_id, since).await?
+            >= MAX_VERIFICATIONS_PER_HOUR
+        {
+            return Err(PixError::TooManyVerifications.into());
+        }
+
+        let (verification, code) = PixKeyVerification::new(user_id, key_type, key);
+        PixRepository::insert_verification(pool, &verification).await?;
+        sender.send(channel, &verification.key, &code).await?;
+
+        Ok(verification)
+    }
+
+    #[tracing::instrument(name = "PixService::register_key", skip_all, fields(account_id = %request.account_id))]
+    pub async fn register_key(
+        pool: &PgPool,
+        owner_id: Uuid,
+        request: &CreatePixKey,
+        audit: &AuditContext,
+    ) -> Result<PixKey, AppError> {
+        let key = PixKeyValidator::validate_new(request.key_type, request.key.as_deref())
+            .map_err(|err| AppError::Validation(err.into()))?
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let verification_id = if request.key_type.is_claimable() {
+            let proof = request
+                .verification
+                .as_ref()
+                .ok_or(PixError::VerificationRequired)?;
+            Some(check_verification(pool, owner_id, &key, proof).await?)
+        } else {
+            None
+        };
+
+        let mut tx = pool.begin().await?;
+        let account = lock_owned_account(
+            &mut tx,
+            owner_id,
+            request.account_id,
+            OperatorPermission::Manage,
+        )
+        .await?;
+        if request.key_type == PixKeyType::Cpf {
+            let holder = UserRepository::find_by_id(pool, account.user_id)
+                .await?
+                .ok_or(UserError::NotFound)?;
+            if holder.document.as_ref().map(|doc| doc.as_str()) != Some(key.as_str()) {
+                return Err(PixError::DocumentMismatch.into());
+            }
+        }
+        ensure_key_limit(&mut tx, account.id).await?;
+        if let Some(verification_id) = verification_id {
+            consume_verification(&mut tx, verification_id).await?;
+        }
+
+        let key = PixKey::new(account.user_id, account.id, request.key_type, key);
+        if !PixRepository::insert(&mut tx, &key).await? {
+            return Err(PixError::KeyAlreadyRegistered.into());
+        }
+        let event = audit
+            .event(AuditAction::PixKeyRegistered, "pix_key", Some(key.id))
+            .after(key.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(key)
+    }
+
+    /// Remove a chave; com reivindicação em aberto, ela precisa ser resolvida antes
+    #[tracing::instrument(name = "PixService::delete_key", skip_all, fields(key_id = %key_id))]
+    pub async fn delete_key(
+        pool: &PgPool,
+        owner_id: Uuid,
+        key_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<PixKey, AppError> {
+        let mut tx = pool.begin().await?;
+        let key = PixRepository::lock_by_id(&mut tx, key_id)
+            .await?
+            .filter(|key| key.user_id == owner_id)
+            .ok_or(PixError::KeyNotFound)?;
+        if PixRepository::has_open_claim(&mut tx, key.id).await? {
+            return Err(PixError::ClaimOpen.into());
+        }
+
+        PixRepository::soft_delete(&mut tx, key.id).await?;
+        let event = audit
+            .event(AuditAction::PixKeyDeleted, "pix_key", Some(key.id))
+            .before(key.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(key)
+    }
+
+    /// Pede uma chave registrada em outra conta
+    ///
+    /// Do mesmo titular é portabilidade e conclui na hora. De outro titular é
+    /// reivindicação de posse (só email e telefone): fica aberta até o dono
+    /// atual confirmar ou cancelar, ou até o prazo acabar.
+    #[tracing::instrument(name = "PixService::open_claim", skip_all, fields(account_id = %request.account_id))]
+    pub async fn open_claim(
+        pool: &PgPool,
+        claimer_id: Uuid,
+        request: &CreatePixClaim,
+        audit: &AuditContext,
+    ) -> Result<PixClaim, AppError> {
+        let (_, key) = PixKeyValidator::parse("key", &request.key)
+            .map_err(|err| AppError::Validation(err.into()))?;
+        // o código é conferido fora da transação principal para a tentativa errada contar
+        let verification_id = match &request.verification {
+            Some(proof) => Some(check_verification(pool, claimer_id, &key, proof).await?),
+            None => None,
+        };
+
+        let mut tx = pool.begin().await?;
+        let account = lock_owned_account(
+            &mut tx,
+            claimer_id,
+            request.account_id,
+            OperatorPermission::Manage,
+        )
+        .await?;
+        let key = PixRepository::lock_active(&mut tx, &key)
+            .await?
+            .ok_or(PixError::KeyNotFound)?;
+        if key.account_id == account.id {
+            return Err(PixError::SameAccount.into());
+        }
+        if PixRepository::has_open_claim(&mut tx, key.id).await? {
+            return Err(PixError::ClaimOpen.into());
+        }
+
+        let mut claim = PixClaim::open(&key, account.user_id, account.id);
+        match claim.claim_type {
+            ClaimType::Portability => {
+                ensure_key_limit(&mut tx, account.id).await?;
+                PixRepository::move_to(&mut tx, key.id, account.user_id, account.id).await?;
+                claim.status = ClaimStatus::Completed;
+                claim.resolved_at = Some(Utc::now());
+                PixRepository::insert_claim(&mut tx, &claim).await?;
+
+                let event = audit
+                    .event(AuditAction::PixClaimCompleted, "pix_claim", Some(claim.id))
+                    .after(claim.audit_snapshot());
+                AuditRepository::append(&mut tx, &event).await?;
+            }
+            ClaimType::Ownership => {
+                if !key.key_type.is_claimable() {
+                    return Err(PixError::NotClaimable.into());
+                }
+                let verification_id = verification_id.ok_or(PixError::VerificationRequired)?;
+                ensure_key_limit(&mut tx, account.id).await?;
+                consume_verification(&mut tx, verification_id).await?;
+                claim.verified_at = Some(Utc::now());
+                if !PixRepository::insert_claim(&mut tx, &claim).await? {
+                    return Err(PixError::ClaimOpen.into());
+                }
+
+                let event = audit
+                    .event(AuditAction::PixClaimOpened, "pix_claim", Some(claim.id))
+                    .after(claim.audit_snapshot());
+                AuditRepository::append(&mut tx, &event).await?;
+                let notification = Notification::pix_key_claimed(&claim);
+                NotificationRepository::publish(&mut tx, &notification).await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(claim)
+    }
+
+    /// O dono atual aceita entregar a chave
+    #[tracing::instrument(name = "PixService::confirm_claim", skip_all, fields(claim_id = %claim_id))]
+    pub async fn confirm_claim(
+        pool: &PgPool,
+        user_id: Uuid,
+        claim_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<PixClaim, AppError> {
+        let mut tx = pool.begin().await?;
+        let claim = lock_open_claim(&mut tx, claim_id, user_id).await?;
+        if claim.donor_user_id != user_id {
+            return Err(PixError::ClaimNotFound.into());
+        }
+
+        let claim = complete(&mut tx, &claim, audit).await?;
+        tx.commit().await?;
+        Ok(claim)
+    }
+
+    /// Qualquer um dos lados desiste; a chave fica onde está
+    #[tracing::instrument(name = "PixService::cancel_claim", skip_all, fields(claim_id = %claim_id))]
+    pub async fn cancel_claim(
+        pool: &PgPool,
+        user_id: Uuid,
+        claim_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<PixClaim, AppError> {
+        let mut tx = pool.begin().await?;
+        let claim = lock_open_claim(&mut tx, claim_id, user_id).await?;
+
+        let cancelled =
+            PixRepository::resolve_claim(&mut tx, claim.id, ClaimStatus::Cancelled).await?;
+        let event = audit
+            .event(AuditAction::PixClaimCancelled, "pix_claim", Some(claim.id))
+            .before(claim.audit_snapshot())
+            .after(cancelled.audit_snapshot())
+            .metadata(
+                json!({ "by": if user_id == claim.donor_user_id { "donor" } else { "claimer" } }),
+            );
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(cancelled)
+    }
+
+    /// O reivindicante conclui sozinho depois do prazo sem resposta do dono atual
+    ///
+    /// Só vale para reivindicação com a posse da chave verificada na abertura.
+    #[tracing::instrument(name = "PixService::complete_claim", skip_all, fields(claim_id = %claim_id))]
+    pub async fn complete_claim(
+        pool: &PgPool,
+        user_id: Uuid,
+        claim_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<PixClaim, AppError> {
+        let mut tx = pool.begin().await?;
+        let claim = lock_open_claim(&mut tx, claim_id, user_id).await?;
+        if claim.claimer_user_id != user_id {
+            return Err(PixError::ClaimNotFound.into());
+        }
+        if Utc::now() < claim.resolution_deadline {
+            return Err(PixError::ClaimDeadlinePending.into());
+        }
+        if claim.verified_at.is_none() {
+            return Err(PixError::ClaimNotVerified.into());
+        }
+
+        let claim = complete(&mut tx, &claim, audit).await?;
+        tx.commit().await?;
+        Ok(claim)
+    }
+
+    /// BR Code para receber na chave; o recebedor é o titular dela
+    ///
+    /// O dinâmico registra antes a cobrança com o valor e a validade, e o
+    /// payload leva a URL dela no lugar da chave.
+    #[tracing::instrument(name = "PixService::create_brcode", skip_all, fields(key_id = %request.pix_key_id))]
+    pub async fn create_brcode(
+        pool: &PgPool,
+        owner_id: Uuid,
+        request: &CreateBrCode,
+    ) -> Result<BrCodeResponse, AppError> {
+        let key = PixRepository::find_owned(pool, request.pix_key_id, owner_id)
+            .await?
+            .ok_or(PixError::KeyNotFound)?;
+        BrCodeValidator::validate(request, &key.key).map_err(AppError::Validation)?;
+        let owner = UserRepository::find_by_id(pool, owner_id)
+            .await?
+            .ok_or(UserError::NotFound)?;
+
+        let mut code = BrCode {
+            kind: request.kind,
+            key: key.key.clone(),
+            location: None,
+            amount: request.amount,
+            merchant_name: owner.name,
+            merchant_city: MERCHANT_CITY.to_string(),
+            txid: request.txid.clone(),
+            description: request
+                .description
+                .as_deref()
+                .map(str::trim)
+                .filter(|description| !description.is_empty())
+                .map(str::to_string),
+        };
+        let mut expires_at = None;
+        if let (BrCodeKind::Dynamic, Some(amount)) = (request.kind, request.amount) {
+            let settings = pix_settings();
+            let charge = PixCharge::new(
+                &key,
+                request.txid.clone().unwrap_or_else(generate_txid),
+                amount,
+                code.description.clone(),
+                Duration::seconds(settings.charge_ttl_secs as i64),
+            );
+            let mut conn = pool.acquire().await?;
+            if !PixRepository::insert_charge(&mut conn, &charge).await? {
+                return Err(PixError::ChargeAlreadyExists.into());
+            }
+            code.location = Some(format!("{}/{}", settings.location_url, charge.id.simple()));
+            code.txid = Some(charge.txid);
+            expires_at = Some(charge.expires_at);
+        }
+
+        Ok(BrCodeResponse {
+            payload: code.payload(),
+            kind: code.kind,
+            key: code.key,
+            amount: code.amount,
+            txid: code.txid,
+            location: code.location,
+            expires_at,
+        })
+    }
+
+    /// Lê um BR Code; no dinâmico, a chave, o valor e o `txid` vêm da cobrança
+    ///
+    /// Só as cobranças deste banco são consultadas: buscar a URL de outro PSP
+    /// exigiria validar a assinatura (JWS) dele.
+    #[tracing::instrument(name = "PixService::parse_brcode", skip_all)]
+    pub async fn parse_brcode(pool: &PgPool, payload: &str) -> Result<ParsedBrCode, AppError> {
+        let mut code = BrCode::parse(payload).map_err(|err| AppError::Validation(err.into()))?;
+        if let Some(location) = &code.location {
+            let charge_id = location
+                .strip_prefix(pix_settings().location_url.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+                .and_then(|id| Uuid::try_parse(id).ok())
+                .ok_or(PixError::ChargeNotFound)?;
+            let charge = PixRepository::find_charge(pool, charge_id)
+                .await?
+                .ok_or(PixError::ChargeNotFound)?;
+            if !charge.is_payable(Utc::now()) {
+                return Err(PixError::ChargeNotPayable.into());
+            }
+            code.key = charge.key;
+            code.amount = Some(charge.amount);
+            code.txid = Some(charge.txid);
+            code.description = charge.description;
+        }
+        Ok(code.into_parsed())
+    }
+
+    /// Cobrança pela URL do BR Code dinâmico; consulta pública, como a do PSP
+    /// que lê o QR
+    #[tracing::instrument(name = "PixService::find_charge", skip_all, fields(charge_id = %charge_id))]
+    pub async fn find_charge(pool: &PgPool, charge_id: Uuid) -> Result<PixCharge, AppError> {
+        let charge = PixRepository::find_charge(pool, charge_id)
+            .await?
+            .ok_or(PixError::ChargeNotFound)?;
+        Ok(charge.with_current_status(Utc::now()))
+    }
+}
+
+async fn ensure_key_limit(conn: &mut PgConnection, account_id: Uuid) -> Result<(), AppError> {
+    if PixRepository::count_by_account(conn, account_id).await? >= MAX_KEYS_PER_ACCOUNT {
+        return Err(PixError::KeyLimitReached.into());
+    }
+    Ok(())
+}
+
+async fn lock_open_claim(
+    conn: &mut PgConnection,
+    claim_id: Uuid,
+    user_id: Uuid,
+) -> Result<PixClaim, AppError> {
+    let claim = PixRepository::lock_claim(conn, claim_id, user_id)
+        .await?
+        .ok_or(PixError::ClaimNotFound)?;
+    if claim.status != ClaimStatus::Open {
+        return Err(PixError::ClaimNotOpen.into());
+    }
+    Ok(claim)
+}
+
+/// Passa a chave para a conta do reivindicante; o limite é conferido de novo
+/// porque a conta pode ter ganhado chaves desde a abertura
+async fn complete(
+    conn: &mut PgConnection,
+    claim: &PixClaim,
+    audit: &AuditContext,
+) -> Result<PixClaim, AppError> {
+    let account = lock_owned_account(
+        conn,
+        claim.claimer_user_id,
+        claim.claimer_account_id,
+        OperatorPermission::Manage,
+    )
+    .await?;
+    ensure_key_limit(conn, account.id).await?;
+    let key = PixRepository::lock_by_id(conn, claim.pix_key_id)
+        .await?
+        .ok_or(PixError::KeyNotFound)?;
+
+    PixRepository::move_to(conn, key.id, account.user_id, account.id).await?;
+    let completed = PixRepository::resolve_claim(conn, claim.id, ClaimStatus::Completed).await?;
+    let event = audit
+        .event(AuditAction::PixClaimCompleted, "pix_claim", Some(claim.id))
+        .before(claim.audit_snapshot())
+        .after(completed.audit_snapshot());
+    AuditRepository::append(conn, &event).await?;
+
+    Ok(completed)
+}
+
+/// Confere o código numa transação própria, para a tentativa errada ficar gravada
+/// mesmo com o erro; devolve o pedido a consumir junto com o cadastro
+async fn check_verification(
+    pool: &PgPool,
+    user_id: Uuid,
+    key: &str,
+    proof: &VerificationProof,
+) -> Result<Uuid, AppError> {
+    let mut tx = pool.begin().await?;
+    let verification = PixRepository::lock_verification(&mut tx, proof.verification_id, user_id)
+        .await?
+        .filter(|verification| verification.key == key && verification.is_usable())
+        .ok_or(PixError::VerificationInvalid)?;
+    if !verification.matches(&proof.code) {
+        PixRepository::record_failed_attempt(&mut tx, verification.id).await?;
+        tx.commit().await?;
+        return Err(PixError::VerificationInvalid.into());
+    }
+    tx.commit().await?;
+    Ok(verification.id)
+}
+
+async fn consume_verification(
+    conn: &mut PgConnection,
+    verification_id: Uuid,
+) -> Result<(), AppError> {
+    if !PixRepository::consume_verification(conn, verification_id).await? {
+        return Err(PixError::VerificationInvalid.into());
+    }
+    Ok(())
+}
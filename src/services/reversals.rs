@@ -0,0 +1,323 @@
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AccountRepository, AuditRepository, NotificationRepository, OutboxRepository,
+        ReversalRepository, TransactionRepository,
+    },
+    metrics,
+    models::{
+        account::{Account, error::AccountError},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        event::DomainEvent,
+        notification::Notification,
+        reversal::{
+            CreateReversal, Reversal, ReversalStatus, ensure_reversible, error::ReversalError,
+            split_debit,
+        },
+        transaction::{Transaction, TransactionType},
+    },
+    services::set_balance,
+    validators::{ReversalValidator, TransactionValidator},
+};
+
+/// Estornos feitos pela operação
+///
+/// O histórico não muda: o estorno grava pernas `reversal` ligadas à transação
+/// original por `reference_id`, creditando quem foi debitado e debitando quem
+/// foi creditado. O que a conta debitada não tiver disponível fica pendente e é
+/// cobrado pelo agendador conforme entra dinheiro.
+pub struct ReversalService;
+
+impl ReversalService {
+    #[tracing::instrument(name = "ReversalService::reverse", skip_all, fields(transaction_id = %transaction_id))]
+    pub async fn reverse(
+        pool: &PgPool,
+        admin_id: Uuid,
+        transaction_id: Uuid,
+        request: &CreateReversal,
+        audit: &AuditContext,
+    ) -> Result<Reversal, AppError> {
+        ReversalValidator::validate(request).map_err(AppError::Validation)?;
+
+        let mut tx = pool.begin().await?;
+        let original = lock_original(&mut tx, transaction_id).await?;
+        ensure_reversible(&original)?;
+        let remaining =
+            original.amount - ReversalRepository::reversed_total(&mut tx, original.id).await?;
+        if remaining <= Decimal::ZERO {
+            return Err(ReversalError::AlreadyReversed.into());
+        }
+        let amount = request.amount.unwrap_or(remaining);
+        if amount > remaining {
+            return Err(ReversalError::ExceedsRemaining.into());
+        }
+        TransactionValidator::validate_minor_units(amount, original.currency)
+            .map_err(AppError::Validation)?;
+
+        let (credit_account, debit_account) =
+            lock_accounts(&mut tx, original.from_account_id, original.to_account_id).await?;
+        // conta encerrada não recebe nem devolve: a cobrança ficaria pendente para sempre
+        if [&credit_account, &debit_account]
+            .into_iter()
+            .flatten()
+            .any(|account| !account.is_active)
+        {
+            return Err(AccountError::Inactive.into());
+        }
+
+        let description = format!("Estorno: {}", original.description);
+        let mut legs = Vec::new();
+        let mut notifications = Vec::new();
+        let mut before = json!({});
+        let mut after = json!({});
+
+        if let Some(account) = &credit_account {
+            let balance = account.balance + amount;
+            set_balance(&mut tx, account, balance).await?;
+            let leg = reversal_leg(&original, None, Some(account.id), amount, &description);
+            TransactionRepository::insert(&mut tx, &leg).await?;
+            before["credit"] = json!({ "account_id": account.id, "balance": account.balance });
+            after["credit"] = json!({ "account_id": account.id, "balance": balance });
+            notifications.push(Notification::balance_changed(
+                account.user_id,
+                account.id,
+                &leg,
+                amount,
+                balance,
+            ));
+            legs.push(leg);
+        }
+
+        let mut outstanding = Decimal::ZERO;
+        if let Some(account) = &debit_account {
+            let (debited, shortfall) = split_debit(amount, account.available_balance());
+            outstanding = shortfall;
+            before["debit"] = json!({ "account_id": account.id, "balance": account.balance });
+            after["debit"] = json!({
+                "account_id": account.id,
+                "balance": account.balance - debited,
+                "outstanding_amount": outstanding,
+            });
+            if !debited.is_zero() {
+                let balance = account.balance - debited;
+                set_balance(&mut tx, account, balance).await?;
+                let leg = reversal_leg(&original, Some(account.id), None, debited, &description);
+                TransactionRepository::insert(&mut tx, &leg).await?;
+                notifications.push(Notification::balance_changed(
+                    account.user_id,
+                    account.id,
+                    &leg,
+                    -debited,
+                    balance,
+                ));
+                legs.push(leg);
+            }
+        }
+
+        let reversal = Reversal::new(&original, admin_id, request, amount, outstanding);
+        ReversalRepository::insert(&mut tx, &reversal).await?;
+
+        let event = audit
+            .event(
+                AuditAction::TransactionReversed,
+                "transaction",
+                Some(original.id),
+            )
+            .before(before)
+            .after(after)
+            .metadata(json!({
+                "reversal": reversal.audit_snapshot(),
+                "reversal_id": reversal.id,
+                "note": reversal.note,
+                "transaction_ids": legs.iter().map(|leg| leg.id).collect::<Vec<_>>(),
+            }));
+        AuditRepository::append(&mut tx, &event).await?;
+        let event = DomainEvent::TransactionReversed {
+            reversal_id: reversal.id,
+            transaction_id: original.id,
+            amount,
+            reason: reversal.reason,
+            credit_account_id: reversal.credit_account_id,
+            debit_account_id: reversal.debit_account_id,
+            outstanding_amount: outstanding,
+        };
+        OutboxRepository::enqueue(&mut tx, event).await?;
+        for notification in &notifications {
+            NotificationRepository::publish(&mut tx, notification).await?;
+        }
+        tx.commit().await?;
+
+        for leg in &legs {
+            metrics::record_transaction(&leg.transaction_type, leg.amount, leg.currency);
+        }
+        Ok(reversal)
+    }
+
+    /// Estornos de uma transação (pela perna de débito, se for transferência ou boleto)
+    #[tracing::instrument(name = "ReversalService::list_for_transaction", skip_all, fields(transaction_id = %transaction_id))]
+    pub async fn list_for_transaction(
+        pool: &PgPool,
+        transaction_id: Uuid,
+    ) -> Result<Vec<Reversal>, AppError> {
+        let mut conn = pool.acquire().await?;
+        let original = TransactionRepository::find(&mut conn, transaction_id)
+            .await?
+            .ok_or(ReversalError::TransactionNotFound)?;
+        let reversals =
+            ReversalRepository::list_by_transaction(pool, debit_leg_id(&original)).await?;
+        Ok(reversals)
+    }
+
+    /// Cobra o estorno pendente mais antigo com o que a conta tiver disponível;
+    /// `false` quando não há nenhum com cobrança vencida
+    #[tracing::instrument(name = "ReversalService::recover_due", skip_all, fields(reversal_id = tracing::field::Empty))]
+    pub async fn recover_due(pool: &PgPool, retry_delay: Duration) -> Result<bool, AppError> {
+        let mut tx = pool.begin().await?;
+        let Some(mut reversal) = ReversalRepository::claim_recoverable(&mut tx).await? else {
+            return Ok(false);
+        };
+        tracing::Span::current().record("reversal_id", tracing::field::display(reversal.id));
+
+        let account_id = reversal
+            .debit_account_id
+            .ok_or_else(|| AppError::Internal("estorno pendente sem conta debitada".into()))?;
+        let account = AccountRepository::lock_by_id(&mut tx, account_id)
+            .await?
+            .ok_or(AccountError::NotFound)?;
+        let (recovered, _) = split_debit(reversal.outstanding_amount, account.available_balance());
+
+        let before = reversal.audit_snapshot();
+        let now = Utc::now();
+        reversal.recover(recovered, now, now + retry_delay);
+
+        let mut leg = None;
+        if !recovered.is_zero() {
+            let original = TransactionRepository::find(&mut tx, reversal.transaction_id)
+                .await?
+                .ok_or(ReversalError::TransactionNotFound)?;
+            let balance = account.balance - recovered;
+            set_balance(&mut tx, &account, balance).await?;
+            let debit = reversal_leg(
+                &original,
+                Some(account.id),
+                None,
+                recovered,
+                &format!("Estorno: {}", original.description),
+            );
+            TransactionRepository::insert(&mut tx, &debit).await?;
+
+            let event = AuditContext::default()
+                .event(
+                    AuditAction::ReversalRecovered,
+                    "reversal",
+                    Some(reversal.id),
+                )
+                .before(json!({ "balance": account.balance, "reversal": before }))
+                .after(json!({ "balance": balance, "reversal": reversal.audit_snapshot() }))
+                .metadata(json!({ "transaction_id": debit.id, "amount": recovered }));
+            AuditRepository::append(&mut tx, &event).await?;
+            let event = DomainEvent::ReversalRecovered {
+                reversal_id: reversal.id,
+                transaction_id: reversal.transaction_id,
+                recovery_transaction_id: debit.id,
+                account_id: account.id,
+                amount: recovered,
+                balance,
+                outstanding_amount: reversal.outstanding_amount,
+            };
+            OutboxRepository::enqueue(&mut tx, event).await?;
+            let notification = Notification::balance_changed(
+                account.user_id,
+                account.id,
+                &debit,
+                -recovered,
+                balance,
+            );
+            NotificationRepository::publish(&mut tx, &notification).await?;
+            leg = Some(debit);
+        }
+        ReversalRepository::save(&mut tx, &reversal).await?;
+        tx.commit().await?;
+
+        if let Some(leg) = leg {
+            metrics::record_transaction(&leg.transaction_type, leg.amount, leg.currency);
+        }
+        if reversal.status == ReversalStatus::Completed {
+            tracing::info!(reversal_id = %reversal.id, "Estorno cobrado por completo");
+        }
+        Ok(true)
+    }
+}
+
+/// Transferências e boletos pagos aqui têm duas pernas; o estorno vale para as
+/// duas e é registrado sempre na de débito
+fn debit_leg_id(transaction: &Transaction) -> Uuid {
+    match (transaction.transaction_type, transaction.reference_id) {
+        (TransactionType::TransferCredit | TransactionType::BoletoCredit, Some(debit_leg)) => {
+            debit_leg
+        }
+        _ => transaction.id,
+    }
+}
+
+/// Trava a transação a estornar (a perna de débito, se houver duas)
+async fn lock_original(
+    conn: &mut PgConnection,
+    transaction_id: Uuid,
+) -> Result<Transaction, AppError> {
+    let transaction = TransactionRepository::find(conn, transaction_id)
+        .await?
+        .ok_or(ReversalError::TransactionNotFound)?;
+    let original = TransactionRepository::lock(conn, debit_leg_id(&transaction))
+        .await?
+        .ok_or(ReversalError::TransactionNotFound)?;
+    Ok(original)
+}
+
+/// Trava as contas a creditar e a debitar sempre na mesma ordem (menor ID primeiro)
+async fn lock_accounts(
+    conn: &mut PgConnection,
+    credit_id: Option<Uuid>,
+    debit_id: Option<Uuid>,
+) -> Result<(Option<Account>, Option<Account>), AppError> {
+    let mut ids: Vec<Uuid> = credit_id.into_iter().chain(debit_id).collect();
+    ids.sort();
+
+    let (mut credit, mut debit) = (None, None);
+    for id in ids {
+        let account = AccountRepository::lock_by_id(conn, id)
+            .await?
+            .ok_or(AccountError::NotFound)?;
+        if Some(id) == credit_id {
+            credit = Some(account);
+        } else {
+            debit = Some(account);
+        }
+    }
+    Ok((credit, debit))
+}
+
+fn reversal_leg(
+    original: &Transaction,
+    from_account_id: Option<Uuid>,
+    to_account_id: Option<Uuid>,
+    amount: Decimal,
+    description: &str,
+) -> Transaction {
+    let mut leg = Transaction::completed(
+        TransactionType::Reversal,
+        from_account_id,
+        to_account_id,
+        amount,
+        original.currency,
+        description,
+    );
+    leg.reference_id = Some(original.id);
+    leg
+}
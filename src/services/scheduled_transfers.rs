@@ -0,0 +1,237 @@
+use actix_web::ResponseError;
+use chrono::Utc;
+use sqlx::{Acquire, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AccountRepository, AuditRepository, NotificationRepository, ScheduledTransferRepository,
+    },
+    fx::FxRateProvider,
+    metrics,
+    models::{
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        business::OperatorPermission,
+        fx::error::FxError,
+        notification::Notification,
+        scheduled_transfer::{
+            CreateScheduledTransfer, RetryPolicy, RunStatus, ScheduleStatus, ScheduledTransfer,
+            ScheduledTransferDetails, ScheduledTransferRun, error::ScheduledTransferError,
+        },
+        transaction::error::TransactionError,
+    },
+    services::{
+        KycService, TransactionService,
+        transactions::{accessible_account, lock_owned_account, resolve_destination},
+    },
+    validators::{ScheduledTransferValidator, TransactionValidator},
+};
+
+/// Execuções mostradas no detalhe do agendamento
+const RECENT_RUNS: u32 = 20;
+
+/// Transferências agendadas e recorrentes
+///
+/// Cada execução passa pelo mesmo `TransactionService::transfer_in` das
+/// transferências imediatas, com as permissões de quem agendou.
+pub struct ScheduledTransferService;
+
+impl ScheduledTransferService {
+    /// Confere a agenda e o destino agora; a execução confere tudo de novo
+    #[tracing::instrument(name = "ScheduledTransferService::create", skip_all, fields(account_id = %account_id))]
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        request: &CreateScheduledTransfer,
+        audit: &AuditContext,
+    ) -> Result<ScheduledTransfer, AppError> {
+        ScheduledTransferValidator::validate(request, Utc::now()).map_err(AppError::Validation)?;
+
+        let mut tx = pool.begin().await?;
+        let account =
+            lock_owned_account(&mut tx, user_id, account_id, OperatorPermission::Transact).await?;
+        TransactionValidator::validate_minor_units(request.amount, account.currency)
+            .map_err(AppError::Validation)?;
+        KycService::ensure_approved(&mut tx, account.user_id).await?;
+        let (destination_id, pix_key) =
+            resolve_destination(&mut tx, &request.transfer_request()).await?;
+        if destination_id == account.id {
+            return Err(TransactionError::SameAccountTransfer.into());
+        }
+        // a cotação vale segundos; agendamento só entre contas da mesma moeda
+        let destination = AccountRepository::find_by_id(pool, destination_id)
+            .await?
+            .ok_or(TransactionError::DestinationAccountNotFound)?;
+        if destination.currency != account.currency {
+            return Err(FxError::QuoteRequired.into());
+        }
+
+        let mut schedule = ScheduledTransfer::new(account.id, user_id, request);
+        schedule.to_pix_key = pix_key.map(|key| key.key);
+        ScheduledTransferRepository::insert(&mut tx, &schedule).await?;
+        let event = audit
+            .event(
+                AuditAction::TransferScheduled,
+                "scheduled_transfer",
+                Some(schedule.id),
+            )
+            .after(schedule.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(schedule)
+    }
+
+    #[tracing::instrument(name = "ScheduledTransferService::list", skip_all, fields(account_id = %account_id))]
+    pub async fn list(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        status: Option<ScheduleStatus>,
+        limit: u32,
+        offset: i64,
+    ) -> Result<(Vec<ScheduledTransfer>, i64), AppError> {
+        accessible_account(pool, user_id, account_id, OperatorPermission::View).await?;
+        let page =
+            ScheduledTransferRepository::list_by_account(pool, account_id, status, limit, offset)
+                .await?;
+        Ok(page)
+    }
+
+    #[tracing::instrument(name = "ScheduledTransferService::details", skip_all, fields(account_id = %account_id))]
+    pub async fn details(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        schedule_id: Uuid,
+    ) -> Result<ScheduledTransferDetails, AppError> {
+        accessible_account(pool, user_id, account_id, OperatorPermission::View).await?;
+        let scheduled_transfer = ScheduledTransferRepository::find(pool, account_id, schedule_id)
+            .await?
+            .ok_or(ScheduledTransferError::NotFound)?;
+        let runs = ScheduledTransferRepository::list_runs(pool, schedule_id, RECENT_RUNS).await?;
+
+        Ok(ScheduledTransferDetails {
+            scheduled_transfer,
+            runs,
+        })
+    }
+
+    /// Cancela as próximas execuções; as já feitas não são desfeitas
+    #[tracing::instrument(name = "ScheduledTransferService::cancel", skip_all, fields(account_id = %account_id))]
+    pub async fn cancel(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+        schedule_id: Uuid,
+        audit: &AuditContext,
+    ) -> Result<ScheduledTransfer, AppError> {
+        accessible_account(pool, user_id, account_id, OperatorPermission::Transact).await?;
+
+        let mut tx = pool.begin().await?;
+        let mut schedule = ScheduledTransferRepository::lock(&mut tx, account_id, schedule_id)
+            .await?
+            .ok_or(ScheduledTransferError::NotFound)?;
+        if schedule.status != ScheduleStatus::Active {
+            return Err(ScheduledTransferError::NotActive.into());
+        }
+
+        let before = schedule.audit_snapshot();
+        schedule.cancel(Utc::now());
+        ScheduledTransferRepository::save(&mut tx, &schedule).await?;
+        let event = audit
+            .event(
+                AuditAction::ScheduledTransferCancelled,
+                "scheduled_transfer",
+                Some(schedule.id),
+            )
+            .before(before)
+            .after(schedule.audit_snapshot());
+        AuditRepository::append(&mut tx, &event).await?;
+        tx.commit().await?;
+
+        Ok(schedule)
+    }
+
+    /// Executa o agendamento vencido mais antigo; `None` quando não há nenhum
+    ///
+    /// A transferência roda num savepoint: uma falha de negócio (ex.: saldo
+    /// insuficiente) desfaz só ela, e a tentativa fica registrada com a nova
+    /// data conforme a `RetryPolicy`. Falha de infraestrutura desfaz tudo e
+    /// adia a execução sem contar tentativa.
+    #[tracing::instrument(name = "ScheduledTransferService::execute_due", skip_all, fields(scheduled_transfer_id = tracing::field::Empty))]
+    pub async fn execute_due(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        policy: &RetryPolicy,
+    ) -> Result<Option<RunStatus>, AppError> {
+        let mut tx = pool.begin().await?;
+        let Some(mut schedule) = ScheduledTransferRepository::claim_due(&mut tx).await? else {
+            return Ok(None);
+        };
+        tracing::Span::current().record(
+            "scheduled_transfer_id",
+            tracing::field::display(schedule.id),
+        );
+
+        let audit = AuditContext::default().with_actor(schedule.created_by);
+        let mut savepoint = tx.begin().await?;
+        let result = TransactionService::transfer_in(
+            &mut savepoint,
+            provider,
+            schedule.created_by,
+            schedule.account_id,
+            &schedule.transfer_request(),
+            &audit,
+        )
+        .await;
+
+        let now = Utc::now();
+        let (run, legs) = match result {
+            Ok(legs) => {
+                savepoint.commit().await?;
+                let run = ScheduledTransferRun::new(&schedule, Some(legs.debit.id), None);
+                schedule.succeed(now);
+                (run, Some(legs))
+            }
+            Err(err) if err.status_code().is_server_error() => {
+                savepoint.rollback().await?;
+                tx.rollback().await?;
+                ScheduledTransferRepository::postpone(pool, schedule.id, now + policy.retry_delay)
+                    .await?;
+                return Err(err);
+            }
+            Err(err) => {
+                savepoint.rollback().await?;
+                let run = ScheduledTransferRun::new(&schedule, None, Some(err.code()));
+                let retry_at = schedule.fail(err.code(), now, policy);
+                tracing::warn!(
+                    scheduled_transfer_id = %schedule.id,
+                    code = err.code(),
+                    attempt = run.attempt,
+                    retry_at = ?retry_at,
+                    "Transferência agendada falhou"
+                );
+                let notification = Notification::scheduled_transfer_failed(
+                    &schedule,
+                    err.code(),
+                    run.attempt,
+                    retry_at,
+                );
+                NotificationRepository::publish(&mut tx, &notification).await?;
+                (run, None)
+            }
+        };
+        ScheduledTransferRepository::insert_run(&mut tx, &run).await?;
+        ScheduledTransferRepository::save(&mut tx, &schedule).await?;
+        tx.commit().await?;
+
+        if let Some(legs) = legs {
+            legs.record_metrics();
+        }
+        metrics::record_scheduled_run(run.status);
+        Ok(Some(run.status))
+    }
+}
@@ -0,0 +1,539 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        AccountRepository, AuditRepository, FxRepository, NotificationRepository,
+        OperatorRepository, OutboxRepository, PixRepository, TransactionRepository,
+    },
+    fx::FxRateProvider,
+    metrics,
+    models::{
+        account::{Account, error::AccountError},
+        app_error::AppError,
+        audit::{AuditAction, AuditContext},
+        business::{OperatorPermission, error::BusinessError},
+        event::DomainEvent,
+        fee::FeeOperation,
+        fx::{FxQuote, error::FxError},
+        notification::Notification,
+        pix::{PixCharge, PixKey, error::PixError},
+        transaction::{
+            CreateTransaction, CreateTransfer, Transaction, TransactionType,
+            error::TransactionError,
+        },
+    },
+    services::{FeeService, KycService, LimitService, fees::fee_metadata, set_balance},
+    validators::{PixKeyValidator, TransactionValidator},
+};
+
+/// Depósito, saque e transferência (RF013–RF015)
+///
+/// Cada operação trava as contas envolvidas (`FOR UPDATE`), atualiza saldo,
+/// grava os lançamentos e o evento de auditoria numa única transação. Saque e
+/// transferência conferem os limites da conta de origem depois da trava.
+pub struct TransactionService;
+
+impl TransactionService {
+    #[tracing::instrument(name = "TransactionService::deposit", skip_all, fields(account_id = %account_id))]
+    pub async fn deposit(
+        pool: &PgPool,
+        owner_id: Uuid,
+        account_id: Uuid,
+        request: &CreateTransaction,
+        audit: &AuditContext,
+    ) -> Result<Transaction, AppError> {
+        TransactionValidator::validate(request.amount, &request.description)
+            .map_err(AppError::Validation)?;
+
+        let mut tx = pool.begin().await?;
+        let account =
+            lock_owned_account(&mut tx, owner_id, account_id, OperatorPermission::Transact).await?;
+        TransactionValidator::validate_minor_units(request.amount, account.currency)
+            .map_err(AppError::Validation)?;
+        let balance = account.balance + request.amount;
+        set_balance(&mut tx, &account, balance).await?;
+
+        let transaction = Transaction::completed(
+            TransactionType::Deposit,
+            None,
+            Some(account.id),
+            request.amount,
+            account.currency,
+            &request.description,
+        );
+        TransactionRepository::insert(&mut tx, &transaction).await?;
+
+        let event = audit
+            .event(AuditAction::Deposit, "account", Some(account.id))
+            .before(json!({ "balance": account.balance }))
+            .after(json!({ "balance": balance }))
+            .metadata(json!({ "transaction_id": transaction.id, "amount": request.amount }));
+        AuditRepository::append(&mut tx, &event).await?;
+        let event = DomainEvent::money_movement(&transaction, account.id, balance);
+        OutboxRepository::enqueue(&mut tx, event).await?;
+        let notification = Notification::balance_changed(
+            account.user_id,
+            account.id,
+            &transaction,
+            request.amount,
+            balance,
+        );
+        NotificationRepository::publish(&mut tx, &notification).await?;
+        tx.commit().await?;
+
+        metrics::record_transaction(
+            &transaction.transaction_type,
+            transaction.amount,
+            transaction.currency,
+        );
+        Ok(transaction)
+    }
+
+    #[tracing::instrument(name = "TransactionService::withdraw", skip_all, fields(account_id = %account_id))]
+    pub async fn withdraw(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        owner_id: Uuid,
+        account_id: Uuid,
+        request: &CreateTransaction,
+        audit: &AuditContext,
+    ) -> Result<Transaction, AppError> {
+        TransactionValidator::validate(request.amount, &request.description)
+            .map_err(AppError::Validation)?;
+
+        let mut tx = pool.begin().await?;
+        let account =
+            lock_owned_account(&mut tx, owner_id, account_id, OperatorPermission::Transact).await?;
+        TransactionValidator::validate_minor_units(request.amount, account.currency)
+            .map_err(AppError::Validation)?;
+        KycService::ensure_approved(&mut tx, account.user_id).await?;
+        let fee = FeeService::assess(
+            &mut tx,
+            provider,
+            &account,
+            FeeOperation::Withdraw,
+            request.amount,
+        )
+        .await?;
+        let fee_amount = fee.map_or(Decimal::ZERO, |fee| fee.amount);
+        let balance = debit(&account, request.amount + fee_amount)?;
+        LimitService::enforce(&mut tx, provider, &account, request.amount).await?;
+        set_balance(&mut tx, &account, balance).await?;
+
+        let transaction = Transaction::completed(
+            TransactionType::Withdraw,
+            Some(account.id),
+            None,
+            request.amount,
+            account.currency,
+            &request.description,
+        );
+        TransactionRepository::insert(&mut tx, &transaction).await?;
+        let fee_leg = match &fee {
+            Some(fee) => {
+                Some(FeeService::post(&mut tx, &account, fee, transaction.id, balance).await?)
+            }
+            None => None,
+        };
+
+        let event = audit
+            .event(AuditAction::Withdraw, "account", Some(account.id))
+            .before(json!({ "balance": account.balance }))
+            .after(json!({ "balance": balance }))
+            .metadata(json!({
+                "transaction_id": transaction.id,
+                "amount": request.amount,
+                "fee": fee_metadata(fee_leg.as_ref()),
+            }));
+        AuditRepository::append(&mut tx, &event).await?;
+        let event = DomainEvent::money_movement(&transaction, account.id, balance + fee_amount);
+        OutboxRepository::enqueue(&mut tx, event).await?;
+        let notification = Notification::balance_changed(
+            account.user_id,
+            account.id,
+            &transaction,
+            -request.amount,
+            balance + fee_amount,
+        );
+        NotificationRepository::publish(&mut tx, &notification).await?;
+        tx.commit().await?;
+
+        metrics::record_transaction(
+            &transaction.transaction_type,
+            transaction.amount,
+            transaction.currency,
+        );
+        if let Some(fee_leg) = &fee_leg {
+            metrics::record_transaction(
+                &fee_leg.transaction_type,
+                fee_leg.amount,
+                fee_leg.currency,
+            );
+        }
+        Ok(transaction)
+    }
+
+    /// Débito na origem e crédito no destino, ligados entre si por `reference_id`;
+    /// devolve a perna de débito
+    ///
+    /// Entre moedas diferentes, o crédito é o valor convertido da cotação em
+    /// `fx_quote_id`, que fica usada.
+    #[tracing::instrument(name = "TransactionService::transfer", skip_all, fields(account_id = %account_id))]
+    pub async fn transfer(
+        pool: &PgPool,
+        provider: &dyn FxRateProvider,
+        owner_id: Uuid,
+        account_id: Uuid,
+        request: &CreateTransfer,
+        audit: &AuditContext,
+    ) -> Result<Transaction, AppError> {
+        let mut tx = pool.begin().await?;
+        let legs =
+            Self::transfer_in(&mut tx, provider, owner_id, account_id, request, audit).await?;
+        tx.commit().await?;
+
+        legs.record_metrics();
+        Ok(legs.debit)
+    }
+
+    /// Transferência dentro da transação de quem chama, sem commit; devolve as
+    /// pernas de débito e de crédito e a tarifa, se houver
+    ///
+    /// É o mesmo caminho para `POST /accounts/{id}/transfer` e para as execuções
+    /// dos agendamentos.
+    pub(crate) async fn transfer_in(
+        conn: &mut PgConnection,
+        provider: &dyn FxRateProvider,
+        owner_id: Uuid,
+        account_id: Uuid,
+        request: &CreateTransfer,
+        audit: &AuditContext,
+    ) -> Result<TransferLegs, AppError> {
+        TransactionValidator::validate_transfer(request).map_err(AppError::Validation)?;
+
+        let (destination_id, pix_key) = resolve_destination(conn, request).await?;
+        if destination_id == account_id {
+            return Err(TransactionError::SameAccountTransfer.into());
+        }
+        let charge = match &request.pix_txid {
+            Some(txid) => {
+                Some(lock_payable_charge(conn, txid, pix_key.as_ref(), request.amount).await?)
+            }
+            None => None,
+        };
+
+        let (source, destination) = lock_owned_and_destination(
+            conn,
+            owner_id,
+            account_id,
+            destination_id,
+            OperatorPermission::Transact,
+        )
+        .await?;
+        let destination = destination
+            .filter(|account| account.is_active)
+            .ok_or(TransactionError::DestinationAccountNotFound)?;
+        TransactionValidator::validate_minor_units(request.amount, source.currency)
+            .map_err(AppError::Validation)?;
+
+        KycService::ensure_approved(conn, source.user_id).await?;
+        let quote = quote_for(conn, owner_id, &source, &destination, request).await?;
+        let credited_amount = quote
+            .as_ref()
+            .map_or(request.amount, |quote| quote.converted_amount);
+        // entre contas do mesmo titular não há tarifa
+        let fee = if destination.user_id == source.user_id {
+            None
+        } else {
+            FeeService::assess(
+                conn,
+                provider,
+                &source,
+                FeeOperation::Transfer,
+                request.amount,
+            )
+            .await?
+        };
+        let fee_amount = fee.map_or(Decimal::ZERO, |fee| fee.amount);
+        let source_balance = debit(&source, request.amount + fee_amount)?;
+        LimitService::enforce(conn, provider, &source, request.amount).await?;
+        let destination_balance = destination.balance + credited_amount;
+        set_balance(conn, &source, source_balance).await?;
+        set_balance(conn, &destination, destination_balance).await?;
+
+        let mut debit_leg = Transaction::completed(
+            TransactionType::TransferDebit,
+            Some(source.id),
+            Some(destination.id),
+            request.amount,
+            source.currency,
+            &request.description,
+        );
+        let mut credit_leg = Transaction::completed(
+            TransactionType::TransferCredit,
+            Some(source.id),
+            Some(destination.id),
+            credited_amount,
+            destination.currency,
+            &request.description,
+        );
+        debit_leg.reference_id = Some(credit_leg.id);
+        credit_leg.reference_id = Some(debit_leg.id);
+        if let Some(quote) = &quote {
+            for leg in [&mut debit_leg, &mut credit_leg] {
+                leg.fx_rate = Some(quote.rate);
+                leg.fx_spread = Some(quote.spread);
+            }
+        }
+        TransactionRepository::insert(conn, &debit_leg).await?;
+        TransactionRepository::insert(conn, &credit_leg).await?;
+        if let Some(quote) = &quote {
+            FxRepository::mark_used(conn, quote.id, debit_leg.id, Utc::now()).await?;
+        }
+        if let Some(charge) = &charge {
+            PixRepository::complete_charge(conn, charge.id, debit_leg.id, Utc::now()).await?;
+        }
+        let fee_leg = match &fee {
+            Some(fee) => {
+                Some(FeeService::post(conn, &source, fee, debit_leg.id, source_balance).await?)
+            }
+            None => None,
+        };
+
+        let event = audit
+            .event(AuditAction::Transfer, "account", Some(source.id))
+            .before(json!({
+                "source": { "account_number": source.account_number, "balance": source.balance },
+                "destination": { "account_number": destination.account_number, "balance": destination.balance },
+            }))
+            .after(json!({
+                "source": { "account_number": source.account_number, "balance": source_balance },
+                "destination": { "account_number": destination.account_number, "balance": destination_balance },
+            }))
+            .metadata(json!({
+                "debit_transaction_id": debit_leg.id,
+                "credit_transaction_id": credit_leg.id,
+                "destination_account_id": destination.id,
+                "pix_key_type": pix_key.as_ref().map(|key| key.key_type),
+                "pix_txid": charge.as_ref().map(|charge| &charge.txid),
+                "amount": request.amount,
+                "credited_amount": credited_amount,
+                "fx": quote.as_ref().map(|quote| json!({
+                    "quote_id": quote.id,
+                    "from_currency": quote.from_currency,
+                    "to_currency": quote.to_currency,
+                    "rate": quote.rate,
+                    "spread": quote.spread,
+                })),
+                "fee": fee_metadata(fee_leg.as_ref()),
+            }));
+        AuditRepository::append(conn, &event).await?;
+        let event = DomainEvent::TransferCompleted {
+            debit_transaction_id: debit_leg.id,
+            credit_transaction_id: credit_leg.id,
+            from_account_id: source.id,
+            to_account_id: destination.id,
+            amount: request.amount,
+            credited_amount,
+            fx_rate: quote.as_ref().map(|quote| quote.rate),
+        };
+        OutboxRepository::enqueue(conn, event).await?;
+        let notifications = [
+            Notification::balance_changed(
+                source.user_id,
+                source.id,
+                &debit_leg,
+                -request.amount,
+                source_balance + fee_amount,
+            ),
+            Notification::balance_changed(
+                destination.user_id,
+                destination.id,
+                &credit_leg,
+                credited_amount,
+                destination_balance,
+            ),
+            Notification::transfer_received(
+                destination.user_id,
+                destination.id,
+                source.id,
+                &credit_leg,
+            ),
+        ];
+        for notification in &notifications {
+            NotificationRepository::publish(conn, notification).await?;
+        }
+        Ok(TransferLegs {
+            debit: debit_leg,
+            credit: credit_leg,
+            fee: fee_leg,
+        })
+    }
+}
+
+/// Lançamentos de uma transferência concluída
+pub(crate) struct TransferLegs {
+    pub debit: Transaction,
+    pub credit: Transaction,
+    /// Tarifa de transferência para outro cliente
+    pub fee: Option<Transaction>,
+}
+
+impl TransferLegs {
+    /// Chame depois do commit
+    pub fn record_metrics(&self) {
+        for leg in [&self.debit, &self.credit].into_iter().chain(&self.fee) {
+            metrics::record_transaction(&leg.transaction_type, leg.amount, leg.currency);
+        }
+    }
+}
+
+/// Confere se o usuário pode operar a conta (RN 6.2)
+///
+/// O dono pode tudo; o operador de uma empresa precisa da permissão exigida.
+pub(crate) async fn authorize_account(
+    conn: &mut PgConnection,
+    account: &Account,
+    user_id: Uuid,
+    permission: OperatorPermission,
+) -> Result<(), AppError> {
+    if account.user_id == user_id {
+        return Ok(());
+    }
+    match OperatorRepository::permissions(conn, account.user_id, user_id).await? {
+        Some(granted) if OperatorPermission::allows(&granted, permission) => Ok(()),
+        Some(_) => Err(BusinessError::PermissionDenied.into()),
+        None => Err(AccountError::Unauthorized.into()),
+    }
+}
+
+/// Cotação travada de uma transferência entre moedas; `None` na mesma moeda
+async fn quote_for(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    source: &Account,
+    destination: &Account,
+    request: &CreateTransfer,
+) -> Result<Option<FxQuote>, AppError> {
+    match request.fx_quote_id {
+        None if source.currency == destination.currency => Ok(None),
+        None => Err(FxError::QuoteRequired.into()),
+        Some(_) if source.currency == destination.currency => Err(FxError::QuoteMismatch.into()),
+        Some(quote_id) => {
+            let quote = FxRepository::lock_quote(conn, user_id, quote_id)
+                .await?
+                .ok_or(FxError::QuoteNotFound)?;
+            quote.ensure_usable(source, destination, request.amount, Utc::now())?;
+            Ok(Some(quote))
+        }
+    }
+}
+
+/// Conta (ativa ou não) que o usuário pode ver ou operar, sem trava
+pub(crate) async fn accessible_account(
+    pool: &PgPool,
+    user_id: Uuid,
+    account_id: Uuid,
+    permission: OperatorPermission,
+) -> Result<Account, AppError> {
+    let account = AccountRepository::find_by_id(pool, account_id)
+        .await?
+        .ok_or(AccountError::NotFound)?;
+    let mut conn = pool.acquire().await?;
+    authorize_account(&mut conn, &account, user_id, permission).await?;
+    Ok(account)
+}
+
+/// Conta de destino pelo número ou pela chave PIX (ativa)
+pub(crate) async fn resolve_destination(
+    conn: &mut PgConnection,
+    request: &CreateTransfer,
+) -> Result<(Uuid, Option<PixKey>), AppError> {
+    match request.to_pix_key.as_deref() {
+        Some(raw) => {
+            let (_, key) = PixKeyValidator::parse("to_pix_key", raw)
+                .map_err(|err| AppError::Validation(err.into()))?;
+            let key = PixRepository::find_active(conn, &key)
+                .await?
+                .ok_or(PixError::KeyNotFound)?;
+            Ok((key.account_id, Some(key)))
+        }
+        None => {
+            let number = request.to_account_number.as_deref().unwrap_or_default();
+            let destination_id = AccountRepository::find_id_by_number(conn, number)
+                .await?
+                .ok_or(TransactionError::DestinationAccountNotFound)?;
+            Ok((destination_id, None))
+        }
+    }
+}
+
+/// Trava a cobrança paga pela transferência e confere se ela ainda vale e se a
+/// chave e o valor são os dela
+async fn lock_payable_charge(
+    conn: &mut PgConnection,
+    txid: &str,
+    key: Option<&PixKey>,
+    amount: Decimal,
+) -> Result<PixCharge, AppError> {
+    let charge = PixRepository::lock_charge(conn, txid)
+        .await?
+        .ok_or(PixError::ChargeNotFound)?;
+    if !charge.is_payable(Utc::now()) {
+        return Err(PixError::ChargeNotPayable.into());
+    }
+    if key.map(|key| key.id) != Some(charge.pix_key_id) || charge.amount != amount {
+        return Err(PixError::ChargeMismatch.into());
+    }
+    Ok(charge)
+}
+
+/// Trava a conta e confere acesso e situação
+pub(crate) async fn lock_owned_account(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    account_id: Uuid,
+    permission: OperatorPermission,
+) -> Result<Account, AppError> {
+    let account = AccountRepository::lock_by_id(conn, account_id)
+        .await?
+        .ok_or(AccountError::NotFound)?;
+    authorize_account(conn, &account, user_id, permission).await?;
+    if !account.is_active {
+        return Err(AccountError::Inactive.into());
+    }
+    Ok(account)
+}
+
+/// Trava a conta do dono e a de destino sempre na mesma ordem (menor ID primeiro),
+/// para duas operações cruzadas não travarem uma à outra
+pub(crate) async fn lock_owned_and_destination(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    account_id: Uuid,
+    destination_id: Uuid,
+    permission: OperatorPermission,
+) -> Result<(Account, Option<Account>), AppError> {
+    if account_id < destination_id {
+        let source = lock_owned_account(conn, user_id, account_id, permission).await?;
+        let destination = AccountRepository::lock_by_id(conn, destination_id).await?;
+        Ok((source, destination))
+    } else {
+        let destination = AccountRepository::lock_by_id(conn, destination_id).await?;
+        let source = lock_owned_account(conn, user_id, account_id, permission).await?;
+        Ok((source, destination))
+    }
+}
+
+/// Saldo contábil após o débito; o valor precisa caber no disponível, então
+/// o saldo só fica negativo dentro do cheque especial e nunca cobre as reservas (RN 6.2)
+pub(crate) fn debit(account: &Account, amount: Decimal) -> Result<Decimal, TransactionError> {
+    if amount > account.available_balance() {
+        return Err(TransactionError::InsufficientFunds);
+    }
+    Ok(account.balance - amount)
+}
@@ -0,0 +1,107 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use uuid::Uuid;
+
+use super::{DocumentStorage, StorageError, StorageFuture, validate_key};
+
+/// Arquivos num diretório local; a chave vira o caminho relativo à raiz
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Cria a raiz se ainda não existir
+    pub async fn open(root: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        let root = root.into();
+        tokio::fs::create_dir_all(&root).await?;
+        Ok(Self { root })
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+impl DocumentStorage for LocalStorage {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn put<'a>(&'a self, key: &'a str, content: &'a [u8]) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            // grava num temporário e renomeia: quem lê nunca vê o arquivo pela metade
+            let tmp = temp_path(&path);
+            tokio::fs::write(&tmp, content).await?;
+            if let Err(err) = tokio::fs::rename(&tmp, &path).await {
+                let _ = tokio::fs::remove_file(&tmp).await;
+                return Err(err.into());
+            }
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            match tokio::fs::read(&path).await {
+                Ok(content) => Ok(content),
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    Err(StorageError::NotFound(key.into()))
+                }
+                Err(err) => Err(err.into()),
+            }
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            match tokio::fs::remove_file(&path).await {
+                Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", Uuid::new_v4()));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_storage_roundtrip() {
+        let root = std::env::temp_dir().join(format!("storage-{}", Uuid::new_v4()));
+        let storage = LocalStorage::open(&root).await.unwrap();
+
+        storage.put("kyc/u1/selfie", b"primeira").await.unwrap();
+        storage.put("kyc/u1/selfie", b"segunda").await.unwrap();
+        assert_eq!(storage.get("kyc/u1/selfie").await.unwrap(), b"segunda");
+
+        storage.delete("kyc/u1/selfie").await.unwrap();
+        storage.delete("kyc/u1/selfie").await.unwrap();
+        assert!(matches!(
+            storage.get("kyc/u1/selfie").await,
+            Err(StorageError::NotFound(_))
+        ));
+        assert!(matches!(
+            storage.put("../fora", b"x").await,
+            Err(StorageError::InvalidKey(_))
+        ));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
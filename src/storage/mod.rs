@@ -0,0 +1,70 @@
+//! Armazenamento dos arquivos enviados pelos usuários (documentos do KYC)
+//!
+//! O banco guarda só os metadados e a chave; o conteúdo fica no backend. Por
+//! enquanto há o backend em disco local, mas os handlers dependem só do trait.
+mod local;
+
+pub use local::LocalStorage;
+
+use std::{future::Future, pin::Pin};
+
+pub type StorageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, StorageError>> + Send + 'a>>;
+
+/// Backend de arquivos endereçados por chave (`kyc/{user_id}/{documento}`)
+///
+/// `put` sobrescreve a chave existente; `delete` de chave inexistente não é erro.
+pub trait DocumentStorage: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn put<'a>(&'a self, key: &'a str, content: &'a [u8]) -> StorageFuture<'a, ()>;
+
+    fn get<'a>(&'a self, key: &'a str) -> StorageFuture<'a, Vec<u8>>;
+
+    fn delete<'a>(&'a self, key: &'a str) -> StorageFuture<'a, ()>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("Arquivo não encontrado: {0}")]
+    NotFound(String),
+
+    #[error("Chave de arquivo inválida: {0}")]
+    InvalidKey(String),
+
+    #[error("Falha de E/S: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Segmentos separados por `/` com letras, dígitos, `-`, `_` e `.` (sem `..`)
+pub(crate) fn validate_key(key: &str) -> Result<(), StorageError> {
+    let valid = !key.is_empty()
+        && key.len() <= 200
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && !segment.starts_with('.')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key_rejects_path_traversal() {
+        assert!(validate_key("kyc/2f1c/selfie.png").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("/etc/passwd").is_err());
+        assert!(validate_key("kyc/../../etc/passwd").is_err());
+        assert!(validate_key("kyc//selfie").is_err());
+        assert!(validate_key("kyc/.hidden").is_err());
+        assert!(validate_key("kyc\\selfie").is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::future::Future;
+
+use actix_web::http::header::HeaderMap;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Tamanho máximo aceito para um `X-Request-Id` vindo do cliente
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    /// Identificadores da requisição em andamento, usados nos erros da API
+    static CURRENT_REQUEST: RequestContext;
+}
+
+/// Correlação da requisição: `X-Request-Id` e contexto W3C (`traceparent`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub trace_id: String,
+    /// Span desta requisição, enviado como pai no `traceparent` da resposta
+    pub span_id: String,
+    /// Span de quem chamou, quando veio um `traceparent` válido
+    pub parent_span_id: Option<String>,
+    pub sampled: bool,
+}
+
+impl RequestContext {
+    /// Reaproveita os ids recebidos; o que faltar (ou vier inválido) é gerado
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let request_id = headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|id| is_valid_request_id(id))
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        let parent = headers
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_traceparent);
+
+        match parent {
+            Some(parent) => Self {
+                request_id,
+                trace_id: parent.trace_id,
+                span_id: new_span_id(),
+                parent_span_id: Some(parent.span_id),
+                sampled: parent.sampled,
+            },
+            None => Self {
+                request_id,
+                trace_id: Uuid::new_v4().simple().to_string(),
+                span_id: new_span_id(),
+                parent_span_id: None,
+                sampled: true,
+            },
+        }
+    }
+
+    /// Valor do header `traceparent` que devolvemos (versão 00)
+    pub fn traceparent(&self) -> String {
+        let flags = if self.sampled { "01" } else { "00" };
+        format!("00-{}-{}-{flags}", self.trace_id, self.span_id)
+    }
+}
+
+pub(crate) struct TraceParent {
+    pub trace_id: String,
+    pub span_id: String,
+    pub sampled: bool,
+}
+
+/// `versão-traceid-parentid-flags`, conforme a recomendação W3C Trace Context
+pub(crate) fn parse_traceparent(raw: &str) -> Option<TraceParent> {
+    let mut parts = raw.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let flags = parts.next()?;
+
+    // versões futuras podem ter mais campos; a 00 tem exatamente quatro
+    if version == "ff" || !is_hex(version, 2) || (version == "00" && parts.next().is_some()) {
+        return None;
+    }
+    if !is_hex(trace_id, 32) || !is_hex(span_id, 16) || !is_hex(flags, 2) {
+        return None;
+    }
+    if trace_id.bytes().all(|b| b == b'0') || span_id.bytes().all(|b| b == b'0') {
+        return None;
+    }
+
+    let flags = u8::from_str_radix(flags, 16).ok()?;
+    Some(TraceParent {
+        trace_id: trace_id.to_string(),
+        span_id: span_id.to_string(),
+        sampled: flags & 0x01 == 0x01,
+    })
+}
+
+fn is_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+fn new_span_id() -> String {
+    format!("{:016x}", Uuid::new_v4().as_u64_pair().0)
+}
+
+/// Identificadores da requisição atual, se houver uma
+pub fn current_request() -> Option<RequestContext> {
+    CURRENT_REQUEST.try_with(RequestContext::clone).ok()
+}
+
+/// Executa `fut` com `context` como requisição atual
+pub fn with_request<F: Future>(context: RequestContext, fut: F) -> impl Future<Output = F::Output> {
+    CURRENT_REQUEST.scope(context, fut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header::{HeaderName, HeaderValue};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn test_propagates_incoming_ids() {
+        let context = RequestContext::from_headers(&headers(&[
+            (REQUEST_ID_HEADER, "req-123"),
+            (
+                TRACEPARENT_HEADER,
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            ),
+        ]));
+
+        assert_eq!(context.request_id, "req-123");
+        assert_eq!(context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(context.parent_span_id.as_deref(), Some("00f067aa0ba902b7"));
+        assert_ne!(context.span_id, "00f067aa0ba902b7");
+        assert!(
+            context
+                .traceparent()
+                .starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-")
+        );
+    }
+
+    #[test]
+    fn test_generates_ids_when_missing_or_invalid() {
+        let context = RequestContext::from_headers(&headers(&[
+            (REQUEST_ID_HEADER, "id com espaco"),
+            (
+                TRACEPARENT_HEADER,
+                "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            ),
+        ]));
+
+        assert!(Uuid::parse_str(&context.request_id).is_ok());
+        assert!(is_hex(&context.trace_id, 32));
+        assert!(is_hex(&context.span_id, 16));
+        assert!(context.parent_span_id.is_none());
+
+        for invalid in [
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(parse_traceparent(invalid).is_none(), "{invalid}");
+        }
+    }
+}
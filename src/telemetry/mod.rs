@@ -0,0 +1,86 @@
+//! Logs estruturados, spans por requisição e exportação OTLP (feature `otlp`)
+mod context;
+#[cfg(feature = "otlp")]
+mod otlp;
+
+pub use context::*;
+#[cfg(feature = "otlp")]
+pub use otlp::link_span;
+
+use tracing_subscriber::{
+    EnvFilter, Layer, filter::ParseError, layer::SubscriberExt, util::SubscriberInitExt,
+    util::TryInitError,
+};
+
+use crate::config::{LogFormat, TelemetryConfig};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TelemetryError {
+    #[error("Filtro de log inválido: {0}")]
+    Filter(#[from] ParseError),
+
+    #[error("Subscriber de tracing já instalado: {0}")]
+    Init(#[from] TryInitError),
+
+    #[error("Falha ao criar o exportador OTLP: {0}")]
+    Exporter(String),
+
+    #[error(
+        "OTEL_EXPORTER_OTLP_ENDPOINT definido, mas o binário foi compilado sem a feature `otlp`"
+    )]
+    OtlpDisabled,
+}
+
+/// Mantém o exportador vivo; ao ser descartado envia os spans pendentes
+#[must_use = "descartar o guard encerra a exportação de traces"]
+pub struct TelemetryGuard {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take()
+            && let Err(err) = provider.shutdown()
+        {
+            eprintln!("Falha ao encerrar o exportador OTLP: {err}");
+        }
+    }
+}
+
+/// Instala o subscriber global conforme `[telemetry]`
+pub fn init(config: &TelemetryConfig) -> Result<TelemetryGuard, TelemetryError> {
+    let filter = EnvFilter::try_new(&config.log_filter)?;
+    let output = match config.log_format {
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .boxed(),
+        LogFormat::Pretty => tracing_subscriber::fmt::layer().boxed(),
+    };
+    let subscriber = tracing_subscriber::registry().with(filter).with(output);
+
+    #[cfg(feature = "otlp")]
+    {
+        let provider = config
+            .otlp_endpoint
+            .as_deref()
+            .map(|endpoint| otlp::tracer_provider(endpoint, &config.service_name))
+            .transpose()?;
+        subscriber
+            .with(provider.as_ref().map(otlp::layer))
+            .try_init()?;
+        Ok(TelemetryGuard { provider })
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        if config.otlp_endpoint.is_some() {
+            return Err(TelemetryError::OtlpDisabled);
+        }
+        subscriber.try_init()?;
+        Ok(TelemetryGuard {})
+    }
+}
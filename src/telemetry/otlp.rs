@@ -0,0 +1,135 @@
+use opentelemetry::{
+    Context,
+    trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider as _,
+    },
+};
+use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{Resource, trace::SdkTracerProvider};
+use tracing::{Span, Subscriber};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{Layer, registry::LookupSpan};
+
+use super::{RequestContext, TelemetryError};
+
+/// Exportador OTLP/HTTP (protobuf) em lote para `{endpoint}/v1/traces`
+pub(super) fn tracer_provider(
+    endpoint: &str,
+    service_name: &str,
+) -> Result<SdkTracerProvider, TelemetryError> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_protocol(Protocol::HttpBinary)
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()
+        .map_err(|err| TelemetryError::Exporter(err.to_string()))?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build())
+}
+
+pub(super) fn layer<S>(provider: &SdkTracerProvider) -> impl Layer<S> + use<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+}
+
+/// Pendura o span da requisição no trace de quem chamou e adota os ids gerados
+/// pelo OpenTelemetry, para o `traceparent` da resposta bater com o exportado
+pub fn link_span(span: &Span, context: &mut RequestContext) {
+    if let Some(parent_span_id) = &context.parent_span_id {
+        let flags = if context.sampled {
+            TraceFlags::SAMPLED
+        } else {
+            TraceFlags::default()
+        };
+        let remote = SpanContext::new(
+            TraceId::from_hex(&context.trace_id).unwrap_or(TraceId::INVALID),
+            SpanId::from_hex(parent_span_id).unwrap_or(SpanId::INVALID),
+            flags,
+            true,
+            TraceState::default(),
+        );
+        let _ = span.set_parent(Context::new().with_remote_span_context(remote));
+    }
+
+    let otel_context = span.context();
+    let span_context = otel_context.span().span_context().clone();
+    if span_context.is_valid() {
+        context.trace_id = span_context.trace_id().to_string();
+        context.span_id = span_context.span_id().to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::Tracer;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    /// Coletor falso: aceita uma requisição HTTP e devolve a linha de requisição
+    fn collector_stub() -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/x-protobuf\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            sender.send(request_line.trim().to_string()).unwrap();
+        });
+
+        (endpoint, receiver)
+    }
+
+    #[test]
+    fn test_exports_spans_to_collector() {
+        let (endpoint, requests) = collector_stub();
+        let provider = tracer_provider(&endpoint, "api_mini_bank_test").unwrap();
+
+        provider
+            .tracer("test")
+            .in_span("http_request", |_context| {});
+        provider.force_flush().unwrap();
+
+        let request_line = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(request_line, "POST /v1/traces HTTP/1.1");
+        provider.shutdown().unwrap();
+    }
+}
@@ -1,18 +1,26 @@
-use crate::JWT_SECRET;
+use crate::config::{
+    AuthSettings, FeesConfig, FxConfig, InterestConfig, LimitsConfig, PixConfig, WebhookConfig,
+};
 use crate::models::{User, claims::Claims};
-use bcrypt::{BcryptResult, DEFAULT_COST, hash, verify};
-use chrono::{Duration, Utc};
+use crate::{
+    AUTH_SETTINGS, FEE_SETTINGS, FX_SETTINGS, INTEREST_SETTINGS, JWT_SECRET, LIMIT_SETTINGS,
+    PIX_SETTINGS, WEBHOOK_SETTINGS,
+};
+use bcrypt::{hash, verify};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, TokenData, Validation, encode};
 use uuid::Uuid;
 
 pub fn create_token(user: &User) -> Result<String, jsonwebtoken::errors::Error> {
     let now = Utc::now();
-    let exp = now + chrono::Duration::minutes(5);
+    let exp = now + Duration::seconds(auth_settings().access_token_ttl_secs);
     let claims = Claims::new(
-        user.id.clone().into(),
+        user.id.into(),
         exp.timestamp() as usize,
         now.timestamp() as usize,
         user.email.clone(),
+        user.locale.clone(),
+        user.role,
     );
 
     let secret = get_jwt_secret(); // Pega o secret da variável global
@@ -24,10 +32,10 @@ pub fn create_token(user: &User) -> Result<String, jsonwebtoken::errors::Error>
     )
 }
 
-pub fn create_token_refresh() -> (String, i64) {
+pub fn create_token_refresh() -> (String, DateTime<Utc>) {
     let refresh_token = Uuid::new_v4().to_string();
-    let expires_at = Utc::now() + Duration::days(2);
-    (refresh_token, expires_at.timestamp())
+    let expires_at = Utc::now() + Duration::seconds(auth_settings().refresh_token_ttl_secs);
+    (refresh_token, expires_at)
 }
 
 pub fn verify_token(token: &str) -> Result<TokenData<Claims>, jsonwebtoken::errors::Error> {
@@ -44,8 +52,36 @@ pub fn get_jwt_secret() -> &'static str {
     JWT_SECRET.get().expect("JWT_SECRET não foi inicializado")
 }
 
+pub fn auth_settings() -> &'static AuthSettings {
+    AUTH_SETTINGS.get_or_init(AuthSettings::default)
+}
+
+pub fn limit_settings() -> &'static LimitsConfig {
+    LIMIT_SETTINGS.get_or_init(LimitsConfig::default)
+}
+
+pub fn fee_settings() -> &'static FeesConfig {
+    FEE_SETTINGS.get_or_init(FeesConfig::default)
+}
+
+pub fn interest_settings() -> &'static InterestConfig {
+    INTEREST_SETTINGS.get_or_init(InterestConfig::default)
+}
+
+pub fn fx_settings() -> &'static FxConfig {
+    FX_SETTINGS.get_or_init(FxConfig::default)
+}
+
+pub fn pix_settings() -> &'static PixConfig {
+    PIX_SETTINGS.get_or_init(PixConfig::default)
+}
+
+pub fn webhook_settings() -> &'static WebhookConfig {
+    WEBHOOK_SETTINGS.get_or_init(WebhookConfig::default)
+}
+
 pub fn hash_password(password: &str) -> Result<String, bcrypt::BcryptError> {
-    hash(password, DEFAULT_COST)
+    hash(password, auth_settings().bcrypt_cost)
 }
 
 pub fn verify_password(password: &str, hash: &str) -> bool {
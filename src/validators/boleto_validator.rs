@@ -0,0 +1,176 @@
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+
+use crate::{
+    models::boleto::{BoletoBarcode, CreateBoleto, MAX_AMOUNT, MAX_DUE_DAYS, PayBoleto},
+    validators::{FieldError, TransactionValidator, ValidationErrors},
+};
+
+const MAX_PAYER_NAME_LEN: usize = 100;
+
+pub struct BoletoValidator;
+
+impl BoletoValidator {
+    /// Valor que cabe no código de barras, vencimento entre hoje e `MAX_DUE_DAYS`,
+    /// pagador obrigatório e instruções opcionais
+    pub fn validate(request: &CreateBoleto, today: NaiveDate) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Err(err) = Self::validate_amount(request.amount) {
+            errors.push(err);
+        }
+        if let Err(err) = Self::validate_due_date(request.due_date, today) {
+            errors.push(err);
+        }
+        let payer_name = request.payer_name.trim();
+        if payer_name.is_empty() {
+            errors.push(
+                FieldError::new("payer_name", "REQUIRED", "Nome do pagador é obrigatório")
+                    .with_constraint("required"),
+            );
+        } else if payer_name.chars().count() > MAX_PAYER_NAME_LEN {
+            errors.push(
+                FieldError::new("payer_name", "INVALID_NAME", "Nome do pagador muito longo")
+                    .with_constraint(format!("max_length={MAX_PAYER_NAME_LEN}")),
+            );
+        }
+        if let Some(description) = optional(request.description.as_deref())
+            && let Err(err) = TransactionValidator::validate_description(description)
+        {
+            errors.push(err);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Linha digitável e valor a debitar: o do boleto ou, se ele não traz valor,
+    /// o informado pelo pagador
+    pub fn validate_payment(
+        request: &PayBoleto,
+    ) -> Result<(BoletoBarcode, Decimal), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let barcode = BoletoBarcode::parse(&request.line)
+            .map_err(|err| errors.push(err))
+            .ok();
+        let amount = match (
+            barcode.as_ref().and_then(BoletoBarcode::amount),
+            request.amount,
+        ) {
+            (Some(amount), None) => Some(amount),
+            (Some(amount), Some(informed)) if informed == amount => Some(amount),
+            (Some(amount), Some(_)) => {
+                errors.push(
+                    FieldError::new(
+                        "amount",
+                        "INVALID_AMOUNT",
+                        "Valor diferente do registrado no boleto",
+                    )
+                    .with_constraint(format!("equals={amount}")),
+                );
+                None
+            }
+            (None, Some(informed)) => Self::validate_amount(informed)
+                .map_err(|err| errors.push(err))
+                .ok()
+                .map(|_| informed),
+            (None, None) => {
+                if barcode.is_some() {
+                    errors.push(
+                        FieldError::new("amount", "REQUIRED", "Boleto sem valor: informe o valor")
+                            .with_constraint("required"),
+                    );
+                }
+                None
+            }
+        };
+        if let Some(description) = optional(request.description.as_deref())
+            && let Err(err) = TransactionValidator::validate_description(description)
+        {
+            errors.push(err);
+        }
+
+        match (barcode, amount) {
+            (Some(barcode), Some(amount)) if errors.is_empty() => Ok((barcode, amount)),
+            _ => Err(errors),
+        }
+    }
+
+    fn validate_amount(amount: Decimal) -> Result<(), FieldError> {
+        TransactionValidator::validate_amount(amount)?;
+        if amount > MAX_AMOUNT {
+            return Err(FieldError::new(
+                "amount",
+                "INVALID_AMOUNT",
+                "Valor acima do limite do boleto",
+            )
+            .with_constraint(format!("max={MAX_AMOUNT}")));
+        }
+        Ok(())
+    }
+
+    fn validate_due_date(due_date: NaiveDate, today: NaiveDate) -> Result<(), FieldError> {
+        let error = FieldError::new("due_date", "INVALID_DUE_DATE", "Vencimento inválido");
+
+        if due_date < today {
+            return Err(error.with_constraint(format!("min={today}")));
+        }
+        if due_date > today + Duration::days(MAX_DUE_DAYS) {
+            return Err(error.with_constraint(format!("max_days={MAX_DUE_DAYS}")));
+        }
+        Ok(())
+    }
+}
+
+fn optional(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn payment(line: &str, amount: Option<&str>) -> PayBoleto {
+        PayBoleto {
+            account_id: Uuid::new_v4(),
+            line: line.into(),
+            amount: amount.map(|amount| amount.parse().unwrap()),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_payment_amount_comes_from_the_boleto() {
+        let line = "00190.50095 40144.816069 06809.350314 3 37370000000100";
+        let (_, amount) = BoletoValidator::validate_payment(&payment(line, None)).unwrap();
+        assert_eq!(amount, Decimal::new(100, 2));
+        assert!(BoletoValidator::validate_payment(&payment(line, Some("1.00"))).is_ok());
+
+        let errors = BoletoValidator::validate_payment(&payment(line, Some("2.00"))).unwrap_err();
+        assert_eq!(
+            errors.errors()[0].constraint.as_deref(),
+            Some("equals=1.00")
+        );
+    }
+
+    #[test]
+    fn test_due_date_window() {
+        let today = NaiveDate::from_ymd_opt(2025, 9, 20).unwrap();
+        let constraint = |days| {
+            BoletoValidator::validate_due_date(today + Duration::days(days), today)
+                .err()
+                .and_then(|err| err.constraint)
+        };
+        assert_eq!(constraint(0), None);
+        assert_eq!(constraint(-1).as_deref(), Some("min=2025-09-20"));
+        assert_eq!(
+            constraint(MAX_DUE_DAYS + 1).as_deref(),
+            Some("max_days=1825")
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    models::brcode::{BrCode, BrCodeKind, CreateBrCode, MAX_TXID_LEN},
+    validators::{FieldError, TransactionValidator, ValidationErrors},
+};
+
+pub struct BrCodeValidator;
+
+impl BrCodeValidator {
+    /// Valor, `txid` e descrição, opcionais no estático; o dinâmico exige o
+    /// valor. `key` é a chave já cadastrada, que limita o espaço da descrição no payload
+    pub fn validate(request: &CreateBrCode, key: &str) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        match request.amount {
+            Some(amount) => {
+                if let Err(err) = TransactionValidator::validate_amount(amount) {
+                    errors.push(err);
+                }
+            }
+            None if request.kind == BrCodeKind::Dynamic => errors.push(
+                FieldError::new("amount", "REQUIRED", "Valor é obrigatório no QR dinâmico")
+                    .with_constraint("required"),
+            ),
+            None => {}
+        }
+        if let Some(txid) = &request.txid
+            && let Err(err) = Self::validate_txid(txid)
+        {
+            errors.push(err);
+        }
+        if let Some(description) = &request.description {
+            let max = BrCode::max_description_len(key);
+            if description.trim().chars().count() > max {
+                errors.push(
+                    FieldError::new(
+                        "description",
+                        "INVALID_DESCRIPTION",
+                        "Descrição muito longa",
+                    )
+                    .with_constraint(format!("max_length={max}")),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Até 25 letras e dígitos, sem espaços (regra do BR Code)
+    pub fn validate_txid(txid: &str) -> Result<(), FieldError> {
+        let error = FieldError::new("txid", "INVALID_TXID", "Identificador da cobrança inválido");
+
+        if txid.is_empty() || !txid.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(error.with_constraint("alphanumeric"));
+        }
+        if txid.len() > MAX_TXID_LEN {
+            return Err(error.with_constraint(format!("max_length={MAX_TXID_LEN}")));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{models::business::OperatorPermission, validators::FieldError};
+
+pub struct BusinessValidator;
+
+impl BusinessValidator {
+    /// Pelo menos uma permissão; repetidas são descartadas, mantendo a ordem
+    pub fn validate_permissions(
+        permissions: &[OperatorPermission],
+    ) -> Result<Vec<OperatorPermission>, FieldError> {
+        if permissions.is_empty() {
+            return Err(FieldError::new(
+                "permissions",
+                "REQUIRED",
+                "Informe ao menos uma permissão",
+            )
+            .with_constraint("required"));
+        }
+
+        let mut unique = Vec::with_capacity(permissions.len());
+        for permission in permissions {
+            if !unique.contains(permission) {
+                unique.push(*permission);
+            }
+        }
+        Ok(unique)
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    models::{CustomerType, Document},
+    validators::FieldError,
+};
+
+/// Documentos brasileiros (CPF e CNPJ)
+pub struct DocumentValidator;
+
+impl DocumentValidator {
+    /// Remove a pontuação usual (`123.456.789-09` → `12345678909`)
+    pub fn strip_formatting(raw: &str) -> String {
+        raw.trim()
+            .chars()
+            .filter(|c| !matches!(c, '.' | '-' | '/' | ' '))
+            .collect()
+    }
+
+    /// 11 dígitos com os dois dígitos verificadores corretos
+    ///
+    /// Sequências repetidas (`111.111.111-11`) passam na conta, mas não são CPFs válidos.
+    pub fn is_valid_cpf(cpf: &str) -> bool {
+        let digits: Vec<u32> = cpf.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() != 11 || cpf.len() != 11 {
+            return false;
+        }
+        if digits.iter().all(|&d| d == digits[0]) {
+            return false;
+        }
+        check_digit(&digits[..9]) == digits[9] && check_digit(&digits[..10]) == digits[10]
+    }
+
+    /// 14 dígitos com os dois dígitos verificadores corretos; sequências repetidas não valem
+    pub fn is_valid_cnpj(cnpj: &str) -> bool {
+        let digits: Vec<u32> = cnpj.chars().filter_map(|c| c.to_digit(10)).collect();
+        if digits.len() != 14 || cnpj.len() != 14 {
+            return false;
+        }
+        if digits.iter().all(|&d| d == digits[0]) {
+            return false;
+        }
+        cnpj_check_digit(&digits[..12]) == digits[12]
+            && cnpj_check_digit(&digits[..13]) == digits[13]
+    }
+
+    /// CPF para pessoa física, CNPJ para empresa; aceita o documento com ou sem pontuação
+    pub fn validate(customer_type: CustomerType, raw: &str) -> Result<Document, FieldError> {
+        let document = Self::strip_formatting(raw);
+        if document.is_empty() {
+            return Err(
+                FieldError::new("document", "REQUIRED", "Documento é obrigatório")
+                    .with_constraint("required"),
+            );
+        }
+
+        let (valid, message, constraint) = match customer_type {
+            CustomerType::Individual => (Self::is_valid_cpf(&document), "CPF inválido", "cpf"),
+            CustomerType::Business => (Self::is_valid_cnpj(&document), "CNPJ inválido", "cnpj"),
+        };
+        if !valid {
+            return Err(FieldError::new("document", "INVALID_DOCUMENT", message)
+                .with_constraint(constraint));
+        }
+        Ok(Document::new(document))
+    }
+}
+
+/// Módulo 11 com pesos decrescentes a partir de `len + 1`
+fn check_digit(digits: &[u32]) -> u32 {
+    let weight = digits.len() as u32 + 1;
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, d)| d * (weight - i as u32))
+        .sum();
+    match sum % 11 {
+        0 | 1 => 0,
+        rest => 11 - rest,
+    }
+}
+
+/// Módulo 11 do CNPJ: pesos de 2 a 9 a partir da direita, recomeçando em 2
+fn cnpj_check_digit(digits: &[u32]) -> u32 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| d * (2 + i as u32 % 8))
+        .sum();
+    match sum % 11 {
+        0 | 1 => 0,
+        rest => 11 - rest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpf_check_digits() {
+        assert!(DocumentValidator::is_valid_cpf("52998224725"));
+        assert!(DocumentValidator::is_valid_cpf(
+            &DocumentValidator::strip_formatting("529.982.247-25")
+        ));
+
+        assert!(!DocumentValidator::is_valid_cpf("52998224724"));
+        assert!(!DocumentValidator::is_valid_cpf("11111111111"));
+        assert!(!DocumentValidator::is_valid_cpf("5299822472"));
+        assert!(!DocumentValidator::is_valid_cpf("529.982.247-25"));
+    }
+
+    #[test]
+    fn test_cnpj_check_digits() {
+        assert!(DocumentValidator::is_valid_cnpj("11222333000181"));
+        assert!(DocumentValidator::is_valid_cnpj("11444777000161"));
+
+        assert!(!DocumentValidator::is_valid_cnpj("11222333000182"));
+        assert!(!DocumentValidator::is_valid_cnpj("00000000000000"));
+        assert!(!DocumentValidator::is_valid_cnpj("1122233300018"));
+    }
+
+    #[test]
+    fn test_document_must_match_customer_type() {
+        let document =
+            DocumentValidator::validate(CustomerType::Business, "11.222.333/0001-81").unwrap();
+        assert_eq!(document.as_str(), "11222333000181");
+
+        let err = DocumentValidator::validate(CustomerType::Individual, "11.222.333/0001-81")
+            .unwrap_err();
+        assert_eq!(err.code, "INVALID_DOCUMENT");
+        assert_eq!(err.constraint.as_deref(), Some("cpf"));
+        let err = DocumentValidator::validate(CustomerType::Business, " ").unwrap_err();
+        assert_eq!(err.code, "REQUIRED");
+    }
+}
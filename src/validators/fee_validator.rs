@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+
+use crate::{
+    models::fee::CreateFeeWaiver,
+    validators::{FieldError, ValidationErrors},
+};
+
+const MAX_REASON_LEN: usize = 500;
+
+pub struct FeeWaiverValidator;
+
+impl FeeWaiverValidator {
+    /// Motivo obrigatório; a validade, quando informada, precisa estar no futuro
+    pub fn validate(request: &CreateFeeWaiver, now: DateTime<Utc>) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let reason = request.reason.trim();
+        if reason.is_empty() {
+            errors.push(
+                FieldError::new("reason", "REQUIRED", "Informe o motivo da isenção")
+                    .with_constraint("required"),
+            );
+        } else if reason.chars().count() > MAX_REASON_LEN {
+            errors.push(
+                FieldError::new("reason", "INVALID_REASON", "Motivo muito longo")
+                    .with_constraint(format!("max_length={MAX_REASON_LEN}")),
+            );
+        }
+        if request
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            errors.push(
+                FieldError::new(
+                    "expires_at",
+                    "INVALID_EXPIRES_AT",
+                    "A validade deve estar no futuro",
+                )
+                .with_constraint("future"),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_waiver_needs_reason_and_future_expiry() {
+        let now = Utc::now();
+        let mut request = CreateFeeWaiver {
+            operation: None,
+            reason: "Campanha".into(),
+            expires_at: Some(now + Duration::days(30)),
+        };
+        assert!(FeeWaiverValidator::validate(&request, now).is_ok());
+
+        request.reason = "  ".into();
+        request.expires_at = Some(now - Duration::days(1));
+        let errors = FeeWaiverValidator::validate(&request, now).unwrap_err();
+        assert!(errors.has_field("reason"));
+        assert!(errors.has_field("expires_at"));
+    }
+}
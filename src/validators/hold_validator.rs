@@ -0,0 +1,72 @@
+use crate::{
+    models::hold::{CaptureHold, CreateHold, MAX_HOLD_TTL_SECS},
+    validators::{FieldError, TransactionValidator, ValidationErrors},
+};
+
+pub struct HoldValidator;
+
+impl HoldValidator {
+    /// Regras de valor e descrição das transações mais a validade da reserva
+    pub fn validate(request: &CreateHold) -> Result<(), ValidationErrors> {
+        let mut errors = TransactionValidator::validate(request.amount, &request.description)
+            .err()
+            .unwrap_or_default();
+
+        if let Some(ttl) = request.ttl_secs
+            && !(1..=MAX_HOLD_TTL_SECS).contains(&ttl)
+        {
+            errors.push(
+                FieldError::new(
+                    "ttl_secs",
+                    "INVALID_HOLD_TTL",
+                    "Validade da reserva inválida",
+                )
+                .with_constraint(format!("max={MAX_HOLD_TTL_SECS}")),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn validate_capture(request: &CaptureHold) -> Result<(), ValidationErrors> {
+        match request.amount {
+            Some(amount) => TransactionValidator::validate_amount(amount).map_err(Into::into),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_hold_rules() {
+        let mut request = CreateHold {
+            amount: Decimal::new(15050, 2),
+            description: "Hotel".into(),
+            ttl_secs: None,
+        };
+        assert!(HoldValidator::validate(&request).is_ok());
+
+        request.ttl_secs = Some(MAX_HOLD_TTL_SECS + 1);
+        let errors = HoldValidator::validate(&request).unwrap_err();
+        let error = errors
+            .errors()
+            .iter()
+            .find(|err| err.field == "ttl_secs")
+            .unwrap();
+        assert_eq!(error.constraint.as_deref(), Some("max=2592000"));
+
+        assert!(HoldValidator::validate_capture(&CaptureHold::default()).is_ok());
+        let capture = CaptureHold {
+            amount: Some(Decimal::ZERO),
+        };
+        assert!(HoldValidator::validate_capture(&capture).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    models::kyc::{ALLOWED_CONTENT_TYPES, KycDocumentType, MAX_DOCUMENT_BYTES},
+    validators::FieldError,
+};
+
+const MAX_REASON_LEN: usize = 500;
+
+pub struct KycValidator;
+
+impl KycValidator {
+    /// Formato aceito e conteúdo condizente com o `Content-Type` informado;
+    /// devolve o tipo normalizado (sem parâmetros como `charset`)
+    pub fn validate_document(
+        content_type: Option<&str>,
+        content: &[u8],
+    ) -> Result<&'static str, FieldError> {
+        if content.is_empty() {
+            return Err(
+                FieldError::new("body", "REQUIRED", "Envie o arquivo do documento")
+                    .with_constraint("required"),
+            );
+        }
+        if content.len() > MAX_DOCUMENT_BYTES {
+            return Err(
+                FieldError::new("body", "PAYLOAD_TOO_LARGE", "Documento muito grande")
+                    .with_constraint(format!("max_bytes={MAX_DOCUMENT_BYTES}")),
+            );
+        }
+
+        let declared = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let Some((content_type, signature)) = ALLOWED_CONTENT_TYPES
+            .iter()
+            .find(|(allowed, _)| *allowed == declared)
+        else {
+            let allowed: Vec<&str> = ALLOWED_CONTENT_TYPES.iter().map(|(ct, _)| *ct).collect();
+            return Err(FieldError::new(
+                "content_type",
+                "INVALID_DOCUMENT_FILE",
+                "Formato de documento não aceito",
+            )
+            .with_constraint(format!("content_type={}", allowed.join(", "))));
+        };
+        if !content.starts_with(signature) {
+            return Err(FieldError::new(
+                "body",
+                "INVALID_DOCUMENT_FILE",
+                "Conteúdo do arquivo não corresponde ao Content-Type",
+            )
+            .with_constraint("signature"));
+        }
+
+        Ok(content_type)
+    }
+
+    /// Todos os documentos exigidos já enviados
+    pub fn validate_submission(missing: &[KycDocumentType]) -> Result<(), FieldError> {
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let missing: Vec<&str> = missing.iter().map(KycDocumentType::as_str).collect();
+        Err(FieldError::new(
+            "documents",
+            "KYC_DOCUMENTS_MISSING",
+            "Documentos obrigatórios não enviados",
+        )
+        .with_constraint(format!("missing={}", missing.join(", "))))
+    }
+
+    /// Motivo obrigatório, mostrado ao usuário para ele corrigir o cadastro
+    pub fn validate_reason(reason: &str) -> Result<String, FieldError> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(
+                FieldError::new("reason", "REQUIRED", "Informe o motivo da rejeição")
+                    .with_constraint("required"),
+            );
+        }
+        if reason.chars().count() > MAX_REASON_LEN {
+            return Err(
+                FieldError::new("reason", "INVALID_REASON", "Motivo muito longo")
+                    .with_constraint(format!("max_length={MAX_REASON_LEN}")),
+            );
+        }
+        Ok(reason.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_must_match_declared_type() {
+        let png = b"\x89PNG\r\n\x1a\nresto";
+
+        assert_eq!(
+            KycValidator::validate_document(Some("image/png"), png),
+            Ok("image/png")
+        );
+        assert_eq!(
+            KycValidator::validate_document(Some("application/PDF; charset=binary"), b"%PDF-1.7"),
+            Ok("application/pdf")
+        );
+
+        let err = KycValidator::validate_document(Some("image/jpeg"), png).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("signature"));
+        let err = KycValidator::validate_document(Some("image/gif"), b"GIF89a").unwrap_err();
+        assert_eq!(err.field, "content_type");
+        let err = KycValidator::validate_document(None, b"").unwrap_err();
+        assert_eq!(err.code, "REQUIRED");
+    }
+}
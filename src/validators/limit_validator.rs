@@ -0,0 +1,65 @@
+use crate::{
+    config::AccountLimits,
+    models::limit::UpdateLimits,
+    validators::{FieldError, TransactionValidator, ValidationErrors},
+};
+
+pub struct LimitValidator;
+
+impl LimitValidator {
+    /// Cada limite pedido segue as regras de valor das transações e não passa do
+    /// teto do tipo de conta
+    pub fn validate(request: &UpdateLimits, max: &AccountLimits) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if request.requested().next().is_none() {
+            errors.push(
+                FieldError::new("limits", "REQUIRED", "Informe ao menos um limite")
+                    .with_constraint("required"),
+            );
+        }
+        for (kind, amount) in request.requested() {
+            if let Err(mut err) = TransactionValidator::validate_amount(amount) {
+                err.field = kind.as_str().to_string();
+                errors.push(err);
+            } else if amount > kind.max(max) {
+                errors.push(
+                    FieldError::new(kind.as_str(), "INVALID_LIMIT", "Limite acima do permitido")
+                        .with_constraint(format!("max={}", kind.max(max))),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LimitsConfig;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_limits_capped_by_account_type() {
+        let max = LimitsConfig::default().checking;
+        let mut request = UpdateLimits {
+            daily: Some(Decimal::from(500)),
+            ..Default::default()
+        };
+        assert!(LimitValidator::validate(&request, &max).is_ok());
+
+        request.monthly = Some(max.monthly + Decimal::ONE);
+        request.nightly = Some(Decimal::ZERO);
+        let errors = LimitValidator::validate(&request, &max).unwrap_err();
+        assert!(errors.has_field("monthly"));
+        assert!(errors.has_field("nightly"));
+
+        let errors = LimitValidator::validate(&UpdateLimits::default(), &max).unwrap_err();
+        assert!(errors.has_field("limits"));
+    }
+}
@@ -1,3 +1,33 @@
+mod boleto_validator;
+mod brcode_validator;
+mod business_validator;
+mod document_validator;
+mod fee_validator;
+mod hold_validator;
+mod kyc_validator;
+mod limit_validator;
+mod overdraft_validator;
+mod pix_validator;
+mod reversal_validator;
+mod scheduled_transfer_validator;
+mod transaction_validator;
 mod user_validator;
+mod validation_error;
+mod webhook_validator;
 
+pub use boleto_validator::*;
+pub use brcode_validator::*;
+pub use business_validator::*;
+pub use document_validator::*;
+pub use fee_validator::*;
+pub use hold_validator::*;
+pub use kyc_validator::*;
+pub use limit_validator::*;
+pub use overdraft_validator::*;
+pub use pix_validator::*;
+pub use reversal_validator::*;
+pub use scheduled_transfer_validator::*;
+pub use transaction_validator::*;
 pub use user_validator::*;
+pub use validation_error::*;
+pub use webhook_validator::*;
@@ -0,0 +1,59 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    models::account::UpdateOverdraft,
+    validators::{FieldError, ValidationErrors},
+};
+
+pub struct OverdraftValidator;
+
+impl OverdraftValidator {
+    /// Limite em centavos, de zero (sem cheque especial) até o teto de `[limits]`
+    pub fn validate(request: &UpdateOverdraft, max: Decimal) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let error = |constraint: String| {
+            FieldError::new(
+                "limit",
+                "INVALID_OVERDRAFT_LIMIT",
+                "Limite de cheque especial inválido",
+            )
+            .with_constraint(constraint)
+        };
+        if request.limit < Decimal::ZERO {
+            errors.push(error("min=0".to_string()));
+        } else if request.limit.normalize().scale() > 2 {
+            errors.push(error("max_scale=2".to_string()));
+        } else if request.limit > max {
+            errors.push(error(format!("max={max}")));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overdraft_limit_range() {
+        let max = Decimal::from(5_000);
+        let request = |limit| UpdateOverdraft { limit };
+        assert!(OverdraftValidator::validate(&request(Decimal::ZERO), max).is_ok());
+        assert!(OverdraftValidator::validate(&request(max), max).is_ok());
+
+        for limit in [
+            Decimal::from(-1),
+            Decimal::new(10001, 3),
+            max + Decimal::ONE,
+        ] {
+            let errors = OverdraftValidator::validate(&request(limit), max).unwrap_err();
+            assert!(errors.has_field("limit"));
+        }
+    }
+}
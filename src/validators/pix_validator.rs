@@ -0,0 +1,160 @@
+use regex::Regex;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+use crate::{
+    models::pix::PixKeyType,
+    validators::{DocumentValidator, FieldError, UserValidator},
+};
+
+/// Tamanho máximo de uma chave PIX (limite do DICT, vale para o email)
+const MAX_KEY_LEN: usize = 77;
+
+static PHONE_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// Celular ou fixo brasileiro: `+55`, DDD e 8 ou 9 dígitos
+fn get_phone_regex() -> &'static Regex {
+    PHONE_REGEX.get_or_init(|| Regex::new(r"^\+55[1-9]{2}9?[0-9]{8}$").unwrap())
+}
+
+pub struct PixKeyValidator;
+
+impl PixKeyValidator {
+    /// Chave informada no cadastro, já normalizada
+    ///
+    /// A chave aleatória (`evp`) é gerada pelo banco: vem `None` e enviar uma é erro.
+    pub fn validate_new(
+        key_type: PixKeyType,
+        key: Option<&str>,
+    ) -> Result<Option<String>, FieldError> {
+        match (key_type, key.map(str::trim).filter(|key| !key.is_empty())) {
+            (PixKeyType::Evp, None) => Ok(None),
+            (PixKeyType::Evp, Some(_)) => Err(invalid_key("key").with_constraint("generated")),
+            (_, None) => Err(invalid_key("key").with_constraint("required")),
+            (key_type, Some(raw)) => Self::normalize("key", key_type, raw).map(Some),
+        }
+    }
+
+    /// Detecta o tipo da chave (pagamentos, consultas e reivindicações) e a normaliza
+    ///
+    /// UUID é chave aleatória, `@` indica email e `+` telefone; o resto é tratado como CPF.
+    pub fn parse(field: &str, raw: &str) -> Result<(PixKeyType, String), FieldError> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return Err(invalid_key(field).with_constraint("required"));
+        }
+        let key_type = if Uuid::try_parse(raw).is_ok() {
+            PixKeyType::Evp
+        } else if raw.contains('@') {
+            PixKeyType::Email
+        } else if raw.starts_with('+') {
+            PixKeyType::Phone
+        } else {
+            PixKeyType::Cpf
+        };
+        Self::normalize(field, key_type, raw).map(|key| (key_type, key))
+    }
+
+    /// Só email e telefone recebem código de verificação
+    pub fn not_verifiable(field: &str) -> FieldError {
+        invalid_key(field).with_constraint("verifiable")
+    }
+
+    fn normalize(field: &str, key_type: PixKeyType, raw: &str) -> Result<String, FieldError> {
+        let format = || invalid_key(field).with_constraint(format!("format={}", key_type.as_str()));
+
+        match key_type {
+            PixKeyType::Cpf => {
+                let cpf = DocumentValidator::strip_formatting(raw);
+                if cpf.len() != 11 || !cpf.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(format());
+                }
+                if !DocumentValidator::is_valid_cpf(&cpf) {
+                    return Err(invalid_key(field).with_constraint("check_digit"));
+                }
+                Ok(cpf)
+            }
+            PixKeyType::Email => {
+                let email = UserValidator::validate_email(raw).map_err(|_| format())?;
+                if email.chars().count() > MAX_KEY_LEN {
+                    return Err(
+                        invalid_key(field).with_constraint(format!("max_length={MAX_KEY_LEN}"))
+                    );
+                }
+                Ok(email)
+            }
+            PixKeyType::Phone => {
+                let phone: String = raw
+                    .chars()
+                    .filter(|c| !matches!(c, ' ' | '-' | '(' | ')'))
+                    .collect();
+                if !get_phone_regex().is_match(&phone) {
+                    return Err(format());
+                }
+                Ok(phone)
+            }
+            PixKeyType::Evp => Uuid::try_parse(raw)
+                .map(|uuid| uuid.hyphenated().to_string())
+                .map_err(|_| format()),
+        }
+    }
+}
+
+fn invalid_key(field: &str) -> FieldError {
+    FieldError::new(field, "INVALID_PIX_KEY", "Chave PIX inválida")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_detects_and_normalizes() {
+        assert_eq!(
+            PixKeyValidator::parse("key", "529.982.247-25").unwrap(),
+            (PixKeyType::Cpf, "52998224725".to_string())
+        );
+        assert_eq!(
+            PixKeyValidator::parse("key", " Maria@Exemplo.com ").unwrap(),
+            (PixKeyType::Email, "maria@exemplo.com".to_string())
+        );
+        assert_eq!(
+            PixKeyValidator::parse("key", "+55 (11) 98765-4321").unwrap(),
+            (PixKeyType::Phone, "+5511987654321".to_string())
+        );
+        assert_eq!(
+            PixKeyValidator::parse("key", "7D9F0335-8DCC-4054-9BF9-0DBD61D36906").unwrap(),
+            (
+                PixKeyType::Evp,
+                "7d9f0335-8dcc-4054-9bf9-0dbd61d36906".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let constraint = |raw| {
+            PixKeyValidator::parse("key", raw)
+                .unwrap_err()
+                .constraint
+                .unwrap()
+        };
+        assert_eq!(constraint("529.982.247-24"), "check_digit");
+        assert_eq!(constraint("1234"), "format=cpf");
+        assert_eq!(constraint("+1 555 0100"), "format=phone");
+        assert_eq!(constraint("maria@"), "format=email");
+        assert_eq!(constraint("  "), "required");
+    }
+
+    #[test]
+    fn test_random_key_is_generated_by_the_bank() {
+        assert_eq!(
+            PixKeyValidator::validate_new(PixKeyType::Evp, None),
+            Ok(None)
+        );
+        let err = PixKeyValidator::validate_new(PixKeyType::Evp, Some("qualquer")).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("generated"));
+        let err = PixKeyValidator::validate_new(PixKeyType::Email, None).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("required"));
+    }
+}
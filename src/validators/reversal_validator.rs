@@ -0,0 +1,66 @@
+use crate::{
+    models::reversal::{CreateReversal, ReversalReason},
+    validators::{FieldError, TransactionValidator, ValidationErrors},
+};
+
+const MAX_NOTE_LEN: usize = 500;
+
+pub struct ReversalValidator;
+
+impl ReversalValidator {
+    /// Valor opcional com as regras das transações; `note` obrigatória quando o motivo é `other`
+    pub fn validate(request: &CreateReversal) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(amount) = request.amount
+            && let Err(err) = TransactionValidator::validate_amount(amount)
+        {
+            errors.push(err);
+        }
+
+        let note = request.note.as_deref().map(str::trim).unwrap_or_default();
+        if note.is_empty() && request.reason == ReversalReason::Other {
+            errors.push(
+                FieldError::new("note", "REQUIRED", "Descreva o motivo do estorno")
+                    .with_constraint("required"),
+            );
+        }
+        if note.chars().count() > MAX_NOTE_LEN {
+            errors.push(
+                FieldError::new("note", "INVALID_NOTE", "Observação muito longa")
+                    .with_constraint(format!("max_length={MAX_NOTE_LEN}")),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_other_reason_requires_note() {
+        let mut request = CreateReversal {
+            amount: Some(Decimal::new(1050, 2)),
+            reason: ReversalReason::Chargeback,
+            note: None,
+        };
+        assert!(ReversalValidator::validate(&request).is_ok());
+
+        request.reason = ReversalReason::Other;
+        let errors = ReversalValidator::validate(&request).unwrap_err();
+        assert_eq!(errors.errors()[0].field, "note");
+
+        request.note = Some("x".repeat(MAX_NOTE_LEN + 1));
+        request.amount = Some(Decimal::new(1, 3));
+        let errors = ReversalValidator::validate(&request).unwrap_err();
+        assert_eq!(errors.errors().len(), 2);
+    }
+}
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    models::scheduled_transfer::{
+        CreateScheduledTransfer, MAX_RUNS, MAX_SCHEDULE_DAYS, ScheduleFrequency,
+    },
+    validators::{FieldError, TransactionValidator, ValidationErrors},
+};
+
+pub struct ScheduledTransferValidator;
+
+impl ScheduledTransferValidator {
+    /// Regras da transferência mais a agenda: primeira execução no futuro (até
+    /// `MAX_SCHEDULE_DAYS`) e fim da recorrência por data ou por quantidade
+    pub fn validate(
+        request: &CreateScheduledTransfer,
+        now: DateTime<Utc>,
+    ) -> Result<(), ValidationErrors> {
+        let mut errors = TransactionValidator::validate_transfer(&request.transfer_request())
+            .err()
+            .unwrap_or_default();
+
+        let error = FieldError::new(
+            "scheduled_for",
+            "INVALID_SCHEDULE_DATE",
+            "Data de execução inválida",
+        );
+        if request.scheduled_for <= now {
+            errors.push(error.with_constraint(format!("min={}", now.format("%Y-%m-%dT%H:%M:%SZ"))));
+        } else if request.scheduled_for > now + Duration::days(MAX_SCHEDULE_DAYS) {
+            errors.push(error.with_constraint(format!("max_days={MAX_SCHEDULE_DAYS}")));
+        }
+
+        if let Err(err) = Self::validate_recurrence(request) {
+            errors.push(err);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn validate_recurrence(request: &CreateScheduledTransfer) -> Result<(), FieldError> {
+        let recurrence = |field: &str| {
+            FieldError::new(field, "INVALID_RECURRENCE", "Fim da recorrência inválido")
+        };
+
+        match (request.end_date, request.max_runs) {
+            (None, None) => return Ok(()),
+            (Some(_), None) if request.frequency == ScheduleFrequency::Once => {
+                return Err(recurrence("end_date").with_constraint("recurring_only"));
+            }
+            (None, Some(_)) if request.frequency == ScheduleFrequency::Once => {
+                return Err(recurrence("max_runs").with_constraint("recurring_only"));
+            }
+            (Some(_), Some(_)) => {
+                return Err(recurrence("end_date").with_constraint("one_of=end_date,max_runs"));
+            }
+            _ => {}
+        }
+
+        let first_day = request.scheduled_for.date_naive();
+        if let Some(end_date) = request.end_date
+            && end_date < first_day
+        {
+            return Err(
+                FieldError::new("end_date", "INVALID_END_DATE", "Data final inválida")
+                    .with_constraint(format!("min={first_day}")),
+            );
+        }
+        if let Some(max_runs) = request.max_runs
+            && !(1..=MAX_RUNS).contains(&max_runs)
+        {
+            return Err(FieldError::new(
+                "max_runs",
+                "INVALID_MAX_RUNS",
+                "Quantidade de execuções inválida",
+            )
+            .with_constraint(format!("max={MAX_RUNS}")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn request(frequency: ScheduleFrequency, days_ahead: i64) -> CreateScheduledTransfer {
+        CreateScheduledTransfer {
+            to_account_number: Some("12345678".into()),
+            to_pix_key: None,
+            amount: Decimal::TEN,
+            description: "Mesada".into(),
+            scheduled_for: Utc::now() + Duration::days(days_ahead),
+            frequency,
+            end_date: None,
+            max_runs: None,
+        }
+    }
+
+    fn constraint(result: Result<(), ValidationErrors>, field: &str) -> Option<String> {
+        result
+            .unwrap_err()
+            .errors()
+            .iter()
+            .find(|err| err.field == field)
+            .and_then(|err| err.constraint.clone())
+    }
+
+    #[test]
+    fn test_schedule_rules() {
+        let now = Utc::now();
+        assert!(
+            ScheduledTransferValidator::validate(&request(ScheduleFrequency::Once, 1), now).is_ok()
+        );
+        assert!(
+            constraint(
+                ScheduledTransferValidator::validate(&request(ScheduleFrequency::Once, -1), now),
+                "scheduled_for"
+            )
+            .unwrap()
+            .starts_with("min=")
+        );
+        assert_eq!(
+            constraint(
+                ScheduledTransferValidator::validate(&request(ScheduleFrequency::Once, 400), now),
+                "scheduled_for"
+            ),
+            Some("max_days=365".into())
+        );
+
+        let mut once = request(ScheduleFrequency::Once, 1);
+        once.max_runs = Some(3);
+        assert_eq!(
+            constraint(ScheduledTransferValidator::validate(&once, now), "max_runs"),
+            Some("recurring_only".into())
+        );
+
+        let mut monthly = request(ScheduleFrequency::Monthly, 1);
+        monthly.max_runs = Some(12);
+        assert!(ScheduledTransferValidator::validate(&monthly, now).is_ok());
+        monthly.end_date = Some(now.date_naive());
+        assert_eq!(
+            constraint(
+                ScheduledTransferValidator::validate(&monthly, now),
+                "end_date"
+            ),
+            Some("one_of=end_date,max_runs".into())
+        );
+        monthly.max_runs = None;
+        assert!(
+            constraint(
+                ScheduledTransferValidator::validate(&monthly, now),
+                "end_date"
+            )
+            .unwrap()
+            .starts_with("min=")
+        );
+    }
+}
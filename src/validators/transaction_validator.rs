@@ -0,0 +1,201 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    models::{currency::Currency, transaction::CreateTransfer},
+    validators::{BrCodeValidator, FieldError, PixKeyValidator, ValidationErrors},
+};
+
+/// Casas decimais aceitas (coluna DECIMAL(15,2))
+const MAX_AMOUNT_SCALE: u32 = 2;
+
+/// Maior valor que cabe em DECIMAL(15,2)
+const MAX_AMOUNT: Decimal = Decimal::from_parts(2_764_472_319, 232_830, 0, false, 2);
+
+const MAX_DESCRIPTION_LEN: usize = 255;
+
+pub struct TransactionValidator;
+
+impl TransactionValidator {
+    /// Valor > 0 com até 2 casas e descrição obrigatória (regras 6.3)
+    pub fn validate(amount: Decimal, description: &str) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Err(err) = Self::validate_amount(amount) {
+            errors.push(err);
+        }
+        if let Err(err) = Self::validate_description(description) {
+            errors.push(err);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Regras de `validate` mais o destino: número da conta ou chave PIX, nunca os dois
+    pub fn validate_transfer(request: &CreateTransfer) -> Result<(), ValidationErrors> {
+        let mut errors = Self::validate(request.amount, &request.description)
+            .err()
+            .unwrap_or_default();
+
+        if let Err(err) = Self::validate_destination(
+            request.to_account_number.as_deref(),
+            request.to_pix_key.as_deref(),
+        ) {
+            errors.push(err);
+        }
+        // a cobrança é paga na chave que ela indica
+        if let Some(txid) = &request.pix_txid {
+            if let Err(err) = BrCodeValidator::validate_txid(txid) {
+                errors.push(FieldError {
+                    field: "pix_txid".into(),
+                    ..err
+                });
+            } else if request.to_pix_key.is_none() {
+                errors.push(
+                    FieldError::new(
+                        "to_pix_key",
+                        "REQUIRED",
+                        "Cobrança PIX exige a chave de destino",
+                    )
+                    .with_constraint("required"),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn validate_destination(
+        to_account_number: Option<&str>,
+        to_pix_key: Option<&str>,
+    ) -> Result<(), FieldError> {
+        match (to_account_number, to_pix_key) {
+            (Some(_), None) => Ok(()),
+            (None, Some(key)) => PixKeyValidator::parse("to_pix_key", key).map(|_| ()),
+            _ => Err(FieldError::new(
+                "to_account_number",
+                "INVALID_DESTINATION",
+                "Informe o número da conta ou a chave PIX de destino",
+            )
+            .with_constraint("one_of=to_account_number,to_pix_key")),
+        }
+    }
+
+    pub fn validate_amount(amount: Decimal) -> Result<(), FieldError> {
+        let error = FieldError::new("amount", "INVALID_AMOUNT", "Valor deve ser maior que zero");
+
+        if amount <= Decimal::ZERO {
+            return Err(error.with_constraint("min=0.01"));
+        }
+        if amount.normalize().scale() > MAX_AMOUNT_SCALE {
+            return Err(error.with_constraint(format!("max_scale={MAX_AMOUNT_SCALE}")));
+        }
+        if amount > MAX_AMOUNT {
+            return Err(error.with_constraint(format!("max={MAX_AMOUNT}")));
+        }
+        Ok(())
+    }
+
+    /// Casas do valor dentro da menor unidade da moeda da conta (ex.: iene sem centavos)
+    ///
+    /// Chamada depois de travar a conta, quando a moeda é conhecida.
+    pub fn validate_minor_units(
+        amount: Decimal,
+        currency: Currency,
+    ) -> Result<(), ValidationErrors> {
+        let minor_units = currency.minor_units();
+        if amount.normalize().scale() > minor_units {
+            return Err(FieldError::new(
+                "amount",
+                "INVALID_AMOUNT",
+                "Valor com mais casas que a moeda permite",
+            )
+            .with_constraint(format!("max_scale={minor_units}"))
+            .into());
+        }
+        Ok(())
+    }
+
+    pub fn validate_description(description: &str) -> Result<(), FieldError> {
+        let trimmed = description.trim();
+        if trimmed.is_empty() {
+            return Err(
+                FieldError::new("description", "REQUIRED", "Descrição é obrigatória")
+                    .with_constraint("required"),
+            );
+        }
+        if trimmed.chars().count() > MAX_DESCRIPTION_LEN {
+            return Err(FieldError::new(
+                "description",
+                "INVALID_DESCRIPTION",
+                "Descrição muito longa",
+            )
+            .with_constraint(format!("max_length={MAX_DESCRIPTION_LEN}")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(raw: &str) -> Decimal {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn test_amount_rules() {
+        assert!(TransactionValidator::validate_amount(dec("10.50")).is_ok());
+        assert!(TransactionValidator::validate_amount(dec("10.500")).is_ok());
+        assert!(TransactionValidator::validate_amount(dec("9999999999999.99")).is_ok());
+
+        let constraint = |amount| {
+            TransactionValidator::validate_amount(amount)
+                .unwrap_err()
+                .constraint
+                .unwrap()
+        };
+        assert_eq!(constraint(dec("0")), "min=0.01");
+        assert_eq!(constraint(dec("-5")), "min=0.01");
+        assert_eq!(constraint(dec("1.005")), "max_scale=2");
+        assert_eq!(constraint(dec("10000000000000")), "max=9999999999999.99");
+
+        assert!(TransactionValidator::validate_minor_units(dec("150.00"), Currency::Jpy).is_ok());
+        assert!(TransactionValidator::validate_minor_units(dec("150.5"), Currency::Jpy).is_err());
+        assert!(TransactionValidator::validate_minor_units(dec("150.5"), Currency::Usd).is_ok());
+    }
+
+    #[test]
+    fn test_collects_all_errors() {
+        let errors = TransactionValidator::validate(dec("0"), "   ").unwrap_err();
+        assert!(errors.has_field("amount"));
+        assert!(errors.has_field("description"));
+    }
+
+    #[test]
+    fn test_transfer_needs_exactly_one_destination() {
+        assert!(TransactionValidator::validate_destination(Some("2440829012"), None).is_ok());
+        assert!(TransactionValidator::validate_destination(None, Some("52998224725")).is_ok());
+
+        let err = TransactionValidator::validate_destination(None, None).unwrap_err();
+        assert_eq!(
+            err.constraint.as_deref(),
+            Some("one_of=to_account_number,to_pix_key")
+        );
+        assert!(
+            TransactionValidator::validate_destination(Some("2440829012"), Some("52998224725"))
+                .is_err()
+        );
+        let err =
+            TransactionValidator::validate_destination(None, Some("52998224724")).unwrap_err();
+        assert_eq!(err.field, "to_pix_key");
+    }
+}
@@ -2,7 +2,11 @@ use regex::Regex;
 use serde::Serialize;
 use std::sync::OnceLock;
 
-use crate::models::error::UserError;
+use crate::{
+    i18n::Locale,
+    models::error::UserError,
+    validators::{FieldError, ValidationErrors},
+};
 
 static EMAIL_REGEX: OnceLock<Regex> = OnceLock::<Regex>::new();
 
@@ -53,55 +57,73 @@ impl UserValidator {
         password: &str,
         criteria: PasswordCriteria,
     ) -> Result<(), UserError> {
-        if password.len() < criteria.min_length {
-            return Err(UserError::WeakPassword);
+        if criteria.violations(password).is_empty() {
+            Ok(())
+        } else {
+            Err(UserError::WeakPassword)
         }
+    }
 
-        if criteria.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
-            return Err(UserError::WeakPassword);
-        }
+    /// Valida todos os campos do cadastro, acumulando cada falha encontrada
+    pub fn validade_user_data(
+        name: &str,
+        email: &str,
+        password: &str,
+    ) -> Result<ValidatedUserData, UserError> {
+        let mut errors = ValidationErrors::new();
 
-        if criteria.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
-            return Err(UserError::WeakPassword);
-        }
+        let validated_name = Self::validate_name(name)
+            .map_err(|_| errors.push(Self::name_error(name)))
+            .ok();
+        let validated_email = Self::validate_email(email)
+            .map_err(|err| errors.push(Self::email_error(email, err)))
+            .ok();
 
-        if criteria.require_number && !password.chars().any(|c| c.is_numeric()) {
-            return Err(UserError::WeakPassword);
+        for rule in PasswordCriteria::default().violations(password) {
+            errors.push(
+                FieldError::new("password", "WEAK_PASSWORD", &rule.to_string())
+                    .with_constraint(rule.constraint()),
+            );
         }
 
-        if criteria.require_special_char {
-            let has_espacial = password
-                .chars()
-                .any(|c| "!@#$%^&*()_+-=[]{}|;:,.<>?".contains(c));
-            if !has_espacial {
-                return Err(UserError::WeakPassword);
-            }
+        match (validated_name, validated_email) {
+            (Some(name), Some(email)) if errors.is_empty() => Ok(ValidatedUserData {
+                name,
+                email,
+                password: password.into(),
+            }),
+            _ => Err(UserError::Validation(errors)),
         }
+    }
 
-        if password
-            .chars()
-            .any(|c| criteria.forbidden_chars.contains(&c))
-        {
-            return Err(UserError::WeakPassword);
-        }
+    /// Valida a tag de idioma preferido (pt-BR, en, es)
+    pub fn validate_locale(tag: &str) -> Result<Locale, FieldError> {
+        Locale::from_tag(tag).ok_or_else(|| {
+            FieldError::new("locale", "INVALID_LOCALE", "Idioma não suportado")
+                .with_constraint("one_of=pt-BR,en,es")
+        })
+    }
+
+    fn name_error(name: &str) -> FieldError {
+        let trimmed = name.trim();
+        let error = FieldError::new("name", "INVALID_NAME", &UserError::InvalidName.to_string());
 
-        Ok(())
+        if trimmed.is_empty() {
+            error.with_constraint("required")
+        } else if trimmed.split_whitespace().count() < 2 {
+            error.with_constraint("min_words=2")
+        } else {
+            error.with_constraint("min_word_length=2")
+        }
     }
 
-    pub fn validade_user_data(
-        name: &str,
-        email: &str,
-        password: &str,
-    ) -> Result<ValidatedUserData, UserError> {
-        let valideted_name = Self::validate_name(name)?;
-        let validated_email = Self::validate_email(email)?;
-
-        Self::validate_password(password)?;
-        Ok(ValidatedUserData {
-            name: valideted_name,
-            email: validated_email,
-            password: password.into(),
-        })
+    fn email_error(email: &str, err: UserError) -> FieldError {
+        let constraint = if email.trim().is_empty() {
+            "required"
+        } else {
+            "format=email"
+        };
+        FieldError::new("email", "INVALID_EMAIL", &err.to_string()).with_constraint(constraint)
     }
 }
 
@@ -122,6 +144,79 @@ pub struct PasswordCriteria {
     pub forbidden_chars: Vec<char>,
 }
 
+impl PasswordCriteria {
+    /// Lista todas as regras que a senha não cumpre
+    pub fn violations(&self, password: &str) -> Vec<PasswordRule> {
+        let mut violations = Vec::new();
+
+        if password.len() < self.min_length {
+            violations.push(PasswordRule::MinLength(self.min_length));
+        }
+
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            violations.push(PasswordRule::Uppercase);
+        }
+
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            violations.push(PasswordRule::Lowercase);
+        }
+
+        if self.require_number && !password.chars().any(|c| c.is_numeric()) {
+            violations.push(PasswordRule::Number);
+        }
+
+        if self.require_special_char
+            && !password
+                .chars()
+                .any(|c| "!@#$%^&*()_+-=[]{}|;:,.<>?".contains(c))
+        {
+            violations.push(PasswordRule::SpecialChar);
+        }
+
+        if password.chars().any(|c| self.forbidden_chars.contains(&c)) {
+            violations.push(PasswordRule::ForbiddenChars);
+        }
+
+        violations
+    }
+}
+
+/// Regra de `PasswordCriteria` violada por uma senha
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PasswordRule {
+    #[error("Senha deve ter pelo menos {0} caracteres")]
+    MinLength(usize),
+
+    #[error("Senha deve ter pelo menos 1 letra maiúscula")]
+    Uppercase,
+
+    #[error("Senha deve ter pelo menos 1 letra minúscula")]
+    Lowercase,
+
+    #[error("Senha deve ter pelo menos 1 número")]
+    Number,
+
+    #[error("Senha deve ter pelo menos 1 caractere especial")]
+    SpecialChar,
+
+    #[error("Senha contém caracteres não permitidos")]
+    ForbiddenChars,
+}
+
+impl PasswordRule {
+    /// Identificador da regra exposto no campo `constraint`
+    pub fn constraint(&self) -> String {
+        match self {
+            PasswordRule::MinLength(len) => format!("min_length={len}"),
+            PasswordRule::Uppercase => "require_uppercase".into(),
+            PasswordRule::Lowercase => "require_lowercase".into(),
+            PasswordRule::Number => "require_number".into(),
+            PasswordRule::SpecialChar => "require_special_char".into(),
+            PasswordRule::ForbiddenChars => "forbidden_chars".into(),
+        }
+    }
+}
+
 impl Default for PasswordCriteria {
     fn default() -> Self {
         Self {
@@ -141,15 +236,26 @@ impl LoginValidator {
         email: &str,
         password: &str,
     ) -> Result<ValidatedLoginData, UserError> {
-        let validated_email = UserValidator::validate_email(email)?;
+        let mut errors = ValidationErrors::new();
+
+        let validated_email = UserValidator::validate_email(email)
+            .map_err(|err| errors.push(UserValidator::email_error(email, err)))
+            .ok();
+
         if password.is_empty() {
-            return Err(UserError::InvalidCredentials);
+            errors.push(
+                FieldError::new("password", "REQUIRED", "Senha é obrigatória")
+                    .with_constraint("required"),
+            );
         }
 
-        Ok(ValidatedLoginData {
-            email: validated_email,
-            password: password.into(),
-        })
+        match validated_email {
+            Some(email) if errors.is_empty() => Ok(ValidatedLoginData {
+                email,
+                password: password.into(),
+            }),
+            _ => Err(UserError::Validation(errors)),
+        }
     }
 }
 
@@ -187,4 +293,36 @@ mod tests {
         assert!(UserValidator::validate_password("NoNumber").is_err());
         assert!(UserValidator::validate_password("nonumber123").is_err());
     }
+
+    #[test]
+    fn test_validate_user_data_collects_every_field() {
+        let result = UserValidator::validade_user_data("João", "invalid-email", "weak");
+
+        let Err(UserError::Validation(errors)) = result else {
+            panic!("Deveria ser Validation");
+        };
+        assert!(errors.has_field("name"));
+        assert!(errors.has_field("email"));
+
+        let constraints: Vec<_> = errors
+            .errors()
+            .iter()
+            .filter(|err| err.field == "password")
+            .filter_map(|err| err.constraint.as_deref())
+            .collect();
+        assert_eq!(
+            constraints,
+            vec!["min_length=8", "require_uppercase", "require_number"]
+        );
+    }
+
+    #[test]
+    fn test_password_violations() {
+        let criteria = PasswordCriteria::default();
+        assert!(criteria.violations("Password123").is_empty());
+        assert_eq!(
+            criteria.violations("Pass word1"),
+            vec![PasswordRule::ForbiddenChars]
+        );
+    }
 }
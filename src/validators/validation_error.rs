@@ -0,0 +1,78 @@
+use serde::Serialize;
+use std::fmt;
+use utoipa::ToSchema;
+
+/// Falha de validação de um campo específico
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldError {
+    #[schema(example = "password")]
+    pub field: String,
+    #[schema(value_type = String, example = "WEAK_PASSWORD")]
+    pub code: &'static str,
+    pub message: String,
+    /// Regra violada, ex.: `min_length=8`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "min_length=8")]
+    pub constraint: Option<String>,
+}
+
+impl FieldError {
+    pub fn new(field: &str, code: &'static str, message: &str) -> Self {
+        Self {
+            field: field.into(),
+            code,
+            message: message.into(),
+            constraint: None,
+        }
+    }
+
+    pub fn with_constraint(mut self, constraint: impl Into<String>) -> Self {
+        self.constraint = Some(constraint.into());
+        self
+    }
+}
+
+/// Todas as falhas encontradas em uma validação (não para no primeiro erro)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors(Vec<FieldError>);
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, error: FieldError) {
+        self.0.push(error);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.0
+    }
+
+    /// Verifica se algum erro foi registrado para o campo
+    pub fn has_field(&self, field: &str) -> bool {
+        self.0.iter().any(|err| err.field == field)
+    }
+}
+
+impl From<FieldError> for ValidationErrors {
+    fn from(error: FieldError) -> Self {
+        Self(vec![error])
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self
+            .0
+            .iter()
+            .map(|err| format!("{}: {}", err.field, err.message))
+            .collect();
+        write!(f, "{}", fields.join("; "))
+    }
+}
@@ -0,0 +1,262 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use reqwest::Url;
+
+use crate::{
+    models::event::EVENT_TYPES,
+    validators::{FieldError, ValidationErrors},
+};
+
+const MAX_URL_LEN: usize = 2048;
+
+/// Segredos curtos demais tornam a assinatura fácil de forjar
+const MIN_SECRET_LEN: usize = 16;
+const MAX_SECRET_LEN: usize = 128;
+
+pub struct WebhookValidator;
+
+impl WebhookValidator {
+    pub fn validate(
+        url: &str,
+        event_types: &[String],
+        secret: Option<&str>,
+        allow_local: bool,
+    ) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Err(err) = Self::validate_url(url, allow_local) {
+            errors.push(err);
+        }
+        if let Err(err) = Self::validate_event_types(event_types) {
+            errors.push(err);
+        }
+        if let Some(secret) = secret
+            && let Err(err) = Self::validate_secret(secret)
+        {
+            errors.push(err);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Só https e, se o host for um IP, só endereço público
+    ///
+    /// `allow_local` (`WEBHOOK_ALLOW_LOCAL_TARGETS`) libera http:// e loopback/rede privada
+    /// para testes contra um stub local.
+    pub fn validate_url(url: &str, allow_local: bool) -> Result<(), FieldError> {
+        let error = FieldError::new("url", "INVALID_URL", "URL inválida");
+        let url = url.trim();
+
+        if url.is_empty() {
+            return Err(error.with_constraint("required"));
+        }
+        if url.len() > MAX_URL_LEN {
+            return Err(error.with_constraint(format!("max_length={MAX_URL_LEN}")));
+        }
+        let host = match url.strip_prefix("https://") {
+            Some(host) => host,
+            None if allow_local => url
+                .strip_prefix("http://")
+                .ok_or_else(|| error.clone().with_constraint("scheme=http,https"))?,
+            None => return Err(error.with_constraint("scheme=https")),
+        };
+        if host.is_empty() || host.starts_with('/') || host.contains(char::is_whitespace) {
+            return Err(error.with_constraint("host"));
+        }
+        let parsed = Url::parse(url).map_err(|_| error.clone().with_constraint("host"))?;
+        if !allow_local
+            && let Some(ip) = Self::literal_ip(&parsed)
+            && !Self::is_public_ip(ip)
+        {
+            return Err(error.with_constraint("public_address"));
+        }
+        Ok(())
+    }
+
+    /// Resolve o host e recusa se algum endereço não for público
+    ///
+    /// Complementa `validate_url` no cadastro; o envio repete a checagem no resolver do cliente
+    /// HTTP, já que o DNS pode mudar depois.
+    pub async fn validate_target(url: &str, allow_local: bool) -> Result<(), FieldError> {
+        if allow_local {
+            return Ok(());
+        }
+        let error = FieldError::new("url", "INVALID_URL", "URL inválida");
+        let parsed = Url::parse(url.trim()).map_err(|_| error.clone().with_constraint("host"))?;
+        if Self::literal_ip(&parsed).is_some() {
+            // já conferido em validate_url
+            return Ok(());
+        }
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| error.clone().with_constraint("host"))?;
+        let port = parsed.port_or_known_default().unwrap_or(443);
+
+        let addresses: Vec<_> = tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|_| error.clone().with_constraint("unresolvable"))?
+            .collect();
+        if addresses.is_empty() {
+            return Err(error.with_constraint("unresolvable"));
+        }
+        if addresses
+            .iter()
+            .any(|address| !Self::is_public_ip(address.ip()))
+        {
+            return Err(error.with_constraint("public_address"));
+        }
+        Ok(())
+    }
+
+    /// Host da URL quando ele já é um IP (sem passar pelo DNS)
+    pub fn literal_ip(url: &Url) -> Option<IpAddr> {
+        let host = url.host_str()?;
+        host.trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse()
+            .ok()
+    }
+
+    /// Fora de loopback, redes privadas, link-local (inclui o metadata `169.254.169.254`),
+    /// CGNAT, multicast e faixas reservadas
+    pub fn is_public_ip(ip: IpAddr) -> bool {
+        match ip {
+            IpAddr::V4(ip) => is_public_v4(ip),
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(mapped) => is_public_v4(mapped),
+                None => is_public_v6(ip),
+            },
+        }
+    }
+
+    pub fn validate_event_types(event_types: &[String]) -> Result<(), FieldError> {
+        let error = FieldError::new(
+            "event_types",
+            "INVALID_EVENT_TYPES",
+            "Tipos de evento inválidos",
+        );
+
+        if event_types.is_empty() {
+            return Err(error.with_constraint("required"));
+        }
+        if event_types
+            .iter()
+            .any(|event_type| !EVENT_TYPES.contains(&event_type.as_str()))
+        {
+            return Err(error.with_constraint(format!("allowed={}", EVENT_TYPES.join(","))));
+        }
+        Ok(())
+    }
+
+    pub fn validate_secret(secret: &str) -> Result<(), FieldError> {
+        let error = FieldError::new("secret", "INVALID_SECRET", "Segredo inválido");
+
+        if secret.len() < MIN_SECRET_LEN {
+            return Err(error.with_constraint(format!("min_length={MIN_SECRET_LEN}")));
+        }
+        if secret.len() > MAX_SECRET_LEN {
+            return Err(error.with_constraint(format!("max_length={MAX_SECRET_LEN}")));
+        }
+        Ok(())
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        // 100.64.0.0/10 (CGNAT; inclui o metadata 100.100.100.200)
+        || (a == 100 && (64..128).contains(&b))
+        // 192.0.0.0/24 e 198.18.0.0/15
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7 (unique local; inclui o metadata fd00:ec2::254)
+        || (first & 0xfe00) == 0xfc00
+        // fe80::/10 (link-local)
+        || (first & 0xffc0) == 0xfe80
+        // 2001:db8::/32 (documentação)
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_rules() {
+        assert!(WebhookValidator::validate_url("https://parceiro.com/hook", false).is_ok());
+        assert!(WebhookValidator::validate_url("http://127.0.0.1:9000/", true).is_ok());
+
+        let err = WebhookValidator::validate_url("http://parceiro.com/hook", false).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("scheme=https"));
+        let err = WebhookValidator::validate_url("ftp://parceiro.com", true).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("scheme=http,https"));
+        let err = WebhookValidator::validate_url("https:///hook", false).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("host"));
+        for url in [
+            "https://127.0.0.1/hook",
+            "https://10.0.0.5/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]/hook",
+            "https://[::ffff:192.168.0.1]/hook",
+        ] {
+            let err = WebhookValidator::validate_url(url, false).unwrap_err();
+            assert_eq!(err.constraint.as_deref(), Some("public_address"), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_public_ip_ranges() {
+        let public = |ip: &str| WebhookValidator::is_public_ip(ip.parse().unwrap());
+        assert!(public("8.8.8.8"));
+        assert!(public("2606:4700:4700::1111"));
+        assert!(!public("172.16.3.4"));
+        assert!(!public("100.100.100.200"));
+        assert!(!public("0.0.0.0"));
+        assert!(!public("fd00:ec2::254"));
+        assert!(!public("fe80::1"));
+    }
+
+    #[tokio::test]
+    async fn test_target_resolving_to_loopback_is_rejected() {
+        let err = WebhookValidator::validate_target("https://localhost/hook", false)
+            .await
+            .unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("public_address"));
+        assert!(
+            WebhookValidator::validate_target("http://localhost/hook", true)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_event_types_must_be_known() {
+        let known = vec!["DepositCompleted".to_string()];
+        assert!(WebhookValidator::validate_event_types(&known).is_ok());
+
+        let err = WebhookValidator::validate_event_types(&[]).unwrap_err();
+        assert_eq!(err.constraint.as_deref(), Some("required"));
+        let err =
+            WebhookValidator::validate_event_types(&["MoneyArrived".to_string()]).unwrap_err();
+        assert!(err.constraint.unwrap().starts_with("allowed="));
+    }
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use super::{Channel, CodeSender, SendError, SendFuture};
+
+/// Entrega o código por um gateway de email/SMS: POST com `channel`,
+/// `destination` e `code`; qualquer status fora de 2xx conta como falha
+pub struct HttpCodeSender {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl HttpCodeSender {
+    pub fn new(
+        url: impl Into<String>,
+        token: Option<String>,
+        timeout: Duration,
+    ) -> Result<Self, SendError> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|err| SendError::Unavailable(err.to_string()))?;
+        Ok(Self {
+            client,
+            url: url.into(),
+            token,
+        })
+    }
+}
+
+impl CodeSender for HttpCodeSender {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn send<'a>(&'a self, channel: Channel, destination: &'a str, code: &'a str) -> SendFuture<'a> {
+        Box::pin(async move {
+            let mut request = self.client.post(&self.url).json(&json!({
+                "channel": channel.to_string(),
+                "destination": destination,
+                "code": code,
+            }));
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+            // a URL do gateway pode carregar credenciais; fica fora da mensagem
+            let response = request
+                .send()
+                .await
+                .map_err(|err| SendError::Unavailable(err.without_url().to_string()))?;
+            if !response.status().is_success() {
+                return Err(SendError::Unavailable(format!(
+                    "gateway respondeu {}",
+                    response.status().as_u16()
+                )));
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use super::{Channel, CodeSender, SendFuture};
+
+/// Só registra no log que um código foi gerado, sem o código nem o destino
+/// completo; para desenvolvimento (`VERIFICATION_LOG_SENDER`), o cliente não recebe nada
+pub struct LogCodeSender;
+
+impl CodeSender for LogCodeSender {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    fn send<'a>(
+        &'a self,
+        channel: Channel,
+        destination: &'a str,
+        _code: &'a str,
+    ) -> SendFuture<'a> {
+        Box::pin(async move {
+            tracing::info!(
+                %channel,
+                destination = %mask(destination),
+                "Código de verificação gerado (envio só no log)"
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Mantém os dois primeiros e os dois últimos caracteres
+fn mask(destination: &str) -> String {
+    let chars: Vec<char> = destination.chars().collect();
+    if chars.len() <= 4 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..2].iter().collect();
+    let tail: String = chars[chars.len() - 2..].iter().collect();
+    format!("{head}{}{tail}", "*".repeat(chars.len() - 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_hides_the_destination() {
+        assert_eq!(mask("joao@email.com"), "jo**********om");
+        assert_eq!(mask("+5511987654321"), "+5**********21");
+        assert_eq!(mask("abc"), "***");
+    }
+}
@@ -0,0 +1,50 @@
+//! Envio dos códigos de uso único que provam a posse de um email ou telefone
+//!
+//! Em produção o código vai por um gateway HTTP de email/SMS; o envio para o
+//! log é só para desenvolvimento e não registra o código. Os serviços dependem
+//! só do trait.
+mod http;
+mod log;
+
+pub use http::HttpCodeSender;
+pub use log::LogCodeSender;
+
+use std::{fmt, future::Future, pin::Pin};
+
+use crate::models::app_error::AppError;
+
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<(), SendError>> + Send + 'a>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Email,
+    Sms,
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Channel::Email => "email",
+            Channel::Sms => "sms",
+        })
+    }
+}
+
+/// Entrega o código ao destino (endereço de email ou telefone `+55…`)
+pub trait CodeSender: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn send<'a>(&'a self, channel: Channel, destination: &'a str, code: &'a str) -> SendFuture<'a>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SendError {
+    #[error("Falha ao enviar o código: {0}")]
+    Unavailable(String),
+}
+
+impl From<SendError> for AppError {
+    fn from(err: SendError) -> Self {
+        AppError::Internal(err.to_string())
+    }
+}